@@ -0,0 +1,104 @@
+#![allow(clippy::unwrap_used)]
+
+// This file is for comparing the committer flow over the in-memory storage and over the persistent
+// MDBX storage. Run with `cargo bench -p committer_cli --features mdbx_storage`.
+// Both storages hold the facts of the single tree flow input.
+
+use std::collections::HashMap;
+
+use committer_cli::tests::utils::parse_from_python::TreeFlowInput;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use papyrus_storage::db::DbConfig;
+use papyrus_storage::StorageConfig;
+use starknet_committer::block_committer::input::StarknetStorageValue;
+use starknet_committer::hash_function::hash::TreeHashFunctionImpl;
+use starknet_committer::patricia_merkle_tree::tree::OriginalSkeletonStorageTrieConfig;
+use starknet_patricia::patricia_merkle_tree::external_test_utils::tree_computation_flow;
+use starknet_patricia::patricia_merkle_tree::filled_tree::tree::FilledTree;
+use starknet_patricia::patricia_merkle_tree::node_data::leaf::LeafModifications;
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_patricia::storage::map_storage::MapStorage;
+use starknet_patricia::storage::mdbx_storage::MdbxStorage;
+use starknet_patricia::storage::storage_trait::{Storage, StorageKey, StorageValue};
+use tempfile::{tempdir, TempDir};
+
+const SINGLE_TREE_FLOW_INPUT: &str = include_str!("../test_inputs/tree_flow_inputs.json");
+
+fn open_mdbx_storage(facts: &HashMap<StorageKey, StorageValue>) -> (MdbxStorage, TempDir) {
+    let dir = tempdir().unwrap();
+    let config = StorageConfig {
+        db_config: DbConfig { path_prefix: dir.path().to_path_buf(), ..Default::default() },
+        ..Default::default()
+    };
+    let mut storage = MdbxStorage::open(config).unwrap();
+    storage.mset(facts.clone()).unwrap();
+    (storage, dir)
+}
+
+fn benchmark_storage(
+    criterion: &mut Criterion,
+    storage_name: &str,
+    storage: &mut impl Storage,
+    input: &TreeFlowInput,
+) {
+    let runtime = tokio::runtime::Builder::new_multi_thread().build().unwrap();
+    let leaf_modifications = input
+        .leaf_modifications
+        .iter()
+        .map(|(k, v)| (NodeIndex::FIRST_LEAF + *k, *v))
+        .collect::<LeafModifications<StarknetStorageValue>>();
+    let keys: Vec<StorageKey> = input.storage.storage.keys().cloned().collect();
+
+    criterion.bench_function(&format!("mget/{storage_name}"), |benchmark| {
+        benchmark.iter(|| storage.mget(&keys).unwrap())
+    });
+
+    criterion.bench_function(&format!("tree_computation_flow/{storage_name}"), |benchmark| {
+        benchmark.iter_batched(
+            || leaf_modifications.clone(),
+            |leaf_modifications_input| {
+                runtime.block_on(
+                    tree_computation_flow::<StarknetStorageValue, TreeHashFunctionImpl>(
+                        leaf_modifications_input,
+                        &*storage,
+                        input.root_hash,
+                        OriginalSkeletonStorageTrieConfig::new(false),
+                    ),
+                );
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    // The new nodes of the tree are written at once, as when a block is committed.
+    let new_facts = runtime
+        .block_on(tree_computation_flow::<StarknetStorageValue, TreeHashFunctionImpl>(
+            leaf_modifications.clone(),
+            &*storage,
+            input.root_hash,
+            OriginalSkeletonStorageTrieConfig::new(false),
+        ))
+        .serialize();
+    criterion.bench_function(&format!("mset/{storage_name}"), |benchmark| {
+        benchmark.iter_batched(
+            || new_facts.clone(),
+            |new_facts_input| storage.mset(new_facts_input).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+pub fn map_storage_benchmark(criterion: &mut Criterion) {
+    let input: TreeFlowInput = serde_json::from_str(SINGLE_TREE_FLOW_INPUT).unwrap();
+    let mut storage = MapStorage::from(input.storage.storage.clone());
+    benchmark_storage(criterion, "map_storage", &mut storage, &input);
+}
+
+pub fn mdbx_storage_benchmark(criterion: &mut Criterion) {
+    let input: TreeFlowInput = serde_json::from_str(SINGLE_TREE_FLOW_INPUT).unwrap();
+    let (mut storage, _dir) = open_mdbx_storage(&input.storage.storage);
+    benchmark_storage(criterion, "mdbx_storage", &mut storage, &input);
+}
+
+criterion_group!(benches, map_storage_benchmark, mdbx_storage_benchmark);
+criterion_main!(benches);
@@ -19,7 +19,7 @@ pub struct Output {
 impl SerializedForest {
     pub fn forest_to_output(&self) -> Output {
         let mut storage = MapStorage::default();
-        self.0.write_to_storage(&mut storage);
+        self.0.write_to_storage(&mut storage).expect("Writing to a map storage should not fail.");
         let contract_storage_root_hash = self.0.get_contract_root_hash().0;
         let compiled_class_root_hash = self.0.get_compiled_class_root_hash().0;
         Output {
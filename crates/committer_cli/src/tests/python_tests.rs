@@ -33,7 +33,7 @@ use starknet_patricia::patricia_merkle_tree::node_data::inner_node::{
 };
 use starknet_patricia::patricia_merkle_tree::types::SubTreeHeight;
 use starknet_patricia::storage::db_object::DBObject;
-use starknet_patricia::storage::errors::DeserializationError;
+use starknet_patricia::storage::errors::{DeserializationError, StorageError};
 use starknet_patricia::storage::map_storage::MapStorage;
 use starknet_patricia::storage::storage_trait::{Storage, StorageKey, StorageValue};
 use starknet_types_core::hash::{Pedersen, StarkHash};
@@ -87,6 +87,8 @@ pub enum PythonTestError {
     DeserializationTestFailure(#[from] DeserializationError),
     #[error("None value found in input.")]
     NoneInputError,
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
 }
 
 /// Implements conversion from a string to a `PythonTest`.
@@ -518,7 +520,7 @@ pub(crate) fn storage_serialize_test() -> Result<String, PythonTestError> {
     for i in 0..=99_u128 {
         let key = StorageKey(Felt::from(i).to_bytes_be().to_vec());
         let value = StorageValue(Felt::from(i).to_bytes_be().to_vec());
-        storage.set(key, value);
+        storage.set(key, value)?;
     }
 
     Ok(serde_json::to_string(&storage)?)
@@ -567,7 +569,7 @@ fn test_storage_node(data: HashMap<String, String>) -> Result<String, PythonTest
     };
 
     // Store the binary node in the storage.
-    rust_fact_storage.set(binary_rust.db_key(), binary_rust.serialize());
+    rust_fact_storage.set(binary_rust.db_key(), binary_rust.serialize())?;
 
     // Parse the edge node data from the input.
     let edge_json = get_or_key_not_found(&data, "edge")?;
@@ -588,7 +590,7 @@ fn test_storage_node(data: HashMap<String, String>) -> Result<String, PythonTest
     };
 
     // Store the edge node in the storage.
-    rust_fact_storage.set(edge_rust.db_key(), edge_rust.serialize());
+    rust_fact_storage.set(edge_rust.db_key(), edge_rust.serialize())?;
 
     // Parse the storage leaf data from the input.
     let storage_leaf_json = get_or_key_not_found(&data, "storage")?;
@@ -604,7 +606,7 @@ fn test_storage_node(data: HashMap<String, String>) -> Result<String, PythonTest
     };
 
     // Store the storage leaf node in the storage.
-    rust_fact_storage.set(storage_leaf_rust.db_key(), storage_leaf_rust.serialize());
+    rust_fact_storage.set(storage_leaf_rust.db_key(), storage_leaf_rust.serialize())?;
 
     // Parse the contract state leaf data from the input.
     let contract_state_leaf = get_or_key_not_found(&data, "contract_state_leaf")?;
@@ -629,7 +631,8 @@ fn test_storage_node(data: HashMap<String, String>) -> Result<String, PythonTest
     };
 
     // Store the contract state leaf node in the storage.
-    rust_fact_storage.set(contract_state_leaf_rust.db_key(), contract_state_leaf_rust.serialize());
+    rust_fact_storage
+        .set(contract_state_leaf_rust.db_key(), contract_state_leaf_rust.serialize())?;
 
     // Parse the compiled class leaf data from the input.
     let compiled_class_leaf = get_or_key_not_found(&data, "contract_class_leaf")?;
@@ -646,7 +649,8 @@ fn test_storage_node(data: HashMap<String, String>) -> Result<String, PythonTest
     };
 
     // Store the compiled class leaf node in the storage.
-    rust_fact_storage.set(compiled_class_leaf_rust.db_key(), compiled_class_leaf_rust.serialize());
+    rust_fact_storage
+        .set(compiled_class_leaf_rust.db_key(), compiled_class_leaf_rust.serialize())?;
 
     // Serialize the storage to a JSON string and handle serialization errors.
     Ok(serde_json::to_string(&rust_fact_storage)?)
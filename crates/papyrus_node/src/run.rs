@@ -14,7 +14,10 @@ use papyrus_common::pending_classes::PendingClasses;
 use papyrus_config::presentation::get_config_presentation;
 use papyrus_config::validators::config_validate;
 use papyrus_consensus::config::ConsensusConfig;
+use papyrus_consensus::decision_certificate::DecisionCertificateVerifier;
+use papyrus_consensus::signing::{create_signer, ConsensusSigner};
 use papyrus_consensus::stream_handler::StreamHandler;
+use papyrus_consensus::wal::Wal;
 use papyrus_consensus_orchestrator::papyrus_consensus_context::PapyrusConsensusContext;
 use papyrus_monitoring_gateway::MonitoringServer;
 use papyrus_network::gossipsub_impl::Topic;
@@ -32,7 +35,7 @@ use papyrus_sync::sources::base_layer::{BaseLayerSourceError, EthereumBaseLayerS
 use papyrus_sync::sources::central::{CentralError, CentralSource, CentralSourceConfig};
 use papyrus_sync::sources::pending::PendingSource;
 use papyrus_sync::{StateSync, SyncConfig};
-use starknet_api::block::{BlockHash, BlockHashAndNumber};
+use starknet_api::block::{BlockHash, BlockHashAndNumber, DecidedHeaderField};
 use starknet_api::felt;
 use starknet_client::reader::objects::pending_data::{PendingBlock, PendingBlockOrDeprecated};
 use starknet_client::reader::PendingData;
@@ -203,11 +206,16 @@ fn spawn_consensus(
     let (outbound_internal_sender, inbound_internal_receiver, _) =
         StreamHandler::get_channels(inbound_network_receiver, outbound_network_sender);
 
+    let signer = ConsensusSigner::new(config.chain_id.clone(), create_signer(&config.signer)?);
+    let wal = config.wal_path.as_deref().map(Wal::open).transpose()?;
     let context = PapyrusConsensusContext::new(
         storage_reader.clone(),
         network_channels.broadcast_topic_client.clone(),
         outbound_internal_sender,
         config.num_validators,
+        config.validator_public_keys.clone(),
+        config.validator_weights.clone(),
+        signer.clone(),
         None,
     );
 
@@ -220,6 +228,8 @@ fn spawn_consensus(
             config.validator_id,
             config.consensus_delay,
             config.timeouts.clone(),
+            signer,
+            wal,
             network_channels.into(),
             inbound_internal_receiver,
             futures::stream::pending(),
@@ -294,18 +304,30 @@ async fn spawn_sync_client(
                 .register_sqmr_protocol_client(Protocol::Transaction.into(), BUFFER_SIZE);
             let class_client_sender =
                 network_manager.register_sqmr_protocol_client(Protocol::Class.into(), BUFFER_SIZE);
+            let decision_certificate_client_sender = network_manager
+                .register_sqmr_protocol_client(Protocol::DecisionCertificate.into(), BUFFER_SIZE);
             let p2p_sync_client_channels = P2PSyncClientChannels::new(
                 header_client_sender,
                 state_diff_client_sender,
                 transaction_client_sender,
                 class_client_sender,
+                decision_certificate_client_sender,
             );
+            // The validators are known only if consensus is configured.
+            // Consensus of papyrus decides on the block hashes.
+            let decision_certificate_verifier = config.consensus.as_ref().map(|consensus_config| {
+                DecisionCertificateVerifier::from_config(
+                    consensus_config,
+                    DecidedHeaderField::BlockHash,
+                )
+            });
             let p2p_sync = P2PSyncClient::new(
                 p2p_sync_client_config,
                 storage_reader,
                 storage_writer,
                 p2p_sync_client_channels,
                 futures::stream::pending().boxed(),
+                decision_certificate_verifier,
             );
             tokio::spawn(async move { Ok(p2p_sync.run().await?) })
         }
@@ -331,6 +353,8 @@ fn spawn_p2p_sync_server(
         network_manager.register_sqmr_protocol_server(Protocol::Class.into(), BUFFER_SIZE);
     let event_server_receiver =
         network_manager.register_sqmr_protocol_server(Protocol::Event.into(), BUFFER_SIZE);
+    let decision_certificate_server_receiver = network_manager
+        .register_sqmr_protocol_server(Protocol::DecisionCertificate.into(), BUFFER_SIZE);
 
     let p2p_sync_server_channels = P2PSyncServerChannels::new(
         header_server_receiver,
@@ -338,6 +362,7 @@ fn spawn_p2p_sync_server(
         transaction_server_receiver,
         class_server_receiver,
         event_server_receiver,
+        decision_certificate_server_receiver,
     );
 
     let p2p_sync_server = P2PSyncServer::new(storage_reader.clone(), p2p_sync_server_channels);
@@ -50,6 +50,7 @@ impl DataStreamBuilder<(ApiContractClass, ClassHash)> for ClassStreamBuilder {
     const BLOCK_NUMBER_LIMIT: BlockNumberLimit = BlockNumberLimit::StateDiffMarker;
 
     fn parse_data_for_block<'a>(
+        &'a mut self,
         classes_response_manager: &'a mut ClientResponsesManager<
             DataOrFin<(ApiContractClass, ClassHash)>,
         >,
@@ -0,0 +1,134 @@
+use futures::StreamExt;
+use papyrus_consensus::decision_certificate::DecisionCertificateVerifier;
+use papyrus_network::network_manager::ClientResponsesManager;
+use papyrus_protobuf::sync::{
+    BlockHashOrNumber,
+    DataOrFin,
+    DecisionCertificateQuery,
+    Direction,
+    Query,
+};
+use starknet_api::block::{BlockHeader, BlockNumber, DecisionCertificate};
+use tracing::{debug, warn};
+
+use super::stream_builder::{BadPeerError, ParseDataError};
+use super::{DecisionCertificateSqmrSender, P2PSyncClientError, NETWORK_DATA_TIMEOUT, STEP};
+
+/// Downloads the decision certificates of the synced headers, and verifies that each block was
+/// finalized by a quorum of the validators. A header is only accepted with the verified
+/// certificate of its block, and the two are written together.
+pub(crate) struct DecisionCertificateFetcher {
+    sqmr_sender: DecisionCertificateSqmrSender,
+    verifier: DecisionCertificateVerifier,
+    num_blocks_per_query: u64,
+    // The responses to the latest query, the block of the next response and the block after the
+    // last block of the query.
+    query:
+        Option<(ClientResponsesManager<DataOrFin<DecisionCertificate>>, BlockNumber, BlockNumber)>,
+}
+
+impl DecisionCertificateFetcher {
+    pub fn new(
+        sqmr_sender: DecisionCertificateSqmrSender,
+        verifier: DecisionCertificateVerifier,
+        num_blocks_per_query: u64,
+    ) -> Self {
+        Self { sqmr_sender, verifier, num_blocks_per_query, query: None }
+    }
+
+    /// Returns the verified decision certificate of the block with the given header, or None if
+    /// the peers didn't send a valid certificate yet. A peer which sent an invalid certificate is
+    /// reported.
+    pub async fn fetch(
+        &mut self,
+        header: &BlockHeader,
+    ) -> Result<Option<DecisionCertificate>, ParseDataError> {
+        let block_number = header.block_header_without_hash.block_number;
+        // The headers are synced in order, so the certificates are read from the latest query as
+        // long as it continues to the block.
+        let (mut responses_manager, end_block_number) = match self.query.take() {
+            Some((responses_manager, next_block_number, end_block_number))
+                if next_block_number == block_number =>
+            {
+                (responses_manager, end_block_number)
+            }
+            _ => {
+                debug!(
+                    "Downloading decision certificates for blocks [{}, {})",
+                    block_number.0,
+                    block_number.0 + self.num_blocks_per_query,
+                );
+                let responses_manager = self
+                    .sqmr_sender
+                    .send_new_query(DecisionCertificateQuery(Query {
+                        start_block: BlockHashOrNumber::Number(block_number),
+                        direction: Direction::Forward,
+                        limit: self.num_blocks_per_query,
+                        step: STEP,
+                    }))
+                    .await
+                    .map_err(P2PSyncClientError::from)?;
+                (responses_manager, BlockNumber(block_number.0 + self.num_blocks_per_query))
+            }
+        };
+
+        let maybe_decision_certificate =
+            tokio::time::timeout(NETWORK_DATA_TIMEOUT, responses_manager.next()).await?.ok_or(
+                P2PSyncClientError::ReceiverChannelTerminated {
+                    type_description: "decision certificates",
+                },
+            )?;
+        let decision_certificate = match maybe_decision_certificate {
+            Ok(DataOrFin(Some(decision_certificate))) => decision_certificate,
+            // The peer doesn't have the certificate yet.
+            Ok(DataOrFin(None)) => return Ok(None),
+            Err(err) => {
+                report_peer(responses_manager, BadPeerError::ProtobufConversionError(err));
+                return Ok(None);
+            }
+        };
+        if let Err(err) = self.verify(header, &decision_certificate) {
+            report_peer(responses_manager, err);
+            return Ok(None);
+        }
+
+        let next_block_number = block_number.unchecked_next();
+        if next_block_number < end_block_number {
+            self.query = Some((responses_manager, next_block_number, end_block_number));
+        }
+        Ok(Some(decision_certificate))
+    }
+
+    fn verify(
+        &self,
+        header: &BlockHeader,
+        decision_certificate: &DecisionCertificate,
+    ) -> Result<(), BadPeerError> {
+        let block_number = header.block_header_without_hash.block_number;
+        if decision_certificate.block_number != block_number {
+            return Err(BadPeerError::DecisionCertificatesUnordered {
+                expected_block_number: block_number,
+                actual_block_number: decision_certificate.block_number,
+            });
+        }
+        if !decision_certificate.is_for_block(header, self.verifier.decided_field()) {
+            return Err(BadPeerError::DecisionCertificateBlockHashMismatch {
+                block_number,
+                block_hash: header.block_hash,
+                certificate_block_hash: decision_certificate.block_hash,
+            });
+        }
+        self.verifier
+            .verify(decision_certificate)
+            .map_err(|error| BadPeerError::InvalidDecisionCertificate { block_number, error })
+    }
+}
+
+// The header is requested again, so only the peer which sent the certificate is reported.
+fn report_peer(
+    responses_manager: ClientResponsesManager<DataOrFin<DecisionCertificate>>,
+    err: BadPeerError,
+) {
+    warn!("Decision certificate query returned with bad peer error: {:?}. reporting peer.", err);
+    responses_manager.report_peer();
+}
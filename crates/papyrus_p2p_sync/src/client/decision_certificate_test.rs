@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use futures::FutureExt;
+use papyrus_protobuf::sync::{BlockHashOrNumber, DataOrFin, Direction, Query};
+use papyrus_storage::header::HeaderStorageReader;
+use papyrus_test_utils::get_rng;
+use starknet_api::block::{BlockHash, BlockNumber, DecisionCertificate};
+use starknet_types_core::felt::Felt;
+
+use super::test_utils::{
+    random_header,
+    run_test,
+    signed_decision_certificate,
+    wait_for_marker,
+    Action,
+    DataType,
+    SLEEP_DURATION_TO_LET_SYNC_ADVANCE,
+    TIMEOUT_FOR_TEST,
+};
+
+#[tokio::test]
+async fn headers_are_synced_with_decision_certificates() {
+    const NUM_BLOCKS: u64 = 2;
+
+    let mut rng = get_rng();
+    let headers: Vec<_> =
+        (0..NUM_BLOCKS).map(|i| random_header(&mut rng, BlockNumber(i), None, None)).collect();
+    let certificates: Vec<_> = headers
+        .iter()
+        .map(|header| {
+            signed_decision_certificate(
+                header.block_header.block_header_without_hash.block_number,
+                header.block_header.block_hash,
+            )
+        })
+        .collect();
+
+    let mut actions = vec![Action::ReceiveQuery(Box::new(|_query| ()), DataType::Header)];
+    actions.extend(headers.into_iter().map(|header| Action::SendHeader(DataOrFin(Some(header)))));
+    actions.push(Action::SendHeader(DataOrFin(None)));
+    // The certificates are requested once the first header is received.
+    actions.push(Action::ReceiveQuery(
+        Box::new(|query| {
+            assert_eq!(
+                query,
+                Query {
+                    start_block: BlockHashOrNumber::Number(BlockNumber(0)),
+                    direction: Direction::Forward,
+                    limit: NUM_BLOCKS,
+                    step: 1,
+                }
+            )
+        }),
+        DataType::DecisionCertificate,
+    ));
+    actions.extend(
+        certificates
+            .iter()
+            .cloned()
+            .map(|certificate| Action::SendDecisionCertificate(DataOrFin(Some(certificate)))),
+    );
+    actions.push(Action::CheckStorage(Box::new(move |reader| {
+        async move {
+            wait_for_marker(
+                DataType::Header,
+                &reader,
+                BlockNumber(NUM_BLOCKS),
+                SLEEP_DURATION_TO_LET_SYNC_ADVANCE,
+                TIMEOUT_FOR_TEST,
+            )
+            .await;
+            // Every header is written with its certificate.
+            let txn = reader.begin_ro_txn().unwrap();
+            assert_eq!(txn.get_decision_certificate_marker().unwrap(), BlockNumber(NUM_BLOCKS));
+            for certificate in certificates {
+                assert_eq!(
+                    txn.get_decision_certificate(certificate.block_number).unwrap(),
+                    Some(certificate)
+                );
+            }
+        }
+        .boxed()
+    })));
+
+    run_test(
+        HashMap::from([
+            (DataType::Header, NUM_BLOCKS),
+            (DataType::DecisionCertificate, NUM_BLOCKS),
+        ]),
+        actions,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn header_with_invalid_decision_certificate_is_not_accepted() {
+    let header = random_header(&mut get_rng(), BlockNumber(0), None, None);
+    let block_hash = header.block_header.block_hash;
+    let certificate_of_another_block =
+        signed_decision_certificate(BlockNumber(0), BlockHash(block_hash.0 + Felt::ONE));
+    let certificate_without_quorum = DecisionCertificate {
+        precommits: vec![],
+        ..signed_decision_certificate(BlockNumber(0), block_hash)
+    };
+
+    let mut actions = Vec::new();
+    for certificate in [certificate_of_another_block, certificate_without_quorum] {
+        actions.extend([
+            // We already validate the query content in other tests.
+            Action::ReceiveQuery(Box::new(|_query| ()), DataType::Header),
+            Action::SendHeader(DataOrFin(Some(header.clone()))),
+            Action::ReceiveQuery(Box::new(|_query| ()), DataType::DecisionCertificate),
+            Action::SendDecisionCertificate(DataOrFin(Some(certificate))),
+            // Only the peer which sent the certificate is reported, and the header is requested
+            // again.
+            Action::ValidateReportSent(DataType::DecisionCertificate),
+        ]);
+    }
+    actions.push(Action::ReceiveQuery(Box::new(|_query| ()), DataType::Header));
+    // The header isn't accepted without a valid certificate.
+    actions.push(Action::CheckStorage(Box::new(|reader| {
+        async move {
+            let txn = reader.begin_ro_txn().unwrap();
+            assert_eq!(BlockNumber(0), txn.get_header_marker().unwrap());
+            assert_eq!(BlockNumber(0), txn.get_decision_certificate_marker().unwrap());
+        }
+        .boxed()
+    })));
+
+    run_test(HashMap::from([(DataType::Header, 1), (DataType::DecisionCertificate, 1)]), actions)
+        .await;
+}
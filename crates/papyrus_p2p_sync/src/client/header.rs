@@ -7,9 +7,10 @@ use papyrus_network::network_manager::ClientResponsesManager;
 use papyrus_protobuf::sync::{DataOrFin, SignedBlockHeader};
 use papyrus_storage::header::{HeaderStorageReader, HeaderStorageWriter};
 use papyrus_storage::{StorageError, StorageReader, StorageWriter};
-use starknet_api::block::BlockNumber;
+use starknet_api::block::{BlockNumber, DecisionCertificate};
 use tracing::debug;
 
+use super::decision_certificate::DecisionCertificateFetcher;
 use super::stream_builder::{
     BadPeerError,
     BlockData,
@@ -19,37 +20,40 @@ use super::stream_builder::{
 };
 use super::{P2PSyncClientError, ALLOWED_SIGNATURES_LENGTH, NETWORK_DATA_TIMEOUT};
 
-impl BlockData for SignedBlockHeader {
+// A header, with the decision certificate of its block if certificates are synced.
+impl BlockData for (SignedBlockHeader, Option<DecisionCertificate>) {
     #[allow(clippy::as_conversions)] // FIXME: use int metrics so `as f64` may be removed.
     fn write_to_storage(
         self: Box<Self>,
         storage_writer: &mut StorageWriter,
     ) -> Result<(), StorageError> {
-        storage_writer
+        let (signed_block_header, decision_certificate) = *self;
+        let block_number = signed_block_header.block_header.block_header_without_hash.block_number;
+        let mut txn = storage_writer
             .begin_rw_txn()?
-            .append_header(
-                self.block_header.block_header_without_hash.block_number,
-                &self.block_header,
-            )?
+            .append_header(block_number, &signed_block_header.block_header)?
             .append_block_signature(
-                self.block_header.block_header_without_hash.block_number,
-                self
+                block_number,
+                signed_block_header
                     .signatures
                     // In the future we will support multiple signatures.
                     .first()
                     // The verification that the size of the vector is 1 is done in the data
                     // verification.
                     .expect("Vec::first should return a value on a vector of size 1"),
-            )?
-            .commit()?;
-        gauge!(
-            papyrus_metrics::PAPYRUS_HEADER_MARKER,
-            self.block_header.block_header_without_hash.block_number.unchecked_next().0 as f64
-        );
+            )?;
+        if let Some(decision_certificate) = decision_certificate {
+            txn = txn.append_decision_certificate(block_number, &decision_certificate)?;
+        }
+        txn.commit()?;
+        gauge!(papyrus_metrics::PAPYRUS_HEADER_MARKER, block_number.unchecked_next().0 as f64);
         // TODO(shahak): Fix code dup with central sync
         let time_delta = Utc::now()
             - Utc
-                .timestamp_opt(self.block_header.block_header_without_hash.timestamp.0 as i64, 0)
+                .timestamp_opt(
+                    signed_block_header.block_header.block_header_without_hash.timestamp.0 as i64,
+                    0,
+                )
                 .single()
                 .expect("block timestamp should be valid");
         let header_latency = time_delta.num_seconds();
@@ -61,15 +65,19 @@ impl BlockData for SignedBlockHeader {
     }
 }
 
-pub(crate) struct HeaderStreamBuilder;
+pub(crate) struct HeaderStreamBuilder {
+    // The decision certificates are synced with the headers if the validators are known.
+    pub decision_certificate_fetcher: Option<DecisionCertificateFetcher>,
+}
 
 impl DataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
-    type Output = SignedBlockHeader;
+    type Output = (SignedBlockHeader, Option<DecisionCertificate>);
 
     const TYPE_DESCRIPTION: &'static str = "headers";
     const BLOCK_NUMBER_LIMIT: BlockNumberLimit = BlockNumberLimit::Unlimited;
 
     fn parse_data_for_block<'a>(
+        &'a mut self,
         signed_headers_response_manager: &'a mut ClientResponsesManager<
             DataOrFin<SignedBlockHeader>,
         >,
@@ -104,7 +112,21 @@ impl DataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
                     signatures: signed_block_header.signatures,
                 }));
             }
-            Ok(Some(signed_block_header))
+            let decision_certificate = match &mut self.decision_certificate_fetcher {
+                Some(decision_certificate_fetcher) => {
+                    match decision_certificate_fetcher
+                        .fetch(&signed_block_header.block_header)
+                        .await?
+                    {
+                        Some(decision_certificate) => Some(decision_certificate),
+                        // The header is accepted only with the certificate of its block, so it is
+                        // requested again.
+                        None => return Ok(None),
+                    }
+                }
+                None => None,
+            };
+            Ok(Some((signed_block_header, decision_certificate)))
         }
         .boxed()
     }
@@ -1,6 +1,9 @@
 mod class;
 #[cfg(test)]
 mod class_test;
+mod decision_certificate;
+#[cfg(test)]
+mod decision_certificate_test;
 mod header;
 #[cfg(test)]
 mod header_test;
@@ -18,6 +21,7 @@ use std::collections::BTreeMap;
 use std::time::Duration;
 
 use class::ClassStreamBuilder;
+use decision_certificate::DecisionCertificateFetcher;
 use futures::channel::mpsc::SendError;
 use futures::stream::BoxStream;
 use futures::Stream;
@@ -26,10 +30,12 @@ use papyrus_common::pending_classes::ApiContractClass;
 use papyrus_config::converters::deserialize_milliseconds_to_duration;
 use papyrus_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use papyrus_consensus::decision_certificate::DecisionCertificateVerifier;
 use papyrus_network::network_manager::SqmrClientSender;
 use papyrus_protobuf::sync::{
     ClassQuery,
     DataOrFin,
+    DecisionCertificateQuery,
     HeaderQuery,
     SignedBlockHeader,
     StateDiffChunk,
@@ -38,9 +44,10 @@ use papyrus_protobuf::sync::{
 };
 use papyrus_storage::{StorageError, StorageReader, StorageWriter};
 use serde::{Deserialize, Serialize};
-use starknet_api::block::BlockNumber;
+use starknet_api::block::{BlockNumber, DecisionCertificate};
 use starknet_api::core::ClassHash;
 use starknet_api::transaction::FullTransaction;
+use starknet_committer::block_committer::errors::BlockCommitmentError;
 use starknet_state_sync_types::state_sync_types::SyncBlock;
 use state_diff::StateDiffStreamBuilder;
 use stream_builder::{DataStreamBuilder, DataStreamResult};
@@ -59,6 +66,7 @@ pub struct P2PSyncClientConfig {
     pub num_block_state_diffs_per_query: u64,
     pub num_block_transactions_per_query: u64,
     pub num_block_classes_per_query: u64,
+    pub num_decision_certificates_per_query: u64,
     #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
     pub wait_period_for_new_data: Duration,
     pub buffer_size: usize,
@@ -93,6 +101,13 @@ impl SerializeConfig for P2PSyncClientConfig {
                 "The maximum amount of block's classes to ask from peers in each iteration.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "num_decision_certificates_per_query",
+                &self.num_decision_certificates_per_query,
+                "The maximum amount of blocks to ask their decision certificates from peers in \
+                 each iteration.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "wait_period_for_new_data",
                 &self.wait_period_for_new_data.as_millis(),
@@ -128,6 +143,7 @@ impl Default for P2PSyncClientConfig {
             num_block_state_diffs_per_query: 100,
             num_block_transactions_per_query: 100,
             num_block_classes_per_query: 100,
+            num_decision_certificates_per_query: 10000,
             wait_period_for_new_data: Duration::from_millis(50),
             // TODO(eitan): split this by protocol
             buffer_size: 100000,
@@ -155,12 +171,16 @@ pub enum P2PSyncClientError {
     StorageError(#[from] StorageError),
     #[error(transparent)]
     SendError(#[from] SendError),
+    #[error(transparent)]
+    StateCommitmentError(#[from] BlockCommitmentError),
 }
 
 type HeaderSqmrSender = SqmrClientSender<HeaderQuery, DataOrFin<SignedBlockHeader>>;
 type StateSqmrDiffSender = SqmrClientSender<StateDiffQuery, DataOrFin<StateDiffChunk>>;
 type TransactionSqmrSender = SqmrClientSender<TransactionQuery, DataOrFin<FullTransaction>>;
 type ClassSqmrSender = SqmrClientSender<ClassQuery, DataOrFin<(ApiContractClass, ClassHash)>>;
+type DecisionCertificateSqmrSender =
+    SqmrClientSender<DecisionCertificateQuery, DataOrFin<DecisionCertificate>>;
 
 pub struct P2PSyncClientChannels {
     header_sender: HeaderSqmrSender,
@@ -168,6 +188,7 @@ pub struct P2PSyncClientChannels {
     transaction_sender: TransactionSqmrSender,
     #[allow(dead_code)]
     class_sender: ClassSqmrSender,
+    decision_certificate_sender: DecisionCertificateSqmrSender,
 }
 
 impl P2PSyncClientChannels {
@@ -176,15 +197,32 @@ impl P2PSyncClientChannels {
         state_diff_sender: StateSqmrDiffSender,
         transaction_sender: TransactionSqmrSender,
         class_sender: ClassSqmrSender,
+        decision_certificate_sender: DecisionCertificateSqmrSender,
     ) -> Self {
-        Self { header_sender, state_diff_sender, transaction_sender, class_sender }
+        Self {
+            header_sender,
+            state_diff_sender,
+            transaction_sender,
+            class_sender,
+            decision_certificate_sender,
+        }
     }
     pub(crate) fn create_stream(
         self,
         storage_reader: StorageReader,
         config: P2PSyncClientConfig,
+        decision_certificate_verifier: Option<DecisionCertificateVerifier>,
     ) -> impl Stream<Item = DataStreamResult> + Send + 'static {
-        let header_stream = HeaderStreamBuilder::create_stream(
+        // Decision certificates can only be verified when the validators are known. Otherwise,
+        // headers are synced without them.
+        let decision_certificate_fetcher = decision_certificate_verifier.map(|verifier| {
+            DecisionCertificateFetcher::new(
+                self.decision_certificate_sender,
+                verifier,
+                config.num_decision_certificates_per_query,
+            )
+        });
+        let header_stream = HeaderStreamBuilder { decision_certificate_fetcher }.create_stream(
             self.header_sender,
             storage_reader.clone(),
             None,
@@ -193,7 +231,7 @@ impl P2PSyncClientChannels {
             config.stop_sync_at_block_number,
         );
 
-        let state_diff_stream = StateDiffStreamBuilder::create_stream(
+        let state_diff_stream = StateDiffStreamBuilder.create_stream(
             self.state_diff_sender,
             storage_reader.clone(),
             None,
@@ -202,7 +240,7 @@ impl P2PSyncClientChannels {
             config.stop_sync_at_block_number,
         );
 
-        let transaction_stream = TransactionStreamFactory::create_stream(
+        let transaction_stream = TransactionStreamFactory.create_stream(
             self.transaction_sender,
             storage_reader.clone(),
             None,
@@ -211,7 +249,7 @@ impl P2PSyncClientChannels {
             config.stop_sync_at_block_number,
         );
 
-        let class_stream = ClassStreamBuilder::create_stream(
+        let class_stream = ClassStreamBuilder.create_stream(
             self.class_sender,
             storage_reader.clone(),
             None,
@@ -231,6 +269,7 @@ pub struct P2PSyncClient {
     p2p_sync_channels: P2PSyncClientChannels,
     #[allow(dead_code)]
     internal_blocks_receiver: BoxStream<'static, (BlockNumber, SyncBlock)>,
+    decision_certificate_verifier: Option<DecisionCertificateVerifier>,
 }
 
 impl P2PSyncClient {
@@ -240,14 +279,25 @@ impl P2PSyncClient {
         storage_writer: StorageWriter,
         p2p_sync_channels: P2PSyncClientChannels,
         internal_blocks_receiver: BoxStream<'static, (BlockNumber, SyncBlock)>,
+        decision_certificate_verifier: Option<DecisionCertificateVerifier>,
     ) -> Self {
-        Self { config, storage_reader, storage_writer, p2p_sync_channels, internal_blocks_receiver }
+        Self {
+            config,
+            storage_reader,
+            storage_writer,
+            p2p_sync_channels,
+            internal_blocks_receiver,
+            decision_certificate_verifier,
+        }
     }
 
     #[instrument(skip(self), level = "debug", err)]
     pub async fn run(mut self) -> Result<(), P2PSyncClientError> {
-        let mut data_stream =
-            self.p2p_sync_channels.create_stream(self.storage_reader.clone(), self.config);
+        let mut data_stream = self.p2p_sync_channels.create_stream(
+            self.storage_reader.clone(),
+            self.config,
+            self.decision_certificate_verifier,
+        );
 
         loop {
             let data = data_stream.next().await.expect("Sync data stream should never end")?;
@@ -12,6 +12,7 @@ use papyrus_storage::state::{StateStorageReader, StateStorageWriter};
 use papyrus_storage::{StorageError, StorageReader, StorageWriter};
 use starknet_api::block::BlockNumber;
 use starknet_api::state::ThinStateDiff;
+use starknet_committer::forest::mdbx_forest::{commit_to_stored_forest, ForestUpdate};
 
 use super::stream_builder::BadPeerError;
 use crate::client::stream_builder::{
@@ -22,15 +23,20 @@ use crate::client::stream_builder::{
 };
 use crate::client::{P2PSyncClientError, NETWORK_DATA_TIMEOUT};
 
-impl BlockData for (ThinStateDiff, BlockNumber) {
+impl BlockData for (ThinStateDiff, BlockNumber, Option<ForestUpdate>) {
     #[latency_histogram("p2p_sync_state_diff_write_to_storage_latency_seconds", true)]
     #[allow(clippy::as_conversions)] // FIXME: use int metrics so `as f64` may be removed.
     fn write_to_storage(
         self: Box<Self>,
         storage_writer: &mut StorageWriter,
     ) -> Result<(), StorageError> {
-        storage_writer.begin_rw_txn()?.append_state_diff(self.1, self.0)?.commit()?;
-        gauge!(papyrus_metrics::PAPYRUS_STATE_MARKER, self.1.unchecked_next().0 as f64);
+        let (state_diff, block_number, forest_update) = *self;
+        let mut txn = storage_writer.begin_rw_txn()?;
+        if let Some(forest_update) = forest_update {
+            txn = forest_update.write(txn, block_number)?;
+        }
+        txn.append_state_diff(block_number, state_diff)?.commit()?;
+        gauge!(papyrus_metrics::PAPYRUS_STATE_MARKER, block_number.unchecked_next().0 as f64);
         Ok(())
     }
 }
@@ -38,13 +44,14 @@ impl BlockData for (ThinStateDiff, BlockNumber) {
 pub(crate) struct StateDiffStreamBuilder;
 
 impl DataStreamBuilder<StateDiffChunk> for StateDiffStreamBuilder {
-    type Output = (ThinStateDiff, BlockNumber);
+    type Output = (ThinStateDiff, BlockNumber, Option<ForestUpdate>);
 
     const TYPE_DESCRIPTION: &'static str = "state diffs";
     const BLOCK_NUMBER_LIMIT: BlockNumberLimit = BlockNumberLimit::HeaderMarker;
 
     #[latency_histogram("p2p_sync_state_diff_parse_data_for_block_latency_seconds", true)]
     fn parse_data_for_block<'a>(
+        &'a mut self,
         state_diff_chunks_response_manager: &'a mut ClientResponsesManager<
             DataOrFin<StateDiffChunk>,
         >,
@@ -102,7 +109,12 @@ impl DataStreamBuilder<StateDiffChunk> for StateDiffStreamBuilder {
             }
 
             validate_deprecated_declared_classes_non_conflicting(&result)?;
-            Ok(Some((result, block_number)))
+            // The state diff of the previous block is already stored, so the forest before this
+            // block is in storage.
+            let forest_update = commit_to_stored_forest(storage_reader, block_number, &result)
+                .await
+                .map_err(P2PSyncClientError::from)?;
+            Ok(Some((result, block_number, forest_update)))
         }
         .boxed()
     }
@@ -6,13 +6,14 @@ use futures::channel::mpsc::Receiver;
 use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use futures::StreamExt;
+use papyrus_consensus::decision_certificate::DecisionCertificateError;
 use papyrus_network::network_manager::{ClientResponsesManager, SqmrClientSender};
 use papyrus_protobuf::converters::ProtobufConversionError;
 use papyrus_protobuf::sync::{BlockHashOrNumber, DataOrFin, Direction, Query};
 use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{StorageError, StorageReader, StorageWriter};
-use starknet_api::block::{BlockNumber, BlockSignature};
+use starknet_api::block::{BlockHash, BlockNumber, BlockSignature};
 use starknet_api::core::ClassHash;
 use tracing::{debug, info, warn};
 
@@ -36,6 +37,7 @@ pub(crate) enum BlockNumberLimit {
 
 pub(crate) trait DataStreamBuilder<InputFromNetwork>
 where
+    Self: Sized + Send + 'static,
     InputFromNetwork: Send + 'static,
     DataOrFin<InputFromNetwork>: TryFrom<Vec<u8>, Error = ProtobufConversionError>,
 {
@@ -46,6 +48,7 @@ where
 
     // Async functions in trait don't work well with argument references
     fn parse_data_for_block<'a>(
+        &'a mut self,
         client_response_manager: &'a mut ClientResponsesManager<DataOrFin<InputFromNetwork>>,
         block_number: BlockNumber,
         storage_reader: &'a StorageReader,
@@ -54,6 +57,7 @@ where
     fn get_start_block_number(storage_reader: &StorageReader) -> Result<BlockNumber, StorageError>;
 
     fn create_stream<TQuery>(
+        mut self,
         mut sqmr_sender: SqmrClientSender<TQuery, DataOrFin<InputFromNetwork>>,
         storage_reader: StorageReader,
         _internal_block_receiver: Option<Receiver<(BlockNumber, Self::Output)>>,
@@ -105,7 +109,7 @@ where
                     .await?;
 
                 while current_block_number.0 < end_block_number {
-                    match Self::parse_data_for_block(
+                    match self.parse_data_for_block(
                         &mut client_response_manager, current_block_number, &storage_reader
                     ).await {
                         Ok(Some(output)) => yield Ok(Box::<dyn BlockData>::from(Box::new(output))),
@@ -194,6 +198,25 @@ pub(crate) enum BadPeerError {
     ClassNotInStateDiff { class_hash: ClassHash },
     #[error("Received two classes with the same hash: {class_hash}.")]
     DuplicateClass { class_hash: ClassHash },
+    #[error(
+        "Decision certificates returned unordered from the network. Expected a certificate of \
+         {expected_block_number}, got {actual_block_number}."
+    )]
+    DecisionCertificatesUnordered {
+        expected_block_number: BlockNumber,
+        actual_block_number: BlockNumber,
+    },
+    #[error(
+        "The decision certificate of {block_number} is for {certificate_block_hash}, which \
+         doesn't match the block with hash {block_hash}."
+    )]
+    DecisionCertificateBlockHashMismatch {
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+        certificate_block_hash: BlockHash,
+    },
+    #[error("The decision certificate of {block_number} is invalid: {error}")]
+    InvalidDecisionCertificate { block_number: BlockNumber, error: DecisionCertificateError },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -6,14 +6,19 @@ use futures::future::BoxFuture;
 use futures::StreamExt;
 use lazy_static::lazy_static;
 use papyrus_common::pending_classes::ApiContractClass;
+use papyrus_consensus::decision_certificate::DecisionCertificateVerifier;
+use papyrus_consensus::signing::vote_hash;
+use papyrus_consensus::types::{ValidatorId, DEFAULT_VALIDATOR_ID};
 use papyrus_network::network_manager::test_utils::{
     mock_register_sqmr_protocol_client,
     MockClientResponsesManager,
 };
 use papyrus_network::network_manager::GenericReceiver;
+use papyrus_protobuf::consensus::{Vote, VoteType};
 use papyrus_protobuf::sync::{
     ClassQuery,
     DataOrFin,
+    DecisionCertificateQuery,
     HeaderQuery,
     Query,
     SignedBlockHeader,
@@ -36,9 +41,12 @@ use starknet_api::block::{
     BlockHeaderWithoutHash,
     BlockNumber,
     BlockSignature,
+    DecidedHeaderField,
+    DecisionCertificate,
+    PrecommitSignature,
 };
-use starknet_api::core::ClassHash;
-use starknet_api::crypto::utils::Signature;
+use starknet_api::core::{ChainId, ClassHash};
+use starknet_api::crypto::utils::{get_public_key, sign_message_hash, Signature};
 use starknet_api::hash::StarkHash;
 use starknet_api::transaction::FullTransaction;
 use starknet_types_core::felt::Felt;
@@ -51,6 +59,7 @@ pub const HEADER_QUERY_LENGTH: u64 = 5;
 pub const STATE_DIFF_QUERY_LENGTH: u64 = 3;
 pub const CLASS_DIFF_QUERY_LENGTH: u64 = 3;
 pub const TRANSACTION_QUERY_LENGTH: u64 = 3;
+pub const DECISION_CERTIFICATE_QUERY_LENGTH: u64 = 3;
 pub const SLEEP_DURATION_TO_LET_SYNC_ADVANCE: Duration = Duration::from_millis(10);
 pub const WAIT_PERIOD_FOR_NEW_DATA: Duration = Duration::from_secs(1);
 pub const TIMEOUT_FOR_NEW_QUERY_AFTER_PARTIAL_RESPONSE: Duration =
@@ -62,6 +71,7 @@ lazy_static! {
         num_block_state_diffs_per_query: STATE_DIFF_QUERY_LENGTH,
         num_block_transactions_per_query: TRANSACTION_QUERY_LENGTH,
         num_block_classes_per_query: CLASS_DIFF_QUERY_LENGTH,
+        num_decision_certificates_per_query: DECISION_CERTIFICATE_QUERY_LENGTH,
         wait_period_for_new_data: WAIT_PERIOD_FOR_NEW_DATA,
         buffer_size: BUFFER_SIZE,
         stop_sync_at_block_number: None,
@@ -75,6 +85,8 @@ pub(crate) type TransactionTestPayload =
     MockClientResponsesManager<TransactionQuery, DataOrFin<FullTransaction>>;
 pub(crate) type ClassTestPayload =
     MockClientResponsesManager<ClassQuery, DataOrFin<(ApiContractClass, ClassHash)>>;
+pub(crate) type DecisionCertificateTestPayload =
+    MockClientResponsesManager<DecisionCertificateQuery, DataOrFin<DecisionCertificate>>;
 
 // TODO(Eitan): Use SqmrSubscriberChannels once there is a utility function for testing
 pub struct TestArgs {
@@ -101,18 +113,22 @@ pub fn setup() -> TestArgs {
         mock_register_sqmr_protocol_client(buffer_size);
     let (class_sender, mock_class_response_manager) =
         mock_register_sqmr_protocol_client(buffer_size);
+    let (decision_certificate_sender, _) = mock_register_sqmr_protocol_client(buffer_size);
     let p2p_sync_channels = P2PSyncClientChannels {
         header_sender,
         state_diff_sender,
         transaction_sender,
         class_sender,
+        decision_certificate_sender,
     };
+    // Decision certificates are not synced without a verifier.
     let p2p_sync = P2PSyncClient::new(
         p2p_sync_config,
         storage_reader.clone(),
         storage_writer,
         p2p_sync_channels,
         futures::stream::pending().boxed(),
+        None,
     );
     TestArgs {
         p2p_sync,
@@ -132,6 +148,7 @@ pub enum DataType {
     StateDiff,
     #[allow(dead_code)]
     Class,
+    DecisionCertificate,
 }
 
 pub enum Action {
@@ -152,6 +169,9 @@ pub enum Action {
     /// call ReceiveQuery with DataType::Class before.
     #[allow(dead_code)]
     SendClass(DataOrFin<(ApiContractClass, ClassHash)>),
+    /// Send a decision certificate as a response to a query we got from ReceiveQuery. Will panic
+    /// if didn't call ReceiveQuery with DataType::DecisionCertificate before.
+    SendDecisionCertificate(DataOrFin<DecisionCertificate>),
     /// Perform custom validations on the storage. Returns back the storage reader it received as
     /// input
     CheckStorage(Box<dyn FnOnce(StorageReader) -> BoxFuture<'static, ()>>),
@@ -172,6 +192,10 @@ pub async fn run_test(max_query_lengths: HashMap<DataType, u64>, actions: Vec<Ac
             .cloned()
             .unwrap_or(1),
         num_block_classes_per_query: max_query_lengths.get(&DataType::Class).cloned().unwrap_or(1),
+        num_decision_certificates_per_query: max_query_lengths
+            .get(&DataType::DecisionCertificate)
+            .cloned()
+            .unwrap_or(1),
         wait_period_for_new_data: WAIT_PERIOD_FOR_NEW_DATA,
         buffer_size: BUFFER_SIZE,
         stop_sync_at_block_number: None,
@@ -184,11 +208,14 @@ pub async fn run_test(max_query_lengths: HashMap<DataType, u64>, actions: Vec<Ac
     let (transaction_sender, mut mock_transaction_network) =
         mock_register_sqmr_protocol_client(buffer_size);
     let (class_sender, mut mock_class_network) = mock_register_sqmr_protocol_client(buffer_size);
+    let (decision_certificate_sender, mut mock_decision_certificate_network) =
+        mock_register_sqmr_protocol_client(buffer_size);
     let p2p_sync_channels = P2PSyncClientChannels {
         header_sender,
         state_diff_sender,
         transaction_sender,
         class_sender,
+        decision_certificate_sender,
     };
     let p2p_sync = P2PSyncClient::new(
         p2p_sync_config,
@@ -196,12 +223,14 @@ pub async fn run_test(max_query_lengths: HashMap<DataType, u64>, actions: Vec<Ac
         storage_writer,
         p2p_sync_channels,
         futures::stream::pending().boxed(),
+        Some(test_decision_certificate_verifier()),
     );
 
     let mut headers_current_query_responses_manager = None;
     let mut state_diff_current_query_responses_manager = None;
     let mut transaction_current_query_responses_manager = None;
     let mut class_current_query_responses_manager = None;
+    let mut decision_certificate_current_query_responses_manager = None;
 
     tokio::select! {
         _ = async {
@@ -233,6 +262,12 @@ pub async fn run_test(max_query_lengths: HashMap<DataType, u64>, actions: Vec<Ac
                                     &mut class_current_query_responses_manager,
                                 ).await.0
                             }
+                            DataType::DecisionCertificate => {
+                                get_next_query_and_update_responses_manager(
+                                    &mut mock_decision_certificate_network,
+                                    &mut decision_certificate_current_query_responses_manager,
+                                ).await.0
+                            }
                         };
                         validate_query_fn(query);
                     }
@@ -256,6 +291,12 @@ pub async fn run_test(max_query_lengths: HashMap<DataType, u64>, actions: Vec<Ac
                             .expect("Called SendClass without calling ReceiveQuery");
                         responses_manager.send_response(class_or_fin).await.unwrap();
                     }
+                    Action::SendDecisionCertificate(decision_certificate_or_fin) => {
+                        let responses_manager =
+                            decision_certificate_current_query_responses_manager.as_mut()
+                                .expect("Called SendDecisionCertificate without calling ReceiveQuery");
+                        responses_manager.send_response(decision_certificate_or_fin).await.unwrap();
+                    }
                     Action::CheckStorage(check_storage_fn) => {
                         // We tried avoiding the clone here but it causes lifetime issues.
                         check_storage_fn(storage_reader.clone()).await;
@@ -288,6 +329,14 @@ pub async fn run_test(max_query_lengths: HashMap<DataType, u64>, actions: Vec<Ac
                                 data type");
                         responses_manager.assert_reported(TIMEOUT_FOR_TEST).await;
                     }
+                    Action::ValidateReportSent(DataType::DecisionCertificate) => {
+                        let responses_manager =
+                            decision_certificate_current_query_responses_manager.take()
+                                .expect(
+                                    "Called ValidateReportSent without calling ReceiveQuery on the \
+                                     same data type");
+                        responses_manager.assert_reported(TIMEOUT_FOR_TEST).await;
+                    }
                 }
             }
         } => {},
@@ -343,6 +392,48 @@ pub fn create_block_hashes_and_signatures(n_blocks: u8) -> Vec<(BlockHash, Block
         .collect()
 }
 
+fn test_chain_id() -> ChainId {
+    ChainId::Other("SN_TEST".to_string())
+}
+
+// The validator set of the tests has a single validator, whose private key is its id.
+fn test_validator() -> ValidatorId {
+    ValidatorId::from(DEFAULT_VALIDATOR_ID)
+}
+
+pub fn test_decision_certificate_verifier() -> DecisionCertificateVerifier {
+    let validator = test_validator();
+    DecisionCertificateVerifier::new(
+        test_chain_id(),
+        [(validator, 1)].into(),
+        [(validator, get_public_key(validator.0.key()))].into(),
+        DecidedHeaderField::BlockHash,
+    )
+}
+
+/// A decision certificate of the given block, with the precommit of the validator of the tests.
+pub fn signed_decision_certificate(
+    block_number: BlockNumber,
+    block_hash: BlockHash,
+) -> DecisionCertificate {
+    let voter = test_validator();
+    let vote = Vote {
+        vote_type: VoteType::Precommit,
+        height: block_number.0,
+        round: 0,
+        block_hash: Some(block_hash),
+        voter,
+        signature: Signature::default(),
+    };
+    let signature = sign_message_hash(&vote_hash(&test_chain_id(), &vote), voter.0.key()).unwrap();
+    DecisionCertificate {
+        block_number,
+        round: 0,
+        block_hash,
+        precommits: vec![PrecommitSignature { voter, signature }],
+    }
+}
+
 // TODO: Consider moving this to storage and to use poll wakeup instead of sleep
 pub(crate) async fn wait_for_marker(
     data_type: DataType,
@@ -362,6 +453,7 @@ pub(crate) async fn wait_for_marker(
             DataType::Transaction => txn.get_body_marker().unwrap(),
             DataType::StateDiff => txn.get_state_marker().unwrap(),
             DataType::Class => txn.get_class_marker().unwrap(),
+            DataType::DecisionCertificate => txn.get_decision_certificate_marker().unwrap(),
         };
 
         if storage_marker >= expected_marker {
@@ -36,6 +36,7 @@ impl DataStreamBuilder<FullTransaction> for TransactionStreamFactory {
     const BLOCK_NUMBER_LIMIT: BlockNumberLimit = BlockNumberLimit::HeaderMarker;
 
     fn parse_data_for_block<'a>(
+        &'a mut self,
         transactions_response_manager: &'a mut ClientResponsesManager<DataOrFin<FullTransaction>>,
         block_number: BlockNumber,
         storage_reader: &'a StorageReader,
@@ -13,6 +13,7 @@ pub enum Protocol {
     Transaction,
     Class,
     Event,
+    DecisionCertificate,
 }
 
 impl Protocol {
@@ -23,6 +24,7 @@ impl Protocol {
             Protocol::Transaction => "/starknet/transactions/0.1.0-rc.0",
             Protocol::Class => "/starknet/classes/0.1.0-rc.0",
             Protocol::Event => "/starknet/events/0.1.0-rc.0",
+            Protocol::DecisionCertificate => "/starknet/decision_certificates/0.1.0-rc.0",
         }
     }
 }
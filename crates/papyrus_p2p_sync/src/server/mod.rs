@@ -9,6 +9,7 @@ use papyrus_protobuf::sync::{
     ClassQuery,
     ContractDiff,
     DataOrFin,
+    DecisionCertificateQuery,
     DeclaredClass,
     DeprecatedDeclaredClass,
     EventQuery,
@@ -24,7 +25,7 @@ use papyrus_storage::class::ClassStorageReader;
 use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{db, StorageReader, StorageTxn};
-use starknet_api::block::BlockNumber;
+use starknet_api::block::{BlockNumber, DecisionCertificate};
 use starknet_api::core::ClassHash;
 use starknet_api::state::ThinStateDiff;
 use starknet_api::transaction::{Event, FullTransaction, TransactionHash};
@@ -73,6 +74,8 @@ type StateDiffReceiver = SqmrServerReceiver<StateDiffQuery, DataOrFin<StateDiffC
 type TransactionReceiver = SqmrServerReceiver<TransactionQuery, DataOrFin<FullTransaction>>;
 type ClassReceiver = SqmrServerReceiver<ClassQuery, DataOrFin<(ApiContractClass, ClassHash)>>;
 type EventReceiver = SqmrServerReceiver<EventQuery, DataOrFin<(Event, TransactionHash)>>;
+type DecisionCertificateReceiver =
+    SqmrServerReceiver<DecisionCertificateQuery, DataOrFin<DecisionCertificate>>;
 
 pub struct P2PSyncServerChannels {
     header_receiver: HeaderReceiver,
@@ -80,6 +83,7 @@ pub struct P2PSyncServerChannels {
     transaction_receiver: TransactionReceiver,
     class_receiver: ClassReceiver,
     event_receiver: EventReceiver,
+    decision_certificate_receiver: DecisionCertificateReceiver,
 }
 
 impl P2PSyncServerChannels {
@@ -89,6 +93,7 @@ impl P2PSyncServerChannels {
         transaction_receiver: TransactionReceiver,
         class_receiver: ClassReceiver,
         event_receiver: EventReceiver,
+        decision_certificate_receiver: DecisionCertificateReceiver,
     ) -> Self {
         Self {
             header_receiver,
@@ -96,6 +101,7 @@ impl P2PSyncServerChannels {
             transaction_receiver,
             class_receiver,
             event_receiver,
+            decision_certificate_receiver,
         }
     }
 }
@@ -114,6 +120,7 @@ impl P2PSyncServer {
             mut transaction_receiver,
             mut class_receiver,
             mut event_receiver,
+            mut decision_certificate_receiver,
         } = self.p2p_sync_channels;
         loop {
             tokio::select! {
@@ -147,6 +154,12 @@ impl P2PSyncServer {
                     );
                     register_query(self.storage_reader.clone(), server_query_manager);
                 }
+                maybe_server_query_manager = decision_certificate_receiver.next() => {
+                    let server_query_manager = maybe_server_query_manager.expect(
+                        "Decision certificate queries sender was unexpectedly dropped."
+                    );
+                    register_query(self.storage_reader.clone(), server_query_manager);
+                }
             };
         }
     }
@@ -326,6 +339,20 @@ impl FetchBlockDataFromDb for (Event, TransactionHash) {
     }
 }
 
+impl FetchBlockDataFromDb for DecisionCertificate {
+    fn fetch_block_data_from_db(
+        block_number: BlockNumber,
+        txn: &StorageTxn<'_, db::RO>,
+    ) -> Result<Vec<Self>, P2PSyncServerError> {
+        let decision_certificate = txn.get_decision_certificate(block_number)?.ok_or(
+            P2PSyncServerError::BlockNotFound {
+                block_hash_or_number: BlockHashOrNumber::Number(block_number),
+            },
+        )?;
+        Ok(vec![decision_certificate])
+    }
+}
+
 pub fn split_thin_state_diff(thin_state_diff: ThinStateDiff) -> Vec<StateDiffChunk> {
     let mut state_diff_chunks = Vec::new();
     #[cfg(not(test))]
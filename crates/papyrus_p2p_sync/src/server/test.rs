@@ -15,6 +15,7 @@ use papyrus_protobuf::sync::{
     BlockHashOrNumber,
     ClassQuery,
     DataOrFin,
+    DecisionCertificateQuery,
     Direction,
     EventQuery,
     HeaderQuery,
@@ -39,6 +40,7 @@ use starknet_api::block::{
     BlockHeaderWithoutHash,
     BlockNumber,
     BlockSignature,
+    DecisionCertificate,
 };
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::state::SierraContractClass;
@@ -169,6 +171,28 @@ async fn class_query_positive_flow() {
     run_test::<_, _, ClassQuery>(assert_class, 0, StartBlockType::Number).await;
 }
 
+#[tokio::test]
+async fn decision_certificate_query_positive_flow() {
+    let assert_decision_certificate = |data: Vec<DecisionCertificate>| {
+        assert_eq!(data.len(), NUM_OF_BLOCKS);
+        for (i, decision_certificate) in data.into_iter().enumerate() {
+            assert_eq!(decision_certificate.block_number.0, u64::try_from(i).unwrap());
+        }
+    };
+    run_test::<_, _, DecisionCertificateQuery>(
+        assert_decision_certificate,
+        0,
+        StartBlockType::Hash,
+    )
+    .await;
+    run_test::<_, _, DecisionCertificateQuery>(
+        assert_decision_certificate,
+        0,
+        StartBlockType::Number,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn header_query_some_blocks_are_missing() {
     let assert_signed_block_header = |data: Vec<SignedBlockHeader>| {
@@ -307,6 +331,25 @@ async fn class_query_some_blocks_are_missing() {
     .await;
 }
 
+#[tokio::test]
+async fn decision_certificate_query_some_blocks_are_missing() {
+    let assert_decision_certificate = |data: Vec<DecisionCertificate>| {
+        assert_eq!(data.len(), BLOCKS_DELTA);
+        for (i, decision_certificate) in data.into_iter().enumerate() {
+            assert_eq!(
+                decision_certificate.block_number.0,
+                u64::try_from(i + NUM_OF_BLOCKS - BLOCKS_DELTA).unwrap()
+            );
+        }
+    };
+    run_test::<_, _, DecisionCertificateQuery>(
+        assert_decision_certificate,
+        NUM_OF_BLOCKS - BLOCKS_DELTA,
+        StartBlockType::Number,
+    )
+    .await;
+}
+
 async fn run_test<T, F, TQuery>(
     assert_fn: F,
     start_block_number: usize,
@@ -332,6 +375,7 @@ async fn run_test<T, F, TQuery>(
         transaction_sender: _transaction_sender,
         class_sender: _class_sender,
         event_sender: _event_sender,
+        decision_certificate_sender: _decision_certificate_sender,
     } = setup();
 
     // put some data in the storage.
@@ -390,6 +434,8 @@ pub struct TestArgs {
     pub class_sender:
         Sender<ServerQueryManager<ClassQuery, DataOrFin<(ApiContractClass, ClassHash)>>>,
     pub event_sender: Sender<ServerQueryManager<EventQuery, DataOrFin<(Event, TransactionHash)>>>,
+    pub decision_certificate_sender:
+        Sender<ServerQueryManager<DecisionCertificateQuery, DataOrFin<DecisionCertificate>>>,
 }
 
 #[allow(clippy::type_complexity)]
@@ -401,12 +447,15 @@ fn setup() -> TestArgs {
         mock_register_sqmr_protocol_server(BUFFER_SIZE);
     let (class_receiver, class_sender) = mock_register_sqmr_protocol_server(BUFFER_SIZE);
     let (event_receiver, event_sender) = mock_register_sqmr_protocol_server(BUFFER_SIZE);
+    let (decision_certificate_receiver, decision_certificate_sender) =
+        mock_register_sqmr_protocol_server(BUFFER_SIZE);
     let p2p_sync_server_channels = P2PSyncServerChannels {
         header_receiver,
         state_diff_receiver,
         transaction_receiver,
         class_receiver,
         event_receiver,
+        decision_certificate_receiver,
     };
 
     let p2p_sync_server =
@@ -420,6 +469,7 @@ fn setup() -> TestArgs {
         transaction_sender,
         class_sender,
         event_sender,
+        decision_certificate_sender,
     }
 }
 use starknet_api::core::ClassHash;
@@ -451,6 +501,15 @@ fn insert_to_storage_test_blocks_up_to(storage_writer: &mut StorageWriter) {
             // right signatures.
             .append_block_signature(block_number, &BlockSignature::default())
             .unwrap()
+            .append_decision_certificate(
+                block_number,
+                &DecisionCertificate {
+                    block_number,
+                    block_hash: block_header.block_hash,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
             .append_state_diff(block_number, THIN_STATE_DIFFS[i].clone())
             .unwrap()
             .append_body(block_number, BlockBody{transactions: TXS[i].clone(),
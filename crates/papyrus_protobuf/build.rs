@@ -60,6 +60,7 @@ fn main() -> io::Result<()> {
             "src/proto/p2p/proto/state.proto",
             "src/proto/p2p/proto/transaction.proto",
             "src/proto/p2p/proto/consensus.proto",
+            "src/proto/p2p/proto/decision_certificate.proto",
         ],
         &["src/proto/"],
     )?;
@@ -1,5 +1,6 @@
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::transaction::{Transaction, TransactionHash};
 
 use crate::converters::ProtobufConversionError;
@@ -29,6 +30,8 @@ pub struct Vote {
     pub round: u32,
     pub block_hash: Option<BlockHash>,
     pub voter: ContractAddress,
+    /// The signature of the voter over the vote.
+    pub signature: Signature,
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -86,8 +89,9 @@ pub struct TransactionBatch {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProposalFin {
     /// The block hash of the proposed block.
-    /// TODO(Matan): Consider changing the content ID to a signature.
     pub proposal_content_id: BlockHash,
+    /// The signature of the proposer over the proposal's init and content id.
+    pub signature: Signature,
 }
 
 /// A part of the proposal.
@@ -116,6 +120,21 @@ impl TryInto<ProposalInit> for ProposalPart {
     }
 }
 
+impl TryInto<ProposalFin> for ProposalPart {
+    type Error = ProtobufConversionError;
+
+    fn try_into(self: ProposalPart) -> Result<ProposalFin, Self::Error> {
+        match self {
+            ProposalPart::Fin(fin) => Ok(fin),
+            _ => Err(ProtobufConversionError::WrongEnumVariant {
+                type_description: "ProposalPart",
+                expected: "Fin",
+                value_as_str: format!("{:?}", self),
+            }),
+        }
+    }
+}
+
 impl From<ProposalInit> for ProposalPart {
     fn from(value: ProposalInit) -> Self {
         ProposalPart::Init(value)
@@ -5,6 +5,7 @@ use std::convert::{TryFrom, TryInto};
 
 use prost::Message;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::crypto::utils::Signature;
 use starknet_api::hash::StarkHash;
 use starknet_api::transaction::{Transaction, TransactionHash};
 use starknet_types_core::felt::Felt;
@@ -102,8 +103,12 @@ impl TryFrom<protobuf::Vote> for Vote {
             .voter
             .ok_or(ProtobufConversionError::MissingField { field_description: "voter" })?
             .try_into()?;
+        let signature = value
+            .signature
+            .ok_or(ProtobufConversionError::MissingField { field_description: "signature" })?
+            .try_into()?;
 
-        Ok(Vote { vote_type, height, round, block_hash, voter })
+        Ok(Vote { vote_type, height, round, block_hash, voter, signature })
     }
 }
 
@@ -120,12 +125,35 @@ impl From<Vote> for protobuf::Vote {
             round: value.round,
             block_hash: value.block_hash.map(|hash| hash.0.into()),
             voter: Some(value.voter.into()),
+            signature: Some(value.signature.into()),
         }
     }
 }
 
 auto_impl_into_and_try_from_vec_u8!(Vote, protobuf::Vote);
 
+impl TryFrom<protobuf::ConsensusSignature> for Signature {
+    type Error = ProtobufConversionError;
+
+    fn try_from(value: protobuf::ConsensusSignature) -> Result<Self, Self::Error> {
+        let r = value
+            .r
+            .ok_or(ProtobufConversionError::MissingField { field_description: "signature::r" })?
+            .try_into()?;
+        let s = value
+            .s
+            .ok_or(ProtobufConversionError::MissingField { field_description: "signature::s" })?
+            .try_into()?;
+        Ok(Signature { r, s })
+    }
+}
+
+impl From<Signature> for protobuf::ConsensusSignature {
+    fn from(value: Signature) -> Self {
+        protobuf::ConsensusSignature { r: Some(value.r.into()), s: Some(value.s.into()) }
+    }
+}
+
 impl<T: Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversionError>>
     TryFrom<protobuf::StreamMessage> for StreamMessage<T>
 {
@@ -266,13 +294,20 @@ impl TryFrom<protobuf::ProposalFin> for ProposalFin {
             })?
             .try_into()?;
         let proposal_content_id = BlockHash(proposal_content_id);
-        Ok(ProposalFin { proposal_content_id })
+        let signature = value
+            .signature
+            .ok_or(ProtobufConversionError::MissingField { field_description: "signature" })?
+            .try_into()?;
+        Ok(ProposalFin { proposal_content_id, signature })
     }
 }
 
 impl From<ProposalFin> for protobuf::ProposalFin {
     fn from(value: ProposalFin) -> Self {
-        protobuf::ProposalFin { proposal_content_id: Some(value.proposal_content_id.0.into()) }
+        protobuf::ProposalFin {
+            proposal_content_id: Some(value.proposal_content_id.0.into()),
+            signature: Some(value.signature.into()),
+        }
     }
 }
 
@@ -0,0 +1,155 @@
+#[cfg(test)]
+#[path = "decision_certificate_test.rs"]
+mod decision_certificate_test;
+
+use prost::Message;
+use starknet_api::block::{BlockHash, BlockNumber, DecisionCertificate, PrecommitSignature};
+
+use super::ProtobufConversionError;
+use crate::sync::{DataOrFin, DecisionCertificateQuery, Query};
+use crate::{auto_impl_into_and_try_from_vec_u8, protobuf};
+
+impl TryFrom<protobuf::DecisionCertificatesResponse> for DataOrFin<DecisionCertificate> {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::DecisionCertificatesResponse) -> Result<Self, Self::Error> {
+        match value.certificate_message {
+            Some(protobuf::decision_certificates_response::CertificateMessage::Certificate(
+                certificate,
+            )) => Ok(Self(Some(certificate.try_into()?))),
+            Some(protobuf::decision_certificates_response::CertificateMessage::Fin(_)) => {
+                Ok(Self(None))
+            }
+            None => Err(ProtobufConversionError::MissingField {
+                field_description: "DecisionCertificatesResponse::certificate_message",
+            }),
+        }
+    }
+}
+
+impl From<DataOrFin<DecisionCertificate>> for protobuf::DecisionCertificatesResponse {
+    fn from(value: DataOrFin<DecisionCertificate>) -> Self {
+        match value.0 {
+            Some(certificate) => protobuf::DecisionCertificatesResponse {
+                certificate_message: Some(
+                    protobuf::decision_certificates_response::CertificateMessage::Certificate(
+                        certificate.into(),
+                    ),
+                ),
+            },
+            None => protobuf::DecisionCertificatesResponse {
+                certificate_message: Some(
+                    protobuf::decision_certificates_response::CertificateMessage::Fin(
+                        protobuf::Fin {},
+                    ),
+                ),
+            },
+        }
+    }
+}
+
+auto_impl_into_and_try_from_vec_u8!(
+    DataOrFin<DecisionCertificate>,
+    protobuf::DecisionCertificatesResponse
+);
+
+impl TryFrom<protobuf::DecisionCertificate> for DecisionCertificate {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::DecisionCertificate) -> Result<Self, Self::Error> {
+        let block_hash = BlockHash(
+            value
+                .block_hash
+                .ok_or(ProtobufConversionError::MissingField {
+                    field_description: "DecisionCertificate::block_hash",
+                })?
+                .try_into()?,
+        );
+        let precommits = value
+            .precommits
+            .into_iter()
+            .map(PrecommitSignature::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DecisionCertificate {
+            block_number: BlockNumber(value.block_number),
+            round: value.round,
+            block_hash,
+            precommits,
+        })
+    }
+}
+
+impl From<DecisionCertificate> for protobuf::DecisionCertificate {
+    fn from(value: DecisionCertificate) -> Self {
+        protobuf::DecisionCertificate {
+            block_number: value.block_number.0,
+            round: value.round,
+            block_hash: Some(value.block_hash.0.into()),
+            precommits: value.precommits.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<protobuf::PrecommitSignature> for PrecommitSignature {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::PrecommitSignature) -> Result<Self, Self::Error> {
+        let voter = value
+            .voter
+            .ok_or(ProtobufConversionError::MissingField {
+                field_description: "PrecommitSignature::voter",
+            })?
+            .try_into()?;
+        let signature = value
+            .signature
+            .ok_or(ProtobufConversionError::MissingField {
+                field_description: "PrecommitSignature::signature",
+            })?
+            .try_into()?;
+        Ok(PrecommitSignature { voter, signature })
+    }
+}
+
+impl From<PrecommitSignature> for protobuf::PrecommitSignature {
+    fn from(value: PrecommitSignature) -> Self {
+        protobuf::PrecommitSignature {
+            voter: Some(value.voter.into()),
+            signature: Some(value.signature.into()),
+        }
+    }
+}
+
+impl TryFrom<protobuf::DecisionCertificatesRequest> for Query {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::DecisionCertificatesRequest) -> Result<Self, Self::Error> {
+        Ok(DecisionCertificateQuery::try_from(value)?.0)
+    }
+}
+
+impl TryFrom<protobuf::DecisionCertificatesRequest> for DecisionCertificateQuery {
+    type Error = ProtobufConversionError;
+    fn try_from(value: protobuf::DecisionCertificatesRequest) -> Result<Self, Self::Error> {
+        Ok(DecisionCertificateQuery(
+            value
+                .iteration
+                .ok_or(ProtobufConversionError::MissingField {
+                    field_description: "DecisionCertificatesRequest::iteration",
+                })?
+                .try_into()?,
+        ))
+    }
+}
+
+impl From<Query> for protobuf::DecisionCertificatesRequest {
+    fn from(value: Query) -> Self {
+        protobuf::DecisionCertificatesRequest { iteration: Some(value.into()) }
+    }
+}
+
+impl From<DecisionCertificateQuery> for protobuf::DecisionCertificatesRequest {
+    fn from(value: DecisionCertificateQuery) -> Self {
+        protobuf::DecisionCertificatesRequest { iteration: Some(value.0.into()) }
+    }
+}
+
+auto_impl_into_and_try_from_vec_u8!(
+    DecisionCertificateQuery,
+    protobuf::DecisionCertificatesRequest
+);
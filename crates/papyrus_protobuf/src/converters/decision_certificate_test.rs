@@ -0,0 +1,31 @@
+use papyrus_test_utils::{get_rng, GetTestInstance};
+use starknet_api::block::DecisionCertificate;
+
+use crate::sync::{DataOrFin, DecisionCertificateQuery};
+
+#[test]
+fn decision_certificate_to_bytes_and_back() {
+    let mut rng = get_rng();
+    let certificate = DecisionCertificate::get_test_instance(&mut rng);
+    let data = DataOrFin(Some(certificate));
+    let bytes_data = Vec::<u8>::from(data.clone());
+    let res_data = DataOrFin::try_from(bytes_data).unwrap();
+    assert_eq!(res_data, data);
+}
+
+#[test]
+fn fin_to_bytes_and_back() {
+    let bytes_data = Vec::<u8>::from(DataOrFin::<DecisionCertificate>(None));
+
+    let res_data = DataOrFin::<DecisionCertificate>::try_from(bytes_data).unwrap();
+    assert!(res_data.0.is_none());
+}
+
+#[test]
+fn decision_certificate_query_to_bytes_and_back() {
+    let mut rng = get_rng();
+    let query = DecisionCertificateQuery::get_test_instance(&mut rng);
+    let bytes = Vec::<u8>::from(query.clone());
+    let res_query = DecisionCertificateQuery::try_from(bytes).unwrap();
+    assert_eq!(query, res_query);
+}
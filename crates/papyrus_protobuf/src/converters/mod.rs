@@ -2,6 +2,7 @@ mod class;
 mod common;
 // TODO(matan): Internalize once we remove the dependency on the protobuf crate.
 pub mod consensus;
+mod decision_certificate;
 mod event;
 mod header;
 mod receipt;
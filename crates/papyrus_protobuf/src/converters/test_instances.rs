@@ -2,6 +2,7 @@ use papyrus_test_utils::{auto_impl_get_test_instance, get_number_of_variants, Ge
 use rand::Rng;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::transaction::{Transaction, TransactionHash};
 
 use crate::consensus::{
@@ -36,6 +37,7 @@ auto_impl_get_test_instance! {
         pub round: u32,
         pub block_hash: Option<BlockHash>,
         pub voter: ContractAddress,
+        pub signature: Signature,
     }
     pub enum VoteType {
         Prevote = 0,
@@ -49,6 +51,7 @@ auto_impl_get_test_instance! {
     }
     pub struct ProposalFin {
         pub proposal_content_id: BlockHash,
+        pub signature: Signature,
     }
     pub struct TransactionBatch {
         pub transactions: Vec<Transaction>,
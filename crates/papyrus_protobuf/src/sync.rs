@@ -48,6 +48,11 @@ impl From<EventQuery> for Query {
         event_query.0
     }
 }
+impl From<DecisionCertificateQuery> for Query {
+    fn from(decision_certificate_query: DecisionCertificateQuery) -> Self {
+        decision_certificate_query.0
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum BlockHashOrNumber {
@@ -109,6 +114,15 @@ impl From<Query> for EventQuery {
     }
 }
 
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecisionCertificateQuery(pub Query);
+
+impl From<Query> for DecisionCertificateQuery {
+    fn from(query: Query) -> Self {
+        Self(query)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SignedBlockHeader {
     pub block_header: BlockHeader,
@@ -207,6 +221,7 @@ auto_impl_get_test_instance! {
         Backward=1,
     }
     pub struct HeaderQuery(pub Query);
+    pub struct DecisionCertificateQuery(pub Query);
     pub struct SignedBlockHeader {
         pub block_header: BlockHeader,
         pub signatures: Vec<BlockSignature>,
@@ -56,7 +56,11 @@ pub use crate::v0_8::transaction::{
     InvokeTransactionV1 as InvokeTransactionV1RPC0_8,
     TransactionVersion1 as TransactionVersion1RPC0_8,
 };
-pub use crate::v0_8::write_api_result::AddInvokeOkResult as AddInvokeOkResultRPC0_8;
+pub use crate::v0_8::write_api_result::{
+    AddDeclareOkResult as AddDeclareOkResultRPC0_8,
+    AddDeployAccountOkResult as AddDeployAccountOkResultRPC0_8,
+    AddInvokeOkResult as AddInvokeOkResultRPC0_8,
+};
 
 // TODO(shahak): Consider adding genesis hash to the config to support chains that have
 // different genesis hash.
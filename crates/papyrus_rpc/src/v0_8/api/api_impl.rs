@@ -18,6 +18,7 @@ use papyrus_storage::body::events::{EventIndex, EventsReader};
 use papyrus_storage::body::{BodyStorageReader, TransactionIndex};
 use papyrus_storage::compiled_class::CasmStorageReader;
 use papyrus_storage::db::{TransactionKind, RO};
+use papyrus_storage::patricia::PatriciaStorageReader;
 use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{StorageError, StorageReader, StorageTxn};
 use starknet_api::block::{BlockHash, BlockHeaderWithoutHash, BlockNumber, BlockStatus};
@@ -83,11 +84,13 @@ use super::super::error::{
     INVALID_TRANSACTION_INDEX,
     NO_BLOCKS,
     PAGE_SIZE_TOO_BIG,
+    STORAGE_PROOF_NOT_SUPPORTED,
     TOO_MANY_KEYS_IN_FILTER,
     TRANSACTION_HASH_NOT_FOUND,
 };
 use super::super::execution::TransactionTrace;
 use super::super::state::{AcceptedStateUpdate, PendingStateUpdate, StateUpdate};
+use super::super::storage_proof::{ContractStorageKeys, StorageProof};
 use super::super::transaction::{
     get_block_tx_hashes_by_number,
     get_block_txs_by_number,
@@ -682,6 +685,34 @@ impl JsonRpcServer for JsonRpcServerImpl {
         .ok_or_else(|| ErrorObjectOwned::from(CONTRACT_NOT_FOUND))
     }
 
+    #[instrument(skip(self), level = "debug", err, ret)]
+    async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: Option<Vec<ClassHash>>,
+        contract_addresses: Option<Vec<ContractAddress>>,
+        contracts_storage_keys: Option<Vec<ContractStorageKeys>>,
+    ) -> RpcResult<StorageProof> {
+        let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
+        let block_number = get_accepted_block_number(&txn, block_id)?;
+        // The tries are stored only for some of the blocks.
+        let roots = txn
+            .get_state_trie_roots(block_number)
+            .map_err(internal_server_error)?
+            .ok_or_else(|| ErrorObjectOwned::from(STORAGE_PROOF_NOT_SUPPORTED))?;
+        let block_hash = get_block_header_by_number(&txn, block_number)?.block_hash;
+
+        StorageProof::fetch(
+            &txn,
+            roots,
+            block_hash,
+            &class_hashes.unwrap_or_default(),
+            &contract_addresses.unwrap_or_default(),
+            &contracts_storage_keys.unwrap_or_default(),
+        )
+        .map_err(internal_server_error)
+    }
+
     #[instrument(skip(self), level = "debug", err, ret)]
     fn chain_id(&self) -> RpcResult<String> {
         Ok(self.chain_id.as_hex())
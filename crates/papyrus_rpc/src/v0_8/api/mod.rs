@@ -46,6 +46,7 @@ use super::error::{
 };
 use super::execution::TransactionTrace;
 use super::state::{ContractClass, StateUpdate};
+use super::storage_proof::{ContractStorageKeys, StorageProof};
 use super::transaction::{
     DeployAccountTransaction,
     DeployAccountTransactionV1,
@@ -180,6 +181,17 @@ pub trait JsonRpc {
         contract_address: ContractAddress,
     ) -> RpcResult<Nonce>;
 
+    /// Gets the Merkle paths in the state tries of the given block to the given classes, contracts
+    /// and storage keys of contracts.
+    #[method(name = "getStorageProof")]
+    async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: Option<Vec<ClassHash>>,
+        contract_addresses: Option<Vec<ContractAddress>>,
+        contracts_storage_keys: Option<Vec<ContractStorageKeys>>,
+    ) -> RpcResult<StorageProof>;
+
     /// Returns the currently configured StarkNet chain id.
     #[method(name = "chainId")]
     fn chain_id(&self) -> RpcResult<String>;
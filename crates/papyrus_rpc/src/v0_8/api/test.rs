@@ -21,6 +21,7 @@ use papyrus_storage::body::{BodyStorageWriter, TransactionIndex};
 use papyrus_storage::class::ClassStorageWriter;
 use papyrus_storage::compiled_class::CasmStorageWriter;
 use papyrus_storage::header::HeaderStorageWriter;
+use papyrus_storage::patricia::{PatriciaStorageWriter, StateTrieRoots};
 use papyrus_storage::state::StateStorageWriter;
 use papyrus_storage::test_utils::get_test_storage;
 use papyrus_storage::StorageScope;
@@ -111,7 +112,24 @@ use starknet_client::writer::objects::transaction::{
 };
 use starknet_client::writer::{MockStarknetWriter, WriterClientError, WriterClientResult};
 use starknet_client::ClientError;
+use starknet_committer::block_committer::commit::commit_state_diff;
+use starknet_committer::block_committer::input::{
+    ConfigImpl,
+    ContractAddress as CommitterContractAddress,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff as CommitterStateDiff,
+};
+use starknet_committer::patricia_merkle_tree::types::{
+    ClassHash as CommitterClassHash,
+    CompiledClassHash as CommitterCompiledClassHash,
+    Nonce as CommitterNonce,
+};
+use starknet_patricia::felt::Felt as PatriciaFelt;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::storage::map_storage::MapStorage;
 use starknet_types_core::felt::Felt;
+use tracing::level_filters::LevelFilter;
 
 use super::super::api::EventsChunk;
 use super::super::block::{Block, GeneralBlockHeader, PendingBlockHeader, ResourcePrice};
@@ -129,6 +147,7 @@ use super::super::error::{
     INVALID_TRANSACTION_INDEX,
     NO_BLOCKS,
     PAGE_SIZE_TOO_BIG,
+    STORAGE_PROOF_NOT_SUPPORTED,
     TOO_MANY_KEYS_IN_FILTER,
     TRANSACTION_HASH_NOT_FOUND,
 };
@@ -145,6 +164,13 @@ use super::super::state::{
     StorageEntry,
     ThinStateDiff,
 };
+use super::super::storage_proof::{
+    ContractLeafData,
+    ContractStorageKeys,
+    GlobalRoots,
+    NodeHashToNode,
+    StorageProof,
+};
 use super::super::transaction::{
     DeployAccountTransaction,
     Event,
@@ -2004,6 +2030,154 @@ async fn get_nonce() {
     assert_matches!(err, Error::Call(err) if err == BLOCK_NOT_FOUND.into());
 }
 
+#[tokio::test]
+async fn get_storage_proof() {
+    let method_name = "starknet_V0_8_getStorageProof";
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let address = contract_address!("0x11");
+    let undeployed_address = contract_address!("0x12");
+    let key = storage_key!("0x21");
+    let class_hash = class_hash!("0x31");
+    let nonce = Nonce(felt!("0x1"));
+
+    // Compute the tries of a state with a single contract.
+    let committer_address = CommitterContractAddress(PatriciaFelt::from(*address.key()));
+    let state_diff = CommitterStateDiff {
+        address_to_class_hash: HashMap::from([(
+            committer_address,
+            CommitterClassHash(PatriciaFelt::from(class_hash.0)),
+        )]),
+        address_to_nonce: HashMap::from([(committer_address, CommitterNonce(PatriciaFelt::ONE))]),
+        class_hash_to_compiled_class_hash: HashMap::from([(
+            CommitterClassHash(PatriciaFelt::from(class_hash.0)),
+            CommitterCompiledClassHash(PatriciaFelt::from(0x41_u8)),
+        )]),
+        storage_updates: HashMap::from([(
+            committer_address,
+            HashMap::from([(
+                StarknetStorageKey(PatriciaFelt::from(*key.key())),
+                StarknetStorageValue(PatriciaFelt::from(0x51_u8)),
+            )]),
+        )]),
+    };
+    let mut forest_storage = MapStorage::default();
+    let filled_forest = commit_state_diff(
+        &forest_storage,
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        &state_diff,
+        &ConfigImpl::new(false, LevelFilter::INFO),
+    )
+    .await
+    .unwrap();
+    filled_forest.write_to_storage(&mut forest_storage).unwrap();
+    let roots = StateTrieRoots {
+        contracts_trie_root: filled_forest.get_contract_root_hash().0.into(),
+        classes_trie_root: filled_forest.get_compiled_class_root_hash().0.into(),
+    };
+
+    let header = BlockHeader::default();
+    let block_number = header.block_header_without_hash.block_number;
+    let mut txn = storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(block_number, &header)
+        .unwrap()
+        .set_state_trie_roots(block_number, &roots)
+        .unwrap();
+    for (key, value) in &forest_storage.storage {
+        txn = txn.set_patricia_node(&key.0, &value.0).unwrap();
+    }
+    txn.commit().unwrap();
+
+    let res = call_and_validate_schema_for_result::<_, StorageProof>(
+        &module,
+        method_name,
+        vec![
+            Box::new(BlockId::Tag(Tag::Latest)),
+            Box::new(vec![class_hash]),
+            Box::new(vec![address, undeployed_address]),
+            Box::new(vec![ContractStorageKeys {
+                contract_address: address,
+                storage_keys: vec![key, storage_key!("0x22")],
+            }]),
+        ],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+    )
+    .await;
+    assert_eq!(
+        res.global_roots,
+        GlobalRoots {
+            contracts_tree_root: roots.contracts_trie_root,
+            classes_tree_root: roots.classes_trie_root,
+            block_hash: header.block_hash,
+        }
+    );
+    let storage_root = res.contracts_proof.contract_leaves_data[0].storage_root;
+    assert_eq!(
+        res.contracts_proof.contract_leaves_data,
+        vec![ContractLeafData { nonce, class_hash, storage_root }, ContractLeafData::default()]
+    );
+    // Each proof starts at the root of its trie.
+    let contains_node = |nodes: &[NodeHashToNode], hash: Felt| {
+        nodes.iter().any(|node_hash_to_node| node_hash_to_node.node_hash == hash)
+    };
+    assert!(contains_node(&res.classes_proof, roots.classes_trie_root));
+    assert!(contains_node(&res.contracts_proof.nodes, roots.contracts_trie_root));
+    assert_eq!(res.contracts_storage_proofs.len(), 1);
+    assert!(contains_node(&res.contracts_storage_proofs[0], storage_root));
+
+    // Ask for a block whose tries are not stored.
+    let header = BlockHeader {
+        block_hash: BlockHash(felt!("0x1")),
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: BlockNumber(1),
+            parent_hash: header.block_hash,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(header.block_header_without_hash.block_number, &header)
+        .unwrap()
+        .commit()
+        .unwrap();
+    call_api_then_assert_and_validate_schema_for_err::<_, StorageProof>(
+        &module,
+        method_name,
+        vec![
+            Box::new(BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(1)))),
+            Box::new(vec![class_hash]),
+            Box::new(Vec::<ContractAddress>::new()),
+            Box::new(Vec::<ContractStorageKeys>::new()),
+        ],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &STORAGE_PROOF_NOT_SUPPORTED.into(),
+    )
+    .await;
+
+    // Ask for an invalid block number.
+    call_api_then_assert_and_validate_schema_for_err::<_, StorageProof>(
+        &module,
+        method_name,
+        vec![
+            Box::new(BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(2)))),
+            Box::new(vec![class_hash]),
+            Box::new(Vec::<ContractAddress>::new()),
+            Box::new(Vec::<ContractStorageKeys>::new()),
+        ],
+        &VERSION,
+        SpecFile::StarknetApiOpenrpc,
+        &BLOCK_NOT_FOUND.into(),
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn get_storage_at() {
     let method_name = "starknet_V0_8_getStorageAt";
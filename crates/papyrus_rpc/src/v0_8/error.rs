@@ -72,6 +72,13 @@ impl From<TransactionExecutionError> for JsonRpcError<TransactionExecutionError>
         Self { code: 41, message: "Transaction execution error", data: Some(tx_execution_error) }
     }
 }
+
+pub const STORAGE_PROOF_NOT_SUPPORTED: JsonRpcError<String> = JsonRpcError {
+    code: 42,
+    message: "the node doesn't support storage proofs for blocks that are too far in the past",
+    data: None,
+};
+
 pub const CLASS_ALREADY_DECLARED: JsonRpcError<String> =
     JsonRpcError { code: 51, message: "Class already declared", data: None };
 
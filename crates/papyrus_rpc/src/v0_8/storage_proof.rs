@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use papyrus_storage::db::TransactionKind;
+use papyrus_storage::patricia::StateTrieRoots;
+use papyrus_storage::StorageTxn;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockHash;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_committer::block_committer::input::{
+    ContractAddress as CommitterContractAddress,
+    StarknetStorageKey,
+};
+use starknet_committer::forest::storage_proof::StorageProof as CommitterStorageProof;
+use starknet_committer::patricia_merkle_tree::types::ClassHash as CommitterClassHash;
+use starknet_patricia::felt::Felt as PatriciaFelt;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::proof::{MerkleProofResult, ProofNode};
+use starknet_patricia::patricia_merkle_tree::node_data::inner_node::{BinaryData, EdgeData};
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_types_core::felt::Felt;
+
+/// The storage keys of a contract to prove, as requested in `starknet_getStorageProof`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ContractStorageKeys {
+    pub contract_address: ContractAddress,
+    pub storage_keys: Vec<StorageKey>,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct StorageProof {
+    pub classes_proof: NodeHashToNodeMapping,
+    pub contracts_proof: ContractsProof,
+    pub contracts_storage_proofs: Vec<NodeHashToNodeMapping>,
+    pub global_roots: GlobalRoots,
+}
+
+/// The nodes in the union of the paths from the root of a trie to the requested leaves.
+pub type NodeHashToNodeMapping = Vec<NodeHashToNode>;
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NodeHashToNode {
+    pub node_hash: Felt,
+    pub node: MerkleNode,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MerkleNode {
+    BinaryNode(BinaryNode),
+    EdgeNode(EdgeNode),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BinaryNode {
+    pub left: Felt,
+    pub right: Felt,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct EdgeNode {
+    pub path: Felt,
+    pub length: u8,
+    pub child: Felt,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ContractsProof {
+    pub nodes: NodeHashToNodeMapping,
+    /// The leaves of the requested contracts, in the order of the request.
+    pub contract_leaves_data: Vec<ContractLeafData>,
+}
+
+/// The data of a contract that is needed to compute its leaf in the contracts trie.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ContractLeafData {
+    pub nonce: Nonce,
+    pub class_hash: ClassHash,
+    pub storage_root: Felt,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct GlobalRoots {
+    pub contracts_tree_root: Felt,
+    pub classes_tree_root: Felt,
+    pub block_hash: BlockHash,
+}
+
+impl StorageProof {
+    /// Reads the proofs from the tries of the block with the given roots.
+    pub(crate) fn fetch<Mode: TransactionKind>(
+        txn: &StorageTxn<'_, Mode>,
+        roots: StateTrieRoots,
+        block_hash: BlockHash,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[ContractAddress],
+        contracts_storage_keys: &[ContractStorageKeys],
+    ) -> MerkleProofResult<Self> {
+        let mut committer_storage_keys = HashMap::<_, Vec<_>>::new();
+        for ContractStorageKeys { contract_address, storage_keys } in contracts_storage_keys {
+            committer_storage_keys
+                .entry(committer_contract_address(contract_address))
+                .or_default()
+                .extend(
+                    storage_keys
+                        .iter()
+                        .map(|key| StarknetStorageKey(PatriciaFelt::from(*key.key()))),
+                );
+        }
+        let committer_proof = CommitterStorageProof::fetch(
+            txn,
+            HashOutput(PatriciaFelt::from(roots.contracts_trie_root)),
+            HashOutput(PatriciaFelt::from(roots.classes_trie_root)),
+            &class_hashes
+                .iter()
+                .map(|class_hash| CommitterClassHash(PatriciaFelt::from(class_hash.0)))
+                .collect::<Vec<_>>(),
+            &contract_addresses.iter().map(committer_contract_address).collect::<Vec<_>>(),
+            &committer_storage_keys,
+        )?;
+
+        let contract_leaves_data = contract_addresses
+            .iter()
+            .map(|address| {
+                // The leaf of a contract that is not deployed is empty.
+                committer_proof
+                    .contracts_proof
+                    .leaves
+                    .get(&NodeIndex::from(&committer_contract_address(address)))
+                    .map(|state| ContractLeafData {
+                        nonce: Nonce(state.nonce.0.into()),
+                        class_hash: ClassHash(state.class_hash.0.into()),
+                        storage_root: state.storage_root_hash.0.into(),
+                    })
+                    .unwrap_or_default()
+            })
+            .collect();
+        let contracts_storage_proofs = contracts_storage_keys
+            .iter()
+            .map(|ContractStorageKeys { contract_address, .. }| {
+                node_hash_to_node_mapping(
+                    &committer_proof.contracts_storage_proofs
+                        [&committer_contract_address(contract_address)]
+                        .nodes,
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            classes_proof: node_hash_to_node_mapping(&committer_proof.classes_proof.nodes),
+            contracts_proof: ContractsProof {
+                nodes: node_hash_to_node_mapping(&committer_proof.contracts_proof.nodes),
+                contract_leaves_data,
+            },
+            contracts_storage_proofs,
+            global_roots: GlobalRoots {
+                contracts_tree_root: roots.contracts_trie_root,
+                classes_tree_root: roots.classes_trie_root,
+                block_hash,
+            },
+        })
+    }
+}
+
+fn committer_contract_address(address: &ContractAddress) -> CommitterContractAddress {
+    CommitterContractAddress(PatriciaFelt::from(*address.key()))
+}
+
+fn node_hash_to_node_mapping(nodes: &HashMap<HashOutput, ProofNode>) -> NodeHashToNodeMapping {
+    let mut mapping = nodes
+        .iter()
+        .map(|(hash, node)| NodeHashToNode {
+            node_hash: hash.0.into(),
+            node: match node {
+                ProofNode::Binary(BinaryData { left_hash, right_hash }) => {
+                    MerkleNode::BinaryNode(BinaryNode {
+                        left: left_hash.0.into(),
+                        right: right_hash.0.into(),
+                    })
+                }
+                ProofNode::Edge(EdgeData { bottom_hash, path_to_bottom }) => {
+                    MerkleNode::EdgeNode(EdgeNode {
+                        path: PatriciaFelt::from(&path_to_bottom.path).into(),
+                        length: path_to_bottom.length.into(),
+                        child: bottom_hash.0.into(),
+                    })
+                }
+            },
+        })
+        .collect::<Vec<_>>();
+    // Return the nodes in a deterministic order.
+    mapping.sort_by_key(|node_hash_to_node| node_hash_to_node.node_hash);
+    mapping
+}
@@ -46,6 +46,8 @@ use starknet_api::block::{
     BlockNumber,
     BlockSignature,
     BlockTimestamp,
+    DecidedHeaderField,
+    DecisionCertificate,
     GasPricePerToken,
     StarknetVersion,
 };
@@ -113,6 +115,17 @@ pub trait HeaderStorageReader {
         &self,
         block_number: BlockNumber,
     ) -> StorageResult<Option<BlockSignature>>;
+
+    /// Returns the decision certificate of the block with the given number, if it was decided by
+    /// consensus.
+    fn get_decision_certificate(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<DecisionCertificate>>;
+
+    /// The decision certificate marker is the block number after the last block that has a
+    /// decision certificate, or 0 if there are none.
+    fn get_decision_certificate_marker(&self) -> StorageResult<BlockNumber>;
 }
 
 /// Interface for writing data related to the block headers.
@@ -136,7 +149,7 @@ where
     ) -> StorageResult<Self>;
 
     /// Removes a block header and its signature (if exists) from the storage and returns the
-    /// removed data.
+    /// removed data. The decision certificate of the block (if exists) is removed as well.
     fn revert_header(
         self,
         block_number: BlockNumber,
@@ -149,6 +162,15 @@ where
         block_number: BlockNumber,
         block_signature: &BlockSignature,
     ) -> StorageResult<Self>;
+
+    /// Appends the decision certificate of a block to the storage. The certificate should be of the
+    /// stored header of the block by either of the fields consensus may decide on, see
+    /// [`DecisionCertificate::is_for_block`].
+    fn append_decision_certificate(
+        self,
+        block_number: BlockNumber,
+        decision_certificate: &DecisionCertificate,
+    ) -> StorageResult<Self>;
 }
 
 impl<Mode: TransactionKind> HeaderStorageReader for StorageTxn<'_, Mode> {
@@ -232,6 +254,26 @@ impl<Mode: TransactionKind> HeaderStorageReader for StorageTxn<'_, Mode> {
         let block_signature = block_signatures_table.get(&self.txn, &block_number)?;
         Ok(block_signature)
     }
+
+    fn get_decision_certificate(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<DecisionCertificate>> {
+        let decision_certificates_table = self.open_table(&self.tables.decision_certificates)?;
+        let decision_certificate = decision_certificates_table.get(&self.txn, &block_number)?;
+        Ok(decision_certificate)
+    }
+
+    fn get_decision_certificate_marker(&self) -> StorageResult<BlockNumber> {
+        // Decision certificates are only stored for blocks with a header.
+        let decision_certificates_table = self.open_table(&self.tables.decision_certificates)?;
+        let mut cursor = decision_certificates_table.cursor(&self.txn)?;
+        cursor.lower_bound(&self.get_header_marker()?)?;
+        match cursor.prev()? {
+            Some((block_number, _decision_certificate)) => Ok(block_number.unchecked_next()),
+            None => Ok(BlockNumber(0)),
+        }
+    }
 }
 
 impl HeaderStorageWriter for StorageTxn<'_, RW> {
@@ -309,6 +351,7 @@ impl HeaderStorageWriter for StorageTxn<'_, RW> {
         let block_hash_to_number_table = self.open_table(&self.tables.block_hash_to_number)?;
         let starknet_version_table = self.open_table(&self.tables.starknet_version)?;
         let block_signatures_table = self.open_table(&self.tables.block_signatures)?;
+        let decision_certificates_table = self.open_table(&self.tables.decision_certificates)?;
 
         // Assert that header marker equals the reverted block number + 1
         let current_header_marker = self.get_header_marker()?;
@@ -354,6 +397,11 @@ impl HeaderStorageWriter for StorageTxn<'_, RW> {
             block_signatures_table.delete(&self.txn, &block_number)?;
         }
 
+        // Revert decision certificate.
+        if decision_certificates_table.get(&self.txn, &block_number)?.is_some() {
+            decision_certificates_table.delete(&self.txn, &block_number)?;
+        }
+
         Ok((
             self,
             Some(BlockHeader {
@@ -399,6 +447,32 @@ impl HeaderStorageWriter for StorageTxn<'_, RW> {
         block_signatures_table.insert(&self.txn, &block_number, block_signature)?;
         Ok(self)
     }
+
+    fn append_decision_certificate(
+        self,
+        block_number: BlockNumber,
+        decision_certificate: &DecisionCertificate,
+    ) -> StorageResult<Self> {
+        let Some(header) = self.get_block_header(block_number)? else {
+            return Err(StorageError::DecisionCertificateForNonExistingBlock { block_number });
+        };
+        // The storage doesn't know which field of the header consensus decides on. The certificate
+        // is verified against that field before it is stored.
+        let is_for_block = [DecidedHeaderField::BlockHash, DecidedHeaderField::StateDiffCommitment]
+            .into_iter()
+            .any(|decided_field| decision_certificate.is_for_block(&header, decided_field));
+        if !is_for_block {
+            return Err(StorageError::DecisionCertificateBlockHashMismatch {
+                block_number,
+                block_hash: header.block_hash,
+                certificate_block_hash: decision_certificate.block_hash,
+            });
+        }
+
+        let decision_certificates_table = self.open_table(&self.tables.decision_certificates)?;
+        decision_certificates_table.insert(&self.txn, &block_number, decision_certificate)?;
+        Ok(self)
+    }
 }
 
 fn update_hash_mapping<'env>(
@@ -6,6 +6,7 @@ use starknet_api::block::{
     BlockHeaderWithoutHash,
     BlockNumber,
     BlockSignature,
+    DecisionCertificate,
 };
 use starknet_api::felt;
 
@@ -294,6 +295,76 @@ fn get_reverted_block_signature_returns_none() {
     assert!(reader.begin_ro_txn().unwrap().get_block_signature(BlockNumber(0)).unwrap().is_none());
 }
 
+#[test]
+fn decision_certificate() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let header = BlockHeader { block_hash: BlockHash(felt!("0x1")), ..Default::default() };
+    let certificate =
+        DecisionCertificate { block_hash: header.block_hash, ..DecisionCertificate::default() };
+
+    // The block doesn't exist yet.
+    let Err(err) =
+        writer.begin_rw_txn().unwrap().append_decision_certificate(BlockNumber(0), &certificate)
+    else {
+        panic!("Unexpected Ok.");
+    };
+    assert_matches!(
+        err,
+        StorageError::DecisionCertificateForNonExistingBlock { block_number }
+        if block_number == BlockNumber(0)
+    );
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &header)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_decision_certificate_marker().unwrap(),
+        BlockNumber(0)
+    );
+
+    // The certificate is for another block.
+    let other_certificate =
+        DecisionCertificate { block_hash: BlockHash(felt!("0x2")), ..certificate.clone() };
+    let Err(err) = writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_decision_certificate(BlockNumber(0), &other_certificate)
+    else {
+        panic!("Unexpected Ok.");
+    };
+    assert_matches!(err, StorageError::DecisionCertificateBlockHashMismatch { .. });
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_decision_certificate(BlockNumber(0), &certificate)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_decision_certificate(BlockNumber(0)).unwrap(),
+        Some(certificate)
+    );
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_decision_certificate_marker().unwrap(),
+        BlockNumber(1)
+    );
+
+    // The certificate is reverted with the header.
+    writer.begin_rw_txn().unwrap().revert_header(BlockNumber(0)).unwrap().0.commit().unwrap();
+    assert!(
+        reader.begin_ro_txn().unwrap().get_decision_certificate(BlockNumber(0)).unwrap().is_none()
+    );
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_decision_certificate_marker().unwrap(),
+        BlockNumber(0)
+    );
+}
+
 #[test]
 fn revert_overflowing_block_number() {
     let ((_, mut writer), _temp_dir) = get_test_storage();
@@ -88,6 +88,7 @@ pub mod compression_utils;
 pub mod db;
 pub mod header;
 pub mod mmap_file;
+pub mod patricia;
 mod serialization;
 pub mod state;
 mod version;
@@ -123,7 +124,13 @@ use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_proc_macros::latency_histogram;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{BlockHash, BlockNumber, BlockSignature, StarknetVersion};
+use starknet_api::block::{
+    BlockHash,
+    BlockNumber,
+    BlockSignature,
+    DecisionCertificate,
+    StarknetVersion,
+};
 use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::state::{SierraContractClass, StateNumber, StorageKey, ThinStateDiff};
@@ -150,6 +157,7 @@ use crate::db::{
 };
 use crate::header::StorageBlockHeader;
 use crate::mmap_file::MMapFileStats;
+use crate::patricia::StateTrieRoots;
 use crate::state::data::IndexedDeprecatedContractClass;
 use crate::version::{VersionStorageReader, VersionStorageWriter};
 
@@ -176,6 +184,7 @@ pub fn open_storage(
         block_signatures: db_writer.create_simple_table("block_signatures")?,
         casms: db_writer.create_simple_table("casms")?,
         contract_storage: db_writer.create_common_prefix_table("contract_storage")?,
+        decision_certificates: db_writer.create_simple_table("decision_certificates")?,
         declared_classes: db_writer.create_simple_table("declared_classes")?,
         declared_classes_block: db_writer.create_simple_table("declared_classes_block")?,
         deprecated_declared_classes: db_writer
@@ -186,7 +195,9 @@ pub fn open_storage(
         markers: db_writer.create_simple_table("markers")?,
         nonces: db_writer.create_common_prefix_table("nonces")?,
         file_offsets: db_writer.create_simple_table("file_offsets")?,
+        patricia_nodes: db_writer.create_simple_table("patricia_nodes")?,
         state_diffs: db_writer.create_simple_table("state_diffs")?,
+        state_trie_roots: db_writer.create_simple_table("state_trie_roots")?,
         transaction_hash_to_idx: db_writer.create_simple_table("transaction_hash_to_idx")?,
         transaction_metadata: db_writer.create_simple_table("transaction_metadata")?,
 
@@ -523,6 +534,8 @@ struct_field_names! {
         // Empirically, defining the common prefix as (ContractAddress, StorageKey) is better space-wise than defining the
         // common prefix only as ContractAddress.
         contract_storage: TableIdentifier<((ContractAddress, StorageKey), BlockNumber), NoVersionValueWrapper<Felt>, CommonPrefix>,
+        // The decision certificates of blocks decided by consensus.
+        decision_certificates: TableIdentifier<BlockNumber, VersionZeroWrapper<DecisionCertificate>, SimpleTable>,
         declared_classes: TableIdentifier<ClassHash, VersionZeroWrapper<LocationInFile>, SimpleTable>,
         declared_classes_block: TableIdentifier<ClassHash, NoVersionValueWrapper<BlockNumber>, SimpleTable>,
         deprecated_declared_classes: TableIdentifier<ClassHash, VersionZeroWrapper<IndexedDeprecatedContractClass>, SimpleTable>,
@@ -533,7 +546,11 @@ struct_field_names! {
         markers: TableIdentifier<MarkerKind, VersionZeroWrapper<BlockNumber>, SimpleTable>,
         nonces: TableIdentifier<(ContractAddress, BlockNumber), VersionZeroWrapper<Nonce>, CommonPrefix>,
         file_offsets: TableIdentifier<OffsetKind, NoVersionValueWrapper<usize>, SimpleTable>,
+        // The nodes of the Patricia-Merkle trees of the state, keyed by their storage key.
+        patricia_nodes: TableIdentifier<Vec<u8>, NoVersionValueWrapper<Vec<u8>>, SimpleTable>,
         state_diffs: TableIdentifier<BlockNumber, VersionZeroWrapper<LocationInFile>, SimpleTable>,
+        // The roots of the Patricia-Merkle trees of the state after each block, for blocks whose trees are stored.
+        state_trie_roots: TableIdentifier<BlockNumber, VersionZeroWrapper<StateTrieRoots>, SimpleTable>,
         transaction_hash_to_idx: TableIdentifier<TransactionHash, NoVersionValueWrapper<TransactionIndex>, SimpleTable>,
         // TODO(dvir): consider not saving transaction hash and calculating it from the transaction on demand.
         transaction_metadata: TableIdentifier<TransactionIndex, VersionZeroWrapper<TransactionMetadata>, SimpleTable>,
@@ -610,6 +627,17 @@ pub enum StorageError {
          {block_number}."
     )]
     BlockSignatureForNonExistingBlock { block_number: BlockNumber, block_signature: BlockSignature },
+    #[error("Attempt to write the decision certificate of non-existing block {block_number}.")]
+    DecisionCertificateForNonExistingBlock { block_number: BlockNumber },
+    #[error(
+        "The decision certificate of block {block_number} is for {certificate_block_hash}, which \
+         doesn't match the stored block with hash {block_hash}."
+    )]
+    DecisionCertificateBlockHashMismatch {
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+        certificate_block_hash: BlockHash,
+    },
 }
 
 /// A type alias that maps to std::result::Result<T, StorageError>.
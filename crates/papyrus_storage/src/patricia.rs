@@ -0,0 +1,145 @@
+//! Interface for handling the nodes of the Patricia-Merkle trees of the Starknet state.
+//!
+//! The nodes are stored as raw bytes, under the keys given by the committer; this module does not
+//! interpret them. The roots of the trees after a block are stored by the block number, so the
+//! trees of the block can be read.
+//! Import [`PatriciaStorageReader`] and [`PatriciaStorageWriter`] to read and write Patricia nodes
+//! using a [`StorageTxn`].
+//! # Example
+//! ```
+//! use papyrus_storage::open_storage;
+//! use papyrus_storage::patricia::{PatriciaStorageReader, PatriciaStorageWriter, StateTrieRoots};
+//! # use papyrus_storage::{db::DbConfig, StorageConfig};
+//! use starknet_api::block::BlockNumber;
+//! # use starknet_api::core::ChainId;
+//! use starknet_api::hash::StarkHash;
+//!
+//! # let dir_handle = tempfile::tempdir().unwrap();
+//! # let dir = dir_handle.path().to_path_buf();
+//! # let db_config = DbConfig {
+//! #     path_prefix: dir,
+//! #     chain_id: ChainId::Mainnet,
+//! #     enforce_file_exists: false,
+//! #     min_size: 1 << 20,    // 1MB
+//! #     max_size: 1 << 35,    // 32GB
+//! #     growth_step: 1 << 26, // 64MB
+//! # };
+//! # let storage_config = StorageConfig{db_config, ..Default::default()};
+//! let (reader, mut writer) = open_storage(storage_config)?;
+//! writer
+//!     .begin_rw_txn()?                                        // Start a RW transaction.
+//!     .set_patricia_node(b"patricia_node:0x1", b"node")?      // Set a node.
+//!     .commit()?; // Commit the transaction.
+//! let node = reader.begin_ro_txn()?.get_patricia_node(b"patricia_node:0x1")?;
+//! assert_eq!(node, Some(b"node".to_vec()));
+//!
+//! let roots = StateTrieRoots {
+//!     contracts_trie_root: StarkHash::from(1_u8),
+//!     classes_trie_root: StarkHash::from(2_u8),
+//! };
+//! writer.begin_rw_txn()?.set_state_trie_roots(BlockNumber(0), &roots)?.commit()?;
+//! assert_eq!(reader.begin_ro_txn()?.get_state_trie_roots(BlockNumber(0))?, Some(roots));
+//! # Ok::<(), papyrus_storage::StorageError>(())
+//! ```
+
+#[cfg(test)]
+#[path = "patricia_test.rs"]
+mod patricia_test;
+
+use starknet_api::block::BlockNumber;
+use starknet_api::hash::StarkHash;
+
+use crate::db::table_types::Table;
+use crate::db::{TransactionKind, RW};
+use crate::{StorageResult, StorageTxn};
+
+/// The roots of the Patricia-Merkle trees of the state after a block.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StateTrieRoots {
+    /// The root of the contracts trie, whose leaves are the states of the contracts.
+    pub contracts_trie_root: StarkHash,
+    /// The root of the classes trie, whose leaves are the compiled class hashes.
+    pub classes_trie_root: StarkHash,
+}
+
+/// Interface for reading Patricia nodes.
+pub trait PatriciaStorageReader {
+    /// Returns the node stored under the given key.
+    fn get_patricia_node(&self, key: &[u8]) -> StorageResult<Option<Vec<u8>>>;
+
+    /// Returns the roots of the trees after the given block, if the trees of the block are stored.
+    fn get_state_trie_roots(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<StateTrieRoots>>;
+}
+
+/// Interface for writing Patricia nodes.
+pub trait PatriciaStorageWriter
+where
+    Self: Sized,
+{
+    /// Stores the node under the given key, overwriting the existing node if there is one.
+    // To enforce that no commit happen after a failure, we consume and return Self on success.
+    fn set_patricia_node(self, key: &[u8], node: &[u8]) -> StorageResult<Self>;
+
+    /// Deletes the node stored under the given key, if there is one.
+    fn delete_patricia_node(self, key: &[u8]) -> StorageResult<Self>;
+
+    /// Stores the roots of the trees after the given block, overwriting the existing roots if
+    /// there are.
+    fn set_state_trie_roots(
+        self,
+        block_number: BlockNumber,
+        roots: &StateTrieRoots,
+    ) -> StorageResult<Self>;
+
+    /// Deletes the roots of the trees after the given block, if they are stored (e.g., once the
+    /// trees of the block are pruned).
+    fn delete_state_trie_roots(self, block_number: BlockNumber) -> StorageResult<Self>;
+}
+
+impl<Mode: TransactionKind> PatriciaStorageReader for StorageTxn<'_, Mode> {
+    fn get_patricia_node(&self, key: &[u8]) -> StorageResult<Option<Vec<u8>>> {
+        let patricia_nodes_table = self.open_table(&self.tables.patricia_nodes)?;
+        Ok(patricia_nodes_table.get(&self.txn, &key.to_vec())?)
+    }
+
+    fn get_state_trie_roots(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<StateTrieRoots>> {
+        let state_trie_roots_table = self.open_table(&self.tables.state_trie_roots)?;
+        Ok(state_trie_roots_table.get(&self.txn, &block_number)?)
+    }
+}
+
+impl PatriciaStorageWriter for StorageTxn<'_, RW> {
+    fn set_patricia_node(self, key: &[u8], node: &[u8]) -> StorageResult<Self> {
+        let patricia_nodes_table = self.open_table(&self.tables.patricia_nodes)?;
+        patricia_nodes_table.upsert(&self.txn, &key.to_vec(), &node.to_vec())?;
+        Ok(self)
+    }
+
+    fn delete_patricia_node(self, key: &[u8]) -> StorageResult<Self> {
+        let patricia_nodes_table = self.open_table(&self.tables.patricia_nodes)?;
+        patricia_nodes_table.delete(&self.txn, &key.to_vec())?;
+        Ok(self)
+    }
+
+    fn set_state_trie_roots(
+        self,
+        block_number: BlockNumber,
+        roots: &StateTrieRoots,
+    ) -> StorageResult<Self> {
+        let state_trie_roots_table = self.open_table(&self.tables.state_trie_roots)?;
+        state_trie_roots_table.upsert(&self.txn, &block_number, roots)?;
+        Ok(self)
+    }
+
+    fn delete_state_trie_roots(self, block_number: BlockNumber) -> StorageResult<Self> {
+        let state_trie_roots_table = self.open_table(&self.tables.state_trie_roots)?;
+        state_trie_roots_table.delete(&self.txn, &block_number)?;
+        Ok(self)
+    }
+}
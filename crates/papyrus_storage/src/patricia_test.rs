@@ -0,0 +1,117 @@
+use starknet_api::block::BlockNumber;
+use starknet_api::hash::StarkHash;
+
+use crate::patricia::{PatriciaStorageReader, PatriciaStorageWriter, StateTrieRoots};
+use crate::test_utils::get_test_storage;
+
+#[test]
+fn set_and_delete_patricia_nodes() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let (key, other_key) = (b"patricia_node:0x1", b"patricia_node:0x2");
+    assert_eq!(reader.begin_ro_txn().unwrap().get_patricia_node(key).unwrap(), None);
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .set_patricia_node(key, b"node")
+        .unwrap()
+        .set_patricia_node(other_key, b"other_node")
+        .unwrap()
+        .commit()
+        .unwrap();
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_patricia_node(key).unwrap(), Some(b"node".to_vec()));
+    assert_eq!(txn.get_patricia_node(other_key).unwrap(), Some(b"other_node".to_vec()));
+
+    // Overwrite a node and delete the other one.
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .set_patricia_node(key, b"new_node")
+        .unwrap()
+        .delete_patricia_node(other_key)
+        .unwrap()
+        .commit()
+        .unwrap();
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_patricia_node(key).unwrap(), Some(b"new_node".to_vec()));
+    assert_eq!(txn.get_patricia_node(other_key).unwrap(), None);
+}
+
+#[test]
+fn uncommitted_patricia_nodes_are_discarded() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let key = b"patricia_node:0x1";
+
+    let txn = writer.begin_rw_txn().unwrap().set_patricia_node(key, b"node").unwrap();
+    drop(txn);
+
+    assert_eq!(reader.begin_ro_txn().unwrap().get_patricia_node(key).unwrap(), None);
+}
+
+#[test]
+fn set_state_trie_roots() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    assert_eq!(reader.begin_ro_txn().unwrap().get_state_trie_roots(BlockNumber(0)).unwrap(), None);
+
+    let roots = StateTrieRoots {
+        contracts_trie_root: StarkHash::from(1_u8),
+        classes_trie_root: StarkHash::from(2_u8),
+    };
+    let new_roots = StateTrieRoots { contracts_trie_root: StarkHash::from(3_u8), ..roots };
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .set_state_trie_roots(BlockNumber(0), &roots)
+        .unwrap()
+        .set_state_trie_roots(BlockNumber(1), &roots)
+        .unwrap()
+        .commit()
+        .unwrap();
+    // The roots of a block are overwritten, e.g., after it is reverted and committed again.
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .set_state_trie_roots(BlockNumber(1), &new_roots)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_state_trie_roots(BlockNumber(0)).unwrap(), Some(roots));
+    assert_eq!(txn.get_state_trie_roots(BlockNumber(1)).unwrap(), Some(new_roots));
+    assert_eq!(txn.get_state_trie_roots(BlockNumber(2)).unwrap(), None);
+}
+
+#[test]
+fn delete_state_trie_roots() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let roots = StateTrieRoots {
+        contracts_trie_root: StarkHash::from(1_u8),
+        classes_trie_root: StarkHash::from(2_u8),
+    };
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .set_state_trie_roots(BlockNumber(0), &roots)
+        .unwrap()
+        .set_state_trie_roots(BlockNumber(1), &roots)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // Deleting missing roots is a no-op.
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .delete_state_trie_roots(BlockNumber(0))
+        .unwrap()
+        .delete_state_trie_roots(BlockNumber(2))
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_state_trie_roots(BlockNumber(0)).unwrap(), None);
+    assert_eq!(txn.get_state_trie_roots(BlockNumber(1)).unwrap(), Some(roots));
+}
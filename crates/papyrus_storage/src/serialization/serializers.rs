@@ -24,8 +24,10 @@ use starknet_api::block::{
     BlockSignature,
     BlockStatus,
     BlockTimestamp,
+    DecisionCertificate,
     GasPrice,
     GasPricePerToken,
+    PrecommitSignature,
     StarknetVersion,
 };
 use starknet_api::contract_class::EntryPointType;
@@ -139,6 +141,7 @@ use crate::db::serialization::{StorageSerde, StorageSerdeError};
 use crate::db::table_types::NoValue;
 use crate::header::StorageBlockHeader;
 use crate::mmap_file::LocationInFile;
+use crate::patricia::StateTrieRoots;
 #[cfg(test)]
 use crate::serialization::serializers_test::{create_storage_serde_test, StorageSerdeTest};
 use crate::state::data::IndexedDeprecatedContractClass;
@@ -198,6 +201,12 @@ auto_storage_serde! {
         L1 = 0,
         L2 = 1,
     }
+    pub struct DecisionCertificate {
+        pub block_number: BlockNumber,
+        pub round: u32,
+        pub block_hash: BlockHash,
+        pub precommits: Vec<PrecommitSignature>,
+    }
     pub enum DeclareTransaction {
         V0(DeclareTransactionV0V1) = 0,
         V1(DeclareTransactionV0V1) = 1,
@@ -350,6 +359,10 @@ auto_storage_serde! {
     }
     pub struct PaymasterData(pub Vec<Felt>);
     pub struct PoseidonHash(pub Felt);
+    pub struct PrecommitSignature {
+        pub voter: ContractAddress,
+        pub signature: Signature,
+    }
     pub struct Program {
         pub attributes: serde_json::Value,
         pub builtins: serde_json::Value,
@@ -414,6 +427,10 @@ auto_storage_serde! {
         V0_13_4 = 19,
     }
     pub struct StateDiffCommitment(pub PoseidonHash);
+    pub struct StateTrieRoots {
+        pub contracts_trie_root: StarkHash,
+        pub classes_trie_root: StarkHash,
+    }
     pub struct Tip(pub u64);
     pub struct TransactionCommitment(pub StarkHash);
     pub struct TypedParameter {
@@ -9,6 +9,7 @@ use starknet_api::core::{
     TransactionCommitment,
 };
 use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::hash::StarkHash;
 use starknet_api::transaction::{
     EventIndexInTransactionOutput,
     TransactionHash,
@@ -19,6 +20,7 @@ use crate::body::TransactionIndex;
 use crate::compression_utils::IsCompressed;
 use crate::header::StorageBlockHeader;
 use crate::mmap_file::LocationInFile;
+use crate::patricia::StateTrieRoots;
 use crate::state::data::IndexedDeprecatedContractClass;
 use crate::version::Version;
 use crate::{EventIndex, MarkerKind, OffsetKind, TransactionMetadata};
@@ -68,6 +70,10 @@ auto_impl_get_test_instance! {
         Casm = 2,
         DeprecatedContractClass = 3,
     }
+    pub struct StateTrieRoots {
+        pub contracts_trie_root: StarkHash,
+        pub classes_trie_root: StarkHash,
+    }
     pub struct TransactionMetadata{
         pub tx_hash: TransactionHash,
         pub tx_location: LocationInFile,
@@ -39,6 +39,8 @@ use starknet_api::core::{ClassHash, CompiledClassHash, SequencerPublicKey};
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::state::{StateDiff, ThinStateDiff};
 use starknet_client::reader::PendingData;
+use starknet_committer::block_committer::errors::BlockCommitmentError;
+use starknet_committer::forest::mdbx_forest::commit_to_stored_forest;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -194,6 +196,8 @@ pub enum StateSyncError {
     },
     #[error("Sequencer public key changed from {old:?} to {new:?}.")]
     SequencerPubKeyChanged { old: SequencerPublicKey, new: SequencerPublicKey },
+    #[error(transparent)]
+    StateCommitmentError(#[from] BlockCommitmentError),
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -268,7 +272,8 @@ impl<
                 | StateSyncError::ParentBlockHashMismatch { .. }
                 | StateSyncError::BaseLayerHashMismatch { .. }
                 | StateSyncError::BaseLayerBlockWithoutMatchingHeader { .. } => true,
-                StateSyncError::SequencerPubKeyChanged { .. } => false,
+                StateSyncError::SequencerPubKeyChanged { .. }
+                | StateSyncError::StateCommitmentError(_) => false,
             }
         }
     }
@@ -380,12 +385,15 @@ impl<
                 block_hash,
                 state_diff,
                 deployed_contract_class_definitions,
-            } => self.store_state_diff(
-                block_number,
-                block_hash,
-                state_diff,
-                deployed_contract_class_definitions,
-            ),
+            } => {
+                self.store_state_diff(
+                    block_number,
+                    block_hash,
+                    state_diff,
+                    deployed_contract_class_definitions,
+                )
+                .await
+            }
             SyncEvent::CompiledClassAvailable {
                 class_hash,
                 compiled_class_hash,
@@ -448,7 +456,7 @@ impl<
     #[latency_histogram("sync_store_state_diff_latency_seconds", false)]
     #[instrument(skip(self, state_diff, deployed_contract_class_definitions), level = "debug", err)]
     #[allow(clippy::as_conversions)] // FIXME: use int metrics so `as f64` may be removed.
-    fn store_state_diff(
+    async fn store_state_diff(
         &mut self,
         block_number: BlockNumber,
         block_hash: BlockHash,
@@ -463,9 +471,13 @@ impl<
         // classes.
         let (thin_state_diff, classes, deprecated_classes) =
             ThinStateDiff::from_state_diff(state_diff);
-        self.writer
-            .begin_rw_txn()?
-            .append_state_diff(block_number, thin_state_diff)?
+        let forest_update =
+            commit_to_stored_forest(&self.reader, block_number, &thin_state_diff).await?;
+        let mut txn = self.writer.begin_rw_txn()?;
+        if let Some(forest_update) = forest_update {
+            txn = forest_update.write(txn, block_number)?;
+        }
+        txn.append_state_diff(block_number, thin_state_diff)?
             .append_classes(
                 block_number,
                 &classes.iter().map(|(class_hash, class)| (*class_hash, class)).collect::<Vec<_>>(),
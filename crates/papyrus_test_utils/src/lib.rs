@@ -45,8 +45,10 @@ use starknet_api::block::{
     BlockSignature,
     BlockStatus,
     BlockTimestamp,
+    DecisionCertificate,
     GasPrice,
     GasPricePerToken,
+    PrecommitSignature,
     StarknetVersion,
 };
 use starknet_api::contract_class::EntryPointType;
@@ -519,6 +521,12 @@ auto_impl_get_test_instance! {
         L1 = 0,
         L2 = 1,
     }
+    pub struct DecisionCertificate {
+        pub block_number: BlockNumber,
+        pub round: u32,
+        pub block_hash: BlockHash,
+        pub precommits: Vec<PrecommitSignature>,
+    }
     pub enum DeclareTransaction {
         V0(DeclareTransactionV0V1) = 0,
         V1(DeclareTransactionV0V1) = 1,
@@ -728,6 +736,10 @@ auto_impl_get_test_instance! {
     pub struct TransactionCommitment(pub StarkHash);
     pub struct PaymasterData(pub Vec<Felt>);
     pub struct PoseidonHash(pub Felt);
+    pub struct PrecommitSignature {
+        pub voter: ContractAddress,
+        pub signature: Signature,
+    }
     pub struct Program {
         pub attributes: serde_json::Value,
         pub builtins: serde_json::Value,
@@ -2,22 +2,36 @@
 //! and its implementation of the `SerializeConfig` trait. The configuration includes parameters
 //! such as the validator ID, the network topic of the consensus, and the starting block height.
 
+#[cfg(test)]
+#[path = "config_test.rs"]
+mod config_test;
+
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use papyrus_config::converters::{
     deserialize_float_seconds_to_duration,
     deserialize_seconds_to_duration,
 };
-use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_network::NetworkConfig;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use starknet_api::block::BlockNumber;
 use starknet_api::core::ChainId;
-use validator::Validate;
+use starknet_api::crypto::utils::{get_public_key, PublicKey};
+use starknet_types_core::felt::Felt;
+use url::Url;
+use validator::{Validate, ValidationError};
 
-use super::types::ValidatorId;
+use super::types::{ValidatorId, VotingWeight};
+use crate::proposer_selection::MAX_TOTAL_WEIGHT;
 use crate::types::DEFAULT_VALIDATOR_ID;
 
 const CONSENSUS_TCP_PORT: u16 = 10100;
@@ -25,6 +39,7 @@ const CONSENSUS_QUIC_PORT: u16 = 10101;
 
 /// Configuration for consensus.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
+#[validate(schema(function = "validate_consensus_config"))]
 pub struct ConsensusConfig {
     /// The chain id of the Starknet chain.
     pub chain_id: ChainId,
@@ -37,6 +52,21 @@ pub struct ConsensusConfig {
     /// The number of validators in the consensus.
     // Used for testing in an early milestones.
     pub num_validators: u64,
+    /// The public keys of the validators, in the order of their IDs. Used to verify the
+    /// signatures of their messages.
+    // Used for testing in an early milestones.
+    #[serde(deserialize_with = "deserialize_public_keys")]
+    pub validator_public_keys: Vec<PublicKey>,
+    /// The voting weights of the validators, in the order of their IDs. Every validator should
+    /// have a positive weight, and the total weight should be at most [`MAX_TOTAL_WEIGHT`].
+    // Used for testing in an early milestones.
+    #[serde(deserialize_with = "deserialize_voting_weights")]
+    pub validator_weights: Vec<VotingWeight>,
+    /// The source of the key that signs the messages of this node.
+    pub signer: SignerConfig,
+    /// The path of the write-ahead log of the votes of this node. Without it, the node may send
+    /// conflicting votes if it restarts in the middle of a height.
+    pub wal_path: Option<PathBuf>,
     /// The delay (seconds) before starting consensus to give time for network peering.
     #[serde(deserialize_with = "deserialize_seconds_to_duration")]
     pub consensus_delay: Duration,
@@ -80,6 +110,20 @@ impl SerializeConfig for ConsensusConfig {
                 "The number of validators in the consensus.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "validator_public_keys",
+                &serialize_public_keys(&self.validator_public_keys),
+                "Comma separated public keys of the validators, in the order of their ids.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "validator_weights",
+                &serialize_voting_weights(&self.validator_weights),
+                "Comma separated voting weights of the validators, in the order of their ids. \
+                 Every validator should have a positive weight, and the total weight should be at \
+                 most 1000.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "consensus_delay",
                 &self.consensus_delay.as_secs(),
@@ -87,6 +131,15 @@ impl SerializeConfig for ConsensusConfig {
                 ParamPrivacyInput::Public,
             ),
         ]);
+        config.extend(ser_optional_param(
+            &self.wal_path,
+            PathBuf::from("./consensus_wal"),
+            "wal_path",
+            "The path of the write-ahead log of the votes of the node, which prevents it from \
+             sending conflicting votes after a restart.",
+            ParamPrivacyInput::Public,
+        ));
+        config.extend(append_sub_config_name(self.signer.dump(), "signer"));
         config.extend(append_sub_config_name(self.timeouts.dump(), "timeouts"));
         config.extend(append_sub_config_name(self.network_config.dump(), "network_config"));
         config
@@ -106,6 +159,12 @@ impl Default for ConsensusConfig {
             network_topic: "consensus".to_string(),
             start_height: BlockNumber::default(),
             num_validators: 1,
+            // The default validator is meant for local testing, and signs with its ID as its
+            // private key.
+            validator_public_keys: vec![get_public_key(&Felt::from(DEFAULT_VALIDATOR_ID))],
+            validator_weights: vec![1],
+            signer: SignerConfig::default(),
+            wal_path: None,
             consensus_delay: Duration::from_secs(5),
             timeouts: TimeoutsConfig::default(),
             network_config,
@@ -113,6 +172,98 @@ impl Default for ConsensusConfig {
     }
 }
 
+fn validate_consensus_config(config: &ConsensusConfig) -> Result<(), ValidationError> {
+    if u64::try_from(config.validator_public_keys.len()) != Ok(config.num_validators) {
+        return Err(ValidationError::new(
+            "validator_public_keys should have a key for each of the num_validators validators",
+        ));
+    }
+    if u64::try_from(config.validator_weights.len()) != Ok(config.num_validators) {
+        return Err(ValidationError::new(
+            "validator_weights should have a weight for each of the num_validators validators",
+        ));
+    }
+    if config.validator_weights.contains(&0) {
+        return Err(ValidationError::new("validator_weights should be positive"));
+    }
+    let total_weight = config
+        .validator_weights
+        .iter()
+        .try_fold(VotingWeight::default(), |total, weight| total.checked_add(*weight));
+    if !total_weight.is_some_and(|total_weight| total_weight <= MAX_TOTAL_WEIGHT) {
+        return Err(ValidationError::new(
+            "The total of validator_weights should be at most MAX_TOTAL_WEIGHT",
+        ));
+    }
+    Ok(())
+}
+
+/// Serializes the public keys of the validators as they are expected in the config.
+pub fn serialize_public_keys(public_keys: &[PublicKey]) -> String {
+    public_keys.iter().map(|public_key| format!("{:#x}", public_key)).collect::<Vec<_>>().join(",")
+}
+
+fn deserialize_public_keys<'de, D>(de: D) -> Result<Vec<PublicKey>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: String = Deserialize::deserialize(de)?;
+    if raw.is_empty() {
+        return Ok(Vec::new());
+    }
+    raw.split(',')
+        .map(|public_key| {
+            Felt::from_hex(public_key.trim()).map(PublicKey).map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
+
+/// Serializes the voting weights of the validators as they are expected in the config.
+pub fn serialize_voting_weights(weights: &[VotingWeight]) -> String {
+    weights.iter().map(|weight| weight.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn deserialize_voting_weights<'de, D>(de: D) -> Result<Vec<VotingWeight>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: String = Deserialize::deserialize(de)?;
+    if raw.is_empty() {
+        return Ok(Vec::new());
+    }
+    raw.split(',').map(|weight| weight.trim().parse().map_err(serde::de::Error::custom)).collect()
+}
+
+/// Configuration for the key that signs the messages of the node. Exactly one source of the key
+/// should be set.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct SignerConfig {
+    /// The path to a file that holds the hex encoded private key.
+    pub private_key_file: Option<PathBuf>,
+    /// The url of a local signing service that holds the private key.
+    pub signing_service_url: Option<Url>,
+}
+
+impl SerializeConfig for SignerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = ser_optional_param(
+            &self.private_key_file,
+            PathBuf::new(),
+            "private_key_file",
+            "The path to a file that holds the hex encoded private key of the node.",
+            ParamPrivacyInput::Private,
+        );
+        config.extend(ser_optional_param(
+            &self.signing_service_url,
+            Url::parse("http://localhost/").expect("The url should be valid."),
+            "signing_service_url",
+            "The url of a local signing service that signs the messages of the node.",
+            ParamPrivacyInput::Public,
+        ));
+        config
+    }
+}
+
 /// Configuration for consensus timeouts.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct TimeoutsConfig {
@@ -0,0 +1,57 @@
+use starknet_api::crypto::utils::PublicKey;
+use starknet_types_core::felt::Felt;
+use validator::Validate;
+
+use crate::config::ConsensusConfig;
+use crate::proposer_selection::MAX_TOTAL_WEIGHT;
+
+#[test]
+fn default_config_is_valid() {
+    assert!(ConsensusConfig::default().validate().is_ok());
+}
+
+#[test]
+fn validator_public_keys_match_num_validators() {
+    let config = ConsensusConfig {
+        num_validators: 2,
+        validator_public_keys: vec![PublicKey(Felt::ONE), PublicKey(Felt::TWO)],
+        validator_weights: vec![1, 1],
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+
+    let missing_key =
+        ConsensusConfig { validator_public_keys: vec![PublicKey(Felt::ONE)], ..config.clone() };
+    assert!(missing_key.validate().is_err());
+
+    let extra_key = ConsensusConfig { num_validators: 1, validator_weights: vec![1], ..config };
+    assert!(extra_key.validate().is_err());
+}
+
+#[test]
+fn validator_weights_are_positive_and_match_num_validators() {
+    let config = ConsensusConfig {
+        num_validators: 2,
+        validator_public_keys: vec![PublicKey(Felt::ONE), PublicKey(Felt::TWO)],
+        validator_weights: vec![1, 2],
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+
+    let missing_weight = ConsensusConfig { validator_weights: vec![1], ..config.clone() };
+    assert!(missing_weight.validate().is_err());
+
+    let zero_weight = ConsensusConfig { validator_weights: vec![1, 0], ..config.clone() };
+    assert!(zero_weight.validate().is_err());
+
+    let max_weights =
+        ConsensusConfig { validator_weights: vec![MAX_TOTAL_WEIGHT - 1, 1], ..config.clone() };
+    assert!(max_weights.validate().is_ok());
+
+    let excessive_weights =
+        ConsensusConfig { validator_weights: vec![MAX_TOTAL_WEIGHT, 1], ..config.clone() };
+    assert!(excessive_weights.validate().is_err());
+
+    let overflowing_weights = ConsensusConfig { validator_weights: vec![u64::MAX, 1], ..config };
+    assert!(overflowing_weights.validate().is_err());
+}
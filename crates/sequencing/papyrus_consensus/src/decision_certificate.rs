@@ -0,0 +1,155 @@
+//! Certificates of the decisions of consensus.
+//!
+//! A [`DecisionCertificate`] holds the precommits that decided a block. It is stored next to the
+//! block and served to syncing nodes, which verify that the block was finalized by a quorum of the
+//! validators without taking part in consensus.
+
+#[cfg(test)]
+#[path = "decision_certificate_test.rs"]
+mod decision_certificate_test;
+
+use std::collections::{HashMap, HashSet};
+
+use papyrus_protobuf::consensus::{Vote, VoteType};
+use starknet_api::block::{
+    BlockHash,
+    BlockNumber,
+    DecidedHeaderField,
+    DecisionCertificate,
+    PrecommitSignature,
+};
+use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::PublicKey;
+
+use crate::config::ConsensusConfig;
+use crate::signing::verify_vote;
+use crate::types::{
+    quorum,
+    total_weight,
+    validators_from_config,
+    ValidatorId,
+    ValidatorSet,
+    VotingWeight,
+};
+
+/// The reason a decision certificate failed verification.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum DecisionCertificateError {
+    /// The precommits don't have more than 2/3 of the voting weight.
+    #[error(
+        "The precommits of the certificate have a weight of {weight}, below the quorum of \
+         {quorum}."
+    )]
+    NoQuorum {
+        /// The weight of the precommits.
+        weight: VotingWeight,
+        /// The weight required for a quorum.
+        quorum: VotingWeight,
+    },
+    /// A precommit is not signed by its voter.
+    #[error("The precommit of {voter} has an invalid signature.")]
+    InvalidSignature {
+        /// The voter of the precommit.
+        voter: ValidatorId,
+    },
+}
+
+/// Returns the certificate of the decision on the given block, from the precommits that decided it.
+///
+/// Panics if there are no precommits or if they are not all for the given block in the same round.
+pub fn create_decision_certificate(block: BlockHash, precommits: &[Vote]) -> DecisionCertificate {
+    let first = precommits.first().expect("A decision should have precommits");
+    assert!(
+        precommits.iter().all(|vote| vote.vote_type == VoteType::Precommit
+            && vote.height == first.height
+            && vote.round == first.round
+            && vote.block_hash == Some(block)),
+        "The precommits of a decision should be for the decided block in the same round"
+    );
+    DecisionCertificate {
+        block_number: BlockNumber(first.height),
+        round: first.round,
+        block_hash: block,
+        precommits: precommits
+            .iter()
+            .map(|vote| PrecommitSignature { voter: vote.voter, signature: vote.signature })
+            .collect(),
+    }
+}
+
+/// Verifies decision certificates against a known validator set.
+#[derive(Clone, Debug)]
+pub struct DecisionCertificateVerifier {
+    chain_id: ChainId,
+    validators: ValidatorSet,
+    validator_public_keys: HashMap<ValidatorId, PublicKey>,
+    decided_field: DecidedHeaderField,
+}
+
+impl DecisionCertificateVerifier {
+    /// Creates a verifier of the given validators, whose messages are signed by the given keys,
+    /// and who decide on the given field of the block headers.
+    pub fn new(
+        chain_id: ChainId,
+        validators: ValidatorSet,
+        validator_public_keys: HashMap<ValidatorId, PublicKey>,
+        decided_field: DecidedHeaderField,
+    ) -> Self {
+        Self { chain_id, validators, validator_public_keys, decided_field }
+    }
+
+    /// Creates a verifier of the validators set in the config, with the same IDs and weights as
+    /// consensus gives them.
+    pub fn from_config(config: &ConsensusConfig, decided_field: DecidedHeaderField) -> Self {
+        let (validators, validator_public_keys) = validators_from_config(
+            config.num_validators,
+            &config.validator_weights,
+            &config.validator_public_keys,
+        );
+        Self::new(config.chain_id.clone(), validators, validator_public_keys, decided_field)
+    }
+
+    /// The field of the block headers the validators decide on, which the certificates cover.
+    pub fn decided_field(&self) -> DecidedHeaderField {
+        self.decided_field
+    }
+
+    /// Verifies that the precommits of the certificate are signed and have more than 2/3 of the
+    /// voting weight. Precommits of unknown validators and repeated precommits are not counted.
+    pub fn verify(
+        &self,
+        certificate: &DecisionCertificate,
+    ) -> Result<(), DecisionCertificateError> {
+        let quorum = quorum(total_weight(&self.validators));
+        let mut voters = HashSet::new();
+        let mut weight: VotingWeight = 0;
+        for precommit in &certificate.precommits {
+            let Some(voter_weight) = self.validators.get(&precommit.voter) else {
+                continue;
+            };
+            if !voters.insert(precommit.voter) {
+                continue;
+            }
+            let vote = Vote {
+                vote_type: VoteType::Precommit,
+                height: certificate.block_number.0,
+                round: certificate.round,
+                block_hash: Some(certificate.block_hash),
+                voter: precommit.voter,
+                signature: precommit.signature,
+            };
+            let is_signed = self
+                .validator_public_keys
+                .get(&precommit.voter)
+                .is_some_and(|public_key| verify_vote(&self.chain_id, &vote, public_key));
+            if !is_signed {
+                return Err(DecisionCertificateError::InvalidSignature { voter: precommit.voter });
+            }
+            weight += voter_weight;
+        }
+        if weight < quorum {
+            return Err(DecisionCertificateError::NoQuorum { weight, quorum });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use papyrus_protobuf::consensus::{ConsensusMessage, Vote};
+use starknet_api::block::{BlockHash, BlockNumber, DecidedHeaderField};
+use starknet_api::crypto::utils::Signature;
+use starknet_types_core::felt::Felt;
+
+use super::{create_decision_certificate, DecisionCertificateError, DecisionCertificateVerifier};
+use crate::test_utils::{precommit, test_chain_id, validator_public_key};
+use crate::types::{ValidatorId, ValidatorSet, DEFAULT_VALIDATOR_ID};
+
+const HEIGHT: u64 = 5;
+const ROUND: u32 = 2;
+const BLOCK: BlockHash = BlockHash(Felt::ONE);
+
+fn validators() -> Vec<ValidatorId> {
+    (DEFAULT_VALIDATOR_ID..DEFAULT_VALIDATOR_ID + 4).map(ValidatorId::from).collect()
+}
+
+/// The validators have weights 1, 1, 1 and 3, so the quorum is 5.
+fn verifier() -> DecisionCertificateVerifier {
+    let validators = validators();
+    let validator_set: ValidatorSet = validators.iter().copied().zip([1, 1, 1, 3]).collect();
+    let public_keys = validators.iter().map(|v| (*v, validator_public_key(*v))).collect();
+    DecisionCertificateVerifier::new(
+        test_chain_id(),
+        validator_set,
+        public_keys,
+        DecidedHeaderField::BlockHash,
+    )
+}
+
+fn precommits(voters: &[ValidatorId]) -> Vec<Vote> {
+    voters
+        .iter()
+        .map(|voter| {
+            let ConsensusMessage::Vote(vote) = precommit(Some(BLOCK.0), HEIGHT, ROUND, *voter)
+            else {
+                unreachable!()
+            };
+            vote
+        })
+        .collect()
+}
+
+#[test]
+fn create_certificate() {
+    let validators = validators();
+    let precommits = precommits(&validators[..2]);
+    let certificate = create_decision_certificate(BLOCK, &precommits);
+    assert_eq!(certificate.block_number, BlockNumber(HEIGHT));
+    assert_eq!(certificate.round, ROUND);
+    assert_eq!(certificate.block_hash, BLOCK);
+    assert_eq!(
+        certificate.precommits.iter().map(|precommit| precommit.voter).collect::<Vec<_>>(),
+        validators[..2]
+    );
+}
+
+#[test]
+fn verify_quorum() {
+    let validators = validators();
+    let certificate = create_decision_certificate(BLOCK, &precommits(&validators[1..]));
+    assert_eq!(verifier().verify(&certificate), Ok(()));
+
+    let certificate = create_decision_certificate(BLOCK, &precommits(&validators[..3]));
+    assert_eq!(
+        verifier().verify(&certificate),
+        Err(DecisionCertificateError::NoQuorum { weight: 3, quorum: 5 })
+    );
+}
+
+#[test]
+fn repeated_and_unknown_precommits_are_not_counted() {
+    let validators = validators();
+    // 1 + 1 + 3 is exactly the quorum.
+    let unknown = ValidatorId::from(DEFAULT_VALIDATOR_ID + 10);
+    let certificate = create_decision_certificate(
+        BLOCK,
+        &precommits(&[validators[0], validators[0], validators[1], unknown, validators[3]]),
+    );
+    assert_eq!(verifier().verify(&certificate), Ok(()));
+
+    let certificate = create_decision_certificate(
+        BLOCK,
+        &precommits(&[validators[0], validators[0], validators[0], unknown, validators[3]]),
+    );
+    assert_eq!(
+        verifier().verify(&certificate),
+        Err(DecisionCertificateError::NoQuorum { weight: 4, quorum: 5 })
+    );
+}
+
+#[test]
+fn invalid_signature() {
+    let validators = validators();
+    let mut certificate = create_decision_certificate(BLOCK, &precommits(&validators));
+    certificate.precommits[1].signature = Signature { r: Felt::ONE, s: Felt::TWO };
+    assert_eq!(
+        verifier().verify(&certificate),
+        Err(DecisionCertificateError::InvalidSignature { voter: validators[1] })
+    );
+}
+
+#[test]
+fn certificate_of_another_block() {
+    let validators = validators();
+    let mut certificate = create_decision_certificate(BLOCK, &precommits(&validators));
+    certificate.block_hash = BlockHash(Felt::TWO);
+    assert_eq!(
+        verifier().verify(&certificate),
+        Err(DecisionCertificateError::InvalidSignature { voter: validators[0] })
+    );
+}
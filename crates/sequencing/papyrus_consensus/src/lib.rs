@@ -25,12 +25,18 @@
 //! 2. It generates and runs its own events (e.g. timeouts).
 
 pub mod config;
+pub mod decision_certificate;
 #[allow(missing_docs)]
 pub mod types;
 pub use manager::run_consensus;
+pub mod proposer_selection;
+#[allow(missing_docs)]
+pub mod signing;
 #[allow(missing_docs)]
 pub mod simulation_network_receiver;
 pub mod stream_handler;
+#[allow(missing_docs)]
+pub mod wal;
 
 mod manager;
 #[allow(missing_docs)]
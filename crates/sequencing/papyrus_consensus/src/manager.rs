@@ -16,15 +16,16 @@ use futures::channel::mpsc;
 use futures::stream::FuturesUnordered;
 use futures::{Stream, StreamExt};
 use papyrus_common::metrics::{PAPYRUS_CONSENSUS_HEIGHT, PAPYRUS_CONSENSUS_SYNC_COUNT};
-use papyrus_network::network_manager::BroadcastTopicClientTrait;
+use papyrus_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalInit};
 use papyrus_protobuf::converters::ProtobufConversionError;
 use starknet_api::block::BlockNumber;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use crate::config::TimeoutsConfig;
-use crate::single_height_consensus::{ShcReturn, SingleHeightConsensus};
+use crate::signing::{verify_vote, ConsensusSigner};
+use crate::single_height_consensus::{ShcEvent, ShcReturn, ShcTask, SingleHeightConsensus};
 use crate::types::{
     BroadcastConsensusMessageChannel,
     ConsensusContext,
@@ -32,6 +33,7 @@ use crate::types::{
     Decision,
     ValidatorId,
 };
+use crate::wal::{Wal, WalEntry, WalError};
 
 /// Run consensus indefinitely.
 ///
@@ -46,10 +48,15 @@ use crate::types::{
 /// - `validator_id`: The ID of this node.
 /// - `consensus_delay`: delay before starting consensus; allowing the network to connect to peers.
 /// - `timeouts`: The timeouts for the consensus algorithm.
+/// - `signer`: Signs the votes of this node. Votes from the network are only accepted if they are
+///   signed by their voters.
+/// - `wal`: The write-ahead log of the votes of this node, used to restore them if the node
+///   restarts in the middle of a height. See [`crate::wal`].
 /// - `vote_receiver`: The channels to receive votes from the network. These are self contained
 ///   messages.
 /// - `proposal_receiver`: The channel to receive proposals from the network. Proposals are
-///   represented as streams (ProposalInit, Content.*, ProposalFin).
+///   represented as streams (ProposalInit, Content.*, ProposalFin), each with the metadata of the
+///   peer which sent it.
 // TODO(dvir): add test for this.
 // TODO(Asmaa): Update documentation when we update for the real sync.
 #[instrument(skip_all, level = "info")]
@@ -62,8 +69,13 @@ pub async fn run_consensus<ContextT, SyncReceiverT>(
     validator_id: ValidatorId,
     consensus_delay: Duration,
     timeouts: TimeoutsConfig,
+    signer: ConsensusSigner,
+    wal: Option<Wal>,
     mut vote_receiver: BroadcastConsensusMessageChannel,
-    mut proposal_receiver: mpsc::Receiver<mpsc::Receiver<ContextT::ProposalPart>>,
+    mut proposal_receiver: mpsc::Receiver<(
+        BroadcastedMessageMetadata,
+        mpsc::Receiver<ContextT::ProposalPart>,
+    )>,
     mut sync_receiver: SyncReceiverT,
 ) -> Result<(), ConsensusError>
 where
@@ -84,7 +96,7 @@ where
     tokio::time::sleep(consensus_delay).await;
     assert!(start_observe_height <= start_active_height);
     let mut current_height = start_observe_height;
-    let mut manager = MultiHeightManager::new(validator_id, timeouts);
+    let mut manager = MultiHeightManager::new(validator_id, timeouts, signer, wal);
     #[allow(clippy::as_conversions)] // FIXME: use int metrics so `as f64` may be removed.
     loop {
         metrics::gauge!(PAPYRUS_CONSENSUS_HEIGHT, current_height.0 as f64);
@@ -110,6 +122,9 @@ where
                 current_height = sync_height.unchecked_next();
             }
         }
+        // The votes of a decided height are no longer needed. This is done only after the context
+        // has learned of the decision, so a restart can't resume the height without them.
+        manager.truncate_wal()?;
     }
 }
 
@@ -123,27 +138,36 @@ pub enum RunHeightRes {
     Sync(BlockNumber),
 }
 
-type ProposalReceiverTuple<T> = (ProposalInit, mpsc::Receiver<T>);
+type ProposalReceiverTuple<T> = (ProposalInit, BroadcastedMessageMetadata, mpsc::Receiver<T>);
 
 /// Runs Tendermint repeatedly across different heights. Handles issues which are not explicitly
 /// part of the single height consensus algorithm (e.g. messages from future heights).
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct MultiHeightManager<ContextT: ConsensusContext> {
     validator_id: ValidatorId,
     cached_messages: BTreeMap<u64, Vec<ConsensusMessage>>,
-    // Mapping: { Height : { Round : (Init, Receiver)}}
+    // Mapping: { Height : { Round : (Init, Sender, Receiver)}}
     cached_proposals: BTreeMap<u64, BTreeMap<u32, ProposalReceiverTuple<ContextT::ProposalPart>>>,
     timeouts: TimeoutsConfig,
+    signer: ConsensusSigner,
+    wal: Option<Wal>,
 }
 
 impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
     /// Create a new consensus manager.
-    pub(crate) fn new(validator_id: ValidatorId, timeouts: TimeoutsConfig) -> Self {
+    pub(crate) fn new(
+        validator_id: ValidatorId,
+        timeouts: TimeoutsConfig,
+        signer: ConsensusSigner,
+        wal: Option<Wal>,
+    ) -> Self {
         Self {
             validator_id,
             cached_messages: BTreeMap::new(),
             cached_proposals: BTreeMap::new(),
             timeouts,
+            signer,
+            wal,
         }
     }
 
@@ -158,6 +182,9 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
     ///
     /// Assumes that `height` is monotonically increasing across calls.
     ///
+    /// If the node restarted in the middle of this height, the votes it sent are restored from the
+    /// WAL, so it doesn't send conflicting votes.
+    ///
     /// Inputs - see [`run_consensus`].
     /// - `must_observer`: Whether the node must observe or if it is allowed to be active (assuming
     ///   it is in the validator set).
@@ -168,14 +195,17 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         height: BlockNumber,
         must_observer: bool,
         broadcast_channels: &mut BroadcastConsensusMessageChannel,
-        proposal_receiver: &mut mpsc::Receiver<mpsc::Receiver<ContextT::ProposalPart>>,
+        proposal_receiver: &mut mpsc::Receiver<(
+            BroadcastedMessageMetadata,
+            mpsc::Receiver<ContextT::ProposalPart>,
+        )>,
         sync_receiver: &mut SyncReceiverT,
     ) -> Result<RunHeightRes, ConsensusError>
     where
         SyncReceiverT: Stream<Item = BlockNumber> + Unpin,
     {
         let validators = context.validators(height).await;
-        let is_observer = must_observer || !validators.contains(&self.validator_id);
+        let is_observer = must_observer || !validators.contains_key(&self.validator_id);
         info!("running consensus for height {height:?} with validator set {validators:?}");
         let mut shc = SingleHeightConsensus::new(
             height,
@@ -183,30 +213,51 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             self.validator_id,
             validators,
             self.timeouts.clone(),
+            self.signer.clone(),
+            self.wal.clone(),
         );
+        let wal_entries = if is_observer { Vec::new() } else { self.read_wal(height)? };
+        if !wal_entries.is_empty() {
+            shc.restore(wal_entries);
+        }
+        // The tasks of a proposal are run with the peer which sent it, so the peer can be reported
+        // if the proposal turns out to be invalid.
         let mut shc_events = FuturesUnordered::new();
 
         match self.start_height(context, height, &mut shc).await? {
             ShcReturn::Decision(decision) => return Ok(RunHeightRes::Decision(decision)),
             ShcReturn::Tasks(tasks) => {
-                for task in tasks {
-                    shc_events.push(task.run());
-                }
+                shc_events.extend(tasks.into_iter().map(|task| run_task(task, None)));
+            }
+        }
+        for (init, sender, content_receiver) in self.get_current_proposal(height) {
+            let ret = shc.handle_proposal(context, init, content_receiver).await;
+            let client = &mut broadcast_channels.broadcast_topic_client;
+            match report_invalid_proposal(ret, Some(&sender), client).await? {
+                ShcReturn::Decision(decision) => return Ok(RunHeightRes::Decision(decision)),
+                ShcReturn::Tasks(tasks) => shc_events
+                    .extend(tasks.into_iter().map(|task| run_task(task, Some(sender.clone())))),
             }
         }
 
         // Loop over incoming proposals, messages, and self generated events.
         loop {
-            let shc_return = tokio::select! {
+            let (shc_return, proposal_sender) = tokio::select! {
                 message = broadcast_channels.broadcasted_messages_receiver.next() => {
-                    self.handle_message(
-                        context, height, &mut shc, message, broadcast_channels).await?
+                    let ret = self.handle_message(
+                        context, height, &mut shc, message, broadcast_channels).await?;
+                    (ret, None)
                 },
-                Some(content_receiver) = proposal_receiver.next() => {
-                    self.handle_proposal(context, height, &mut shc, content_receiver).await?
+                Some((sender, content_receiver)) = proposal_receiver.next() => {
+                    let ret = self.handle_proposal(
+                        context, height, &mut shc, sender.clone(), content_receiver).await;
+                    let client = &mut broadcast_channels.broadcast_topic_client;
+                    (report_invalid_proposal(ret, Some(&sender), client).await?, Some(sender))
                 },
-                Some(shc_event) = shc_events.next() => {
-                    shc.handle_event(context, shc_event).await?
+                Some((shc_event, sender)) = shc_events.next() => {
+                    let ret = shc.handle_event(context, shc_event).await;
+                    let client = &mut broadcast_channels.broadcast_topic_client;
+                    (report_invalid_proposal(ret, sender.as_ref(), client).await?, None)
                 },
                 sync_height = sync_receiver.next() => {
                     let Some(sync_height) = sync_height else {
@@ -225,13 +276,33 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
                 ShcReturn::Decision(decision) => return Ok(RunHeightRes::Decision(decision)),
                 ShcReturn::Tasks(tasks) => {
                     for task in tasks {
-                        shc_events.push(task.run());
+                        shc_events.push(run_task(task, proposal_sender.clone()));
                     }
                 }
             }
         }
     }
 
+    // Returns the entries of the height in the WAL. Entries of other heights are left from a height
+    // which was decided before a restart, and are ignored.
+    fn read_wal(&self, height: BlockNumber) -> Result<Vec<WalEntry>, WalError> {
+        let Some(wal) = &self.wal else {
+            return Ok(Vec::new());
+        };
+        Ok(wal
+            .read_entries()?
+            .into_iter()
+            .filter(|entry| entry.vote.height == height.0 && entry.vote.voter == self.validator_id)
+            .collect())
+    }
+
+    fn truncate_wal(&self) -> Result<(), WalError> {
+        let Some(wal) = &self.wal else {
+            return Ok(());
+        };
+        wal.truncate()
+    }
+
     async fn start_height(
         &mut self,
         context: &mut ContextT,
@@ -243,17 +314,14 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             ShcReturn::Tasks(tasks) => tasks,
         };
 
-        for (init, content_receiver) in self.get_current_proposal(height) {
-            match shc.handle_proposal(context, init, content_receiver).await? {
-                decision @ ShcReturn::Decision(_) => return Ok(decision),
-                ShcReturn::Tasks(new_tasks) => tasks.extend(new_tasks),
-            }
-        }
-
         for msg in self.get_current_height_messages(height) {
-            match shc.handle_message(context, msg).await? {
-                decision @ ShcReturn::Decision(_) => return Ok(decision),
-                ShcReturn::Tasks(new_tasks) => tasks.extend(new_tasks),
+            match shc.handle_message(context, msg).await {
+                Ok(decision @ ShcReturn::Decision(_)) => return Ok(decision),
+                Ok(ShcReturn::Tasks(new_tasks)) => tasks.extend(new_tasks),
+                // The evidence was reported by the SHC. The peer which sent the cached message is
+                // unknown, so it can't be reported.
+                Err(ConsensusError::Equivocation(_)) => {}
+                Err(err) => return Err(err),
             }
         }
 
@@ -266,6 +334,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         context: &mut ContextT,
         height: BlockNumber,
         shc: &mut SingleHeightConsensus,
+        sender: BroadcastedMessageMetadata,
         mut content_receiver: mpsc::Receiver<ContextT::ProposalPart>,
     ) -> Result<ShcReturn, ConsensusError> {
         // Get the first message to verify the init was sent.
@@ -286,7 +355,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
                     .entry(proposal_init.height.0)
                     .or_default()
                     .entry(proposal_init.round)
-                    .or_insert((proposal_init, content_receiver));
+                    .or_insert((proposal_init, sender, content_receiver));
             }
             return Ok(ShcReturn::Tasks(Vec::new()));
         }
@@ -305,15 +374,19 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         )>,
         broadcast_channels: &mut BroadcastConsensusMessageChannel,
     ) -> Result<ShcReturn, ConsensusError> {
-        let message = match message {
+        let (message, metadata) = match message {
             None => Err(ConsensusError::InternalNetworkError(
                 "NetworkReceiver should never be closed".to_string(),
             )),
             Some((Ok(msg), metadata)) => {
-                // TODO(matan): Hold onto report_sender for use in later errors by SHC.
+                if !self.is_signed_by_sender(context, &msg) {
+                    warn!("Received a message with an invalid signature. {:?}", msg);
+                    let _ = broadcast_channels.broadcast_topic_client.report_peer(metadata).await;
+                    return Ok(ShcReturn::Tasks(Vec::new()));
+                }
                 let _ =
                     broadcast_channels.broadcast_topic_client.continue_propagation(&metadata).await;
-                Ok(msg)
+                Ok((msg, metadata))
             }
             Some((Err(e), metadata)) => {
                 // Failed to parse consensus message
@@ -334,7 +407,26 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             return Ok(ShcReturn::Tasks(Vec::new()));
         }
 
-        shc.handle_message(context, message).await
+        match shc.handle_message(context, message).await {
+            Err(ConsensusError::Equivocation(evidence)) => {
+                // The evidence was reported to the context by the SHC. Votes are signed by their
+                // voters, so only the voter is at fault and not the peer which relayed the vote.
+                debug!("Ignoring an equivocating vote. {:?}", evidence);
+                Ok(ShcReturn::Tasks(Vec::new()))
+            }
+            ret => ret,
+        }
+    }
+
+    // Votes are only accepted if they are signed by their voters. Proposals are streamed, and their
+    // fins are verified by the SHC once their content is validated.
+    fn is_signed_by_sender(&self, context: &ContextT, message: &ConsensusMessage) -> bool {
+        match message {
+            ConsensusMessage::Vote(vote) => context
+                .validator_public_key(BlockNumber(vote.height), vote.voter)
+                .is_some_and(|public_key| verify_vote(self.signer.chain_id(), vote, &public_key)),
+            ConsensusMessage::Proposal(_) => true,
+        }
     }
 
     // Checks if a cached proposal already exists (with correct height)
@@ -345,7 +437,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
     fn get_current_proposal(
         &mut self,
         height: BlockNumber,
-    ) -> Vec<(ProposalInit, mpsc::Receiver<ContextT::ProposalPart>)> {
+    ) -> Vec<ProposalReceiverTuple<ContextT::ProposalPart>> {
         loop {
             let Some(entry) = self.cached_proposals.first_entry() else {
                 return Vec::new();
@@ -383,3 +475,33 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         }
     }
 }
+
+async fn run_task(
+    task: ShcTask,
+    proposal_sender: Option<BroadcastedMessageMetadata>,
+) -> (ShcEvent, Option<BroadcastedMessageMetadata>) {
+    (task.run().await, proposal_sender)
+}
+
+// An invalid or a conflicting proposal is not fatal to consensus. The peer which sent it is
+// reported, if it is known. Proposals are streamed by their proposers, so the sender of a
+// conflicting proposal is the offender. The evidence was reported to the context by the SHC.
+async fn report_invalid_proposal(
+    ret: Result<ShcReturn, ConsensusError>,
+    proposal_sender: Option<&BroadcastedMessageMetadata>,
+    broadcast_topic_client: &mut BroadcastTopicClient<ConsensusMessage>,
+) -> Result<ShcReturn, ConsensusError> {
+    match ret {
+        Err(err @ (ConsensusError::InvalidProposal(..) | ConsensusError::Equivocation(_))) => {
+            match proposal_sender {
+                Some(sender) => {
+                    warn!("Reporting the peer which sent an invalid proposal. {err}");
+                    let _ = broadcast_topic_client.report_peer(sender.clone()).await;
+                }
+                None => warn!("Ignoring an invalid proposal of an unknown peer. {err}"),
+            }
+            Ok(ShcReturn::Tasks(Vec::new()))
+        }
+        ret => ret,
+    }
+}
@@ -4,7 +4,7 @@ use std::vec;
 
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
-use futures::SinkExt;
+use futures::{SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use mockall::mock;
 use mockall::predicate::eq;
@@ -23,20 +23,34 @@ use papyrus_protobuf::consensus::{
 };
 use papyrus_test_utils::{get_rng, GetTestInstance};
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::crypto::utils::PublicKey;
 use starknet_types_core::felt::Felt;
+use tempfile::NamedTempFile;
 use tokio::sync::Notify;
 
-use super::{run_consensus, MultiHeightManager, RunHeightRes};
+use super::{report_invalid_proposal, run_consensus, MultiHeightManager, RunHeightRes};
 use crate::config::TimeoutsConfig;
-use crate::test_utils::{precommit, prevote, proposal_init};
+use crate::single_height_consensus::ShcReturn;
+use crate::test_utils::{
+    equal_weight_validators,
+    precommit,
+    prevote,
+    proposal_fin,
+    proposal_init,
+    validator_public_key,
+    validator_signer,
+};
 use crate::types::{
     ConsensusContext,
     ConsensusError,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
+    ValidatorSet,
     DEFAULT_VALIDATOR_ID,
 };
+use crate::wal::{Wal, WalEntry};
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = DEFAULT_VALIDATOR_ID.into();
@@ -80,10 +94,16 @@ mock! {
             init: ProposalInit,
         );
 
-        async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+        async fn validators(&self, height: BlockNumber) -> ValidatorSet;
 
         fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
+        fn validator_public_key(
+            &self,
+            height: BlockNumber,
+            validator: ValidatorId,
+        ) -> Option<PublicKey>;
+
         async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError>;
 
         async fn decision_reached(
@@ -92,6 +112,8 @@ mock! {
             precommits: Vec<Vote>,
         ) -> Result<(), ConsensusError>;
 
+        async fn report_evidence(&mut self, evidence: Evidence);
+
         async fn set_height_and_round(&mut self, height: BlockNumber, round: Round);
     }
 }
@@ -103,11 +125,16 @@ async fn send(sender: &mut MockBroadcastedMessagesSender<ConsensusMessage>, msg:
 }
 
 async fn send_proposal(
-    proposal_receiver_sender: &mut mpsc::Sender<mpsc::Receiver<ProposalPart>>,
+    proposal_receiver_sender: &mut mpsc::Sender<(
+        BroadcastedMessageMetadata,
+        mpsc::Receiver<ProposalPart>,
+    )>,
     content: Vec<ProposalPart>,
 ) {
+    let broadcasted_message_metadata =
+        BroadcastedMessageMetadata::get_test_instance(&mut get_rng());
     let (mut proposal_sender, proposal_receiver) = mpsc::channel(CHANNEL_SIZE);
-    proposal_receiver_sender.send(proposal_receiver).await.unwrap();
+    proposal_receiver_sender.send((broadcasted_message_metadata, proposal_receiver)).await.unwrap();
     for item in content {
         proposal_sender.send(item).await.unwrap();
     }
@@ -116,19 +143,31 @@ async fn send_proposal(
 fn expect_validate_proposal(context: &mut MockTestContext, block_hash: Felt) {
     context
         .expect_validate_proposal()
-        .return_once(move |_, _, _, _, _| {
+        .return_once(move |height, round, proposer, _, _| {
             let (block_sender, block_receiver) = oneshot::channel();
+            let init = proposal_init(height.0, round, proposer);
             block_sender
-                .send((
-                    BlockHash(block_hash),
-                    ProposalFin { proposal_content_id: BlockHash(block_hash) },
-                ))
+                .send((BlockHash(block_hash), proposal_fin(&init, BlockHash(block_hash))))
                 .unwrap();
             block_receiver
         })
         .times(1);
 }
 
+fn expect_validator_public_keys(context: &mut MockTestContext) {
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
+}
+
+fn wal_entry(vote: ConsensusMessage, locked_value_round: Option<(Felt, Round)>) -> WalEntry {
+    let ConsensusMessage::Vote(vote) = vote else {
+        unreachable!("Only votes are written to the WAL");
+    };
+    let locked_value_round = locked_value_round.map(|(block, round)| (BlockHash(block), round));
+    WalEntry { vote, locked_value_round, valid_value_round: locked_value_round }
+}
+
 fn assert_decision(res: RunHeightRes, id: Felt) {
     match res {
         RunHeightRes::Decision(decision) => assert_eq!(decision.block, BlockHash(id)),
@@ -151,7 +190,10 @@ async fn manager_multiple_heights_unordered() {
         &mut proposal_receiver_sender,
         vec![
             ProposalPart::Init(proposal_init(2, 0, *PROPOSER_ID)),
-            ProposalPart::Fin(ProposalFin { proposal_content_id: BlockHash(Felt::TWO) }),
+            ProposalPart::Fin(proposal_fin(
+                &proposal_init(2, 0, *PROPOSER_ID),
+                BlockHash(Felt::TWO),
+            )),
         ],
     )
     .await;
@@ -162,7 +204,10 @@ async fn manager_multiple_heights_unordered() {
         &mut proposal_receiver_sender,
         vec![
             ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)),
-            ProposalPart::Fin(ProposalFin { proposal_content_id: BlockHash(Felt::ONE) }),
+            ProposalPart::Fin(proposal_fin(
+                &proposal_init(1, 0, *PROPOSER_ID),
+                BlockHash(Felt::ONE),
+            )),
         ],
     )
     .await;
@@ -172,12 +217,20 @@ async fn manager_multiple_heights_unordered() {
     let mut context = MockTestContext::new();
     // Run the manager for height 1.
     expect_validate_proposal(&mut context, Felt::ONE);
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
+    context
+        .expect_validators()
+        .returning(move |_| equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID]));
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
 
-    let mut manager = MultiHeightManager::new(*VALIDATOR_ID, TIMEOUTS.clone());
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID),
+        None,
+    );
     let mut subscriber_channels = subscriber_channels.into();
     let decision = manager
         .run_height(
@@ -218,8 +271,11 @@ async fn run_consensus_sync() {
     let (mut proposal_receiver_sender, proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
 
     expect_validate_proposal(&mut context, Felt::TWO);
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
+    context
+        .expect_validators()
+        .returning(move |_| equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID]));
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
     context.expect_decision_reached().return_once(move |block, votes| {
@@ -241,8 +297,14 @@ async fn run_consensus_sync() {
     send(&mut network_sender, prevote(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
     send(&mut network_sender, precommit(Some(Felt::TWO), 2, 0, *PROPOSER_ID)).await;
 
+    // A vote of height 1 from before a restart.
+    let wal_file = NamedTempFile::new().unwrap();
+    let wal = Wal::open(wal_file.path()).unwrap();
+    wal.append(&wal_entry(prevote(None, 1, 0, *VALIDATOR_ID), None)).unwrap();
+
     // Start at height 1.
     let (mut sync_sender, mut sync_receiver) = mpsc::unbounded();
+    let consensus_wal = wal.clone();
     let consensus_handle = tokio::spawn(async move {
         run_consensus(
             context,
@@ -251,6 +313,8 @@ async fn run_consensus_sync() {
             *VALIDATOR_ID,
             Duration::ZERO,
             TIMEOUTS.clone(),
+            validator_signer(*VALIDATOR_ID),
+            Some(consensus_wal),
             subscriber_channels.into(),
             proposal_receiver_receiver,
             &mut sync_receiver,
@@ -269,6 +333,8 @@ async fn run_consensus_sync() {
     // Drop the sender to close consensus and gracefully shut down.
     drop(sync_sender);
     assert!(matches!(consensus_handle.await.unwrap(), Err(ConsensusError::SyncError(_))));
+    // The WAL is truncated once a height is decided.
+    assert!(wal.read_entries().unwrap().is_empty());
 }
 
 // Check for cancellation safety when ignoring old heights. If the current height check was done
@@ -283,8 +349,11 @@ async fn run_consensus_sync_cancellation_safety() {
     let (mut proposal_receiver_sender, proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
 
     expect_validate_proposal(&mut context, Felt::ONE);
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
+    context
+        .expect_validators()
+        .returning(move |_| equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID]));
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
     context.expect_set_height_and_round().returning(move |_, _| ());
     let proposal_handled_clone = Arc::clone(&proposal_handled);
     context
@@ -315,6 +384,8 @@ async fn run_consensus_sync_cancellation_safety() {
             *VALIDATOR_ID,
             Duration::ZERO,
             TIMEOUTS.clone(),
+            validator_signer(*VALIDATOR_ID),
+            None,
             subscriber_channels.into(),
             proposal_receiver_receiver,
             &mut sync_receiver,
@@ -368,17 +439,19 @@ async fn test_timeouts() {
 
     let mut context = MockTestContext::new();
     context.expect_set_height_and_round().returning(move |_, _| ());
-    context.expect_validate_proposal().returning(move |_, _, _, _, _| {
+    context.expect_validate_proposal().returning(move |height, round, proposer, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
+        let init = proposal_init(height.0, round, proposer);
         block_sender
-            .send((BlockHash(Felt::ONE), ProposalFin { proposal_content_id: BlockHash(Felt::ONE) }))
+            .send((BlockHash(Felt::ONE), proposal_fin(&init, BlockHash(Felt::ONE))))
             .unwrap();
         block_receiver
     });
-    context
-        .expect_validators()
-        .returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3]);
+    context.expect_validators().returning(move |_| {
+        equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3])
+    });
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
 
     let (timeout_send, timeout_receive) = oneshot::channel();
     // Node handled Timeout events and responded with NIL vote.
@@ -392,7 +465,12 @@ async fn test_timeouts() {
         });
     context.expect_broadcast().returning(move |_| Ok(()));
 
-    let mut manager = MultiHeightManager::new(*VALIDATOR_ID, TIMEOUTS.clone());
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID),
+        None,
+    );
     let manager_handle = tokio::spawn(async move {
         let decision = manager
             .run_height(
@@ -425,3 +503,341 @@ async fn test_timeouts() {
 
     manager_handle.await.unwrap();
 }
+
+#[tokio::test]
+async fn report_vote_with_invalid_signature() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut sender = mock_network.broadcasted_messages_sender;
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let (_proposal_receiver_sender, mut proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
+
+    // A prevote on behalf of the proposer, signed by another validator.
+    let ConsensusMessage::Vote(vote) = prevote(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_2) else {
+        unreachable!("prevote returns a vote");
+    };
+    send(&mut sender, ConsensusMessage::Vote(Vote { voter: *PROPOSER_ID, ..vote })).await;
+
+    let mut context = MockTestContext::new();
+    context
+        .expect_validators()
+        .returning(move |_| equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID]));
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID),
+        None,
+    );
+    let manager_handle = tokio::spawn(async move {
+        manager
+            .run_height(
+                &mut context,
+                BlockNumber(1),
+                false,
+                &mut subscriber_channels.into(),
+                &mut proposal_receiver_receiver,
+                &mut futures::stream::pending(),
+            )
+            .await
+    });
+
+    // The sender of the forged vote is reported.
+    assert!(reported_messages_receiver.next().await.is_some());
+    manager_handle.abort();
+}
+
+#[tokio::test]
+async fn report_proposal_with_invalid_signature() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    // A proposal on behalf of the proposer, signed by another validator.
+    let forged_fin = proposal_fin(&proposal_init(1, 0, *VALIDATOR_ID_2), BlockHash(Felt::ONE));
+    send_proposal(
+        &mut proposal_receiver_sender,
+        vec![
+            ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)),
+            ProposalPart::Fin(forged_fin.clone()),
+        ],
+    )
+    .await;
+
+    let mut context = MockTestContext::new();
+    context.expect_validate_proposal().return_once(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BlockHash(Felt::ONE), forged_fin)).unwrap();
+        block_receiver
+    });
+    context
+        .expect_validators()
+        .returning(move |_| equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID]));
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID),
+        None,
+    );
+    let manager_handle = tokio::spawn(async move {
+        manager
+            .run_height(
+                &mut context,
+                BlockNumber(1),
+                false,
+                &mut subscriber_channels.into(),
+                &mut proposal_receiver_receiver,
+                &mut futures::stream::pending(),
+            )
+            .await
+    });
+
+    // The sender of the forged proposal is reported, and consensus continues.
+    assert!(reported_messages_receiver.next().await.is_some());
+    assert!(!manager_handle.is_finished());
+    manager_handle.abort();
+}
+
+#[tokio::test]
+async fn report_conflicting_proposal() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    // Two proposals for the same round, both signed by the proposer.
+    let first_fin = proposal_fin(&proposal_init(1, 0, *PROPOSER_ID), BlockHash(Felt::ONE));
+    let conflicting_fin = proposal_fin(&proposal_init(1, 0, *PROPOSER_ID), BlockHash(Felt::TWO));
+    send_proposal(
+        &mut proposal_receiver_sender,
+        vec![ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)), ProposalPart::Fin(first_fin)],
+    )
+    .await;
+    send_proposal(
+        &mut proposal_receiver_sender,
+        vec![
+            ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)),
+            ProposalPart::Fin(conflicting_fin),
+        ],
+    )
+    .await;
+
+    let mut context = MockTestContext::new();
+    expect_validate_proposal(&mut context, Felt::ONE);
+    context
+        .expect_validators()
+        .returning(move |_| equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID]));
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+    context
+        .expect_report_evidence()
+        .times(1)
+        .withf(move |evidence| matches!(evidence, Evidence::ConflictingProposals(..)))
+        .returning(move |_| ());
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID),
+        None,
+    );
+    let manager_handle = tokio::spawn(async move {
+        manager
+            .run_height(
+                &mut context,
+                BlockNumber(1),
+                false,
+                &mut subscriber_channels.into(),
+                &mut proposal_receiver_receiver,
+                &mut futures::stream::pending(),
+            )
+            .await
+    });
+
+    // The proposer streamed the conflicting proposals, so it is reported, and consensus continues.
+    assert!(reported_messages_receiver.next().await.is_some());
+    assert!(!manager_handle.is_finished());
+    manager_handle.abort();
+}
+
+// Proposals validated in tasks started with the height have no known sender.
+#[tokio::test]
+async fn invalid_proposal_of_unknown_sender_is_not_fatal() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic::<ConsensusMessage>().unwrap();
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let mut broadcast_topic_client = subscriber_channels.broadcast_topic_client;
+
+    let invalid_proposal =
+        Err(ConsensusError::InvalidProposal(*PROPOSER_ID, BlockNumber(1), "invalid".to_string()));
+    let ret = report_invalid_proposal(invalid_proposal, None, &mut broadcast_topic_client).await;
+
+    assert!(matches!(ret, Ok(ShcReturn::Tasks(tasks)) if tasks.is_empty()));
+    assert!(!matches!(reported_messages_receiver.try_next(), Ok(Some(_))));
+}
+
+#[tokio::test]
+async fn equivocating_vote_is_reported_as_evidence() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut sender = mock_network.broadcasted_messages_sender;
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    send_proposal(
+        &mut proposal_receiver_sender,
+        vec![
+            ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)),
+            ProposalPart::Fin(proposal_fin(
+                &proposal_init(1, 0, *PROPOSER_ID),
+                BlockHash(Felt::ONE),
+            )),
+        ],
+    )
+    .await;
+    let first_vote = prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID);
+    let conflicting_vote = prevote(Some(Felt::TWO), 1, 0, *PROPOSER_ID);
+    send(&mut sender, first_vote.clone()).await;
+    send(&mut sender, conflicting_vote.clone()).await;
+    send(&mut sender, precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+
+    let mut context = MockTestContext::new();
+    expect_validate_proposal(&mut context, Felt::ONE);
+    context
+        .expect_validators()
+        .returning(move |_| equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID]));
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+    let (ConsensusMessage::Vote(first_vote), ConsensusMessage::Vote(conflicting_vote)) =
+        (first_vote, conflicting_vote)
+    else {
+        unreachable!("prevote returns a vote");
+    };
+    context
+        .expect_report_evidence()
+        .times(1)
+        .withf(move |evidence| {
+            evidence == &Evidence::ConflictingVotes(first_vote.clone(), conflicting_vote.clone())
+        })
+        .returning(move |_| ());
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID),
+        None,
+    );
+    // Consensus continues after the conflicting vote is ignored.
+    let decision = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels.into(),
+            &mut proposal_receiver_receiver,
+            &mut futures::stream::pending(),
+        )
+        .await
+        .unwrap();
+    assert_decision(decision, Felt::ONE);
+
+    // The peer which relayed the conflicting vote isn't at fault, so it isn't reported.
+    assert!(!matches!(reported_messages_receiver.try_next(), Ok(Some(_))));
+}
+
+#[tokio::test]
+async fn run_height_restores_votes_from_wal() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut sender = mock_network.broadcasted_messages_sender;
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    // Before the restart, the node timed out on the proposal and then got a Prevote quorum on block
+    // 1. The vote of height 0 is left from a height which was decided.
+    let own_votes =
+        [prevote(None, 1, 0, *VALIDATOR_ID), precommit(Some(Felt::ONE), 1, 0, *VALIDATOR_ID)];
+    let wal_file = NamedTempFile::new().unwrap();
+    let wal = Wal::open(wal_file.path()).unwrap();
+    wal.append(&wal_entry(precommit(Some(Felt::TWO), 0, 0, *VALIDATOR_ID), Some((Felt::TWO, 0))))
+        .unwrap();
+    wal.append(&wal_entry(own_votes[0].clone(), None)).unwrap();
+    wal.append(&wal_entry(own_votes[1].clone(), Some((Felt::ONE, 0)))).unwrap();
+
+    send_proposal(
+        &mut proposal_receiver_sender,
+        vec![
+            ProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID)),
+            ProposalPart::Fin(proposal_fin(
+                &proposal_init(1, 0, *PROPOSER_ID),
+                BlockHash(Felt::ONE),
+            )),
+        ],
+    )
+    .await;
+    for voter in [*PROPOSER_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3] {
+        send(&mut sender, prevote(Some(Felt::ONE), 1, 0, voter)).await;
+    }
+    for voter in [*PROPOSER_ID, *VALIDATOR_ID_2] {
+        send(&mut sender, precommit(Some(Felt::ONE), 1, 0, voter)).await;
+    }
+
+    let mut context = MockTestContext::new();
+    expect_validate_proposal(&mut context, Felt::ONE);
+    context.expect_validators().returning(move |_| {
+        equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3])
+    });
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    expect_validator_public_keys(&mut context);
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    // Only the restored votes are sent. Without them, the node would now prevote for the proposal.
+    let expected_votes = own_votes.clone();
+    context
+        .expect_broadcast()
+        .withf(move |msg: &ConsensusMessage| expected_votes.contains(msg))
+        .times(2..)
+        .returning(move |_| Ok(()));
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID),
+        Some(wal),
+    );
+    let decision = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels.into(),
+            &mut proposal_receiver_receiver,
+            &mut futures::stream::pending(),
+        )
+        .await
+        .unwrap();
+    let RunHeightRes::Decision(decision) = decision else {
+        panic!("Expected decision");
+    };
+    assert_eq!(decision.block, BlockHash(Felt::ONE));
+    let ConsensusMessage::Vote(own_precommit) = own_votes[1].clone() else {
+        unreachable!("precommit returns a vote");
+    };
+    assert!(decision.precommits.contains(&own_precommit));
+}
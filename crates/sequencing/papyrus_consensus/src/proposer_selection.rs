@@ -0,0 +1,115 @@
+//! Deterministic, stake proportional selection of the proposer of each round.
+//!
+//! The proposer is selected as in Tendermint's proposer priority algorithm: at each selection every
+//! validator's priority grows by its voting weight, the validator with the highest priority is
+//! selected (ties are broken by the lowest ID), and the priority of the selected validator is
+//! reduced by the total weight. Over any `total_weight` consecutive selections, each validator is
+//! selected exactly `weight` times, and the selections of a validator are spread evenly.
+//!
+//! Selections are indexed by `height + round`, so the proposer is a function of the validator set,
+//! the height and the round alone, and is agreed on by all nodes without sharing state. Note that
+//! `(height, round + 1)` and `(height + 1, round)` therefore have the same proposer.
+
+#[cfg(test)]
+#[path = "proposer_selection_test.rs"]
+mod proposer_selection_test;
+
+use starknet_api::block::BlockNumber;
+
+use crate::types::{Round, ValidatorId, ValidatorSet, VotingWeight};
+
+/// The maximal total voting weight of the validators, which bounds the number of selections a call
+/// to [`ProposerSelector::proposer`] replays.
+pub const MAX_TOTAL_WEIGHT: VotingWeight = 1000;
+
+/// Selects the proposers of a validator set.
+///
+/// The priorities are kept between calls, at the start of the latest height and at the latest
+/// round, so moving to the next round or height costs a single selection, which is linear in the
+/// number of validators. The priorities return to their initial values after `total_weight`
+/// selections (with the weights divided by their greatest common divisor), so the first call, or a
+/// call for an earlier height, replays up to that many selections. The weights should hence be
+/// small, e.g. stake expressed in coarse units, see [`MAX_TOTAL_WEIGHT`].
+#[derive(Debug)]
+pub struct ProposerSelector {
+    weights: Vec<(ValidatorId, i128)>,
+    total_weight: i128,
+    height_priorities: Priorities,
+    round_priorities: Priorities,
+}
+
+impl ProposerSelector {
+    /// Panics if the total voting weight of the validators is zero.
+    pub fn new(validators: &ValidatorSet) -> Self {
+        let weights_gcd = validators.values().copied().fold(0, gcd);
+        assert!(weights_gcd > 0, "The validators should have a positive total weight.");
+        let weights: Vec<(ValidatorId, i128)> = validators
+            .iter()
+            .map(|(validator, weight)| (*validator, i128::from(weight / weights_gcd)))
+            .collect();
+        let total_weight = weights.iter().map(|(_, weight)| weight).sum();
+        let priorities = Priorities { index: 0, values: vec![0; weights.len()] };
+        Self {
+            weights,
+            total_weight,
+            height_priorities: priorities.clone(),
+            round_priorities: priorities,
+        }
+    }
+
+    /// Returns the proposer of the given height and round.
+    pub fn proposer(&mut self, height: BlockNumber, round: Round) -> ValidatorId {
+        let height_index = i128::from(height.0) % self.total_weight;
+        let index = (i128::from(height.0) + i128::from(round)) % self.total_weight;
+        self.height_priorities.advance(&self.weights, self.total_weight, height_index);
+        // The rounds of a height are usually reached in order, so the selections continue from the
+        // latest round, unless the start of the height is closer.
+        if self.steps(self.round_priorities.index, index)
+            > self.steps(self.height_priorities.index, index)
+        {
+            self.round_priorities = self.height_priorities.clone();
+        }
+        self.round_priorities.advance(&self.weights, self.total_weight, index);
+        self.weights[self.round_priorities.selected(&self.weights)].0
+    }
+
+    // The selections are cyclic, so any index is reached by advancing.
+    fn steps(&self, from_index: i128, to_index: i128) -> i128 {
+        (to_index - from_index).rem_euclid(self.total_weight)
+    }
+}
+
+// The priorities of the validators before the selection of the given index, modulo the total
+// weight.
+#[derive(Clone, Debug)]
+struct Priorities {
+    index: i128,
+    values: Vec<i128>,
+}
+
+impl Priorities {
+    // Returns the position of the validator selected at the index.
+    fn selected(&self, weights: &[(ValidatorId, i128)]) -> usize {
+        // The first maximum, so ties are broken by the lowest ID.
+        (0..self.values.len())
+            .map(|i| (i, self.values[i] + weights[i].1))
+            .reduce(|best, candidate| if candidate.1 > best.1 { candidate } else { best })
+            .expect("There should be at least one validator")
+            .0
+    }
+
+    fn advance(&mut self, weights: &[(ValidatorId, i128)], total_weight: i128, index: i128) {
+        while self.index != index {
+            let selected = self.selected(weights);
+            for (priority, (_, weight)) in self.values.iter_mut().zip(weights) {
+                *priority += weight;
+            }
+            self.values[selected] -= total_weight;
+            self.index = (self.index + 1) % total_weight;
+        }
+    }
+}
+
+fn gcd(a: VotingWeight, b: VotingWeight) -> VotingWeight {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
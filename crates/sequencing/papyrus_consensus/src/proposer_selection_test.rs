@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+use starknet_api::block::BlockNumber;
+
+use super::ProposerSelector;
+use crate::types::{ValidatorId, ValidatorSet, VotingWeight, DEFAULT_VALIDATOR_ID};
+
+fn validator_set(weights: impl IntoIterator<Item = VotingWeight>) -> ValidatorSet {
+    (DEFAULT_VALIDATOR_ID..).map(ValidatorId::from).zip(weights).collect()
+}
+
+/// Uneven voting weights, with a positive total.
+fn weights() -> impl Strategy<Value = Vec<VotingWeight>> {
+    prop::collection::vec(0..=20_u64, 1..6)
+        .prop_filter("positive total weight", |weights| weights.iter().sum::<u64>() > 0)
+}
+
+fn proposers_count(
+    validators: &ValidatorSet,
+    first_height: u64,
+    n_heights: u64,
+) -> HashMap<ValidatorId, VotingWeight> {
+    let mut proposer_selector = ProposerSelector::new(validators);
+    let mut proposers_count = HashMap::new();
+    for height in first_height..first_height + n_heights {
+        *proposers_count.entry(proposer_selector.proposer(BlockNumber(height), 0)).or_default() +=
+            1;
+    }
+    proposers_count
+}
+
+#[test]
+fn equal_weights_are_round_robin() {
+    let validators = validator_set([1, 1, 1, 1]);
+    let ids: Vec<ValidatorId> = validators.keys().copied().collect();
+    let mut proposer_selector = ProposerSelector::new(&validators);
+    for height in 0..10 {
+        for round in 0..10 {
+            assert_eq!(
+                proposer_selector.proposer(BlockNumber(height), round),
+                ids[usize::try_from(height + u64::from(round)).unwrap() % ids.len()]
+            );
+        }
+    }
+}
+
+#[test]
+fn selections_are_spread_by_weight() {
+    let validators = validator_set([3, 1, 1]);
+    let ids: Vec<ValidatorId> = validators.keys().copied().collect();
+    let mut proposer_selector = ProposerSelector::new(&validators);
+    let proposers: Vec<ValidatorId> =
+        (0..5).map(|height| proposer_selector.proposer(BlockNumber(height), 0)).collect();
+    assert_eq!(proposers, [ids[0], ids[1], ids[0], ids[2], ids[0]]);
+}
+
+#[test]
+#[should_panic]
+fn zero_total_weight() {
+    ProposerSelector::new(&validator_set([0, 0]));
+}
+
+proptest! {
+    #[test]
+    fn proposals_are_proportional_to_weight(weights in weights(), first_height in 0..1000_u64) {
+        let validators = validator_set(weights);
+        let total_weight: VotingWeight = validators.values().sum();
+
+        let proposers_count = proposers_count(&validators, first_height, total_weight);
+        for (validator, weight) in &validators {
+            prop_assert_eq!(proposers_count.get(validator).copied().unwrap_or_default(), *weight);
+        }
+    }
+
+    #[test]
+    fn proposer_depends_on_height_plus_round(
+        weights in weights(),
+        height in 0..1000_u64,
+        round in 0..100_u32,
+    ) {
+        let validators = validator_set(weights);
+        prop_assert_eq!(
+            ProposerSelector::new(&validators).proposer(BlockNumber(height), round),
+            ProposerSelector::new(&validators).proposer(BlockNumber(height + u64::from(round)), 0)
+        );
+    }
+
+    #[test]
+    fn proposer_is_invariant_to_scaling_weights(
+        weights in weights(),
+        factor in 1..1000_u64,
+        height in 0..1000_u64,
+        round in 0..100_u32,
+    ) {
+        let scaled_weights = weights.iter().map(|weight| weight * factor);
+        prop_assert_eq!(
+            ProposerSelector::new(&validator_set(weights.clone()))
+                .proposer(BlockNumber(height), round),
+            ProposerSelector::new(&validator_set(scaled_weights))
+                .proposer(BlockNumber(height), round)
+        );
+    }
+
+    #[test]
+    fn proposer_does_not_depend_on_previous_selections(
+        weights in weights(),
+        selections in prop::collection::vec((0..1000_u64, 0..100_u32), 1..20),
+    ) {
+        let validators = validator_set(weights);
+        let mut proposer_selector = ProposerSelector::new(&validators);
+        for (height, round) in selections {
+            prop_assert_eq!(
+                proposer_selector.proposer(BlockNumber(height), round),
+                ProposerSelector::new(&validators).proposer(BlockNumber(height), round)
+            );
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Signing and verification of consensus messages.
+//!
+//! Votes and proposal fins are signed by their senders, so a peer can't forge a message on behalf
+//! of a validator. A message is signed over a Poseidon hash of its fields, prefixed by a domain
+//! separator for the type of the message and by a hash of the chain id, so a signature can't be
+//! replayed as a different message or on a different chain.
+//!
+//! [`Signer`] - signs message hashes with the key of this node, which is held either in a file
+//! ([`PrivateKeySigner`]) or by a local signing service ([`SigningServiceSigner`]).
+//!
+//! [`ConsensusSigner`] - signs the consensus messages of this node.
+
+#[cfg(test)]
+#[path = "signing_test.rs"]
+mod signing_test;
+
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use papyrus_protobuf::consensus::{ProposalFin, ProposalInit, Vote, VoteType};
+use serde::Serialize;
+use starknet_api::core::{ascii_as_felt, ChainId};
+use starknet_api::crypto::utils::{
+    get_public_key,
+    sign_message_hash,
+    verify_message_hash_signature,
+    CryptoError,
+    PublicKey,
+    Signature,
+};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+use url::Url;
+
+use crate::config::SignerConfig;
+use crate::types::ProposalContentId;
+
+const VOTE_DOMAIN_SEPARATOR: &str = "STARKNET_CONSENSUS_VOTE";
+const PROPOSAL_FIN_DOMAIN_SEPARATOR: &str = "STARKNET_CONSENSUS_PROPOSAL_FIN";
+
+#[derive(thiserror::Error, Debug)]
+pub enum SignerError {
+    #[error(transparent)]
+    Crypto(#[from] CryptoError),
+    #[error("Failed to read the private key file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("Invalid signer config: {0}")]
+    InvalidConfig(String),
+    #[error("Signing service request failed: {0}")]
+    SigningService(#[from] reqwest::Error),
+}
+
+/// Signs message hashes with the key of this node.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    async fn sign(&self, message_hash: Felt) -> Result<Signature, SignerError>;
+}
+
+/// Signs message hashes with a private key held in memory.
+pub struct PrivateKeySigner {
+    private_key: Felt,
+}
+
+impl PrivateKeySigner {
+    pub fn new(private_key: Felt) -> Self {
+        Self { private_key }
+    }
+
+    /// Reads a hex encoded private key from the given file.
+    pub fn from_file(path: &Path) -> Result<Self, SignerError> {
+        let private_key = std::fs::read_to_string(path)?;
+        let private_key = Felt::from_hex(private_key.trim())
+            .map_err(|err| SignerError::InvalidPrivateKey(err.to_string()))?;
+        Ok(Self::new(private_key))
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        get_public_key(&self.private_key)
+    }
+}
+
+#[async_trait]
+impl Signer for PrivateKeySigner {
+    async fn sign(&self, message_hash: Felt) -> Result<Signature, SignerError> {
+        Ok(sign_message_hash(&message_hash, &self.private_key)?)
+    }
+}
+
+#[derive(Serialize)]
+struct SignRequest {
+    message_hash: Felt,
+}
+
+/// Signs message hashes with a local signing service, so the private key is not held by the node.
+///
+/// The service is expected to respond to a POST request with the body `{"message_hash": "0x..."}`
+/// with the signature `{"r": "0x...", "s": "0x..."}`.
+pub struct SigningServiceSigner {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl SigningServiceSigner {
+    pub fn new(url: Url) -> Self {
+        Self { client: reqwest::Client::new(), url }
+    }
+}
+
+#[async_trait]
+impl Signer for SigningServiceSigner {
+    async fn sign(&self, message_hash: Felt) -> Result<Signature, SignerError> {
+        Ok(self
+            .client
+            .post(self.url.clone())
+            .json(&SignRequest { message_hash })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+/// Creates the signer of the key set in the config. Exactly one source of the key must be set.
+pub fn create_signer(config: &SignerConfig) -> Result<Arc<dyn Signer>, SignerError> {
+    match (&config.private_key_file, &config.signing_service_url) {
+        (Some(private_key_file), None) => {
+            Ok(Arc::new(PrivateKeySigner::from_file(private_key_file)?))
+        }
+        (None, Some(signing_service_url)) => {
+            Ok(Arc::new(SigningServiceSigner::new(signing_service_url.clone())))
+        }
+        (None, None) => Err(SignerError::InvalidConfig(
+            "either a private key file or a signing service url must be set".to_string(),
+        )),
+        (Some(_), Some(_)) => Err(SignerError::InvalidConfig(
+            "only one of a private key file and a signing service url may be set".to_string(),
+        )),
+    }
+}
+
+/// Signs the consensus messages of this node.
+#[derive(Clone)]
+pub struct ConsensusSigner {
+    chain_id: ChainId,
+    signer: Arc<dyn Signer>,
+}
+
+// The signer may hold a private key, so only the chain id is printed.
+impl std::fmt::Debug for ConsensusSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsensusSigner").field("chain_id", &self.chain_id).finish_non_exhaustive()
+    }
+}
+
+impl ConsensusSigner {
+    pub fn new(chain_id: ChainId, signer: Arc<dyn Signer>) -> Self {
+        Self { chain_id, signer }
+    }
+
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
+    }
+
+    /// Returns the vote with the signature of this node. The given signature is ignored.
+    pub async fn sign_vote(&self, vote: Vote) -> Result<Vote, SignerError> {
+        let signature = self.signer.sign(vote_hash(&self.chain_id, &vote)).await?;
+        Ok(Vote { signature, ..vote })
+    }
+
+    /// Returns the fin of the proposal with the given init and content, signed by this node.
+    pub async fn sign_proposal_fin(
+        &self,
+        init: &ProposalInit,
+        proposal_content_id: ProposalContentId,
+    ) -> Result<ProposalFin, SignerError> {
+        let signature =
+            self.signer.sign(proposal_fin_hash(&self.chain_id, init, proposal_content_id)).await?;
+        Ok(ProposalFin { proposal_content_id, signature })
+    }
+}
+
+/// The hash of the vote that is signed by the voter. The signature of the vote is not hashed.
+pub fn vote_hash(chain_id: &ChainId, vote: &Vote) -> Felt {
+    let vote_type = match vote.vote_type {
+        VoteType::Prevote => Felt::ZERO,
+        VoteType::Precommit => Felt::ONE,
+    };
+    let mut elements = domain_prefix(VOTE_DOMAIN_SEPARATOR, chain_id);
+    elements.extend([vote_type, vote.height.into(), vote.round.into()]);
+    elements.extend(optional_elements(vote.block_hash.map(|block_hash| block_hash.0)));
+    elements.push(*vote.voter.0.key());
+    Poseidon::hash_array(&elements)
+}
+
+/// The hash of the fin of the proposal with the given init and content, that is signed by the
+/// proposer. Hashing the init binds the content to the height and round it was proposed at.
+pub fn proposal_fin_hash(
+    chain_id: &ChainId,
+    init: &ProposalInit,
+    proposal_content_id: ProposalContentId,
+) -> Felt {
+    let mut elements = domain_prefix(PROPOSAL_FIN_DOMAIN_SEPARATOR, chain_id);
+    elements.extend([Felt::from(init.height.0), Felt::from(init.round)]);
+    elements.extend(optional_elements(init.valid_round.map(Felt::from)));
+    elements.extend([*init.proposer.0.key(), proposal_content_id.0]);
+    Poseidon::hash_array(&elements)
+}
+
+/// Returns whether the vote is signed by the owner of the given public key.
+pub fn verify_vote(chain_id: &ChainId, vote: &Vote, public_key: &PublicKey) -> bool {
+    is_valid_signature(&vote_hash(chain_id, vote), &vote.signature, public_key)
+}
+
+/// Returns whether the fin of the proposal with the given init is signed by the owner of the given
+/// public key.
+pub fn verify_proposal_fin(
+    chain_id: &ChainId,
+    init: &ProposalInit,
+    fin: &ProposalFin,
+    public_key: &PublicKey,
+) -> bool {
+    is_valid_signature(
+        &proposal_fin_hash(chain_id, init, fin.proposal_content_id),
+        &fin.signature,
+        public_key,
+    )
+}
+
+// A signature which is malformed for the public key is as invalid as a signature of another key.
+fn is_valid_signature(message_hash: &Felt, signature: &Signature, public_key: &PublicKey) -> bool {
+    verify_message_hash_signature(message_hash, signature, public_key).unwrap_or(false)
+}
+
+fn domain_prefix(domain_separator: &str, chain_id: &ChainId) -> Vec<Felt> {
+    vec![
+        ascii_as_felt(domain_separator).expect("The domain separator should fit into a felt."),
+        chain_id_hash(chain_id),
+    ]
+}
+
+// The chain id may be longer than fits into a felt, so its bytes are hashed instead.
+fn chain_id_hash(chain_id: &ChainId) -> Felt {
+    let bytes: Vec<_> = chain_id.to_string().bytes().map(Felt::from).collect();
+    Poseidon::hash_array(&bytes)
+}
+
+// Optional fields are prefixed by whether they are set, so the hashed elements are unambiguous.
+fn optional_elements(element: Option<Felt>) -> Vec<Felt> {
+    match element {
+        Some(element) => vec![Felt::ONE, element],
+        None => vec![Felt::ZERO],
+    }
+}
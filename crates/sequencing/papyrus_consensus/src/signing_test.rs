@@ -0,0 +1,162 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use papyrus_protobuf::consensus::{ProposalInit, Vote, VoteType};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::{get_public_key, Signature};
+use starknet_types_core::felt::Felt;
+use tempfile::NamedTempFile;
+
+use super::{
+    create_signer,
+    proposal_fin_hash,
+    verify_proposal_fin,
+    verify_vote,
+    vote_hash,
+    ConsensusSigner,
+    PrivateKeySigner,
+    Signer,
+    SigningServiceSigner,
+};
+use crate::config::SignerConfig;
+use crate::types::ValidatorId;
+
+const PRIVATE_KEY: Felt = Felt::from_hex_unchecked("0x1234567890abcdef");
+
+fn chain_id() -> ChainId {
+    ChainId::Other("SN_TEST".to_string())
+}
+
+fn consensus_signer() -> ConsensusSigner {
+    ConsensusSigner::new(chain_id(), Arc::new(PrivateKeySigner::new(PRIVATE_KEY)))
+}
+
+fn vote() -> Vote {
+    Vote {
+        vote_type: VoteType::Prevote,
+        height: 1,
+        round: 2,
+        block_hash: Some(BlockHash(Felt::THREE)),
+        voter: ValidatorId::from(100_u64),
+        signature: Signature::default(),
+    }
+}
+
+fn proposal_init() -> ProposalInit {
+    ProposalInit {
+        height: BlockNumber(1),
+        round: 2,
+        valid_round: None,
+        proposer: ValidatorId::from(100_u64),
+    }
+}
+
+#[test]
+fn vote_hash_is_domain_separated() {
+    let vote = vote();
+    let hash = vote_hash(&chain_id(), &vote);
+    assert_ne!(hash, vote_hash(&ChainId::Mainnet, &vote));
+    assert_ne!(
+        hash,
+        vote_hash(&chain_id(), &Vote { vote_type: VoteType::Precommit, ..vote.clone() })
+    );
+    assert_ne!(hash, vote_hash(&chain_id(), &Vote { block_hash: None, ..vote.clone() }));
+    assert_ne!(hash, proposal_fin_hash(&chain_id(), &proposal_init(), BlockHash(Felt::THREE)));
+    // The signature is not part of the signed message.
+    assert_eq!(
+        hash,
+        vote_hash(
+            &chain_id(),
+            &Vote { signature: Signature { r: Felt::ONE, s: Felt::ONE }, ..vote.clone() }
+        )
+    );
+}
+
+#[tokio::test]
+async fn sign_and_verify_vote() {
+    let public_key = get_public_key(&PRIVATE_KEY);
+    let vote = consensus_signer().sign_vote(vote()).await.unwrap();
+    assert!(verify_vote(&chain_id(), &vote, &public_key));
+
+    assert!(!verify_vote(&ChainId::Mainnet, &vote, &public_key));
+    assert!(!verify_vote(&chain_id(), &Vote { round: 3, ..vote.clone() }, &public_key));
+    assert!(!verify_vote(&chain_id(), &vote, &get_public_key(&Felt::TWO)));
+    assert!(!verify_vote(
+        &chain_id(),
+        &Vote { signature: Signature::default(), ..vote },
+        &public_key
+    ));
+}
+
+// Chain ids longer than fit into a felt are valid, and are separated as well.
+#[tokio::test]
+async fn sign_and_verify_with_a_long_chain_id() {
+    let long_chain_id = ChainId::Other("A_CHAIN_ID_LONGER_THAN_31_BYTES_0".to_string());
+    let other_long_chain_id = ChainId::Other("A_CHAIN_ID_LONGER_THAN_31_BYTES_1".to_string());
+    let signer =
+        ConsensusSigner::new(long_chain_id.clone(), Arc::new(PrivateKeySigner::new(PRIVATE_KEY)));
+    let vote = signer.sign_vote(vote()).await.unwrap();
+    let public_key = get_public_key(&PRIVATE_KEY);
+
+    assert!(verify_vote(&long_chain_id, &vote, &public_key));
+    assert!(!verify_vote(&other_long_chain_id, &vote, &public_key));
+}
+
+#[tokio::test]
+async fn sign_and_verify_proposal_fin() {
+    let public_key = get_public_key(&PRIVATE_KEY);
+    let init = proposal_init();
+    let fin = consensus_signer().sign_proposal_fin(&init, BlockHash(Felt::THREE)).await.unwrap();
+    assert_eq!(fin.proposal_content_id, BlockHash(Felt::THREE));
+    assert!(verify_proposal_fin(&chain_id(), &init, &fin, &public_key));
+
+    // The fin is bound to the init of the proposal.
+    let reproposal_init = ProposalInit { round: 3, valid_round: Some(2), ..init.clone() };
+    assert!(!verify_proposal_fin(&chain_id(), &reproposal_init, &fin, &public_key));
+    let mut other_content_fin = fin.clone();
+    other_content_fin.proposal_content_id = BlockHash(Felt::TWO);
+    assert!(!verify_proposal_fin(&chain_id(), &init, &other_content_fin, &public_key));
+}
+
+#[tokio::test]
+async fn private_key_signer_from_file() {
+    let mut private_key_file = NamedTempFile::new().unwrap();
+    writeln!(private_key_file, "{:#x}", PRIVATE_KEY).unwrap();
+
+    let signer = PrivateKeySigner::from_file(private_key_file.path()).unwrap();
+    assert_eq!(signer.public_key(), get_public_key(&PRIVATE_KEY));
+    assert_eq!(
+        signer.sign(Felt::ONE).await.unwrap(),
+        PrivateKeySigner::new(PRIVATE_KEY).sign(Felt::ONE).await.unwrap()
+    );
+
+    writeln!(private_key_file, "not a key").unwrap();
+    assert!(PrivateKeySigner::from_file(private_key_file.path()).is_err());
+}
+
+#[tokio::test]
+async fn signing_service_signer() {
+    let expected_signature = PrivateKeySigner::new(PRIVATE_KEY).sign(Felt::ONE).await.unwrap();
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/sign")
+        .match_body(mockito::Matcher::Json(serde_json::json!({ "message_hash": "0x1" })))
+        .with_body(serde_json::to_string(&expected_signature).unwrap())
+        .create_async()
+        .await;
+
+    let signer = SigningServiceSigner::new(format!("{}/sign", server.url()).parse().unwrap());
+    assert_eq!(signer.sign(Felt::ONE).await.unwrap(), expected_signature);
+    mock.assert_async().await;
+}
+
+#[test]
+fn create_signer_requires_a_single_key_source() {
+    assert!(create_signer(&SignerConfig::default()).is_err());
+    let config = SignerConfig {
+        private_key_file: Some("key".into()),
+        signing_service_url: Some("http://localhost/".parse().unwrap()),
+    };
+    assert!(create_signer(&config).is_err());
+}
@@ -11,26 +11,33 @@
 mod single_height_consensus_test;
 
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::Duration;
 
 #[cfg(test)]
 use enum_as_inner::EnumAsInner;
 use futures::channel::{mpsc, oneshot};
+use futures::StreamExt;
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalFin, ProposalInit, Vote, VoteType};
 use starknet_api::block::BlockNumber;
+use starknet_api::crypto::utils::Signature;
 use tracing::{debug, info, instrument, trace, warn};
 
 use crate::config::TimeoutsConfig;
+use crate::signing::{verify_proposal_fin, ConsensusSigner};
 use crate::state_machine::{StateMachine, StateMachineEvent};
 use crate::types::{
     ConsensusContext,
     ConsensusError,
     Decision,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
+    ValidatorSet,
+    VotingWeight,
 };
+use crate::wal::{Wal, WalEntry};
 
 /// The SHC can either update the manager of a decision or return tasks that should be run without
 /// blocking further calls to itself.
@@ -50,8 +57,8 @@ pub enum ShcEvent {
     Prevote(StateMachineEvent),
     Precommit(StateMachineEvent),
     BuildProposal(StateMachineEvent),
-    // TODO: Replace ProposalContentId with the unvalidated signature from the proposer.
     ValidateProposal(StateMachineEvent, Option<ProposalFin>),
+    DuplicateProposal(ProposalInit, Option<ProposalFin>),
 }
 
 /// A task which should be run without blocking calls to SHC.
@@ -79,6 +86,9 @@ pub enum ShcTask {
     /// 3. Once validation is complete, the manager returns the built proposal to the SHC as an
     ///    event, which can be sent to the SM.
     ValidateProposal(ProposalInit, oneshot::Receiver<(ProposalContentId, ProposalFin)>),
+    /// Another proposal for a round which already has one. Only its fin is read, without
+    /// validating its content, to check if the proposer signed conflicting proposals.
+    DuplicateProposal(ProposalInit, oneshot::Receiver<ProposalFin>),
 }
 
 impl PartialEq for ShcTask {
@@ -90,7 +100,10 @@ impl PartialEq for ShcTask {
             | (ShcTask::Prevote(d1, e1), ShcTask::Prevote(d2, e2))
             | (ShcTask::Precommit(d1, e1), ShcTask::Precommit(d2, e2)) => d1 == d2 && e1 == e2,
             (ShcTask::BuildProposal(r1, _), ShcTask::BuildProposal(r2, _)) => r1 == r2,
-            (ShcTask::ValidateProposal(pi1, _), ShcTask::ValidateProposal(pi2, _)) => pi1 == pi2,
+            (ShcTask::ValidateProposal(pi1, _), ShcTask::ValidateProposal(pi2, _))
+            | (ShcTask::DuplicateProposal(pi1, _), ShcTask::DuplicateProposal(pi2, _)) => {
+                pi1 == pi2
+            }
             _ => false,
         }
     }
@@ -143,6 +156,23 @@ impl ShcTask {
                     received_proposal_id,
                 )
             }
+            ShcTask::DuplicateProposal(init, fin_receiver) => {
+                ShcEvent::DuplicateProposal(init, fin_receiver.await.ok())
+            }
+        }
+    }
+}
+
+// Reads the parts of a proposal until its fin, which is sent without validating the content.
+async fn read_proposal_fin<ProposalPartT: TryInto<ProposalFin>>(
+    mut content_receiver: mpsc::Receiver<ProposalPartT>,
+    fin_sender: oneshot::Sender<ProposalFin>,
+) {
+    while let Some(part) = content_receiver.next().await {
+        if let Ok(fin) = part.try_into() {
+            // The receiver may have been dropped if the height ended.
+            let _ = fin_sender.send(fin);
+            return;
         }
     }
 }
@@ -161,11 +191,20 @@ impl ShcTask {
 /// tasks). SHC does have side effects, such as sending messages to the network via the context.
 pub(crate) struct SingleHeightConsensus {
     height: BlockNumber,
-    validators: Vec<ValidatorId>,
+    validators: ValidatorSet,
     id: ValidatorId,
     timeouts: TimeoutsConfig,
+    signer: ConsensusSigner,
+    // Own votes are written to the WAL before they are broadcast.
+    wal: Option<Wal>,
     state_machine: StateMachine,
     proposals: HashMap<Round, Option<ProposalContentId>>,
+    // The first proposal fin signed by the proposer of each round, to detect conflicting
+    // proposals.
+    proposal_fins: HashMap<Round, (ProposalInit, ProposalFin)>,
+    // The rounds with a duplicate proposal whose fin is being read. Further duplicates of these
+    // rounds are dropped, so a peer can't make the node spawn a task for every repeated proposal.
+    pending_duplicate_proposals: HashSet<Round>,
     prevotes: HashMap<(Round, ValidatorId), Vote>,
     precommits: HashMap<(Round, ValidatorId), Vote>,
     last_prevote: Option<Vote>,
@@ -177,20 +216,23 @@ impl SingleHeightConsensus {
         height: BlockNumber,
         is_observer: bool,
         id: ValidatorId,
-        validators: Vec<ValidatorId>,
+        validators: ValidatorSet,
         timeouts: TimeoutsConfig,
+        signer: ConsensusSigner,
+        wal: Option<Wal>,
     ) -> Self {
-        // TODO(matan): Use actual weights, not just `len`.
-        let n_validators =
-            u32::try_from(validators.len()).expect("Should have way less than u32::MAX validators");
-        let state_machine = StateMachine::new(id, n_validators, is_observer);
+        let state_machine = StateMachine::new(id, &validators, is_observer);
         Self {
             height,
             validators,
             id,
             timeouts,
+            signer,
+            wal,
             state_machine,
             proposals: HashMap::new(),
+            proposal_fins: HashMap::new(),
+            pending_duplicate_proposals: HashSet::new(),
             prevotes: HashMap::new(),
             precommits: HashMap::new(),
             last_prevote: None,
@@ -205,13 +247,82 @@ impl SingleHeightConsensus {
     ) -> Result<ShcReturn, ConsensusError> {
         info!("Starting consensus with validators {:?}", self.validators);
         context.set_height_and_round(self.height, self.state_machine.round()).await;
+        let mut tasks = self.resend_restored_votes(context).await?;
         let leader_fn = |round: Round| -> ValidatorId { context.proposer(self.height, round) };
         let events = self.state_machine.start(&leader_fn);
-        let ret = self.handle_state_machine_events(context, events).await;
+        let ret = match self.handle_state_machine_events(context, events).await {
+            Ok(ShcReturn::Tasks(new_tasks)) => {
+                tasks.extend(new_tasks);
+                Ok(ShcReturn::Tasks(tasks))
+            }
+            ret => ret,
+        };
         context.set_height_and_round(self.height, self.state_machine.round()).await;
         ret
     }
 
+    /// Restores the votes this node sent in this height before a restart, so it doesn't send
+    /// conflicting votes. Must be called before `start`. See [`crate::wal`].
+    pub(crate) fn restore(&mut self, wal_entries: Vec<WalEntry>) {
+        let weight = self.own_weight();
+        let mut own_votes = Vec::new();
+        let (mut locked_value_round, mut valid_value_round) = (None, None);
+        for entry in wal_entries {
+            let vote = entry.vote;
+            assert_eq!(vote.height, self.height.0, "Restored a vote from another height.");
+            assert_eq!(vote.voter, self.id, "Restored a vote of another validator.");
+            let (votes, last_vote, event) = match vote.vote_type {
+                VoteType::Prevote => (
+                    &mut self.prevotes,
+                    &mut self.last_prevote,
+                    StateMachineEvent::Prevote(vote.block_hash, vote.round, weight),
+                ),
+                VoteType::Precommit => (
+                    &mut self.precommits,
+                    &mut self.last_precommit,
+                    StateMachineEvent::Precommit(vote.block_hash, vote.round, weight),
+                ),
+            };
+            if last_vote.as_ref().map_or(true, |last| last.round < vote.round) {
+                *last_vote = Some(vote.clone());
+            }
+            votes.insert((vote.round, vote.voter), vote);
+            own_votes.push(event);
+            locked_value_round = entry.locked_value_round;
+            valid_value_round = entry.valid_value_round;
+        }
+        info!("Restored {} votes from the WAL", own_votes.len());
+        self.state_machine.restore(own_votes, locked_value_round, valid_value_round);
+    }
+
+    // The last restored votes may not have been broadcast before the restart, so they are sent
+    // again. Does nothing if no votes were restored.
+    async fn resend_restored_votes<ContextT: ConsensusContext>(
+        &self,
+        context: &mut ContextT,
+    ) -> Result<Vec<ShcTask>, ConsensusError> {
+        let weight = self.own_weight();
+        let mut tasks = Vec::new();
+        for vote in [&self.last_prevote, &self.last_precommit].into_iter().flatten() {
+            context.broadcast(ConsensusMessage::Vote(vote.clone())).await?;
+            tasks.push(match vote.vote_type {
+                VoteType::Prevote => ShcTask::Prevote(
+                    self.timeouts.prevote_timeout,
+                    StateMachineEvent::Prevote(vote.block_hash, vote.round, weight),
+                ),
+                VoteType::Precommit => ShcTask::Precommit(
+                    self.timeouts.precommit_timeout,
+                    StateMachineEvent::Precommit(vote.block_hash, vote.round, weight),
+                ),
+            });
+        }
+        Ok(tasks)
+    }
+
+    fn own_weight(&self) -> VotingWeight {
+        self.validators.get(&self.id).copied().unwrap_or_default()
+    }
+
     /// Process the proposal init and initiate block validation. See [`ShcTask::ValidateProposal`]
     /// for more details on the full proposal flow.
     #[instrument(
@@ -240,8 +351,17 @@ impl SingleHeightConsensus {
             return Err(ConsensusError::InvalidProposal(proposer_id, self.height, msg));
         }
         let Entry::Vacant(proposal_entry) = self.proposals.entry(init.round) else {
-            warn!("Round {} already has a proposal, ignoring", init.round);
-            return Ok(ShcReturn::Tasks(Vec::new()));
+            if !self.pending_duplicate_proposals.insert(init.round) {
+                debug!("Round {} already has a pending duplicate proposal, dropping", init.round);
+                return Ok(ShcReturn::Tasks(Vec::new()));
+            }
+            debug!("Round {} already has a proposal, only reading its fin", init.round);
+            let (fin_sender, fin_receiver) = oneshot::channel();
+            tokio::spawn(tokio::time::timeout(
+                self.timeouts.proposal_timeout,
+                read_proposal_fin(p2p_messages_receiver, fin_sender),
+            ));
+            return Ok(ShcReturn::Tasks(vec![ShcTask::DuplicateProposal(init, fin_receiver)]));
         };
         // Since validating the proposal is non-blocking, we want to avoid validating the same round
         // twice in parallel. This could be caused by a network repeat or a malicious spam attack.
@@ -304,7 +424,7 @@ impl SingleHeightConsensus {
                 let sm_events = self.state_machine.handle_event(event, &leader_fn);
                 self.handle_state_machine_events(context, sm_events).await
             }
-            ShcEvent::Prevote(StateMachineEvent::Prevote(proposal_id, round)) => {
+            ShcEvent::Prevote(event @ StateMachineEvent::Prevote(_, round, _)) => {
                 let Some(last_vote) = &self.last_prevote else {
                     return Err(ConsensusError::InvalidEvent("No prevote to send".to_string()));
                 };
@@ -312,12 +432,9 @@ impl SingleHeightConsensus {
                     return Ok(ShcReturn::Tasks(Vec::new()));
                 }
                 context.broadcast(ConsensusMessage::Vote(last_vote.clone())).await?;
-                Ok(ShcReturn::Tasks(vec![ShcTask::Prevote(
-                    self.timeouts.prevote_timeout,
-                    StateMachineEvent::Prevote(proposal_id, round),
-                )]))
+                Ok(ShcReturn::Tasks(vec![ShcTask::Prevote(self.timeouts.prevote_timeout, event)]))
             }
-            ShcEvent::Precommit(StateMachineEvent::Precommit(proposal_id, round)) => {
+            ShcEvent::Precommit(event @ StateMachineEvent::Precommit(_, round, _)) => {
                 let Some(last_vote) = &self.last_precommit else {
                     return Err(ConsensusError::InvalidEvent("No precommit to send".to_string()));
                 };
@@ -327,30 +444,45 @@ impl SingleHeightConsensus {
                 context.broadcast(ConsensusMessage::Vote(last_vote.clone())).await?;
                 Ok(ShcReturn::Tasks(vec![ShcTask::Precommit(
                     self.timeouts.precommit_timeout,
-                    StateMachineEvent::Precommit(proposal_id, round),
+                    event,
                 )]))
             }
             ShcEvent::ValidateProposal(
                 StateMachineEvent::Proposal(built_content_id, round, valid_round),
                 received_proposal_id,
             ) => {
-                // TODO(matan): Switch to signature validation.
-
-                let mut id = None;
-                if let (Some(built_content_id), Some(ProposalFin { proposal_content_id })) =
-                    (built_content_id, received_proposal_id.clone())
-                {
-                    if built_content_id == proposal_content_id {
-                        id = Some(built_content_id);
+                let init = ProposalInit {
+                    height: self.height,
+                    round,
+                    valid_round,
+                    proposer: context.proposer(self.height, round),
+                };
+                if let Some(fin) = &received_proposal_id {
+                    if let Err(err) = self.handle_proposal_fin(context, &init, fin).await {
+                        // A proposal which is not signed by the proposer leaves the round open to
+                        // the proposer's proposal. A proposer which signed conflicting proposals
+                        // gets no proposal accepted in the round, so the round times out.
+                        if matches!(err, ConsensusError::InvalidProposal(..)) {
+                            self.proposals.remove(&round);
+                        }
+                        return Err(err);
                     }
                 }
-                if id.is_none() {
-                    warn!(
-                        "proposal_id built from content receiver does not match fin: {:#064x?} != \
-                         {:#064x?}",
-                        built_content_id, received_proposal_id
-                    );
-                }
+                let id = match (built_content_id, &received_proposal_id) {
+                    (Some(built_content_id), Some(fin))
+                        if built_content_id == fin.proposal_content_id =>
+                    {
+                        Some(built_content_id)
+                    }
+                    _ => {
+                        warn!(
+                            "proposal_id built from content receiver does not match fin: \
+                             {:#064x?} != {:#064x?}",
+                            built_content_id, received_proposal_id
+                        );
+                        None
+                    }
+                };
                 // Retaining the entry for this round prevents us from receiving another proposal on
                 // this round. If the validations failed, which can be caused by a network issue, we
                 // may want to re-open ourselves to this round. The downside is that this may open
@@ -364,6 +496,13 @@ impl SingleHeightConsensus {
                 )
                 .await
             }
+            ShcEvent::DuplicateProposal(init, fin) => {
+                self.pending_duplicate_proposals.remove(&init.round);
+                if let Some(fin) = fin {
+                    self.handle_proposal_fin(context, &init, &fin).await?;
+                }
+                Ok(ShcReturn::Tasks(Vec::new()))
+            }
             ShcEvent::BuildProposal(StateMachineEvent::GetProposal(proposal_id, round)) => {
                 let old = self.proposals.insert(round, proposal_id);
                 assert!(old.is_none(), "There should be no entry for this round.");
@@ -386,18 +525,20 @@ impl SingleHeightConsensus {
         context: &mut ContextT,
         vote: Vote,
     ) -> Result<ShcReturn, ConsensusError> {
-        if !self.validators.contains(&vote.voter) {
+        let Some(weight) = self.validators.get(&vote.voter).copied() else {
             debug!("Ignoring vote from voter not in validators: vote={:?}", vote);
             return Ok(ShcReturn::Tasks(Vec::new()));
-        }
+        };
 
         let (votes, sm_vote) = match vote.vote_type {
-            VoteType::Prevote => {
-                (&mut self.prevotes, StateMachineEvent::Prevote(vote.block_hash, vote.round))
-            }
-            VoteType::Precommit => {
-                (&mut self.precommits, StateMachineEvent::Precommit(vote.block_hash, vote.round))
-            }
+            VoteType::Prevote => (
+                &mut self.prevotes,
+                StateMachineEvent::Prevote(vote.block_hash, vote.round, weight),
+            ),
+            VoteType::Precommit => (
+                &mut self.precommits,
+                StateMachineEvent::Precommit(vote.block_hash, vote.round, weight),
+            ),
         };
 
         match votes.entry((vote.round, vote.voter)) {
@@ -407,11 +548,10 @@ impl SingleHeightConsensus {
             Entry::Occupied(entry) => {
                 let old = entry.get();
                 if old.block_hash != vote.block_hash {
-                    return Err(ConsensusError::Equivocation(
-                        self.height,
-                        ConsensusMessage::Vote(old.clone()),
-                        ConsensusMessage::Vote(vote),
-                    ));
+                    let evidence = Evidence::ConflictingVotes(old.clone(), vote);
+                    warn!("Received conflicting votes: {:?}", evidence);
+                    context.report_evidence(evidence.clone()).await;
+                    return Err(ConsensusError::Equivocation(evidence));
                 } else {
                     // Replay, ignore.
                     return Ok(ShcReturn::Tasks(Vec::new()));
@@ -446,23 +586,25 @@ impl SingleHeightConsensus {
                 StateMachineEvent::Decision(proposal_id, round) => {
                     return self.handle_state_machine_decision(proposal_id, round).await;
                 }
-                StateMachineEvent::Prevote(proposal_id, round) => {
+                StateMachineEvent::Prevote(proposal_id, round, weight) => {
                     ret_val.extend(
                         self.handle_state_machine_vote(
                             context,
                             proposal_id,
                             round,
+                            weight,
                             VoteType::Prevote,
                         )
                         .await?,
                     );
                 }
-                StateMachineEvent::Precommit(proposal_id, round) => {
+                StateMachineEvent::Precommit(proposal_id, round, weight) => {
                     ret_val.extend(
                         self.handle_state_machine_vote(
                             context,
                             proposal_id,
                             round,
+                            weight,
                             VoteType::Precommit,
                         )
                         .await?,
@@ -542,6 +684,7 @@ impl SingleHeightConsensus {
         context: &mut ContextT,
         proposal_id: Option<ProposalContentId>,
         round: Round,
+        weight: VotingWeight,
         vote_type: VoteType,
     ) -> Result<Vec<ShcTask>, ConsensusError> {
         let (votes, last_vote, task) = match vote_type {
@@ -550,7 +693,7 @@ impl SingleHeightConsensus {
                 &mut self.last_prevote,
                 ShcTask::Prevote(
                     self.timeouts.prevote_timeout,
-                    StateMachineEvent::Prevote(proposal_id, round),
+                    StateMachineEvent::Prevote(proposal_id, round, weight),
                 ),
             ),
             VoteType::Precommit => (
@@ -558,7 +701,7 @@ impl SingleHeightConsensus {
                 &mut self.last_precommit,
                 ShcTask::Precommit(
                     self.timeouts.precommit_timeout,
-                    StateMachineEvent::Precommit(proposal_id, round),
+                    StateMachineEvent::Precommit(proposal_id, round, weight),
                 ),
             ),
         };
@@ -568,7 +711,21 @@ impl SingleHeightConsensus {
             round,
             block_hash: proposal_id,
             voter: self.id,
+            signature: Signature::default(),
         };
+        let vote = self
+            .signer
+            .sign_vote(vote)
+            .await
+            .map_err(|err| ConsensusError::SigningError(err.to_string()))?;
+        if let Some(wal) = &self.wal {
+            let entry = WalEntry {
+                vote: vote.clone(),
+                locked_value_round: self.state_machine.locked_value_round(),
+                valid_value_round: self.state_machine.valid_value_round(),
+            };
+            wal.append(&entry)?;
+        }
         if let Some(old) = votes.insert((round, self.id), vote.clone()) {
             // TODO(matan): Consider refactoring not to panic, rather log and return the error.
             panic!("State machine should not send repeat votes: old={:?}, new={:?}", old, vote);
@@ -581,6 +738,51 @@ impl SingleHeightConsensus {
         Ok(vec![task])
     }
 
+    // Fails if the fin is not signed by the proposer. The first signed fin of each round is kept,
+    // and evidence is reported (and returned) if the proposer signed a conflicting one.
+    async fn handle_proposal_fin<ContextT: ConsensusContext>(
+        &mut self,
+        context: &mut ContextT,
+        init: &ProposalInit,
+        fin: &ProposalFin,
+    ) -> Result<(), ConsensusError> {
+        if !self.is_signed_by_proposer(context, init, fin) {
+            let msg = format!("invalid fin signature: init={:?}, fin={:?}", init, fin);
+            return Err(ConsensusError::InvalidProposal(init.proposer, self.height, msg));
+        }
+        match self.proposal_fins.entry(init.round) {
+            Entry::Vacant(entry) => {
+                entry.insert((init.clone(), fin.clone()));
+            }
+            Entry::Occupied(entry) => {
+                let (first_init, first_fin) = entry.get();
+                if (first_init.valid_round, first_fin.proposal_content_id)
+                    != (init.valid_round, fin.proposal_content_id)
+                {
+                    let evidence = Evidence::ConflictingProposals(
+                        (first_init.clone(), first_fin.clone()),
+                        (init.clone(), fin.clone()),
+                    );
+                    warn!("Received conflicting proposals: {:?}", evidence);
+                    context.report_evidence(evidence.clone()).await;
+                    return Err(ConsensusError::Equivocation(evidence));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn is_signed_by_proposer<ContextT: ConsensusContext>(
+        &self,
+        context: &ContextT,
+        init: &ProposalInit,
+        fin: &ProposalFin,
+    ) -> bool {
+        context.validator_public_key(self.height, init.proposer).is_some_and(|public_key| {
+            verify_proposal_fin(self.signer.chain_id(), init, fin, &public_key)
+        })
+    }
+
     #[instrument(skip_all)]
     async fn handle_state_machine_decision(
         &mut self,
@@ -593,20 +795,20 @@ impl SingleHeightConsensus {
             .expect("StateMachine arrived at an unknown decision")
             .expect("StateMachine should not decide on a missing proposal");
         assert_eq!(block, proposal_id, "StateMachine block hash should match the stored block");
+        let mut supporting_weight: VotingWeight = 0;
         let supporting_precommits: Vec<Vote> = self
             .validators
             .iter()
-            .filter_map(|v| {
+            .filter_map(|(v, weight)| {
                 let vote = self.precommits.get(&(round, *v))?;
-                if vote.block_hash == Some(proposal_id) { Some(vote.clone()) } else { None }
+                if vote.block_hash != Some(proposal_id) {
+                    return None;
+                }
+                supporting_weight += weight;
+                Some(vote.clone())
             })
             .collect();
-        // TODO(matan): Check actual weights.
-        assert!(
-            supporting_precommits.len()
-                >= usize::try_from(self.state_machine.quorum_size())
-                    .expect("u32 should fit in usize")
-        );
+        assert!(supporting_weight >= self.state_machine.quorum_size());
         Ok(ShcReturn::Decision(Decision { precommits: supporting_precommits, block }))
     }
 }
@@ -4,6 +4,7 @@ use lazy_static::lazy_static;
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalFin, ProposalInit};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_types_core::felt::Felt;
+use tempfile::NamedTempFile;
 use test_case::test_case;
 use tokio;
 
@@ -11,16 +12,27 @@ use super::SingleHeightConsensus;
 use crate::config::TimeoutsConfig;
 use crate::single_height_consensus::{ShcEvent, ShcReturn, ShcTask};
 use crate::state_machine::StateMachineEvent;
-use crate::test_utils::{precommit, prevote, MockProposalPart, MockTestContext, TestBlock};
-use crate::types::{ConsensusError, ValidatorId, DEFAULT_VALIDATOR_ID};
+use crate::test_utils::{
+    equal_weight_validators,
+    precommit,
+    prevote,
+    proposal_fin,
+    validator_public_key,
+    validator_signer,
+    MockProposalPart,
+    MockTestContext,
+    TestBlock,
+};
+use crate::types::{ConsensusError, Evidence, ValidatorId, ValidatorSet, DEFAULT_VALIDATOR_ID};
+use crate::wal::Wal;
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = DEFAULT_VALIDATOR_ID.into();
     static ref VALIDATOR_ID_1: ValidatorId = (DEFAULT_VALIDATOR_ID + 1).into();
     static ref VALIDATOR_ID_2: ValidatorId = (DEFAULT_VALIDATOR_ID + 2).into();
     static ref VALIDATOR_ID_3: ValidatorId = (DEFAULT_VALIDATOR_ID + 3).into();
-    static ref VALIDATORS: Vec<ValidatorId> =
-        vec![*PROPOSER_ID, *VALIDATOR_ID_1, *VALIDATOR_ID_2, *VALIDATOR_ID_3];
+    static ref VALIDATORS: ValidatorSet =
+        equal_weight_validators(&[*PROPOSER_ID, *VALIDATOR_ID_1, *VALIDATOR_ID_2, *VALIDATOR_ID_3]);
     static ref BLOCK: TestBlock = TestBlock { content: vec![1, 2, 3], id: BlockHash(Felt::ONE) };
     static ref PROPOSAL_INIT: ProposalInit = ProposalInit {
         height: BlockNumber(0),
@@ -31,9 +43,9 @@ lazy_static! {
     static ref TIMEOUTS: TimeoutsConfig = TimeoutsConfig::default();
     static ref VALIDATE_PROPOSAL_EVENT: ShcEvent = ShcEvent::ValidateProposal(
         StateMachineEvent::Proposal(Some(BLOCK.id), PROPOSAL_INIT.round, PROPOSAL_INIT.valid_round,),
-        Some(ProposalFin { proposal_content_id: BLOCK.id }),
+        Some(PROPOSAL_FIN.clone()),
     );
-    static ref PROPOSAL_FIN: ProposalFin = ProposalFin { proposal_content_id: BLOCK.id };
+    static ref PROPOSAL_FIN: ProposalFin = proposal_fin(&PROPOSAL_INIT, BLOCK.id);
 }
 
 const CHANNEL_SIZE: usize = 1;
@@ -41,14 +53,14 @@ const CHANNEL_SIZE: usize = 1;
 fn prevote_task(block_felt: Option<Felt>, round: u32) -> ShcTask {
     ShcTask::Prevote(
         TIMEOUTS.prevote_timeout,
-        StateMachineEvent::Prevote(block_felt.map(BlockHash), round),
+        StateMachineEvent::Prevote(block_felt.map(BlockHash), round, 1),
     )
 }
 
 fn precommit_task(block_felt: Option<Felt>, round: u32) -> ShcTask {
     ShcTask::Precommit(
         TIMEOUTS.precommit_timeout,
-        StateMachineEvent::Precommit(block_felt.map(BlockHash), round),
+        StateMachineEvent::Precommit(block_felt.map(BlockHash), round, 1),
     )
 }
 
@@ -82,8 +94,10 @@ async fn proposer() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        validator_signer(*PROPOSER_ID),
+        None,
     );
 
     context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
@@ -168,11 +182,16 @@ async fn validator(repeat_proposal: bool) {
         BlockNumber(0),
         false,
         *VALIDATOR_ID_1,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        None,
     );
 
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
     context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
@@ -193,9 +212,25 @@ async fn validator(repeat_proposal: bool) {
         Ok(ShcReturn::Tasks(vec![prevote_task(Some(BLOCK.id.0), 0)]))
     );
     if repeat_proposal {
-        // Send the same proposal again, which should be ignored (no expectations).
+        // Send the same proposal again, which is not validated again (no expectations). Only its
+        // fin is read to check for conflicting proposals.
         let shc_ret = handle_proposal(&mut shc, &mut context).await;
-        assert_eq!(shc_ret, ShcReturn::Tasks(Vec::new()));
+        assert_eq!(
+            shc_ret,
+            ShcReturn::Tasks(vec![ShcTask::DuplicateProposal(
+                PROPOSAL_INIT.clone(),
+                oneshot::channel().1
+            )])
+        );
+        // The same signed fin is not evidence (no expectations).
+        assert_eq!(
+            shc.handle_event(
+                &mut context,
+                ShcEvent::DuplicateProposal(PROPOSAL_INIT.clone(), Some(PROPOSAL_FIN.clone()))
+            )
+            .await,
+            Ok(ShcReturn::Tasks(Vec::new()))
+        );
     }
     assert_eq!(
         shc.handle_message(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID)).await,
@@ -238,6 +273,268 @@ async fn validator(repeat_proposal: bool) {
     );
 }
 
+#[tokio::test]
+async fn validator_with_uneven_weights() {
+    let mut context = MockTestContext::new();
+
+    // VALIDATOR_ID_3 holds 4 of the 7 voting weight, so its votes together with those of the node
+    // form a quorum.
+    let validators = ValidatorSet::from([
+        (*PROPOSER_ID, 1),
+        (*VALIDATOR_ID_1, 1),
+        (*VALIDATOR_ID_2, 1),
+        (*VALIDATOR_ID_3, 4),
+    ]);
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        validators,
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        None,
+    );
+
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
+    context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &ConsensusMessage| {
+            msg == &prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)
+        })
+        .returning(move |_| Ok(()));
+    handle_proposal(&mut shc, &mut context).await;
+    assert_eq!(
+        shc.handle_event(&mut context, VALIDATE_PROPOSAL_EVENT.clone()).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(Some(BLOCK.id.0), 0)]))
+    );
+
+    // 3 Prevotes out of 4 validators, but only 3 of the 7 voting weight, are not a quorum.
+    assert_eq!(
+        shc.handle_message(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID)).await,
+        Ok(ShcReturn::Tasks(Vec::new()))
+    );
+    assert_eq!(
+        shc.handle_message(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_2)).await,
+        Ok(ShcReturn::Tasks(Vec::new()))
+    );
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &ConsensusMessage| {
+            msg == &precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)
+        })
+        .returning(move |_| Ok(()));
+    assert_eq!(
+        shc.handle_message(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_3)).await,
+        Ok(ShcReturn::Tasks(vec![timeout_prevote_task(0), precommit_task(Some(BLOCK.id.0), 0)]))
+    );
+
+    // The Precommits of the node and of VALIDATOR_ID_3 are 5 of the 7 voting weight.
+    let precommits = vec![
+        precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_3),
+        precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1),
+    ];
+    let ShcReturn::Decision(decision) =
+        shc.handle_message(&mut context, precommits[0].clone()).await.unwrap()
+    else {
+        panic!("Expected decision");
+    };
+    assert_eq!(decision.block, BLOCK.id);
+    assert_eq!(decision.precommits.len(), precommits.len());
+    assert!(
+        decision
+            .precommits
+            .into_iter()
+            .all(|item| precommits.contains(&ConsensusMessage::Vote(item)))
+    );
+}
+
+#[tokio::test]
+async fn validator_rejects_proposal_fin_with_invalid_signature() {
+    let mut context = MockTestContext::new();
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        None,
+    );
+
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
+    context.expect_validate_proposal().times(2).returning(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    handle_proposal(&mut shc, &mut context).await;
+
+    // A fin of the proposal signed by another validator.
+    let forged_fin = ProposalFin {
+        signature: proposal_fin(
+            &ProposalInit { proposer: *VALIDATOR_ID_2, ..PROPOSAL_INIT.clone() },
+            BLOCK.id,
+        )
+        .signature,
+        ..PROPOSAL_FIN.clone()
+    };
+    let event = ShcEvent::ValidateProposal(
+        StateMachineEvent::Proposal(Some(BLOCK.id), PROPOSAL_INIT.round, PROPOSAL_INIT.valid_round),
+        Some(forged_fin),
+    );
+    // The error is returned, so the manager can report the peer which sent the proposal. The node
+    // doesn't vote on it.
+    assert!(matches!(
+        shc.handle_event(&mut context, event).await,
+        Err(ConsensusError::InvalidProposal(proposer, BlockNumber(0), _)) if proposer == *PROPOSER_ID
+    ));
+
+    // The proposal of the proposer is still validated.
+    let shc_ret = handle_proposal(&mut shc, &mut context).await;
+    assert_eq!(shc_ret.as_tasks().unwrap()[0].as_validate_proposal().unwrap().0, &*PROPOSAL_INIT);
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &ConsensusMessage| {
+            msg == &prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)
+        })
+        .returning(move |_| Ok(()));
+    assert_eq!(
+        shc.handle_event(&mut context, VALIDATE_PROPOSAL_EVENT.clone()).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(Some(BLOCK.id.0), 0)]))
+    );
+}
+
+#[test_case(true; "signed_by_proposer")]
+#[test_case(false; "forged")]
+#[tokio::test]
+async fn conflicting_proposal(signed_by_proposer: bool) {
+    let mut context = MockTestContext::new();
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        None,
+    );
+
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
+    context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().times(1).returning(move |_| Ok(()));
+    handle_proposal(&mut shc, &mut context).await;
+    assert_eq!(
+        shc.handle_event(&mut context, VALIDATE_PROPOSAL_EVENT.clone()).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(Some(BLOCK.id.0), 0)]))
+    );
+
+    // A second proposal for the same round, with different content.
+    let signer = if signed_by_proposer { *PROPOSER_ID } else { *VALIDATOR_ID_2 };
+    let conflicting_fin = ProposalFin {
+        signature: proposal_fin(
+            &ProposalInit { proposer: signer, ..PROPOSAL_INIT.clone() },
+            BlockHash(Felt::TWO),
+        )
+        .signature,
+        proposal_content_id: BlockHash(Felt::TWO),
+    };
+    // Only a proposal signed by the proposer is evidence against it.
+    let expected_evidence = Evidence::ConflictingProposals(
+        (PROPOSAL_INIT.clone(), PROPOSAL_FIN.clone()),
+        (PROPOSAL_INIT.clone(), conflicting_fin.clone()),
+    );
+    context
+        .expect_report_evidence()
+        .times(if signed_by_proposer { 1 } else { 0 })
+        .withf(move |evidence| evidence == &expected_evidence)
+        .returning(move |_| ());
+    let ret = shc
+        .handle_event(
+            &mut context,
+            ShcEvent::DuplicateProposal(PROPOSAL_INIT.clone(), Some(conflicting_fin)),
+        )
+        .await;
+    // The error is returned, so the manager can report the peer which sent the proposal.
+    if signed_by_proposer {
+        assert!(
+            matches!(ret, Err(ConsensusError::Equivocation(evidence)) if evidence.offender() == *PROPOSER_ID)
+        );
+    } else {
+        assert!(matches!(ret, Err(ConsensusError::InvalidProposal(..))));
+    }
+}
+
+#[tokio::test]
+async fn one_pending_duplicate_proposal_per_round() {
+    let mut context = MockTestContext::new();
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        None,
+    );
+
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
+    context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    handle_proposal(&mut shc, &mut context).await;
+
+    let duplicate_task = ShcTask::DuplicateProposal(PROPOSAL_INIT.clone(), oneshot::channel().1);
+    assert_eq!(
+        handle_proposal(&mut shc, &mut context).await,
+        ShcReturn::Tasks(vec![duplicate_task])
+    );
+    // The fin of the first duplicate is still being read, so further duplicates are dropped.
+    assert_eq!(handle_proposal(&mut shc, &mut context).await, ShcReturn::Tasks(Vec::new()));
+
+    // Once the fin was read, the next duplicate is read as well.
+    shc.handle_event(&mut context, ShcEvent::DuplicateProposal(PROPOSAL_INIT.clone(), None))
+        .await
+        .unwrap();
+    let duplicate_task = ShcTask::DuplicateProposal(PROPOSAL_INIT.clone(), oneshot::channel().1);
+    assert_eq!(
+        handle_proposal(&mut shc, &mut context).await,
+        ShcReturn::Tasks(vec![duplicate_task])
+    );
+}
+
 #[test_case(true; "repeat")]
 #[test_case(false; "equivocation")]
 #[tokio::test]
@@ -248,11 +545,16 @@ async fn vote_twice(same_vote: bool) {
         BlockNumber(0),
         false,
         *VALIDATOR_ID_1,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        None,
     );
 
-    context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
     context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
@@ -293,11 +595,25 @@ async fn vote_twice(same_vote: bool) {
 
     let second_vote =
         if same_vote { first_vote.clone() } else { precommit(Some(Felt::TWO), 0, 0, *PROPOSER_ID) };
+    let (ConsensusMessage::Vote(first), ConsensusMessage::Vote(second)) =
+        (first_vote.clone(), second_vote.clone())
+    else {
+        panic!("Expected votes");
+    };
+    let expected_evidence = Evidence::ConflictingVotes(first, second);
+    context
+        .expect_report_evidence()
+        .times(if same_vote { 0 } else { 1 })
+        .withf(move |evidence| evidence == &expected_evidence)
+        .returning(move |_| ());
     let res = shc.handle_message(&mut context, second_vote.clone()).await;
     if same_vote {
         assert_eq!(res, Ok(ShcReturn::Tasks(Vec::new())));
     } else {
-        assert!(matches!(res, Err(ConsensusError::Equivocation(_, _, _))));
+        assert!(matches!(
+            res,
+            Err(ConsensusError::Equivocation(evidence)) if evidence.offender() == *PROPOSER_ID
+        ));
     }
 
     let ShcReturn::Decision(decision) = shc
@@ -318,8 +634,10 @@ async fn rebroadcast_votes() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        validator_signer(*PROPOSER_ID),
+        None,
     );
 
     context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
@@ -366,7 +684,7 @@ async fn rebroadcast_votes() {
     assert_eq!(
         shc.handle_event(
             &mut context,
-            ShcEvent::Precommit(StateMachineEvent::Precommit(Some(BLOCK.id), 0))
+            ShcEvent::Precommit(StateMachineEvent::Precommit(Some(BLOCK.id), 0, 1))
         )
         .await,
         Ok(ShcReturn::Tasks(vec![precommit_task(Some(BLOCK.id.0), 0),]))
@@ -381,8 +699,10 @@ async fn repropose() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         TIMEOUTS.clone(),
+        validator_signer(*PROPOSER_ID),
+        None,
     );
 
     context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
@@ -466,3 +786,149 @@ async fn repropose() {
             .all(|item| precommits.contains(&ConsensusMessage::Vote(item)))
     );
 }
+
+#[tokio::test]
+async fn restart_after_precommit() {
+    let wal_file = NamedTempFile::new().unwrap();
+    let mut context = MockTestContext::new();
+
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        Some(Wal::open(wal_file.path()).unwrap()),
+    );
+
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
+    context.expect_validate_proposal().times(1).returning(move |_, _, _, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((BLOCK.id, PROPOSAL_FIN.clone())).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context
+        .expect_broadcast()
+        .times(2)
+        .withf(move |msg: &ConsensusMessage| {
+            msg == &prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)
+                || msg == &precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)
+        })
+        .returning(move |_| Ok(()));
+    handle_proposal(&mut shc, &mut context).await;
+    shc.handle_event(&mut context, VALIDATE_PROPOSAL_EVENT.clone()).await.unwrap();
+    shc.handle_message(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID)).await.unwrap();
+    // The Node got a Prevote quorum, and locked on the block.
+    assert_eq!(
+        shc.handle_message(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_2)).await,
+        Ok(ShcReturn::Tasks(vec![timeout_prevote_task(0), precommit_task(Some(BLOCK.id.0), 0)]))
+    );
+
+    // Crash, and restart from the WAL.
+    drop(shc);
+    let wal = Wal::open(wal_file.path()).unwrap();
+    let wal_entries = wal.read_entries().unwrap();
+    assert_eq!(wal_entries.len(), 2);
+    assert_eq!(wal_entries[1].locked_value_round, Some((BLOCK.id, 0)));
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        TIMEOUTS.clone(),
+        validator_signer(*VALIDATOR_ID_1),
+        Some(wal.clone()),
+    );
+    shc.restore(wal_entries);
+
+    // Only the votes sent before the crash may be sent in round 0.
+    let mut context = MockTestContext::new();
+    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validator_public_key()
+        .returning(move |_, validator| Some(validator_public_key(validator)));
+    let other_block = BlockHash(Felt::TWO);
+    context.expect_validate_proposal().times(2).returning(move |_, round, _, _, _| {
+        let init = ProposalInit { round, ..PROPOSAL_INIT.clone() };
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send((other_block, proposal_fin(&init, other_block))).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context
+        .expect_broadcast()
+        .times(2)
+        .withf(move |msg: &ConsensusMessage| {
+            msg == &prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)
+                || msg == &precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)
+        })
+        .returning(move |_| Ok(()));
+    // The restored votes are sent again, since they may not have been sent before the crash.
+    assert_eq!(
+        shc.start(&mut context).await,
+        Ok(ShcReturn::Tasks(vec![
+            prevote_task(Some(BLOCK.id.0), 0),
+            precommit_task(Some(BLOCK.id.0), 0)
+        ]))
+    );
+    // A conflicting proposal in round 0 isn't voted for.
+    let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(MockProposalPart(1)).await.unwrap();
+    shc.handle_proposal(&mut context, PROPOSAL_INIT.clone(), content_receiver).await.unwrap();
+    assert_eq!(
+        shc.handle_event(
+            &mut context,
+            ShcEvent::ValidateProposal(
+                StateMachineEvent::Proposal(Some(other_block), 0, None),
+                Some(proposal_fin(&PROPOSAL_INIT, other_block)),
+            ),
+        )
+        .await,
+        Ok(ShcReturn::Tasks(Vec::new()))
+    );
+    // Neither is a NIL Prevote quorum.
+    for voter in [*PROPOSER_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3] {
+        assert_eq!(
+            shc.handle_message(&mut context, prevote(None, 0, 0, voter)).await,
+            Ok(ShcReturn::Tasks(Vec::new()))
+        );
+    }
+    // Advance to the next round, where the node is still locked on the block.
+    shc.handle_message(&mut context, precommit(None, 0, 0, *PROPOSER_ID)).await.unwrap();
+    assert_eq!(
+        shc.handle_message(&mut context, precommit(None, 0, 0, *VALIDATOR_ID_2)).await,
+        Ok(ShcReturn::Tasks(vec![timeout_precommit_task(0)]))
+    );
+    shc.handle_event(
+        &mut context,
+        ShcEvent::TimeoutPrecommit(StateMachineEvent::TimeoutPrecommit(0)),
+    )
+    .await
+    .unwrap();
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &ConsensusMessage| msg == &prevote(None, 0, 1, *VALIDATOR_ID_1))
+        .returning(move |_| Ok(()));
+    let init = ProposalInit { round: 1, ..PROPOSAL_INIT.clone() };
+    let (mut content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+    content_sender.send(MockProposalPart(1)).await.unwrap();
+    shc.handle_proposal(&mut context, init.clone(), content_receiver).await.unwrap();
+    assert_eq!(
+        shc.handle_event(
+            &mut context,
+            ShcEvent::ValidateProposal(
+                StateMachineEvent::Proposal(Some(other_block), 1, None),
+                Some(proposal_fin(&init, other_block)),
+            ),
+        )
+        .await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(None, 1)]))
+    );
+    assert_eq!(wal.read_entries().unwrap().len(), 3);
+}
@@ -11,7 +11,15 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use tracing::trace;
 
-use crate::types::{ProposalContentId, Round, ValidatorId};
+use crate::types::{
+    quorum,
+    total_weight,
+    ProposalContentId,
+    Round,
+    ValidatorId,
+    ValidatorSet,
+    VotingWeight,
+};
 
 /// Events which the state machine sends/receives.
 #[derive(Debug, Clone, PartialEq)]
@@ -25,9 +33,11 @@ pub enum StateMachineEvent {
     // (proposal_id, round, valid_round)
     Proposal(Option<ProposalContentId>, Round, Option<Round>),
     /// Consensus message, can be both sent from and to the state machine.
-    Prevote(Option<ProposalContentId>, Round),
+    // (proposal_id, round, voting weight of the voter)
+    Prevote(Option<ProposalContentId>, Round, VotingWeight),
     /// Consensus message, can be both sent from and to the state machine.
-    Precommit(Option<ProposalContentId>, Round),
+    // (proposal_id, round, voting weight of the voter)
+    Precommit(Option<ProposalContentId>, Round, VotingWeight),
     /// The state machine returns this event to the caller when a decision is reached. Not
     /// expected as an inbound message. We presume that the caller is able to recover the set of
     /// precommits which led to this decision from the information returned here.
@@ -40,7 +50,7 @@ pub enum StateMachineEvent {
     TimeoutPrecommit(Round),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum Step {
     Propose,
     Prevote,
@@ -54,16 +64,17 @@ pub enum Step {
 /// Each height is begun with a call to `start`, with no further calls to it.
 pub struct StateMachine {
     id: ValidatorId,
+    weight: VotingWeight,
     round: Round,
     step: Step,
-    quorum: u32,
-    round_skip_threshold: u32,
+    quorum: VotingWeight,
+    round_skip_threshold: VotingWeight,
     is_observer: bool,
     // {round: (proposal_id, valid_round)}
     proposals: HashMap<Round, (Option<ProposalContentId>, Option<Round>)>,
-    // {round: {proposal_id: vote_count}
-    prevotes: HashMap<Round, HashMap<Option<ProposalContentId>, u32>>,
-    precommits: HashMap<Round, HashMap<Option<ProposalContentId>, u32>>,
+    // {round: {proposal_id: vote_weight}
+    prevotes: HashMap<Round, HashMap<Option<ProposalContentId>, VotingWeight>>,
+    precommits: HashMap<Round, HashMap<Option<ProposalContentId>, VotingWeight>>,
     // When true, the state machine will wait for a GetProposal event, buffering all other input
     // events in `events_queue`.
     awaiting_get_proposal: bool,
@@ -76,13 +87,16 @@ pub struct StateMachine {
 }
 
 impl StateMachine {
-    /// total_weight - the total voting weight of all validators for this height.
-    pub fn new(id: ValidatorId, total_weight: u32, is_observer: bool) -> Self {
+    /// validators - the validators of this height. The quorum is more than 2/3 of their total
+    /// voting weight, and the round skip threshold is more than 1/3 of it.
+    pub fn new(id: ValidatorId, validators: &ValidatorSet, is_observer: bool) -> Self {
+        let total_weight = total_weight(validators);
         Self {
             id,
+            weight: validators.get(&id).copied().unwrap_or_default(),
             round: 0,
             step: Step::Propose,
-            quorum: (2 * total_weight / 3) + 1,
+            quorum: quorum(total_weight),
             round_skip_threshold: total_weight / 3 + 1,
             is_observer,
             proposals: HashMap::new(),
@@ -102,17 +116,69 @@ impl StateMachine {
         self.round
     }
 
-    pub fn quorum_size(&self) -> u32 {
+    pub fn quorum_size(&self) -> VotingWeight {
         self.quorum
     }
 
+    pub fn locked_value_round(&self) -> Option<(ProposalContentId, Round)> {
+        self.locked_value_round
+    }
+
+    pub fn valid_value_round(&self) -> Option<(ProposalContentId, Round)> {
+        self.valid_value_round
+    }
+
+    /// Restores the state of a node which restarted in the middle of this height, so it doesn't
+    /// send votes which conflict with the votes it sent before the restart. Must be called before
+    /// `start`.
+    ///
+    /// - `own_votes`: the Prevotes and Precommits sent by this node.
+    /// - `locked_value_round`, `valid_value_round`: as they were when the last vote was sent.
+    ///
+    /// The state machine resumes from the step of its last vote, so it doesn't vote again in the
+    /// round of that vote.
+    pub fn restore(
+        &mut self,
+        own_votes: Vec<StateMachineEvent>,
+        locked_value_round: Option<(ProposalContentId, Round)>,
+        valid_value_round: Option<(ProposalContentId, Round)>,
+    ) {
+        assert!(
+            self.round == 0 && self.step == Step::Propose,
+            "The state machine should be restored before it starts."
+        );
+        for vote in own_votes {
+            let (votes, proposal_id, round, weight, step) = match vote {
+                StateMachineEvent::Prevote(proposal_id, round, weight) => {
+                    (&mut self.prevotes, proposal_id, round, weight, Step::Prevote)
+                }
+                StateMachineEvent::Precommit(proposal_id, round, weight) => {
+                    (&mut self.precommits, proposal_id, round, weight, Step::Precommit)
+                }
+                _ => panic!("Only votes can be restored, got {:?}", vote),
+            };
+            *votes.entry(round).or_default().entry(proposal_id).or_insert(0) += weight;
+            if (round, &step) > (self.round, &self.step) {
+                self.round = round;
+                self.step = step;
+            }
+        }
+        self.locked_value_round = locked_value_round;
+        self.valid_value_round = valid_value_round;
+    }
+
     /// Starts the state machine, effectively calling `StartRound(0)` from the paper. This is
     /// needed to trigger the first leader to propose.
     /// See [`GetProposal`](StateMachineEvent::GetProposal)
+    ///
+    /// A restored state machine instead waits in the round and step it was restored to.
     pub fn start<LeaderFn>(&mut self, leader_fn: &LeaderFn) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
+        if self.step != Step::Propose {
+            return VecDeque::new();
+        }
         self.advance_to_round(0, leader_fn)
     }
 
@@ -168,8 +234,8 @@ impl StateMachine {
             while let Some(e) = resultant_events.pop_front() {
                 match e {
                     StateMachineEvent::Proposal(_, _, _)
-                    | StateMachineEvent::Prevote(_, _)
-                    | StateMachineEvent::Precommit(_, _) => {
+                    | StateMachineEvent::Prevote(_, _, _)
+                    | StateMachineEvent::Precommit(_, _, _) => {
                         if self.is_observer {
                             continue;
                         }
@@ -213,11 +279,11 @@ impl StateMachine {
             StateMachineEvent::Proposal(proposal_id, round, valid_round) => {
                 self.handle_proposal(proposal_id, round, valid_round, leader_fn)
             }
-            StateMachineEvent::Prevote(proposal_id, round) => {
-                self.handle_prevote(proposal_id, round, leader_fn)
+            StateMachineEvent::Prevote(proposal_id, round, weight) => {
+                self.handle_prevote(proposal_id, round, weight, leader_fn)
             }
-            StateMachineEvent::Precommit(proposal_id, round) => {
-                self.handle_precommit(proposal_id, round, leader_fn)
+            StateMachineEvent::Precommit(proposal_id, round, weight) => {
+                self.handle_precommit(proposal_id, round, weight, leader_fn)
             }
             StateMachineEvent::Decision(_, _) => {
                 unimplemented!(
@@ -264,7 +330,7 @@ impl StateMachine {
         if self.step != Step::Propose || round != self.round {
             return VecDeque::new();
         };
-        let mut output = VecDeque::from([StateMachineEvent::Prevote(None, round)]);
+        let mut output = VecDeque::from([StateMachineEvent::Prevote(None, round, self.weight)]);
         output.append(&mut self.advance_to_step(Step::Prevote));
         output
     }
@@ -274,14 +340,15 @@ impl StateMachine {
         &mut self,
         proposal_id: Option<ProposalContentId>,
         round: u32,
+        weight: VotingWeight,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
-        let prevote_count = self.prevotes.entry(round).or_default().entry(proposal_id).or_insert(0);
-        // TODO(matan): Use variable weight.
-        *prevote_count += 1;
+        let prevote_weight =
+            self.prevotes.entry(round).or_default().entry(proposal_id).or_insert(0);
+        *prevote_weight += weight;
         self.map_round_to_upons(round, leader_fn)
     }
 
@@ -289,7 +356,7 @@ impl StateMachine {
         if self.step != Step::Prevote || round != self.round {
             return VecDeque::new();
         };
-        let mut output = VecDeque::from([StateMachineEvent::Precommit(None, round)]);
+        let mut output = VecDeque::from([StateMachineEvent::Precommit(None, round, self.weight)]);
         output.append(&mut self.advance_to_step(Step::Precommit));
         output
     }
@@ -299,15 +366,15 @@ impl StateMachine {
         &mut self,
         proposal_id: Option<ProposalContentId>,
         round: u32,
+        weight: VotingWeight,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
         LeaderFn: Fn(Round) -> ValidatorId,
     {
-        let precommit_count =
+        let precommit_weight =
             self.precommits.entry(round).or_default().entry(proposal_id).or_insert(0);
-        // TODO(matan): Use variable weight.
-        *precommit_count += 1;
+        *precommit_weight += weight;
         self.map_round_to_upons(round, leader_fn)
     }
 
@@ -412,9 +479,9 @@ impl StateMachine {
         let mut output = if proposal_id.is_some_and(|v| {
             self.locked_value_round.map_or(true, |(locked_value, _)| v == locked_value)
         }) {
-            VecDeque::from([StateMachineEvent::Prevote(*proposal_id, self.round)])
+            VecDeque::from([StateMachineEvent::Prevote(*proposal_id, self.round, self.weight)])
         } else {
-            VecDeque::from([StateMachineEvent::Prevote(None, self.round)])
+            VecDeque::from([StateMachineEvent::Prevote(None, self.round, self.weight)])
         };
         output.append(&mut self.advance_to_step(Step::Prevote));
         output
@@ -442,9 +509,9 @@ impl StateMachine {
                 locked_round <= *valid_round || locked_value == v
             })
         }) {
-            VecDeque::from([StateMachineEvent::Prevote(*proposal_id, self.round)])
+            VecDeque::from([StateMachineEvent::Prevote(*proposal_id, self.round, self.weight)])
         } else {
-            VecDeque::from([StateMachineEvent::Prevote(None, self.round)])
+            VecDeque::from([StateMachineEvent::Prevote(None, self.round, self.weight)])
         };
         output.append(&mut self.advance_to_step(Step::Prevote));
         output
@@ -485,8 +552,11 @@ impl StateMachine {
             return VecDeque::new();
         }
         self.locked_value_round = Some((*proposal_id, self.round));
-        let mut output =
-            VecDeque::from([StateMachineEvent::Precommit(Some(*proposal_id), self.round)]);
+        let mut output = VecDeque::from([StateMachineEvent::Precommit(
+            Some(*proposal_id),
+            self.round,
+            self.weight,
+        )]);
         output.append(&mut self.advance_to_step(Step::Precommit));
         output
     }
@@ -499,7 +569,8 @@ impl StateMachine {
         if !value_has_enough_votes(&self.prevotes, self.round, &None, self.quorum) {
             return VecDeque::new();
         }
-        let mut output = VecDeque::from([StateMachineEvent::Precommit(None, self.round)]);
+        let mut output =
+            VecDeque::from([StateMachineEvent::Precommit(None, self.round, self.weight)]);
         output.append(&mut self.advance_to_step(Step::Precommit));
         output
     }
@@ -548,18 +619,18 @@ impl StateMachine {
 }
 
 fn round_has_enough_votes(
-    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, u32>>,
+    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, VotingWeight>>,
     round: u32,
-    threshold: u32,
+    threshold: VotingWeight,
 ) -> bool {
     votes.get(&round).map_or(0, |v| v.values().sum()) >= threshold
 }
 
 fn value_has_enough_votes(
-    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, u32>>,
+    votes: &HashMap<u32, HashMap<Option<ProposalContentId>, VotingWeight>>,
     round: u32,
     value: &Option<ProposalContentId>,
-    threshold: u32,
+    threshold: VotingWeight,
 ) -> bool {
     votes.get(&round).map_or(0, |v| *v.get(value).unwrap_or(&0)) >= threshold
 }
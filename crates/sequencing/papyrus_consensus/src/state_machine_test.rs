@@ -1,13 +1,21 @@
 use std::collections::VecDeque;
 
 use lazy_static::lazy_static;
+use proptest::prelude::*;
+use proptest::sample::subsequence;
 use starknet_api::block::BlockHash;
 use starknet_types_core::felt::Felt;
 use test_case::test_case;
 
 use super::Round;
 use crate::state_machine::{StateMachine, StateMachineEvent};
-use crate::types::{ProposalContentId, ValidatorId, DEFAULT_VALIDATOR_ID};
+use crate::types::{
+    ProposalContentId,
+    ValidatorId,
+    ValidatorSet,
+    VotingWeight,
+    DEFAULT_VALIDATOR_ID,
+};
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = DEFAULT_VALIDATOR_ID.into();
@@ -24,9 +32,22 @@ struct TestWrapper<LeaderFn: Fn(Round) -> ValidatorId> {
 }
 
 impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
-    pub fn new(id: ValidatorId, total_weight: u32, leader_fn: LeaderFn, is_observer: bool) -> Self {
+    /// Creates a state machine for `n_validators` validators of weight 1, starting from
+    /// `DEFAULT_VALIDATOR_ID`.
+    pub fn new(id: ValidatorId, n_validators: u64, leader_fn: LeaderFn, is_observer: bool) -> Self {
+        let validators =
+            (0..n_validators).map(|i| (ValidatorId::from(DEFAULT_VALIDATOR_ID + i), 1)).collect();
+        Self::with_validators(id, &validators, leader_fn, is_observer)
+    }
+
+    pub fn with_validators(
+        id: ValidatorId,
+        validators: &ValidatorSet,
+        leader_fn: LeaderFn,
+        is_observer: bool,
+    ) -> Self {
         Self {
-            state_machine: StateMachine::new(id, total_weight, is_observer),
+            state_machine: StateMachine::new(id, validators, is_observer),
             leader_fn,
             events: VecDeque::new(),
         }
@@ -49,11 +70,29 @@ impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
     }
 
     pub fn send_prevote(&mut self, proposal_id: Option<ProposalContentId>, round: Round) {
-        self.send_event(StateMachineEvent::Prevote(proposal_id, round))
+        self.send_event(StateMachineEvent::Prevote(proposal_id, round, 1))
     }
 
     pub fn send_precommit(&mut self, proposal_id: Option<ProposalContentId>, round: Round) {
-        self.send_event(StateMachineEvent::Precommit(proposal_id, round))
+        self.send_event(StateMachineEvent::Precommit(proposal_id, round, 1))
+    }
+
+    pub fn send_weighted_prevote(
+        &mut self,
+        proposal_id: Option<ProposalContentId>,
+        round: Round,
+        weight: VotingWeight,
+    ) {
+        self.send_event(StateMachineEvent::Prevote(proposal_id, round, weight))
+    }
+
+    pub fn send_weighted_precommit(
+        &mut self,
+        proposal_id: Option<ProposalContentId>,
+        round: Round,
+        weight: VotingWeight,
+    ) {
+        self.send_event(StateMachineEvent::Precommit(proposal_id, round, weight))
     }
 
     pub fn send_timeout_propose(&mut self, round: Round) {
@@ -93,7 +132,7 @@ fn events_arrive_in_ideal_order(is_proposer: bool) {
         assert!(wrapper.next_event().is_none());
         wrapper.send_proposal(PROPOSAL_ID, ROUND);
     }
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1));
     assert!(wrapper.next_event().is_none());
 
     wrapper.send_prevote(PROPOSAL_ID, ROUND);
@@ -102,7 +141,7 @@ fn events_arrive_in_ideal_order(is_proposer: bool) {
     wrapper.send_prevote(PROPOSAL_ID, ROUND);
     // The Node got a Prevote quorum.
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND, 1));
     assert!(wrapper.next_event().is_none());
 
     wrapper.send_precommit(PROPOSAL_ID, ROUND);
@@ -141,9 +180,9 @@ fn validator_receives_votes_first() {
 
     // Finally the proposal arrives.
     wrapper.send_proposal(PROPOSAL_ID, ROUND);
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1));
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND, 1));
     assert_eq!(
         wrapper.next_event().unwrap(),
         StateMachineEvent::Decision(PROPOSAL_ID.unwrap(), ROUND)
@@ -171,9 +210,9 @@ fn buffer_events_during_get_proposal(vote: Option<ProposalContentId>) {
         wrapper.next_event().unwrap(),
         StateMachineEvent::Proposal(PROPOSAL_ID, ROUND, None)
     );
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1));
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(vote, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(vote, ROUND, 1));
     assert!(wrapper.next_event().is_none());
 }
 
@@ -194,9 +233,9 @@ fn only_send_precommit_with_prevote_quorum_and_proposal() {
 
     // Finally the proposal arrives.
     wrapper.send_proposal(PROPOSAL_ID, ROUND);
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1));
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND, 1));
     assert!(wrapper.next_event().is_none());
 }
 
@@ -219,9 +258,9 @@ fn only_decide_with_prcommit_quorum_and_proposal() {
 
     // Finally the proposal arrives.
     wrapper.send_proposal(PROPOSAL_ID, ROUND);
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1));
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND, 1));
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrecommit(ROUND));
     assert_eq!(
         wrapper.next_event().unwrap(),
@@ -240,7 +279,7 @@ fn advance_to_the_next_round() {
     assert!(wrapper.next_event().is_none());
 
     wrapper.send_proposal(PROPOSAL_ID, ROUND);
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1));
     wrapper.send_precommit(None, ROUND);
     wrapper.send_precommit(None, ROUND);
     assert!(wrapper.next_event().is_none());
@@ -253,7 +292,10 @@ fn advance_to_the_next_round() {
     wrapper.send_timeout_precommit(ROUND);
     // The Node sends Prevote after advancing to the next round.
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND + 1));
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1));
+    assert_eq!(
+        wrapper.next_event().unwrap(),
+        StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1, 1)
+    );
 }
 
 #[test]
@@ -277,7 +319,10 @@ fn prevote_when_receiving_proposal_in_current_round() {
     assert!(wrapper.next_event().is_none());
     // The node should prevote when receiving a proposal for the current round.
     wrapper.send_proposal(PROPOSAL_ID, ROUND + 1);
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1));
+    assert_eq!(
+        wrapper.next_event().unwrap(),
+        StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1, 1)
+    );
 }
 
 #[test_case(true ; "send_proposal")]
@@ -292,17 +337,20 @@ fn mixed_quorum(send_prposal: bool) {
 
     if send_prposal {
         wrapper.send_proposal(PROPOSAL_ID, ROUND);
-        assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+        assert_eq!(
+            wrapper.next_event().unwrap(),
+            StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1)
+        );
     } else {
         wrapper.send_timeout_propose(ROUND);
-        assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(None, ROUND));
+        assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(None, ROUND, 1));
     }
     wrapper.send_prevote(PROPOSAL_ID, ROUND);
     wrapper.send_prevote(None, ROUND);
     // The Node got a Prevote quorum.
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
     wrapper.send_timeout_prevote(ROUND);
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(None, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(None, ROUND, 1));
     wrapper.send_precommit(PROPOSAL_ID, ROUND);
     wrapper.send_precommit(PROPOSAL_ID, ROUND);
     // The Node got a Precommit quorum.
@@ -369,12 +417,12 @@ fn return_proposal_if_locked_value_is_set() {
         wrapper.next_event().unwrap(),
         StateMachineEvent::Proposal(PROPOSAL_ID, ROUND, None)
     );
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1));
     // locked_value is set after receiving a Prevote quorum.
     wrapper.send_prevote(PROPOSAL_ID, ROUND);
     wrapper.send_prevote(PROPOSAL_ID, ROUND);
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND, 1));
 
     wrapper.send_precommit(None, ROUND);
     wrapper.send_precommit(None, ROUND);
@@ -387,7 +435,10 @@ fn return_proposal_if_locked_value_is_set() {
         wrapper.next_event().unwrap(),
         StateMachineEvent::Proposal(PROPOSAL_ID, ROUND + 1, Some(ROUND))
     );
-    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1));
+    assert_eq!(
+        wrapper.next_event().unwrap(),
+        StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1, 1)
+    );
 }
 
 #[test]
@@ -415,3 +466,122 @@ fn observer_node_reaches_decision() {
     );
     assert!(wrapper.next_event().is_none());
 }
+
+#[test]
+fn validator_with_quorum_weight_decides_alone() {
+    // The node holds 3 of the 4 voting weight, which is a quorum.
+    let validators = ValidatorSet::from([(*PROPOSER_ID, 1), (*VALIDATOR_ID, 3)]);
+    let mut wrapper =
+        TestWrapper::with_validators(*VALIDATOR_ID, &validators, |_: Round| *PROPOSER_ID, false);
+
+    wrapper.start();
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    wrapper.send_proposal(PROPOSAL_ID, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 3));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND, 3));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrecommit(ROUND));
+    assert_eq!(
+        wrapper.next_event().unwrap(),
+        StateMachineEvent::Decision(PROPOSAL_ID.unwrap(), ROUND)
+    );
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn restored_node_does_not_vote_again() {
+    let other_proposal_id = Some(BlockHash(Felt::TWO));
+    let mut wrapper = TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false);
+
+    // Before the restart, the node precommitted and locked on the proposal.
+    wrapper.state_machine.restore(
+        vec![
+            StateMachineEvent::Prevote(PROPOSAL_ID, ROUND, 1),
+            StateMachineEvent::Precommit(PROPOSAL_ID, ROUND, 1),
+        ],
+        Some((PROPOSAL_ID.unwrap(), ROUND)),
+        Some((PROPOSAL_ID.unwrap(), ROUND)),
+    );
+    wrapper.start();
+    assert!(wrapper.next_event().is_none());
+
+    // No votes are sent again in the round, even for a conflicting proposal or a NIL quorum.
+    wrapper.send_proposal(other_proposal_id, ROUND);
+    wrapper.send_prevote(None, ROUND);
+    wrapper.send_prevote(None, ROUND);
+    wrapper.send_prevote(None, ROUND);
+    assert!(wrapper.next_event().is_none());
+
+    // The restored Precommit counts towards the Precommit quorum.
+    wrapper.send_precommit(None, ROUND);
+    wrapper.send_precommit(None, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrecommit(ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    // The node is still locked in the next round.
+    wrapper.send_timeout_precommit(ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND + 1));
+    wrapper.send_proposal(other_proposal_id, ROUND + 1);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(None, ROUND + 1, 1));
+    assert!(wrapper.next_event().is_none());
+}
+
+/// Validators with uneven voting weights, and a subset of them.
+fn validators_and_voters() -> impl Strategy<Value = (ValidatorSet, Vec<ValidatorId>)> {
+    prop::collection::vec(1..=1000_u64, 1..10).prop_flat_map(|weights| {
+        let validators: ValidatorSet =
+            (DEFAULT_VALIDATOR_ID..).map(ValidatorId::from).zip(weights).collect();
+        let ids: Vec<ValidatorId> = validators.keys().copied().collect();
+        let n_validators = ids.len();
+        (Just(validators), subsequence(ids, 0..=n_validators))
+    })
+}
+
+// The observer is not a validator, so only the votes of the voters count.
+fn observer(validators: &ValidatorSet) -> TestWrapper<impl Fn(Round) -> ValidatorId> {
+    let proposer = *validators.keys().next().unwrap();
+    let observer_id = ValidatorId::from(DEFAULT_VALIDATOR_ID - 1);
+    TestWrapper::with_validators(observer_id, validators, move |_: Round| proposer, true)
+}
+
+proptest! {
+    #[test]
+    fn decision_requires_more_than_two_thirds_of_the_weight(
+        (validators, voters) in validators_and_voters()
+    ) {
+        let total_weight: VotingWeight = validators.values().sum();
+        let voters_weight: VotingWeight = voters.iter().map(|voter| validators[voter]).sum();
+        let mut wrapper = observer(&validators);
+
+        wrapper.start();
+        wrapper.send_proposal(PROPOSAL_ID, ROUND);
+        for voter in &voters {
+            wrapper.send_weighted_precommit(PROPOSAL_ID, ROUND, validators[voter]);
+        }
+
+        let decision = StateMachineEvent::Decision(PROPOSAL_ID.unwrap(), ROUND);
+        prop_assert_eq!(
+            wrapper.events.contains(&decision),
+            3 * voters_weight > 2 * total_weight
+        );
+    }
+
+    #[test]
+    fn round_skip_requires_more_than_a_third_of_the_weight(
+        (validators, voters) in validators_and_voters()
+    ) {
+        let total_weight: VotingWeight = validators.values().sum();
+        let voters_weight: VotingWeight = voters.iter().map(|voter| validators[voter]).sum();
+        let mut wrapper = observer(&validators);
+
+        wrapper.start();
+        for voter in &voters {
+            wrapper.send_weighted_prevote(PROPOSAL_ID, ROUND + 1, validators[voter]);
+        }
+
+        let expected_round = if 3 * voters_weight > total_weight { ROUND + 1 } else { ROUND };
+        prop_assert_eq!(wrapper.state_machine.round(), expected_round);
+    }
+}
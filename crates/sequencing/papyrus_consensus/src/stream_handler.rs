@@ -60,8 +60,9 @@ pub struct StreamHandler<
     T: Clone + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversionError> + 'static,
 > {
     // For each stream ID from the network, send the application a Receiver
-    // that will receive the messages in order. This allows sending such Receivers.
-    inbound_channel_sender: mpsc::Sender<mpsc::Receiver<T>>,
+    // that will receive the messages in order, with the metadata of the peer which sent the
+    // stream. This allows sending such Receivers.
+    inbound_channel_sender: mpsc::Sender<(BroadcastedMessageMetadata, mpsc::Receiver<T>)>,
     // This receives messages from the network.
     inbound_receiver: BroadcastTopicServer<StreamMessage<T>>,
     // A map from (peer_id, stream_id) to a struct that contains all the information
@@ -85,7 +86,7 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
 {
     /// Create a new StreamHandler.
     pub fn new(
-        inbound_channel_sender: mpsc::Sender<mpsc::Receiver<T>>,
+        inbound_channel_sender: mpsc::Sender<(BroadcastedMessageMetadata, mpsc::Receiver<T>)>,
         inbound_receiver: BroadcastTopicServer<StreamMessage<T>>,
         outbound_channel_receiver: mpsc::Receiver<(StreamId, mpsc::Receiver<T>)>,
         outbound_sender: BroadcastTopicClient<StreamMessage<T>>,
@@ -109,15 +110,15 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
         outbound_network_sender: BroadcastTopicClient<StreamMessage<T>>,
     ) -> (
         mpsc::Sender<(StreamId, mpsc::Receiver<T>)>,
-        mpsc::Receiver<mpsc::Receiver<T>>,
+        mpsc::Receiver<(BroadcastedMessageMetadata, mpsc::Receiver<T>)>,
         tokio::task::JoinHandle<()>,
     ) {
         // The inbound messages come into StreamHandler via inbound_network_receiver.
         // The application gets the messages from inbound_internal_receiver
         // (the StreamHandler keeps the inbound_internal_sender to pass the messages).
         let (inbound_internal_sender, inbound_internal_receiver): (
-            mpsc::Sender<mpsc::Receiver<T>>,
-            mpsc::Receiver<mpsc::Receiver<T>>,
+            mpsc::Sender<(BroadcastedMessageMetadata, mpsc::Receiver<T>)>,
+            mpsc::Receiver<(BroadcastedMessageMetadata, mpsc::Receiver<T>)>,
         ) = mpsc::channel(CHANNEL_BUFFER_LENGTH);
         // The outbound messages that an application would like to send are:
         //  1. Sent into outbound_internal_sender as tuples of (StreamId, Receiver)
@@ -129,10 +130,10 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
         ) = mpsc::channel(CHANNEL_BUFFER_LENGTH);
 
         let mut stream_handler = StreamHandler::<T>::new(
-            inbound_internal_sender,    // Sender<Receiver<T>>,
-            inbound_network_receiver,   // BroadcastTopicServer<StreamMessage<T>>,
+            inbound_internal_sender, // Sender<(BroadcastedMessageMetadata, Receiver<T>)>,
+            inbound_network_receiver, // BroadcastTopicServer<StreamMessage<T>>,
             outbound_internal_receiver, // Receiver<(StreamId, Receiver<T>)>,
-            outbound_network_sender,    // BroadcastTopicClient<StreamMessage<T>>
+            outbound_network_sender, // BroadcastTopicClient<StreamMessage<T>>
         );
         let handle = tokio::spawn(async move {
             stream_handler.run().await;
@@ -225,7 +226,7 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
                 return;
             }
         };
-        let peer_id = metadata.originator_id;
+        let peer_id = metadata.originator_id.clone();
         let stream_id = message.stream_id;
         let key = (peer_id, stream_id);
         let message_id = message.message_id;
@@ -237,7 +238,9 @@ impl<T: Clone + Send + Into<Vec<u8>> + TryFrom<Vec<u8>, Error = ProtobufConversi
                 // we need to create a new receiver for it.
                 let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_LENGTH);
                 // TODO(guyn): reconsider the "expect" here.
-                self.inbound_channel_sender.try_send(receiver).expect("Send should succeed");
+                self.inbound_channel_sender
+                    .try_send((metadata, receiver))
+                    .expect("Send should succeed");
 
                 let data = StreamData::new(sender);
                 e.insert(data)
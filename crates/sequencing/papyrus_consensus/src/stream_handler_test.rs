@@ -58,7 +58,7 @@ mod tests {
     fn setup_test() -> (
         StreamHandler<ConsensusMessage>,
         MockBroadcastedMessagesSender<StreamMessage<ConsensusMessage>>,
-        mpsc::Receiver<mpsc::Receiver<ConsensusMessage>>,
+        mpsc::Receiver<(BroadcastedMessageMetadata, mpsc::Receiver<ConsensusMessage>)>,
         BroadcastedMessageMetadata,
         mpsc::Sender<(StreamId, mpsc::Receiver<ConsensusMessage>)>,
         futures::stream::Map<
@@ -98,8 +98,10 @@ mod tests {
         // The inbound_channel_sender is given to StreamHandler so it can output new channels for
         // each stream. The inbound_channel_receiver is given to the "mock consensus" that
         // gets new channels and inbounds to them.
-        let (inbound_channel_sender, inbound_channel_receiver) =
-            mpsc::channel::<mpsc::Receiver<ConsensusMessage>>(CHANNEL_SIZE);
+        let (inbound_channel_sender, inbound_channel_receiver) = mpsc::channel::<(
+            BroadcastedMessageMetadata,
+            mpsc::Receiver<ConsensusMessage>,
+        )>(CHANNEL_SIZE);
 
         // TODO(guyn): We should also give the broadcast_topic_client to the StreamHandler
         // This will allow reporting to the network things like bad peers.
@@ -139,7 +141,8 @@ mod tests {
 
         join_handle.await.expect("Task should succeed");
 
-        let mut receiver = inbound_channel_receiver.next().await.unwrap();
+        let (receiver_metadata, mut receiver) = inbound_channel_receiver.next().await.unwrap();
+        assert_eq!(receiver_metadata, metadata);
         for _ in 0..9 {
             // message number 9 is Fin, so it will not be sent!
             let _ = receiver.next().await.unwrap();
@@ -174,7 +177,7 @@ mod tests {
         let mut stream_handler = join_handle.await.expect("Task should succeed");
 
         // Get the receiver for the stream.
-        let mut receiver = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver) = inbound_channel_receiver.next().await.unwrap();
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver.try_next().is_err());
 
@@ -294,19 +297,19 @@ mod tests {
         ));
 
         // Get the receiver for the first stream.
-        let mut receiver1 = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver1) = inbound_channel_receiver.next().await.unwrap();
 
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver1.try_next().is_err());
 
         // Get the receiver for the second stream.
-        let mut receiver2 = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver2) = inbound_channel_receiver.next().await.unwrap();
 
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver2.try_next().is_err());
 
         // Get the receiver for the third stream.
-        let mut receiver3 = inbound_channel_receiver.next().await.unwrap();
+        let (_, mut receiver3) = inbound_channel_receiver.next().await.unwrap();
 
         // Check that the channel is empty (no messages were sent yet).
         assert!(receiver3.try_next().is_err());
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -6,14 +7,19 @@ use mockall::mock;
 use papyrus_protobuf::consensus::{ConsensusMessage, ProposalFin, ProposalInit, Vote, VoteType};
 use papyrus_protobuf::converters::ProtobufConversionError;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::{get_public_key, sign_message_hash, PublicKey, Signature};
 use starknet_types_core::felt::Felt;
 
+use crate::signing::{proposal_fin_hash, vote_hash, ConsensusSigner, PrivateKeySigner};
 use crate::types::{
     ConsensusContext,
     ConsensusError,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
+    ValidatorSet,
     DEFAULT_VALIDATOR_ID,
 };
 
@@ -44,6 +50,17 @@ impl TryFrom<MockProposalPart> for ProposalInit {
     }
 }
 
+impl TryFrom<MockProposalPart> for ProposalFin {
+    type Error = ProtobufConversionError;
+    fn try_from(part: MockProposalPart) -> Result<Self, Self::Error> {
+        Err(ProtobufConversionError::WrongEnumVariant {
+            type_description: "MockProposalPart",
+            value_as_str: format!("{:?}", part),
+            expected: "Fin",
+        })
+    }
+}
+
 impl From<MockProposalPart> for Vec<u8> {
     fn from(part: MockProposalPart) -> Vec<u8> {
         vec![u8::try_from(part.0).expect("Invalid MockProposalPart conversion")]
@@ -87,10 +104,16 @@ mock! {
             init: ProposalInit,
         );
 
-        async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+        async fn validators(&self, height: BlockNumber) -> ValidatorSet;
 
         fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
+        fn validator_public_key(
+            &self,
+            height: BlockNumber,
+            validator: ValidatorId,
+        ) -> Option<PublicKey>;
+
         async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError>;
 
         async fn decision_reached(
@@ -99,18 +122,60 @@ mock! {
             precommits: Vec<Vote>,
         ) -> Result<(), ConsensusError>;
 
+        async fn report_evidence(&mut self, evidence: Evidence);
+
         async fn set_height_and_round(&mut self, height: BlockNumber, round: Round);
     }
 }
 
-pub fn prevote(
+/// A validator set in which all the validators have the same weight.
+pub fn equal_weight_validators(validators: &[ValidatorId]) -> ValidatorSet {
+    validators.iter().map(|validator| (*validator, 1)).collect()
+}
+
+pub fn test_chain_id() -> ChainId {
+    ChainId::Other("SN_TEST".to_string())
+}
+
+/// The private key of a validator in tests is its id.
+pub fn validator_private_key(validator: ValidatorId) -> Felt {
+    *validator.0.key()
+}
+
+pub fn validator_public_key(validator: ValidatorId) -> PublicKey {
+    get_public_key(&validator_private_key(validator))
+}
+
+pub fn validator_signer(validator: ValidatorId) -> ConsensusSigner {
+    ConsensusSigner::new(
+        test_chain_id(),
+        Arc::new(PrivateKeySigner::new(validator_private_key(validator))),
+    )
+}
+
+fn signed_vote(
+    vote_type: VoteType,
     block_felt: Option<Felt>,
     height: u64,
     round: u32,
     voter: ValidatorId,
 ) -> ConsensusMessage {
     let block_hash = block_felt.map(BlockHash);
-    ConsensusMessage::Vote(Vote { vote_type: VoteType::Prevote, height, round, block_hash, voter })
+    let vote =
+        Vote { vote_type, height, round, block_hash, voter, signature: Signature::default() };
+    let signature =
+        sign_message_hash(&vote_hash(&test_chain_id(), &vote), &validator_private_key(voter))
+            .unwrap();
+    ConsensusMessage::Vote(Vote { signature, ..vote })
+}
+
+pub fn prevote(
+    block_felt: Option<Felt>,
+    height: u64,
+    round: u32,
+    voter: ValidatorId,
+) -> ConsensusMessage {
+    signed_vote(VoteType::Prevote, block_felt, height, round, voter)
 }
 
 pub fn precommit(
@@ -119,15 +184,17 @@ pub fn precommit(
     round: u32,
     voter: ValidatorId,
 ) -> ConsensusMessage {
-    let block_hash = block_felt.map(BlockHash);
-    ConsensusMessage::Vote(Vote {
-        vote_type: VoteType::Precommit,
-        height,
-        round,
-        block_hash,
-        voter,
-    })
+    signed_vote(VoteType::Precommit, block_felt, height, round, voter)
+}
+
+/// The fin of the proposal with the given init and content, signed by the proposer.
+pub fn proposal_fin(init: &ProposalInit, proposal_content_id: ProposalContentId) -> ProposalFin {
+    let message_hash = proposal_fin_hash(&test_chain_id(), init, proposal_content_id);
+    let signature =
+        sign_message_hash(&message_hash, &validator_private_key(init.proposer)).unwrap();
+    ProposalFin { proposal_content_id, signature }
 }
+
 pub fn proposal_init(height: u64, round: u32, proposer: ValidatorId) -> ProposalInit {
     ProposalInit { height: BlockNumber(height), round, proposer, valid_round: None }
 }
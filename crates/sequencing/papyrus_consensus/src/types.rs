@@ -1,5 +1,6 @@
 //! Types for interfacing between consensus and the node.
 
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use std::time::Duration;
 
@@ -15,6 +16,7 @@ use papyrus_protobuf::consensus::{ConsensusMessage, ProposalFin, ProposalInit, V
 use papyrus_protobuf::converters::ProtobufConversionError;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::PublicKey;
 
 /// Used to identify the node by consensus.
 /// 1. This ID is derived from the id registered with Starknet's L2 staking contract.
@@ -24,11 +26,48 @@ use starknet_api::core::ContractAddress;
 pub type ValidatorId = ContractAddress;
 pub type Round = u32;
 pub type ProposalContentId = BlockHash;
+/// The voting weight of a validator. Quorums are reached by the weight of the votes, not by their
+/// count.
+pub type VotingWeight = u64;
+/// The validators of a height and their voting weights. Ordered by ID, so all nodes iterate over
+/// the validators in the same order (e.g. for deterministic proposer selection).
+pub type ValidatorSet = BTreeMap<ValidatorId, VotingWeight>;
 
 /// A temporary constant to use as a validator ID. Zero is not a valid contract address.
 // TODO(Matan): Remove this once we have a proper validator set.
 pub const DEFAULT_VALIDATOR_ID: u64 = 100;
 
+/// Returns the validator set given in the consensus config, and the public keys of the validators.
+/// The weights and the public keys are given in the order of the validator IDs.
+// TODO(Matan): Set the actual validator IDs (contract addresses).
+pub fn validators_from_config(
+    num_validators: u64,
+    validator_weights: &[VotingWeight],
+    validator_public_keys: &[PublicKey],
+) -> (ValidatorSet, HashMap<ValidatorId, PublicKey>) {
+    let validator_ids = (0..num_validators).map(|i| ValidatorId::from(DEFAULT_VALIDATOR_ID + i));
+    let validators = validator_ids.clone().zip(validator_weights.iter().copied()).collect();
+    let validator_public_keys = validator_ids.zip(validator_public_keys.iter().copied()).collect();
+    (validators, validator_public_keys)
+}
+
+/// Returns the total voting weight of the validators.
+///
+/// Panics if the total overflows, which the validation of the consensus config rules out.
+pub fn total_weight(validators: &ValidatorSet) -> VotingWeight {
+    validators
+        .values()
+        .try_fold(VotingWeight::default(), |total, weight| total.checked_add(*weight))
+        .expect("The total voting weight of the validators should fit in a VotingWeight")
+}
+
+/// Returns the quorum of the given total voting weight: the smallest weight which is more than 2/3
+/// of it.
+pub fn quorum(total_weight: VotingWeight) -> VotingWeight {
+    let quorum = 2 * u128::from(total_weight) / 3 + 1;
+    VotingWeight::try_from(quorum).expect("A quorum is at most the total weight")
+}
+
 /// Interface for consensus to call out to the node.
 ///
 /// Function calls should be assumed to not be cancel safe.
@@ -39,10 +78,12 @@ pub trait ConsensusContext {
     type ProposalPart: TryFrom<Vec<u8>, Error = ProtobufConversionError>
         + Into<Vec<u8>>
         + TryInto<ProposalInit, Error = ProtobufConversionError>
+        + TryInto<ProposalFin, Error = ProtobufConversionError>
         + From<ProposalInit>
         + Clone
         + Send
-        + Debug;
+        + Debug
+        + 'static;
 
     // TODO(matan): The oneshot for receiving the build block could be generalized to just be some
     // future which returns a block.
@@ -96,16 +137,22 @@ pub trait ConsensusContext {
     /// - `init`: The `ProposalInit` that is broadcast to the network.
     async fn repropose(&mut self, id: ProposalContentId, init: ProposalInit);
 
-    /// Get the set of validators for a given height. These are the nodes that can propose and vote
-    /// on blocks.
-    // TODO(matan): We expect this to change in the future to BTreeMap. Why?
-    // 1. Map - The nodes will have associated information (e.g. voting weight).
-    // 2. BTreeMap - We want a stable ordering of the nodes for deterministic leader selection.
-    async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+    /// Get the set of validators for a given height and their voting weights. These are the nodes
+    /// that can propose and vote on blocks.
+    async fn validators(&self, height: BlockNumber) -> ValidatorSet;
 
     /// Calculates the ID of the Proposer based on the inputs.
     fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
 
+    /// Get the public key of a validator at a given height, which is used to verify the signatures
+    /// of its votes and proposals. Returns `None` if the key is unknown, in which case the messages
+    /// of the validator are rejected.
+    fn validator_public_key(
+        &self,
+        height: BlockNumber,
+        validator: ValidatorId,
+    ) -> Option<PublicKey>;
+
     async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError>;
 
     /// Update the context that a decision has been reached for a given height.
@@ -118,6 +165,10 @@ pub trait ConsensusContext {
         precommits: Vec<Vote>,
     ) -> Result<(), ConsensusError>;
 
+    /// Called when a validator is caught signing conflicting messages, so the node can report or
+    /// slash it. See [`Evidence`].
+    async fn report_evidence(&mut self, evidence: Evidence);
+
     /// Update the context with the current height and round.
     /// Must be called at the beginning of each height.
     async fn set_height_and_round(&mut self, height: BlockNumber, round: Round);
@@ -138,6 +189,35 @@ impl Debug for Decision {
     }
 }
 
+/// Proof that a validator equivocated, made of the two conflicting messages it signed. Anyone who
+/// knows the public key of the validator can verify it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Evidence {
+    /// Two votes of the same type, height and round for different values.
+    ConflictingVotes(Vote, Vote),
+    /// Two proposals of the same height and round with different content. The fin of each proposal
+    /// is signed by the proposer over its init and content id.
+    ConflictingProposals((ProposalInit, ProposalFin), (ProposalInit, ProposalFin)),
+}
+
+impl Evidence {
+    /// The validator which signed the conflicting messages.
+    pub fn offender(&self) -> ValidatorId {
+        match self {
+            Evidence::ConflictingVotes(vote, _) => vote.voter,
+            Evidence::ConflictingProposals((init, _), _) => init.proposer,
+        }
+    }
+
+    /// The height of the conflicting messages.
+    pub fn height(&self) -> BlockNumber {
+        match self {
+            Evidence::ConflictingVotes(vote, _) => BlockNumber(vote.height),
+            Evidence::ConflictingProposals((init, _), _) => init.height,
+        }
+    }
+}
+
 pub struct BroadcastConsensusMessageChannel {
     pub broadcasted_messages_receiver: GenericReceiver<(
         Result<ConsensusMessage, ProtobufConversionError>,
@@ -170,12 +250,18 @@ pub enum ConsensusError {
     InvalidProposal(ValidatorId, BlockNumber, String),
     #[error(transparent)]
     SendError(#[from] mpsc::SendError),
-    #[error("Conflicting messages for block {0}. Old: {1:?}, New: {2:?}")]
-    Equivocation(BlockNumber, ConsensusMessage, ConsensusMessage),
+    #[error("Conflicting messages from the same validator: {0:?}")]
+    Equivocation(Evidence),
     // Indicates an error in communication between consensus and the node's networking component.
     // As opposed to an error between this node and peer nodes.
     #[error("{0}")]
     InternalNetworkError(String),
     #[error("{0}")]
     SyncError(String),
+    #[error("Failed to sign a consensus message: {0}")]
+    SigningError(String),
+    #[error("Consensus WAL error: {0}")]
+    WalError(String),
+    #[error("Invalid consensus config: {0}")]
+    InvalidConfig(String),
 }
@@ -0,0 +1,197 @@
+//! A write-ahead log (WAL) of the votes of this node.
+//!
+//! A node that restarts in the middle of a height must not send votes which conflict with the votes
+//! it sent before the restart, as that is equivocation. Therefore, each vote of this node is
+//! durably written to the WAL before it is broadcast, together with the locked and valid values of
+//! the state machine at that time. When the height is restarted, its entries are replayed to
+//! restore the state of consensus. The WAL is truncated once the height is decided.
+//!
+//! Each entry is written as its length (4 bytes, big endian) followed by its content. A crash in
+//! the middle of a write leaves a partial entry at the end of the file, which is dropped when the
+//! WAL is read. The vote of such an entry was never broadcast.
+
+#[cfg(test)]
+#[path = "wal_test.rs"]
+mod wal_test;
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use papyrus_protobuf::consensus::Vote;
+use papyrus_protobuf::converters::ProtobufConversionError;
+use starknet_api::block::BlockHash;
+use starknet_types_core::felt::Felt;
+use tracing::warn;
+
+use crate::types::{ConsensusError, ProposalContentId, Round};
+
+const LENGTH_SIZE: usize = 4;
+const FELT_SIZE: usize = 32;
+
+#[derive(thiserror::Error, Debug)]
+pub enum WalError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Corrupted WAL entry: {0}")]
+    Corrupted(String),
+    #[error(transparent)]
+    ProtobufConversion(#[from] ProtobufConversionError),
+}
+
+impl From<WalError> for ConsensusError {
+    fn from(err: WalError) -> Self {
+        ConsensusError::WalError(err.to_string())
+    }
+}
+
+/// A vote of this node, and the state of the state machine when it was sent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WalEntry {
+    pub vote: Vote,
+    pub locked_value_round: Option<(ProposalContentId, Round)>,
+    pub valid_value_round: Option<(ProposalContentId, Round)>,
+}
+
+/// A handle to the WAL file. Clones of the handle write to the same file.
+#[derive(Clone, Debug)]
+pub struct Wal {
+    file: Arc<Mutex<File>>,
+}
+
+impl Wal {
+    /// Opens the WAL at the given path, creating it if it doesn't exist.
+    pub fn open(path: &Path) -> Result<Self, WalError> {
+        let file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+        Ok(Self { file: Arc::new(Mutex::new(file)) })
+    }
+
+    /// Returns the entries in the order they were appended. A partial entry at the end of the file
+    /// is removed.
+    pub fn read_entries(&self) -> Result<Vec<WalEntry>, WalError> {
+        let mut file = self.lock();
+        let mut content = Vec::new();
+        file.seek(SeekFrom::Start(0))?;
+        file.read_to_end(&mut content)?;
+
+        let mut entries = Vec::new();
+        let mut reader = EntryReader::new(&content);
+        while let Some(entry) = reader.next_entry() {
+            entries.push(decode_entry(entry)?);
+        }
+        if reader.offset < content.len() {
+            warn!("Dropping a partial entry at the end of the WAL.");
+            file.set_len(u64::try_from(reader.offset).expect("usize should fit in u64"))?;
+            file.sync_all()?;
+        }
+        Ok(entries)
+    }
+
+    /// Durably appends the entry. Returns once the entry is synced to the disk.
+    pub fn append(&self, entry: &WalEntry) -> Result<(), WalError> {
+        let mut file = self.lock();
+        file.write_all(&with_length(encode_entry(entry)))?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// Removes all the entries.
+    pub fn truncate(&self) -> Result<(), WalError> {
+        let file = self.lock();
+        file.set_len(0)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, File> {
+        self.file.lock().expect("Lock on the WAL file was poisoned due to a previous panic")
+    }
+}
+
+fn encode_entry(entry: &WalEntry) -> Vec<u8> {
+    let mut bytes = with_length(entry.vote.clone().into());
+    for value_round in [entry.locked_value_round, entry.valid_value_round] {
+        match value_round {
+            Some((value, round)) => {
+                bytes.push(1);
+                bytes.extend(value.0.to_bytes_be());
+                bytes.extend(round.to_be_bytes());
+            }
+            None => bytes.push(0),
+        }
+    }
+    bytes
+}
+
+fn decode_entry(bytes: &[u8]) -> Result<WalEntry, WalError> {
+    let mut reader = EntryReader::new(bytes);
+    let vote = reader.next_entry().ok_or_else(|| corrupted("missing vote"))?;
+    let vote = Vote::try_from(vote.to_vec())?;
+    let locked_value_round = reader.value_round()?;
+    let valid_value_round = reader.value_round()?;
+    if reader.offset != bytes.len() {
+        return Err(corrupted("trailing bytes"));
+    }
+    Ok(WalEntry { vote, locked_value_round, valid_value_round })
+}
+
+fn with_length(content: Vec<u8>) -> Vec<u8> {
+    let length = u32::try_from(content.len()).expect("A WAL entry should be shorter than 4GB");
+    let mut bytes = length.to_be_bytes().to_vec();
+    bytes.extend(content);
+    bytes
+}
+
+fn corrupted(msg: &str) -> WalError {
+    WalError::Corrupted(msg.to_string())
+}
+
+struct EntryReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> EntryReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let taken = self.bytes.get(self.offset..self.offset.checked_add(n)?)?;
+        self.offset += n;
+        Some(taken)
+    }
+
+    // Returns the next length prefixed entry. Returns None without advancing if the rest of the
+    // bytes are a partial entry.
+    fn next_entry(&mut self) -> Option<&'a [u8]> {
+        let start = self.offset;
+        let entry = self.take(LENGTH_SIZE).and_then(|length| {
+            let length =
+                u32::from_be_bytes(length.try_into().expect("The length should be 4 bytes"));
+            self.take(usize::try_from(length).expect("u32 should fit in usize"))
+        });
+        if entry.is_none() {
+            self.offset = start;
+        }
+        entry
+    }
+
+    fn value_round(&mut self) -> Result<Option<(ProposalContentId, Round)>, WalError> {
+        match self.take(1) {
+            Some([0]) => Ok(None),
+            Some([1]) => {
+                let value = self.take(FELT_SIZE).ok_or_else(|| corrupted("missing value"))?;
+                let round = self.take(4).ok_or_else(|| corrupted("missing round"))?;
+                Ok(Some((
+                    BlockHash(Felt::from_bytes_be(
+                        value.try_into().expect("The value should be 32 bytes"),
+                    )),
+                    Round::from_be_bytes(round.try_into().expect("The round should be 4 bytes")),
+                )))
+            }
+            _ => Err(corrupted("invalid value flag")),
+        }
+    }
+}
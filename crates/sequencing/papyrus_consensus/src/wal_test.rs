@@ -0,0 +1,89 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use papyrus_protobuf::consensus::{Vote, VoteType};
+use starknet_api::block::BlockHash;
+use starknet_api::crypto::utils::Signature;
+use starknet_types_core::felt::Felt;
+use tempfile::NamedTempFile;
+
+use super::{Wal, WalEntry, WalError};
+use crate::types::{ValidatorId, DEFAULT_VALIDATOR_ID};
+
+fn entry(vote_type: VoteType, round: u32) -> WalEntry {
+    WalEntry {
+        vote: Vote {
+            vote_type,
+            height: 1,
+            round,
+            block_hash: Some(BlockHash(Felt::TWO)),
+            voter: ValidatorId::from(DEFAULT_VALIDATOR_ID),
+            signature: Signature { r: Felt::ONE, s: Felt::THREE },
+        },
+        locked_value_round: Some((BlockHash(Felt::TWO), round)),
+        valid_value_round: None,
+    }
+}
+
+#[test]
+fn append_and_read() {
+    let wal_file = NamedTempFile::new().unwrap();
+    let wal = Wal::open(wal_file.path()).unwrap();
+    assert!(wal.read_entries().unwrap().is_empty());
+
+    let entries = vec![entry(VoteType::Prevote, 0), entry(VoteType::Precommit, 0)];
+    for entry in &entries {
+        wal.append(entry).unwrap();
+    }
+    assert_eq!(wal.read_entries().unwrap(), entries);
+
+    // The entries are durable across reopening the WAL.
+    let wal = Wal::open(wal_file.path()).unwrap();
+    assert_eq!(wal.read_entries().unwrap(), entries);
+}
+
+#[test]
+fn truncate() {
+    let wal_file = NamedTempFile::new().unwrap();
+    let wal = Wal::open(wal_file.path()).unwrap();
+    wal.append(&entry(VoteType::Prevote, 0)).unwrap();
+    wal.truncate().unwrap();
+    assert!(wal.read_entries().unwrap().is_empty());
+
+    wal.append(&entry(VoteType::Prevote, 1)).unwrap();
+    assert_eq!(wal.read_entries().unwrap(), vec![entry(VoteType::Prevote, 1)]);
+}
+
+#[test]
+fn partial_entry_is_dropped() {
+    let wal_file = NamedTempFile::new().unwrap();
+    let wal = Wal::open(wal_file.path()).unwrap();
+    wal.append(&entry(VoteType::Prevote, 0)).unwrap();
+    let full_length = wal_file.as_file().metadata().unwrap().len();
+
+    // A crash in the middle of writing the second entry.
+    wal.append(&entry(VoteType::Precommit, 0)).unwrap();
+    let partial_length =
+        full_length + (wal_file.as_file().metadata().unwrap().len() - full_length) / 2;
+    wal_file.as_file().set_len(partial_length).unwrap();
+
+    assert_eq!(wal.read_entries().unwrap(), vec![entry(VoteType::Prevote, 0)]);
+    assert_eq!(wal_file.as_file().metadata().unwrap().len(), full_length);
+
+    // New entries follow the last complete entry.
+    wal.append(&entry(VoteType::Precommit, 1)).unwrap();
+    assert_eq!(
+        wal.read_entries().unwrap(),
+        vec![entry(VoteType::Prevote, 0), entry(VoteType::Precommit, 1)]
+    );
+}
+
+#[test]
+fn corrupted_entry() {
+    let wal_file = NamedTempFile::new().unwrap();
+    let mut file = OpenOptions::new().append(true).open(wal_file.path()).unwrap();
+    file.write_all(&[0, 0, 0, 2, 0xff, 0xff]).unwrap();
+
+    let wal = Wal::open(wal_file.path()).unwrap();
+    assert!(matches!(wal.read_entries(), Err(WalError::Corrupted(_))));
+}
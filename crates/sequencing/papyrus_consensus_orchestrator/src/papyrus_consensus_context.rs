@@ -15,13 +15,17 @@ use std::time::Duration;
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use futures::{SinkExt, StreamExt};
+use papyrus_consensus::signing::ConsensusSigner;
 use papyrus_consensus::types::{
+    validators_from_config,
     ConsensusContext,
     ConsensusError,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
-    DEFAULT_VALIDATOR_ID,
+    ValidatorSet,
+    VotingWeight,
 };
 use papyrus_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
 use papyrus_protobuf::consensus::{
@@ -37,6 +41,7 @@ use papyrus_storage::body::BodyStorageReader;
 use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::{StorageError, StorageReader};
 use starknet_api::block::BlockNumber;
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::transaction::Transaction;
 use tracing::{debug, debug_span, info, warn, Instrument};
 
@@ -50,7 +55,9 @@ pub struct PapyrusConsensusContext {
     storage_reader: StorageReader,
     network_broadcast_client: BroadcastTopicClient<ConsensusMessage>,
     network_proposal_sender: mpsc::Sender<(u64, mpsc::Receiver<ProposalPart>)>,
-    validators: Vec<ValidatorId>,
+    validators: ValidatorSet,
+    validator_public_keys: HashMap<ValidatorId, PublicKey>,
+    signer: ConsensusSigner,
     sync_broadcast_sender: Option<BroadcastTopicClient<Vote>>,
     // Proposal building/validating returns immediately, leaving the actual processing to a spawned
     // task. The spawned task processes the proposal asynchronously and updates the
@@ -64,16 +71,20 @@ impl PapyrusConsensusContext {
         network_broadcast_client: BroadcastTopicClient<ConsensusMessage>,
         network_proposal_sender: mpsc::Sender<(u64, mpsc::Receiver<ProposalPart>)>,
         num_validators: u64,
+        validator_public_keys: Vec<PublicKey>,
+        validator_weights: Vec<VotingWeight>,
+        signer: ConsensusSigner,
         sync_broadcast_sender: Option<BroadcastTopicClient<Vote>>,
     ) -> Self {
+        let (validators, validator_public_keys) =
+            validators_from_config(num_validators, &validator_weights, &validator_public_keys);
         Self {
             storage_reader,
             network_broadcast_client,
             network_proposal_sender,
-            // TODO(Matan): Set the actual validator IDs (contract addresses).
-            validators: (0..num_validators)
-                .map(|i| ValidatorId::from(DEFAULT_VALIDATOR_ID + i))
-                .collect(),
+            validators,
+            validator_public_keys,
+            signer,
             sync_broadcast_sender,
             valid_proposals: Arc::new(Mutex::new(BTreeMap::new())),
         }
@@ -94,6 +105,7 @@ impl ConsensusContext for PapyrusConsensusContext {
 
         let storage_reader = self.storage_reader.clone();
         let valid_proposals = Arc::clone(&self.valid_proposals);
+        let signer = self.signer.clone();
         tokio::spawn(
             async move {
                 // TODO(dvir): consider fix this for the case of reverts. If between the check that
@@ -142,10 +154,11 @@ impl ConsensusContext for PapyrusConsensusContext {
                     }))
                     .await
                     .expect("Failed to send transactions");
-                proposal_sender
-                    .send(ProposalPart::Fin(ProposalFin { proposal_content_id: block_hash }))
+                let fin = signer
+                    .sign_proposal_fin(&proposal_init, block_hash)
                     .await
-                    .expect("Failed to send fin");
+                    .expect("Failed to sign the proposal fin");
+                proposal_sender.send(ProposalPart::Fin(fin)).await.expect("Failed to send fin");
                 {
                     let mut proposals = valid_proposals
                         .lock()
@@ -194,7 +207,7 @@ impl ConsensusContext for PapyrusConsensusContext {
 
                 // First gather all the non-fin transactions.
                 let mut content_transactions: Vec<Transaction> = Vec::new();
-                let received_block_hash = loop {
+                let received_fin = loop {
                     match content.next().await {
                         Some(ProposalPart::Transactions(batch)) => {
                             for tx in batch.transactions {
@@ -202,7 +215,7 @@ impl ConsensusContext for PapyrusConsensusContext {
                             }
                         }
                         Some(ProposalPart::Fin(fin)) => {
-                            break fin.proposal_content_id;
+                            break fin;
                         }
                         msg => panic!("Unexpected message: {msg:?}"),
                     }
@@ -238,11 +251,9 @@ impl ConsensusContext for PapyrusConsensusContext {
                 // Done after inserting the proposal into the map to avoid race conditions between
                 // insertion and calls to `repropose`.
                 // This can happen as a result of sync interrupting `run_height`.
-                fin_sender
-                    .send((block_hash, ProposalFin { proposal_content_id: received_block_hash }))
-                    .unwrap_or_else(|_| {
-                        warn!("Failed to send block to consensus. height={height}");
-                    })
+                fin_sender.send((block_hash, received_fin)).unwrap_or_else(|_| {
+                    warn!("Failed to send block to consensus. height={height}");
+                })
             }
             .instrument(debug_span!("consensus_validate_proposal")),
         );
@@ -275,12 +286,20 @@ impl ConsensusContext for PapyrusConsensusContext {
             .expect("Failed to send proposal");
     }
 
-    async fn validators(&self, _height: BlockNumber) -> Vec<ValidatorId> {
+    async fn validators(&self, _height: BlockNumber) -> ValidatorSet {
         self.validators.clone()
     }
 
     fn proposer(&self, _height: BlockNumber, _round: Round) -> ValidatorId {
-        *self.validators.first().expect("there should be at least one validator")
+        *self.validators.keys().next().expect("there should be at least one validator")
+    }
+
+    fn validator_public_key(
+        &self,
+        _height: BlockNumber,
+        validator: ValidatorId,
+    ) -> Option<PublicKey> {
+        self.validator_public_keys.get(&validator).copied()
     }
 
     async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError> {
@@ -308,6 +327,11 @@ impl ConsensusContext for PapyrusConsensusContext {
         Ok(())
     }
 
+    async fn report_evidence(&mut self, evidence: Evidence) {
+        // Slashing is not supported yet, so the evidence is only logged.
+        warn!("Validator {:?} equivocated: {evidence:?}", evidence.offender());
+    }
+
     async fn set_height_and_round(&mut self, _height: BlockNumber, _round: Round) {
         // No-op
     }
@@ -1,7 +1,9 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
+use papyrus_consensus::signing::{ConsensusSigner, PrivateKeySigner};
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::{ConsensusContext, ValidatorId, DEFAULT_VALIDATOR_ID};
 use papyrus_network::network_manager::test_utils::{
@@ -24,6 +26,9 @@ use papyrus_storage::header::HeaderStorageWriter;
 use papyrus_storage::test_utils::get_test_storage;
 use papyrus_test_utils::get_test_block;
 use starknet_api::block::{Block, BlockHash};
+use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::{get_public_key, Signature};
+use starknet_types_core::felt::Felt;
 
 use crate::papyrus_consensus_context::PapyrusConsensusContext;
 
@@ -31,6 +36,7 @@ use crate::papyrus_consensus_context::PapyrusConsensusContext;
 // happen until it should (for example, not creating a block before we have it in storage).
 
 const TEST_CHANNEL_SIZE: usize = 10;
+const PRIVATE_KEY: Felt = Felt::from_hex_unchecked("0x1234567890abcdef");
 
 #[tokio::test]
 async fn build_proposal() {
@@ -62,7 +68,10 @@ async fn validate_proposal_success() {
         });
         validate_sender.try_send(tx_part).unwrap();
     }
-    let fin_part = ProposalPart::Fin(ProposalFin { proposal_content_id: block.header.block_hash });
+    let fin_part = ProposalPart::Fin(ProposalFin {
+        proposal_content_id: block.header.block_hash,
+        signature: Signature::default(),
+    });
     validate_sender.try_send(fin_part).unwrap();
     validate_sender.close_channel();
 
@@ -155,6 +164,12 @@ fn test_setup() -> (
         network_channels.subscriber_channels.broadcast_topic_client,
         outbound_internal_sender,
         4,
+        vec![get_public_key(&PRIVATE_KEY)],
+        vec![1; 4],
+        ConsensusSigner::new(
+            ChainId::Other("SN_TEST".to_string()),
+            Arc::new(PrivateKeySigner::new(PRIVATE_KEY)),
+        ),
         Some(sync_channels.subscriber_channels.broadcast_topic_client),
     );
     (block, papyrus_context, network_channels.mock_network, sync_channels.mock_network)
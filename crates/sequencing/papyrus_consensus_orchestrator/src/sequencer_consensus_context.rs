@@ -12,13 +12,19 @@ use std::time::Duration;
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use futures::{SinkExt, StreamExt};
+use papyrus_consensus::decision_certificate::create_decision_certificate;
+use papyrus_consensus::proposer_selection::ProposerSelector;
+use papyrus_consensus::signing::ConsensusSigner;
 use papyrus_consensus::types::{
+    validators_from_config,
     ConsensusContext,
     ConsensusError,
+    Evidence,
     ProposalContentId,
     Round,
     ValidatorId,
-    DEFAULT_VALIDATOR_ID,
+    ValidatorSet,
+    VotingWeight,
 };
 use papyrus_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
 use papyrus_protobuf::consensus::{
@@ -40,6 +46,7 @@ use starknet_api::block::{
     NonzeroGasPrice,
 };
 use starknet_api::core::ChainId;
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::executable_transaction::Transaction as ExecutableTransaction;
 use starknet_batcher_types::batcher_types::{
     DecisionReachedInput,
@@ -83,7 +90,11 @@ const CHANNEL_SIZE: usize = 100;
 
 pub struct SequencerConsensusContext {
     batcher: Arc<dyn BatcherClient>,
-    validators: Vec<ValidatorId>,
+    validators: ValidatorSet,
+    proposer_selector: Mutex<ProposerSelector>,
+    validator_public_keys: HashMap<ValidatorId, PublicKey>,
+    // Signs the fins of the proposals built by this node.
+    signer: ConsensusSigner,
     // Proposal building/validating returns immediately, leaving the actual processing to a spawned
     // task. The spawned task processes the proposal asynchronously and updates the
     // valid_proposals map upon completion, ensuring consistency across tasks.
@@ -115,16 +126,21 @@ impl SequencerConsensusContext {
         outbound_proposal_sender: mpsc::Sender<(u64, mpsc::Receiver<ProposalPart>)>,
         vote_broadcast_client: BroadcastTopicClient<ConsensusMessage>,
         num_validators: u64,
+        validator_public_keys: Vec<PublicKey>,
+        validator_weights: Vec<VotingWeight>,
+        signer: ConsensusSigner,
         chain_id: ChainId,
     ) -> Self {
+        let (validators, validator_public_keys) =
+            validators_from_config(num_validators, &validator_weights, &validator_public_keys);
         Self {
             batcher,
             outbound_proposal_sender,
             vote_broadcast_client,
-            // TODO(Matan): Set the actual validator IDs (contract addresses).
-            validators: (0..num_validators)
-                .map(|i| ValidatorId::from(DEFAULT_VALIDATOR_ID + i))
-                .collect(),
+            proposer_selector: Mutex::new(ProposerSelector::new(&validators)),
+            validators,
+            validator_public_keys,
+            signer,
             valid_proposals: Arc::new(Mutex::new(HeightToIdToContent::new())),
             proposal_id: 0,
             current_height: None,
@@ -155,6 +171,7 @@ impl ConsensusContext for SequencerConsensusContext {
 
         let batcher = Arc::clone(&self.batcher);
         let valid_proposals = Arc::clone(&self.valid_proposals);
+        let signer = self.signer.clone();
 
         let proposal_id = ProposalId(self.proposal_id);
         self.proposal_id += 1;
@@ -203,12 +220,13 @@ impl ConsensusContext for SequencerConsensusContext {
         tokio::spawn(
             async move {
                 stream_build_proposal(
-                    proposal_init.height,
+                    proposal_init,
                     proposal_id,
                     batcher,
                     valid_proposals,
                     proposal_sender,
                     fin_sender,
+                    signer,
                 )
                 .await;
             }
@@ -265,17 +283,23 @@ impl ConsensusContext for SequencerConsensusContext {
         // TODO(guyn): Stream the TXs to the network.
     }
 
-    async fn validators(&self, _height: BlockNumber) -> Vec<ValidatorId> {
+    async fn validators(&self, _height: BlockNumber) -> ValidatorSet {
         self.validators.clone()
     }
 
     fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId {
-        let height: usize = height.0.try_into().expect("Cannot convert to usize");
-        let round: usize = round.try_into().expect("Cannot convert to usize");
-        *self
-            .validators
-            .get((height + round) % self.validators.len())
-            .expect("There should be at least one validator")
+        self.proposer_selector
+            .lock()
+            .expect("Lock on the proposer selector was poisoned due to a previous panic")
+            .proposer(height, round)
+    }
+
+    fn validator_public_key(
+        &self,
+        _height: BlockNumber,
+        validator: ValidatorId,
+    ) -> Option<PublicKey> {
+        self.validator_public_keys.get(&validator).copied()
     }
 
     async fn broadcast(&mut self, message: ConsensusMessage) -> Result<(), ConsensusError> {
@@ -292,7 +316,8 @@ impl ConsensusContext for SequencerConsensusContext {
         let height = precommits[0].height;
         info!("Finished consensus for height: {height}. Agreed on block: {:#064x}", block.0);
 
-        // TODO(matan): Broadcast the decision to the network.
+        // The certificate is stored with the block, and served to other nodes by the p2p sync.
+        let decision_certificate = create_decision_certificate(block, &precommits);
 
         let proposal_id;
         {
@@ -303,11 +328,19 @@ impl ConsensusContext for SequencerConsensusContext {
             proposal_id = proposals.get(&BlockNumber(height)).unwrap().get(&block).unwrap().1;
             proposals.retain(|&h, _| h > BlockNumber(height));
         }
-        self.batcher.decision_reached(DecisionReachedInput { proposal_id }).await.unwrap();
+        self.batcher
+            .decision_reached(DecisionReachedInput { proposal_id, decision_certificate })
+            .await
+            .unwrap();
 
         Ok(())
     }
 
+    async fn report_evidence(&mut self, evidence: Evidence) {
+        // Slashing is not supported yet, so the evidence is only logged.
+        warn!("Validator {:?} equivocated: {evidence:?}", evidence.offender());
+    }
+
     async fn set_height_and_round(&mut self, height: BlockNumber, round: Round) {
         if self.current_height.map(|h| height > h).unwrap_or(true) {
             self.current_height = Some(height);
@@ -436,13 +469,15 @@ impl SequencerConsensusContext {
 // 4. Store the proposal for re-proposal.
 // 5. Send the commitment to the stream handler (to send fin).
 async fn stream_build_proposal(
-    height: BlockNumber,
+    proposal_init: ProposalInit,
     proposal_id: ProposalId,
     batcher: Arc<dyn BatcherClient>,
     valid_proposals: Arc<Mutex<HeightToIdToContent>>,
     mut proposal_sender: mpsc::Sender<ProposalPart>,
     fin_sender: oneshot::Sender<ProposalContentId>,
+    signer: ConsensusSigner,
 ) {
+    let height = proposal_init.height;
     let mut content = Vec::new();
     loop {
         let response =
@@ -485,9 +520,17 @@ async fn stream_build_proposal(
                     content.len(),
                     height
                 );
-                debug!("Broadcasting proposal fin: {proposal_content_id:?}");
+                let fin = match signer.sign_proposal_fin(&proposal_init, proposal_content_id).await
+                {
+                    Ok(fin) => fin,
+                    Err(e) => {
+                        warn!("Failed to sign proposal fin: {e:?}");
+                        return;
+                    }
+                };
+                debug!("Broadcasting proposal fin: {fin:?}");
                 proposal_sender
-                    .send(ProposalPart::Fin(ProposalFin { proposal_content_id }))
+                    .send(ProposalPart::Fin(fin))
                     .await
                     .expect("Failed to broadcast proposal fin");
                 // Update valid_proposals before sending fin to avoid a race condition
@@ -523,7 +566,7 @@ async fn stream_validate_proposal(
     chain_id: ChainId,
 ) {
     let mut content = Vec::new();
-    let network_block_id = loop {
+    let network_fin = loop {
         let Some(prop_part) = content_receiver.next().await else {
             // TODO(Asmaa): Tell the batcher to abort.
             warn!("Failed to receive proposal content: {proposal_id:?}");
@@ -557,9 +600,10 @@ async fn stream_validate_proposal(
                     status => panic!("Unexpected status: for {proposal_id:?}, {status:?}"),
                 }
             }
-            ProposalPart::Fin(ProposalFin { proposal_content_id: id }) => {
-                // Output this along with the ID from batcher, to compare them.
-                break id;
+            ProposalPart::Fin(fin) => {
+                // Output this along with the ID from batcher, to compare them. The signature is
+                // verified by consensus.
+                break fin;
             }
             _ => panic!("Invalid proposal part: {:?}", prop_part),
         }
@@ -582,20 +626,16 @@ async fn stream_validate_proposal(
         "Finished validating proposal {:?}: network_block_id: {:?}, batcher_block_id = {:?}, \
          num_txs = {:?}, height = {:?}",
         proposal_id,
-        network_block_id,
+        network_fin.proposal_content_id,
         batcher_block_id,
         content.len(),
         height
     );
     // Update valid_proposals before sending fin to avoid a race condition
     // with `get_proposal` being called before `valid_proposals` is updated.
-    // TODO(Matan): Consider validating the ProposalFin signature here.
     let mut valid_proposals = valid_proposals.lock().unwrap();
     valid_proposals.entry(height).or_default().insert(batcher_block_id, (content, proposal_id));
-    if fin_sender
-        .send((batcher_block_id, ProposalFin { proposal_content_id: network_block_id }))
-        .is_err()
-    {
+    if fin_sender.send((batcher_block_id, network_fin)).is_err() {
         // Consensus may exit early (e.g. sync).
         warn!("Failed to send proposal content ids");
     }
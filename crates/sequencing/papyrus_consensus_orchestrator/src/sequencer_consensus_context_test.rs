@@ -5,6 +5,7 @@ use std::vec;
 use futures::channel::mpsc;
 use futures::{FutureExt, SinkExt};
 use lazy_static::lazy_static;
+use papyrus_consensus::signing::{ConsensusSigner, PrivateKeySigner};
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::{ConsensusContext, ValidatorId, DEFAULT_VALIDATOR_ID};
 use papyrus_network::network_manager::test_utils::{
@@ -23,6 +24,7 @@ use papyrus_protobuf::consensus::{
 };
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::{ChainId, Nonce, StateDiffCommitment};
+use starknet_api::crypto::utils::{get_public_key, Signature};
 use starknet_api::executable_transaction::Transaction as ExecutableTransaction;
 use starknet_api::felt;
 use starknet_api::hash::PoseidonHash;
@@ -50,6 +52,7 @@ const CHANNEL_SIZE: usize = 5000;
 const NUM_VALIDATORS: u64 = 4;
 const STATE_DIFF_COMMITMENT: StateDiffCommitment = StateDiffCommitment(PoseidonHash(Felt::ZERO));
 const CHAIN_ID: ChainId = ChainId::Mainnet;
+const PRIVATE_KEY: Felt = Felt::from_hex_unchecked("0x1234567890abcdef");
 
 lazy_static! {
     static ref TX_BATCH: Vec<ExecutableTransaction> = vec![generate_executable_invoke_tx()];
@@ -90,6 +93,9 @@ fn setup(batcher: MockBatcherClient) -> (SequencerConsensusContext, NetworkDepen
         outbound_proposal_stream_sender,
         votes_topic_client,
         NUM_VALIDATORS,
+        vec![get_public_key(&PRIVATE_KEY)],
+        vec![1; usize::try_from(NUM_VALIDATORS).unwrap()],
+        ConsensusSigner::new(CHAIN_ID, Arc::new(PrivateKeySigner::new(PRIVATE_KEY))),
         CHAIN_ID,
     );
 
@@ -192,6 +198,7 @@ async fn validate_proposal_success() {
     content_sender
         .send(ProposalPart::Fin(ProposalFin {
             proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+            signature: Signature::default(),
         }))
         .await
         .unwrap();
@@ -250,6 +257,7 @@ async fn repropose() {
     content_sender
         .send(ProposalPart::Fin(ProposalFin {
             proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+            signature: Signature::default(),
         }))
         .await
         .unwrap();
@@ -322,6 +330,7 @@ async fn proposals_from_different_rounds() {
     });
     let prop_part_fin = ProposalPart::Fin(ProposalFin {
         proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        signature: Signature::default(),
     });
 
     // The proposal from the past round is ignored.
@@ -442,6 +451,7 @@ async fn interrupt_active_proposal() {
     content_sender_1
         .send(ProposalPart::Fin(ProposalFin {
             proposal_content_id: BlockHash(STATE_DIFF_COMMITMENT.0.0),
+            signature: Signature::default(),
         }))
         .await
         .unwrap();
@@ -505,6 +505,61 @@ pub struct BlockInfo {
 )]
 pub struct BlockSignature(pub Signature);
 
+/// The proof that a block was decided by consensus: the precommits of a quorum of validators for
+/// the block in the round it was decided.
+///
+/// All the precommits are for the same height, round and block, so only their voters and
+/// signatures are kept. The signed messages are defined by the consensus protocol.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct DecisionCertificate {
+    pub block_number: BlockNumber,
+    pub round: u32,
+    pub block_hash: BlockHash,
+    pub precommits: Vec<PrecommitSignature>,
+}
+
+impl DecisionCertificate {
+    /// Returns whether the certificate is of the given block, i.e. of its block number and of the
+    /// header field consensus decides on.
+    ///
+    /// A certificate of the block hash covers the whole header. A certificate of the state diff
+    /// commitment, which the sequencer decides on since it is known before the block hash, covers
+    /// only the block number and the state diff commitment of the header. The other fields of the
+    /// header, and its hash, are not bound by it.
+    pub fn is_for_block(&self, header: &BlockHeader, decided_field: DecidedHeaderField) -> bool {
+        if self.block_number != header.block_header_without_hash.block_number {
+            return false;
+        }
+        match decided_field {
+            DecidedHeaderField::BlockHash => self.block_hash == header.block_hash,
+            DecidedHeaderField::StateDiffCommitment => {
+                header.state_diff_commitment.is_some_and(|state_diff_commitment| {
+                    state_diff_commitment.0.0 == self.block_hash.0
+                })
+            }
+        }
+    }
+}
+
+/// The field of a block header that consensus decides on, and thus the value signed by the
+/// precommits of a [`DecisionCertificate`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum DecidedHeaderField {
+    /// The hash of the block.
+    BlockHash,
+    /// The state diff commitment of the block.
+    StateDiffCommitment,
+}
+
+/// The signature of a validator on a precommit of a [`DecisionCertificate`].
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
+)]
+pub struct PrecommitSignature {
+    pub voter: ContractAddress,
+    pub signature: Signature,
+}
+
 /// The error type returned from the block verification functions.
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum BlockVerificationError {
@@ -2,10 +2,19 @@ use serde_json::json;
 use strum::IntoEnumIterator;
 
 use super::{verify_block_signature, StarknetVersion};
-use crate::block::{BlockHash, BlockNumber, BlockSignature};
-use crate::core::{GlobalRoot, SequencerPublicKey};
+use crate::block::{
+    BlockHash,
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockNumber,
+    BlockSignature,
+    DecidedHeaderField,
+    DecisionCertificate,
+};
+use crate::core::{GlobalRoot, SequencerPublicKey, StateDiffCommitment};
 use crate::crypto::utils::{PublicKey, Signature};
 use crate::felt;
+use crate::hash::PoseidonHash;
 
 #[test]
 fn test_block_number_iteration() {
@@ -49,6 +58,48 @@ fn block_signature_verification() {
     );
 }
 
+#[test]
+fn decision_certificate_is_for_block() {
+    let header = BlockHeader {
+        block_hash: BlockHash(felt!("0x1")),
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: BlockNumber(5),
+            ..Default::default()
+        },
+        state_diff_commitment: Some(StateDiffCommitment(PoseidonHash(felt!("0x2")))),
+        ..Default::default()
+    };
+    let certificate = |block_number, block_hash| DecisionCertificate {
+        block_number: BlockNumber(block_number),
+        block_hash: BlockHash(block_hash),
+        ..Default::default()
+    };
+
+    let block_hash = DecidedHeaderField::BlockHash;
+    assert!(certificate(5, felt!("0x1")).is_for_block(&header, block_hash));
+    assert!(!certificate(5, felt!("0x2")).is_for_block(&header, block_hash));
+    assert!(!certificate(6, felt!("0x1")).is_for_block(&header, block_hash));
+
+    let state_diff_commitment = DecidedHeaderField::StateDiffCommitment;
+    assert!(certificate(5, felt!("0x2")).is_for_block(&header, state_diff_commitment));
+    assert!(!certificate(5, felt!("0x1")).is_for_block(&header, state_diff_commitment));
+    assert!(!certificate(6, felt!("0x2")).is_for_block(&header, state_diff_commitment));
+
+    // A header which differs only outside the state diff has another hash.
+    let other_header = BlockHeader {
+        block_hash: BlockHash(felt!("0x3")),
+        block_header_without_hash: BlockHeaderWithoutHash {
+            parent_hash: BlockHash(felt!("0x4")),
+            ..header.block_header_without_hash.clone()
+        },
+        ..header.clone()
+    };
+    // It isn't bound by a certificate of the block hash, but is by one of the state diff
+    // commitment.
+    assert!(!certificate(5, felt!("0x1")).is_for_block(&other_header, block_hash));
+    assert!(certificate(5, felt!("0x2")).is_for_block(&other_header, state_diff_commitment));
+}
+
 #[test]
 fn test_version_serde() {
     for version in StarknetVersion::iter() {
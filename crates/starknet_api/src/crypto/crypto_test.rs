@@ -2,7 +2,13 @@
 
 use starknet_types_core::hash::{Poseidon, StarkHash};
 
-use crate::crypto::utils::{verify_message_hash_signature, PublicKey, Signature};
+use crate::crypto::utils::{
+    get_public_key,
+    sign_message_hash,
+    verify_message_hash_signature,
+    PublicKey,
+    Signature,
+};
 use crate::felt;
 
 #[test]
@@ -24,3 +30,17 @@ fn signature_verification() {
     let result = verify_message_hash_signature(&message_hash, &signature, &public_key).unwrap();
     assert!(result);
 }
+
+#[test]
+fn sign_and_verify() {
+    let private_key = felt!("0x1234567890abcdef");
+    let public_key = get_public_key(&private_key);
+    let message_hash = Poseidon::hash_array(&[felt!("0x1"), felt!("0x2")]);
+
+    let signature = sign_message_hash(&message_hash, &private_key).unwrap();
+    assert_eq!(signature, sign_message_hash(&message_hash, &private_key).unwrap());
+    assert!(verify_message_hash_signature(&message_hash, &signature, &public_key).unwrap());
+
+    let other_message_hash = Poseidon::hash_array(&[felt!("0x2"), felt!("0x1")]);
+    assert!(!verify_message_hash_signature(&other_message_hash, &signature, &public_key).unwrap());
+}
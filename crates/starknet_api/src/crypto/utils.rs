@@ -66,6 +66,34 @@ pub fn verify_message_hash_signature(
     )
 }
 
+/// Signs a message hash with the given private key. The signature is deterministic, as the nonce is
+/// derived from the message hash and the private key (RFC 6979).
+pub fn sign_message_hash(
+    message_hash: &Felt,
+    private_key: &Felt,
+) -> Result<Signature, CryptoError> {
+    // A nonce that yields an invalid signature is replaced by the next nonce in the RFC 6979
+    // sequence, seeded by the number of attempts.
+    let mut seed = None;
+    loop {
+        let k = starknet_crypto::rfc6979_generate_k(message_hash, private_key, seed.as_ref());
+        match starknet_crypto::sign(private_key, message_hash, &k) {
+            Ok(signature) => return Ok(Signature { r: signature.r, s: signature.s }),
+            Err(starknet_crypto::SignError::InvalidMessageHash) => {
+                return Err(CryptoError::InvalidMessageHash(*message_hash));
+            }
+            Err(starknet_crypto::SignError::InvalidK) => {
+                seed = Some(seed.unwrap_or(Felt::ZERO) + Felt::ONE);
+            }
+        }
+    }
+}
+
+/// Returns the public key of the given private key.
+pub fn get_public_key(private_key: &Felt) -> PublicKey {
+    PublicKey(starknet_crypto::get_public_key(private_key))
+}
+
 // Collect elements for applying hash chain.
 pub(crate) struct HashChain {
     elements: Vec<Felt>,
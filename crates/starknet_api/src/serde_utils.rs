@@ -29,7 +29,16 @@ impl<'de, const N: usize, const PREFIXED: bool> Deserialize<'de> for BytesAsHex<
             type Value = BytesAsHex<N, PREFIXED>;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("a byte array")
+                formatter.write_str("a byte array or a hex string")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                bytes_from_hex_str::<N, PREFIXED>(value)
+                    .map_err(serde::de::Error::custom)
+                    .map(BytesAsHex)
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -47,10 +56,9 @@ impl<'de, const N: usize, const PREFIXED: bool> Deserialize<'de> for BytesAsHex<
         }
 
         if deserializer.is_human_readable() {
-            let s = String::deserialize(deserializer)?;
-            bytes_from_hex_str::<N, PREFIXED>(s.as_str())
-                .map_err(serde::de::Error::custom)
-                .map(BytesAsHex)
+            // Serde buffers the content of internally tagged enums in a deserializer that is
+            // always human readable, so the byte array of a binary format may show up here too.
+            deserializer.deserialize_any(ByteArrayVisitor)
         } else {
             deserializer.deserialize_tuple(N, ByteArrayVisitor)
         }
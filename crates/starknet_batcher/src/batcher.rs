@@ -1,14 +1,22 @@
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use async_trait::async_trait;
 use blockifier::state::global_cache::GlobalContractCache;
 #[cfg(test)]
 use mockall::automock;
+use papyrus_storage::body::{BodyStorageReader, BodyStorageWriter};
+use papyrus_storage::class::{ClassStorageReader, ClassStorageWriter};
+use papyrus_storage::compiled_class::CasmStorageWriter;
+use papyrus_storage::header::{HeaderStorageReader, HeaderStorageWriter};
+use papyrus_storage::patricia::{PatriciaStorageReader, StateTrieRoots};
 use papyrus_storage::state::{StateStorageReader, StateStorageWriter};
-use starknet_api::block::BlockNumber;
-use starknet_api::core::{ContractAddress, Nonce};
+use papyrus_storage::StorageError;
+use serde_json::json;
+use starknet_api::block::{BlockBody, BlockHash, BlockNumber, DecisionCertificate};
+use starknet_api::core::{ClassHash, ContractAddress, GlobalRoot, Nonce};
 use starknet_api::executable_transaction::Transaction;
-use starknet_api::state::ThinStateDiff;
+use starknet_api::state::{SierraContractClass, ThinStateDiff};
 use starknet_api::transaction::TransactionHash;
 use starknet_batcher_types::batcher_types::{
     BatcherResult,
@@ -29,12 +37,15 @@ use starknet_batcher_types::batcher_types::{
     ValidateBlockInput,
 };
 use starknet_batcher_types::errors::BatcherError;
+use starknet_committer::forest::mdbx_forest::ForestUpdate;
 use starknet_l1_provider_types::SharedL1ProviderClient;
 use starknet_mempool_types::communication::SharedMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
+use starknet_patricia::storage::storage_trait::{StorageKey, StorageValue};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::component_health::{ComponentHealth, HealthReport, HealthStatus};
 use starknet_state_sync_types::state_sync_types::SyncBlock;
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::block_builder::{
     BlockBuilderExecutionParams,
@@ -43,7 +54,9 @@ use crate::block_builder::{
     BlockMetadata,
 };
 use crate::config::BatcherConfig;
+use crate::executed_block::ExecutedBlock;
 use crate::proposal_manager::{GenerateProposalError, ProposalManager, ProposalManagerTrait};
+use crate::state_committer::StateCommitter;
 use crate::transaction_provider::{ProposeTransactionProvider, ValidateTransactionProvider};
 use crate::utils::{
     deadline_as_instant,
@@ -63,7 +76,10 @@ pub struct Batcher {
     pub l1_provider_client: SharedL1ProviderClient,
     pub mempool_client: SharedMempoolClient,
 
+    state_committer: StateCommitter,
     active_height: Option<BlockNumber>,
+    // The latest height consensus asked to start, shared with the health reporter.
+    requested_height: Arc<Mutex<Option<BlockNumber>>>,
     proposal_manager: Box<dyn ProposalManagerTrait>,
 
     block_builder_factory: Box<dyn BlockBuilderFactoryTrait>,
@@ -83,11 +99,13 @@ impl Batcher {
     ) -> Self {
         Self {
             config: config.clone(),
+            state_committer: StateCommitter::new(storage_reader.clone()),
             storage_reader,
             storage_writer,
             l1_provider_client,
             mempool_client,
             active_height: None,
+            requested_height: Arc::new(Mutex::new(None)),
             block_builder_factory,
             proposal_manager,
             propose_tx_streams: HashMap::new(),
@@ -95,11 +113,22 @@ impl Batcher {
         }
     }
 
+    /// Returns a handle reporting the state of the batcher's storage, which outlives moving the
+    /// batcher into its server.
+    pub fn health_reporter(&self) -> BatcherHealth {
+        BatcherHealth {
+            storage_reader: self.storage_reader.clone(),
+            requested_height: self.requested_height.clone(),
+        }
+    }
+
     #[instrument(skip(self), err)]
     pub async fn start_height(&mut self, input: StartHeightInput) -> BatcherResult<()> {
         if self.active_height == Some(input.height) {
             return Err(BatcherError::HeightInProgress);
         }
+        *self.requested_height.lock().expect("Requested height lock should not be poisoned.") =
+            Some(input.height);
 
         let storage_height = self.get_height_from_storage()?;
         if storage_height < input.height {
@@ -360,12 +389,28 @@ impl Batcher {
             self.active_height = None;
         }
 
-        let SyncBlock { state_diff, transaction_hashes, .. } = sync_block;
+        let SyncBlock {
+            state_diff,
+            transaction_hashes,
+            block_header,
+            transactions,
+            transaction_outputs,
+            classes,
+            deprecated_classes,
+            ..
+        } = sync_block;
         let address_to_nonce = state_diff.nonces.iter().map(|(k, v)| (*k, *v)).collect();
-        let tx_hashes = transaction_hashes.into_iter().collect();
+        let tx_hashes = transaction_hashes.iter().copied().collect();
+        let block = ExecutedBlock {
+            header: block_header,
+            body: BlockBody { transactions, transaction_outputs, transaction_hashes },
+            classes,
+            deprecated_classes,
+            ..Default::default()
+        };
 
         // TODO(Arni): Assert the input `sync_block` corresponds to this `height`.
-        self.commit_proposal_and_block(state_diff, address_to_nonce, tx_hashes).await
+        self.commit_proposal_and_block(state_diff, block, None, address_to_nonce, tx_hashes).await
     }
 
     #[instrument(skip(self), err)]
@@ -373,23 +418,35 @@ impl Batcher {
         &mut self,
         input: DecisionReachedInput,
     ) -> BatcherResult<DecisionReachedResponse> {
-        let proposal_id = input.proposal_id;
+        let DecisionReachedInput { proposal_id, decision_certificate } = input;
         let proposal_output = self
             .proposal_manager
             .take_proposal_result(proposal_id)
             .await
             .ok_or(BatcherError::ExecutedProposalNotFound { proposal_id })?
             .map_err(|_| BatcherError::InternalError)?;
-        let ProposalOutput { state_diff, nonces: address_to_nonce, tx_hashes, .. } =
+        let ProposalOutput { state_diff, nonces: address_to_nonce, tx_hashes, block, .. } =
             proposal_output;
 
-        self.commit_proposal_and_block(state_diff.clone(), address_to_nonce, tx_hashes).await?;
+        self.commit_proposal_and_block(
+            state_diff.clone(),
+            block,
+            Some(decision_certificate),
+            address_to_nonce,
+            tx_hashes,
+        )
+        .await?;
         Ok(DecisionReachedResponse { state_diff })
     }
 
+    // Commits the block to storage and notifies the mempool. Blocks built by the batcher are
+    // finalized and stored with the certificate of their decision, while blocks received from state
+    // sync are already final, and are stored as they are.
     async fn commit_proposal_and_block(
         &mut self,
         state_diff: ThinStateDiff,
+        mut block: ExecutedBlock,
+        decision_certificate: Option<DecisionCertificate>,
         address_to_nonce: HashMap<ContractAddress, Nonce>,
         tx_hashes: HashSet<TransactionHash>,
     ) -> BatcherResult<()> {
@@ -397,10 +454,22 @@ impl Batcher {
         let height = self.get_height_from_storage()?;
         info!("Committing block at height {} and notifying mempool of the block.", height);
         trace!("Transactions: {:#?}, State diff: {:#?}.", tx_hashes, state_diff);
-        self.storage_writer.commit_proposal(height, state_diff).map_err(|err| {
-            error!("Failed to commit proposal to storage: {}", err);
-            BatcherError::InternalError
-        })?;
+        let (state_root, forest_update) = self.commit_to_state_root(height, &state_diff).await?;
+        if decision_certificate.is_some() {
+            block.classes = self.get_sierra_classes(height, &block).await;
+            let parent_hash = self.get_parent_hash(height)?;
+            let block_hash = block.finalize(parent_hash, state_root).map_err(|err| {
+                error!("Failed to calculate the block hash: {}", err);
+                BatcherError::InternalError
+            })?;
+            info!("Block {} has hash {} and state root {}.", height, block_hash, state_root);
+        }
+        self.storage_writer
+            .commit_block(height, state_diff, forest_update, block, decision_certificate)
+            .map_err(|err| {
+                error!("Failed to commit proposal to storage: {}", err);
+                BatcherError::InternalError
+            })?;
         if let Err(mempool_err) =
             self.mempool_client.commit_block(CommitBlockArgs { address_to_nonce, tx_hashes }).await
         {
@@ -410,6 +479,70 @@ impl Batcher {
         Ok(())
     }
 
+    // Applies the state diff to the Patricia forest and returns the new state root, and the update
+    // of the forest to store with the block. The forest is updated with every block, including
+    // blocks from state sync, to keep the state roots of the next blocks correct.
+    async fn commit_to_state_root(
+        &self,
+        height: BlockNumber,
+        state_diff: &ThinStateDiff,
+    ) -> BatcherResult<(GlobalRoot, ForestUpdate)> {
+        self.state_committer.commit(height, state_diff).await.map_err(|err| {
+            error!("Failed to commit the state diff to the Patricia forest: {}", err);
+            BatcherError::InternalError
+        })
+    }
+
+    // The Sierra classes are not needed for execution, so they are not part of the executed
+    // transactions; the mempool holds them next to the declare transactions until the block is
+    // committed to it. Declare transactions of other nodes are unknown to the mempool.
+    async fn get_sierra_classes(
+        &self,
+        height: BlockNumber,
+        block: &ExecutedBlock,
+    ) -> Vec<(ClassHash, SierraContractClass)> {
+        let declare_tx_hashes = block.declare_tx_hashes();
+        if declare_tx_hashes.is_empty() {
+            return Vec::new();
+        }
+        let n_declare_txs = declare_tx_hashes.len();
+        match self.mempool_client.get_sierra_classes(declare_tx_hashes).await {
+            Ok(classes) => {
+                if classes.len() < n_declare_txs {
+                    warn!(
+                        "Storing block {} without {} of its declared Sierra classes, which are \
+                         unknown to the mempool.",
+                        height,
+                        n_declare_txs - classes.len()
+                    );
+                }
+                classes
+            }
+            Err(err) => {
+                error!(
+                    "Failed to get the Sierra classes of block {} from the mempool: {}",
+                    height, err
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    fn get_parent_hash(&self, height: BlockNumber) -> BatcherResult<BlockHash> {
+        // The parent hash of the genesis block is zero.
+        let Some(parent_height) = height.prev() else {
+            return Ok(BlockHash::default());
+        };
+        let parent_hash = self.storage_reader.block_hash(parent_height).map_err(|err| {
+            error!("Failed to read the hash of block {} from storage: {}", parent_height, err);
+            BatcherError::InternalError
+        })?;
+        parent_hash.ok_or_else(|| {
+            error!("The hash of block {} is not stored.", parent_height);
+            BatcherError::InternalError
+        })
+    }
+
     async fn is_active(&self, proposal_id: ProposalId) -> bool {
         self.proposal_manager.get_active_proposal().await == Some(proposal_id)
     }
@@ -459,35 +592,130 @@ pub fn create_batcher(
     )
 }
 
+/// Reports the height the batcher's storage is synced to. The batcher is degraded while its storage
+/// is behind the height consensus asked it to start, and unhealthy if its storage can't be read.
+#[derive(Clone)]
+pub struct BatcherHealth {
+    storage_reader: Arc<dyn BatcherStorageReaderTrait>,
+    requested_height: Arc<Mutex<Option<BlockNumber>>>,
+}
+
+#[async_trait]
+impl ComponentHealth for BatcherHealth {
+    async fn health(&self) -> HealthReport {
+        let height = match self.storage_reader.height() {
+            Ok(height) => height,
+            Err(err) => return HealthReport::unhealthy(err),
+        };
+        let requested_height =
+            *self.requested_height.lock().expect("Requested height lock should not be poisoned.");
+        let storage_synced = requested_height.map_or(true, |requested| height >= requested);
+        let status = if storage_synced { HealthStatus::Healthy } else { HealthStatus::Degraded };
+        HealthReport::new(
+            status,
+            json!({
+                "height": height,
+                "requested_height": requested_height,
+                "storage_synced": storage_synced,
+            }),
+        )
+    }
+}
+
 #[cfg_attr(test, automock)]
 pub trait BatcherStorageReaderTrait: Send + Sync {
     /// Returns the next height that the batcher should work on.
     fn height(&self) -> papyrus_storage::StorageResult<BlockNumber>;
+
+    /// Returns the hash of the given block, if its header is stored.
+    fn block_hash(&self, height: BlockNumber) -> papyrus_storage::StorageResult<Option<BlockHash>>;
+
+    /// Returns the roots of the state tries after the given block, if they are stored.
+    fn state_trie_roots(
+        &self,
+        height: BlockNumber,
+    ) -> papyrus_storage::StorageResult<Option<StateTrieRoots>>;
+
+    /// Returns the Patricia nodes stored under the given keys, read from the same snapshot.
+    fn patricia_nodes(
+        &self,
+        keys: &[StorageKey],
+    ) -> papyrus_storage::StorageResult<Vec<Option<StorageValue>>>;
 }
 
 impl BatcherStorageReaderTrait for papyrus_storage::StorageReader {
     fn height(&self) -> papyrus_storage::StorageResult<BlockNumber> {
         self.begin_ro_txn()?.get_state_marker()
     }
+
+    fn block_hash(&self, height: BlockNumber) -> papyrus_storage::StorageResult<Option<BlockHash>> {
+        Ok(self.begin_ro_txn()?.get_block_header(height)?.map(|header| header.block_hash))
+    }
+
+    fn state_trie_roots(
+        &self,
+        height: BlockNumber,
+    ) -> papyrus_storage::StorageResult<Option<StateTrieRoots>> {
+        self.begin_ro_txn()?.get_state_trie_roots(height)
+    }
+
+    fn patricia_nodes(
+        &self,
+        keys: &[StorageKey],
+    ) -> papyrus_storage::StorageResult<Vec<Option<StorageValue>>> {
+        let txn = self.begin_ro_txn()?;
+        keys.iter().map(|key| Ok(txn.get_patricia_node(&key.0)?.map(StorageValue))).collect()
+    }
 }
 
 #[cfg_attr(test, automock)]
 pub trait BatcherStorageWriterTrait: Send + Sync {
-    fn commit_proposal(
+    /// Atomically writes a block: its header, body, state diff and declared classes, the update of
+    /// the Patricia forest by it, and, for blocks built by the batcher, the certificate of their
+    /// decision. Fails if a part of a previous block is missing from storage.
+    fn commit_block(
         &mut self,
         height: BlockNumber,
         state_diff: ThinStateDiff,
+        forest_update: ForestUpdate,
+        block: ExecutedBlock,
+        decision_certificate: Option<DecisionCertificate>,
     ) -> papyrus_storage::StorageResult<()>;
 }
 
 impl BatcherStorageWriterTrait for papyrus_storage::StorageWriter {
-    fn commit_proposal(
+    fn commit_block(
         &mut self,
         height: BlockNumber,
         state_diff: ThinStateDiff,
+        forest_update: ForestUpdate,
+        block: ExecutedBlock,
+        decision_certificate: Option<DecisionCertificate>,
     ) -> papyrus_storage::StorageResult<()> {
-        // TODO: write casms.
-        self.begin_rw_txn()?.append_state_diff(height, state_diff)?.commit()
+        let ExecutedBlock { header, body, classes, deprecated_classes, casms, .. } = block;
+        let mut txn = self.begin_rw_txn()?;
+        // The block is stored in full, so all of its parts must be appended at its height.
+        for marker in [txn.get_header_marker()?, txn.get_body_marker()?, txn.get_class_marker()?] {
+            if marker != height {
+                return Err(StorageError::MarkerMismatch { expected: marker, found: height });
+            }
+        }
+        let classes: Vec<_> =
+            classes.iter().map(|(class_hash, class)| (*class_hash, class)).collect();
+        let deprecated_classes: Vec<_> =
+            deprecated_classes.iter().map(|(class_hash, class)| (*class_hash, class)).collect();
+        txn = txn.append_header(height, &header)?;
+        if let Some(decision_certificate) = &decision_certificate {
+            txn = txn.append_decision_certificate(height, decision_certificate)?;
+        }
+        txn = txn
+            .append_body(height, body)?
+            .append_state_diff(height, state_diff)?
+            .append_classes(height, &classes, &deprecated_classes)?;
+        for (class_hash, casm) in &casms {
+            txn = txn.append_casm(class_hash, casm)?;
+        }
+        forest_update.write(txn, height)?.commit()
     }
 }
 
@@ -11,14 +11,33 @@ use futures::FutureExt;
 use indexmap::indexmap;
 use mockall::automock;
 use mockall::predicate::{always, eq};
+use papyrus_storage::body::BodyStorageReader;
+use papyrus_storage::class::ClassStorageReader;
+use papyrus_storage::header::HeaderStorageReader;
+use papyrus_storage::patricia::{PatriciaStorageReader, StateTrieRoots};
+use papyrus_storage::state::StateStorageReader;
+use papyrus_storage::test_utils::get_test_storage;
 use rstest::rstest;
-use starknet_api::block::{BlockInfo, BlockNumber};
-use starknet_api::core::{ContractAddress, Nonce, StateDiffCommitment};
+use starknet_api::block::{
+    BlockHash,
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockInfo,
+    BlockNumber,
+    DecisionCertificate,
+};
+use starknet_api::core::{ContractAddress, GlobalRoot, Nonce, StateDiffCommitment};
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::hash::PoseidonHash;
-use starknet_api::state::ThinStateDiff;
-use starknet_api::transaction::TransactionHash;
-use starknet_api::{contract_address, felt, nonce, tx_hash};
+use starknet_api::state::{SierraContractClass, ThinStateDiff};
+use starknet_api::test_utils::declare::declare_tx;
+use starknet_api::transaction::{
+    DeclareTransactionOutput,
+    Transaction as StarknetApiTransaction,
+    TransactionHash,
+    TransactionOutput,
+};
+use starknet_api::{class_hash, contract_address, declare_tx_args, felt, nonce, tx_hash};
 use starknet_batcher_types::batcher_types::{
     DecisionReachedInput,
     GetHeightResponse,
@@ -39,6 +58,7 @@ use starknet_batcher_types::errors::BatcherError;
 use starknet_l1_provider_types::MockL1ProviderClient;
 use starknet_mempool_types::communication::MockMempoolClient;
 use starknet_mempool_types::mempool_types::CommitBlockArgs;
+use starknet_sequencer_infra::component_health::{ComponentHealth, HealthStatus};
 use starknet_state_sync_types::state_sync_types::SyncBlock;
 use tokio::sync::Mutex;
 
@@ -52,6 +72,7 @@ use crate::block_builder::{
     MockBlockBuilderTrait,
 };
 use crate::config::BatcherConfig;
+use crate::executed_block::ExecutedBlock;
 use crate::proposal_manager::{GenerateProposalError, ProposalManagerTrait};
 use crate::test_utils::test_txs;
 use crate::transaction_provider::NextTxs;
@@ -76,6 +97,50 @@ fn proposal_output() -> ProposalOutput {
     ProposalOutput { commitment: proposal_commitment(), ..Default::default() }
 }
 
+fn parent_hash() -> BlockHash {
+    BlockHash(felt!("0x1234"))
+}
+
+fn test_executed_block() -> ExecutedBlock {
+    let header = BlockHeader {
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: INITIAL_HEIGHT,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    ExecutedBlock { header, ..Default::default() }
+}
+
+fn test_decision_certificate() -> DecisionCertificate {
+    DecisionCertificate {
+        block_number: INITIAL_HEIGHT,
+        block_hash: BlockHash(felt!("0x5678")),
+        ..Default::default()
+    }
+}
+
+fn test_sync_block(height: BlockNumber) -> SyncBlock {
+    let block_header = BlockHeader {
+        block_hash: BlockHash(felt!("0x1234")),
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: height,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    SyncBlock {
+        block_number: height,
+        state_diff: ThinStateDiff::default(),
+        transaction_hashes: Vec::new(),
+        block_header,
+        transactions: Vec::new(),
+        transaction_outputs: Vec::new(),
+        classes: Vec::new(),
+        deprecated_classes: Vec::new(),
+    }
+}
+
 fn deadline() -> chrono::DateTime<Utc> {
     chrono::Utc::now() + BLOCK_GENERATION_TIMEOUT
 }
@@ -115,6 +180,12 @@ impl Default for MockDependencies {
     fn default() -> Self {
         let mut storage_reader = MockBatcherStorageReaderTrait::new();
         storage_reader.expect_height().returning(|| Ok(INITIAL_HEIGHT));
+        // The state and the forest before the initial height are empty.
+        storage_reader
+            .expect_state_trie_roots()
+            .with(eq(INITIAL_HEIGHT.prev().unwrap()))
+            .returning(|_| Ok(Some(StateTrieRoots::default())));
+        storage_reader.expect_patricia_nodes().returning(|keys| Ok(vec![None; keys.len()]));
         Self {
             storage_reader,
             storage_writer: MockBatcherStorageWriterTrait::new(),
@@ -277,6 +348,39 @@ async fn duplicate_start_height() {
     assert_eq!(batcher.start_height(initial_height).await, Err(BatcherError::HeightInProgress));
 }
 
+#[tokio::test]
+async fn health_reports_storage_height() {
+    let batcher = create_batcher(MockDependencies::default());
+
+    let report = batcher.health_reporter().health().await;
+
+    assert_eq!(report.status, HealthStatus::Healthy);
+    assert_eq!(
+        report.details,
+        serde_json::json!({
+            "height": INITIAL_HEIGHT,
+            "requested_height": null,
+            "storage_synced": true,
+        })
+    );
+}
+
+#[tokio::test]
+async fn health_reports_storage_not_synced() {
+    let mut batcher = create_batcher(MockDependencies::default());
+    let requested_height = INITIAL_HEIGHT.unchecked_next();
+
+    assert_matches!(
+        batcher.start_height(StartHeightInput { height: requested_height }).await,
+        Err(BatcherError::StorageNotSynced { .. })
+    );
+    let report = batcher.health_reporter().health().await;
+
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.details["requested_height"], serde_json::json!(requested_height));
+    assert_eq!(report.details["storage_synced"], false);
+}
+
 #[rstest]
 #[tokio::test]
 async fn no_active_height() {
@@ -487,12 +591,19 @@ async fn get_content_from_unknown_proposal() {
 async fn add_sync_block() {
     let mut mock_dependencies = MockDependencies::default();
 
+    // Blocks from state sync are stored as they are, without a decision certificate.
     mock_dependencies
         .storage_writer
-        .expect_commit_proposal()
+        .expect_commit_block()
         .times(1)
-        .with(eq(INITIAL_HEIGHT), eq(test_state_diff()))
-        .returning(|_, _| Ok(()));
+        .withf(|height, state_diff, forest_update, block, decision_certificate| {
+            *height == INITIAL_HEIGHT
+                && *state_diff == test_state_diff()
+                && forest_update.roots != StateTrieRoots::default()
+                && block.header == test_sync_block(INITIAL_HEIGHT).block_header
+                && decision_certificate.is_none()
+        })
+        .returning(|_, _, _, _, _| Ok(()));
 
     mock_dependencies
         .mempool_client
@@ -507,9 +618,9 @@ async fn add_sync_block() {
     let mut batcher = create_batcher(mock_dependencies);
 
     let sync_block = SyncBlock {
-        block_number: INITIAL_HEIGHT,
         state_diff: test_state_diff(),
         transaction_hashes: test_tx_hashes().into_iter().collect(),
+        ..test_sync_block(INITIAL_HEIGHT)
     };
     batcher.add_sync_block(sync_block).await.unwrap();
 }
@@ -531,6 +642,7 @@ async fn decision_reached() {
                     commitment: ProposalCommitment::default(),
                     tx_hashes: test_tx_hashes(),
                     nonces: test_contract_nonces(),
+                    block: test_executed_block(),
                 }))
             }
             .boxed()
@@ -546,20 +658,173 @@ async fn decision_reached() {
         }))
         .returning(|_| Ok(()));
 
+    mock_dependencies
+        .storage_reader
+        .expect_block_hash()
+        .times(1)
+        .with(eq(INITIAL_HEIGHT.prev().unwrap()))
+        .returning(|_| Ok(Some(parent_hash())));
+
     mock_dependencies
         .storage_writer
-        .expect_commit_proposal()
+        .expect_commit_block()
         .times(1)
-        .with(eq(INITIAL_HEIGHT), eq(test_state_diff()))
-        .returning(|_, _| Ok(()));
+        .withf(|height, state_diff, forest_update, block, decision_certificate| {
+            let header = &block.header;
+            *height == INITIAL_HEIGHT
+                && *state_diff == test_state_diff()
+                && forest_update.roots != StateTrieRoots::default()
+                && header.block_header_without_hash.parent_hash == parent_hash()
+                && header.block_header_without_hash.state_root != GlobalRoot::default()
+                && header.block_hash != BlockHash::default()
+                && *decision_certificate == Some(test_decision_certificate())
+        })
+        .returning(|_, _, _, _, _| Ok(()));
 
     let mut batcher = create_batcher(mock_dependencies);
 
-    let response =
-        batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+    let response = batcher
+        .decision_reached(DecisionReachedInput {
+            proposal_id: PROPOSAL_ID,
+            decision_certificate: test_decision_certificate(),
+        })
+        .await
+        .unwrap();
     assert_eq!(response.state_diff, test_state_diff());
 }
 
+#[tokio::test]
+async fn decision_reached_commits_consecutive_blocks_to_storage() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    let class_hash = class_hash!("0x10");
+    let declare_tx_hash = tx_hash!(0x20);
+    let block_at = move |height: BlockNumber| {
+        let mut block = test_executed_block();
+        block.header.block_header_without_hash.block_number = height;
+        // The certificates decide on the state diff commitment of the block.
+        block.header.state_diff_commitment =
+            Some(StateDiffCommitment(PoseidonHash(test_decision_certificate().block_hash.0)));
+        if height == BlockNumber(1) {
+            // The Sierra class of the declare transaction is held by the mempool.
+            let declare_tx = declare_tx(declare_tx_args!(class_hash, tx_hash: declare_tx_hash));
+            block.body.transactions.push(StarknetApiTransaction::Declare(declare_tx));
+            block
+                .body
+                .transaction_outputs
+                .push(TransactionOutput::Declare(DeclareTransactionOutput::default()));
+            block.body.transaction_hashes.push(declare_tx_hash);
+        }
+        block
+    };
+
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies.proposal_manager.expect_wrap_take_proposal_result().times(2).returning(
+        move |proposal_id| {
+            let block = block_at(BlockNumber(proposal_id.0));
+            async move { Some(Ok(ProposalOutput { block, ..Default::default() })) }.boxed()
+        },
+    );
+    mock_dependencies
+        .mempool_client
+        .expect_get_sierra_classes()
+        .times(1)
+        .with(eq(vec![declare_tx_hash]))
+        .returning(move |_| Ok(vec![(class_hash, SierraContractClass::default())]));
+    mock_dependencies.mempool_client.expect_commit_block().times(2).returning(|_| Ok(()));
+    let mut batcher = Batcher::new(
+        BatcherConfig::default(),
+        Arc::new(storage_reader.clone()),
+        Box::new(storage_writer),
+        Arc::new(mock_dependencies.l1_provider_client),
+        Arc::new(mock_dependencies.mempool_client),
+        Box::new(mock_dependencies.block_builder_factory),
+        Box::new(mock_dependencies.proposal_manager),
+    );
+
+    for height in [BlockNumber(0), BlockNumber(1)] {
+        let decision_certificate =
+            DecisionCertificate { block_number: height, ..test_decision_certificate() };
+        batcher
+            .decision_reached(DecisionReachedInput {
+                proposal_id: ProposalId(height.0),
+                decision_certificate,
+            })
+            .await
+            .unwrap();
+    }
+
+    let txn = storage_reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_body_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_class_marker().unwrap(), BlockNumber(2));
+    let first_header = txn.get_block_header(BlockNumber(0)).unwrap().unwrap();
+    let second_header = txn.get_block_header(BlockNumber(1)).unwrap().unwrap();
+    assert_ne!(first_header.block_hash, BlockHash::default());
+    assert_ne!(second_header.block_hash, first_header.block_hash);
+    assert_eq!(second_header.block_header_without_hash.parent_hash, first_header.block_hash);
+    assert_eq!(txn.get_class(&class_hash).unwrap(), Some(SierraContractClass::default()));
+    // The forest is stored with the blocks.
+    assert!(txn.get_state_trie_roots(BlockNumber(1)).unwrap().is_some());
+    assert_eq!(
+        txn.get_decision_certificate(BlockNumber(1)).unwrap().map(|cert| cert.block_number),
+        Some(BlockNumber(1))
+    );
+}
+
+#[tokio::test]
+async fn decision_reached_after_add_sync_block() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    let sync_block = test_sync_block(BlockNumber(0));
+    let synced_block_hash = sync_block.block_header.block_hash;
+
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies.proposal_manager.expect_wrap_take_proposal_result().times(1).returning(
+        |_| {
+            let mut block = test_executed_block();
+            block.header.block_header_without_hash.block_number = BlockNumber(1);
+            // The certificate decides on the state diff commitment of the block.
+            block.header.state_diff_commitment =
+                Some(StateDiffCommitment(PoseidonHash(test_decision_certificate().block_hash.0)));
+            async move { Some(Ok(ProposalOutput { block, ..Default::default() })) }.boxed()
+        },
+    );
+    mock_dependencies.mempool_client.expect_commit_block().times(2).returning(|_| Ok(()));
+    let mut batcher = Batcher::new(
+        BatcherConfig::default(),
+        Arc::new(storage_reader.clone()),
+        Box::new(storage_writer),
+        Arc::new(mock_dependencies.l1_provider_client),
+        Arc::new(mock_dependencies.mempool_client),
+        Box::new(mock_dependencies.block_builder_factory),
+        Box::new(mock_dependencies.proposal_manager),
+    );
+
+    batcher.add_sync_block(sync_block).await.unwrap();
+    batcher
+        .decision_reached(DecisionReachedInput {
+            proposal_id: PROPOSAL_ID,
+            decision_certificate: DecisionCertificate {
+                block_number: BlockNumber(1),
+                ..test_decision_certificate()
+            },
+        })
+        .await
+        .unwrap();
+
+    let txn = storage_reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_body_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_class_marker().unwrap(), BlockNumber(2));
+    let synced_header = txn.get_block_header(BlockNumber(0)).unwrap().unwrap();
+    let decided_header = txn.get_block_header(BlockNumber(1)).unwrap().unwrap();
+    assert_eq!(synced_header.block_hash, synced_block_hash);
+    assert_eq!(decided_header.block_header_without_hash.parent_hash, synced_block_hash);
+    assert_eq!(txn.get_decision_certificate(BlockNumber(0)).unwrap(), None);
+    assert!(txn.get_decision_certificate(BlockNumber(1)).unwrap().is_some());
+}
+
 #[rstest]
 #[tokio::test]
 async fn decision_reached_no_executed_proposal() {
@@ -573,8 +838,12 @@ async fn decision_reached_no_executed_proposal() {
         .return_once(|_| async move { None }.boxed());
 
     let mut batcher = create_batcher(MockDependencies { proposal_manager, ..Default::default() });
-    let decision_reached_result =
-        batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await;
+    let decision_reached_result = batcher
+        .decision_reached(DecisionReachedInput {
+            proposal_id: PROPOSAL_ID,
+            decision_certificate: test_decision_certificate(),
+        })
+        .await;
     assert_eq!(decision_reached_result, Err(expected_error));
 }
 
@@ -65,6 +65,9 @@ pub enum FailOnErrorCause {
 #[cfg_attr(test, derive(Clone))]
 #[derive(Debug, PartialEq)]
 pub struct BlockExecutionArtifacts {
+    pub block_info: BlockInfo,
+    // The successfully executed transactions, in execution order, and their execution infos.
+    pub executed_txs: Vec<Transaction>,
     pub execution_infos: IndexMap<TransactionHash, TransactionExecutionInfo>,
     pub commitment_state_diff: CommitmentStateDiff,
     pub visited_segments_mapping: VisitedSegmentsMapping,
@@ -88,6 +91,7 @@ pub struct BlockBuilderExecutionParams {
 pub struct BlockBuilder {
     // TODO(Yael 14/10/2024): make the executor thread safe and delete this mutex.
     executor: Box<dyn TransactionExecutorTrait>,
+    block_info: BlockInfo,
     tx_provider: Box<dyn TransactionProvider>,
     output_content_sender: Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
     abort_signal_receiver: tokio::sync::oneshot::Receiver<()>,
@@ -100,6 +104,7 @@ pub struct BlockBuilder {
 impl BlockBuilder {
     pub fn new(
         executor: Box<dyn TransactionExecutorTrait>,
+        block_info: BlockInfo,
         tx_provider: Box<dyn TransactionProvider>,
         output_content_sender: Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
         abort_signal_receiver: tokio::sync::oneshot::Receiver<()>,
@@ -108,6 +113,7 @@ impl BlockBuilder {
     ) -> Self {
         Self {
             executor,
+            block_info,
             tx_provider,
             output_content_sender,
             abort_signal_receiver,
@@ -121,6 +127,7 @@ impl BlockBuilder {
 impl BlockBuilderTrait for BlockBuilder {
     async fn build_block(&mut self) -> BlockBuilderResult<BlockExecutionArtifacts> {
         let mut block_is_full = false;
+        let mut executed_txs = Vec::new();
         let mut execution_infos = IndexMap::new();
         // TODO(yael 6/10/2024): delete the timeout condition once the executor has a timeout
         while !block_is_full {
@@ -158,6 +165,7 @@ impl BlockBuilderTrait for BlockBuilder {
             block_is_full = collect_execution_results_and_stream_txs(
                 next_tx_chunk,
                 results,
+                &mut executed_txs,
                 &mut execution_infos,
                 &self.output_content_sender,
                 self.execution_params.fail_on_err,
@@ -167,6 +175,8 @@ impl BlockBuilderTrait for BlockBuilder {
         let (commitment_state_diff, visited_segments_mapping, bouncer_weights) =
             self.executor.close_block()?;
         Ok(BlockExecutionArtifacts {
+            block_info: self.block_info.clone(),
+            executed_txs,
             execution_infos,
             commitment_state_diff,
             visited_segments_mapping,
@@ -179,6 +189,7 @@ impl BlockBuilderTrait for BlockBuilder {
 async fn collect_execution_results_and_stream_txs(
     tx_chunk: Vec<Transaction>,
     results: Vec<TransactionExecutorResult<TransactionExecutionInfo>>,
+    executed_txs: &mut Vec<Transaction>,
     execution_infos: &mut IndexMap<TransactionHash, TransactionExecutionInfo>,
     output_content_sender: &Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
     fail_on_err: bool,
@@ -187,6 +198,7 @@ async fn collect_execution_results_and_stream_txs(
         match result {
             Ok(tx_execution_info) => {
                 execution_infos.insert(input_tx.tx_hash(), tx_execution_info);
+                executed_txs.push(input_tx.clone());
                 if let Some(output_content_sender) = output_content_sender {
                     output_content_sender.send(input_tx)?;
                 }
@@ -322,10 +334,12 @@ impl BlockBuilderFactoryTrait for BlockBuilderFactory {
         tx_provider: Box<dyn TransactionProvider>,
         output_content_sender: Option<tokio::sync::mpsc::UnboundedSender<Transaction>>,
     ) -> BlockBuilderResult<(Box<dyn BlockBuilderTrait>, AbortSignalSender)> {
+        let block_info = block_metadata.block_info.clone();
         let executor = self.preprocess_and_create_transaction_executor(block_metadata)?;
         let (abort_signal_sender, abort_signal_receiver) = tokio::sync::oneshot::channel();
         let block_builder = Box::new(BlockBuilder::new(
             Box::new(executor),
+            block_info,
             tx_provider,
             output_content_sender,
             abort_signal_receiver,
@@ -9,6 +9,7 @@ use indexmap::{indexmap, IndexMap};
 use mockall::predicate::eq;
 use mockall::Sequence;
 use rstest::rstest;
+use starknet_api::block::BlockInfo;
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::TransactionHash;
@@ -24,7 +25,7 @@ use crate::block_builder::{
     BlockExecutionArtifacts,
     FailOnErrorCause,
 };
-use crate::test_utils::test_txs;
+use crate::test_utils::{test_tx, test_txs};
 use crate::transaction_executor::MockTransactionExecutorTrait;
 use crate::transaction_provider::{MockTransactionProvider, NextTxs};
 
@@ -48,6 +49,8 @@ fn block_execution_artifacts(
     execution_infos: IndexMap<TransactionHash, TransactionExecutionInfo>,
 ) -> BlockExecutionArtifacts {
     BlockExecutionArtifacts {
+        block_info: BlockInfo::default(),
+        executed_txs: execution_infos.keys().copied().map(test_tx).collect(),
         execution_infos,
         commitment_state_diff: Default::default(),
         visited_segments_mapping: Default::default(),
@@ -384,6 +387,7 @@ async fn run_build_block(
     let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(deadline_secs);
     let mut block_builder = BlockBuilder::new(
         Box::new(mock_transaction_executor),
+        BlockInfo::default(),
         Box::new(tx_provider),
         output_sender,
         abort_receiver,
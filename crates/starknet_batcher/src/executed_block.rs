@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use blockifier::execution::call_info::CallInfo;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use blockifier::utils::u64_from_usize;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use indexmap::IndexMap;
+use starknet_api::block::{
+    BlockBody,
+    BlockHash,
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockInfo,
+    GasPricePerToken,
+    StarknetVersion,
+};
+use starknet_api::block_hash::block_hash_calculator::{
+    calculate_block_commitments,
+    calculate_block_hash,
+    BlockHeaderCommitments,
+    TransactionHashingData,
+    TransactionOutputForHash,
+};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ClassHash, GlobalRoot, SequencerContractAddress};
+use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use starknet_api::executable_transaction::{AccountTransaction, Transaction};
+use starknet_api::execution_resources::{Builtin, ExecutionResources};
+use starknet_api::state::{SierraContractClass, ThinStateDiff};
+use starknet_api::transaction::fields::TransactionSignature;
+use starknet_api::transaction::{
+    DeclareTransactionOutput,
+    DeployAccountTransactionOutput,
+    Event,
+    InvokeTransactionOutput,
+    L1HandlerTransactionOutput,
+    MessageToL1,
+    RevertedTransactionExecutionStatus,
+    TransactionExecutionStatus,
+    TransactionHash,
+    TransactionOutput,
+};
+use starknet_api::StarknetApiResult;
+use strum::IntoEnumIterator;
+
+/// A block built by the batcher, in the form it is written to storage once consensus decides on
+/// it. Blocks received from state sync are written in the same form.
+#[derive(Debug, Default, PartialEq)]
+pub struct ExecutedBlock {
+    pub header: BlockHeader,
+    pub body: BlockBody,
+    // The classes declared in the block, along with the compiled classes of the Sierra classes.
+    // The Sierra classes are not part of the executed transactions; they are added once the block
+    // is decided.
+    pub classes: Vec<(ClassHash, SierraContractClass)>,
+    pub deprecated_classes: Vec<(ClassHash, DeprecatedContractClass)>,
+    pub casms: Vec<(ClassHash, CasmContractClass)>,
+    // The commitments to the content of the block, which its hash commits to.
+    pub commitments: BlockHeaderCommitments,
+}
+
+impl ExecutedBlock {
+    /// Builds the block from the transactions executed by the block builder, in execution order,
+    /// and their execution infos.
+    pub fn new(
+        block_info: BlockInfo,
+        executed_txs: Vec<Transaction>,
+        execution_infos: &IndexMap<TransactionHash, TransactionExecutionInfo>,
+        state_diff: &ThinStateDiff,
+    ) -> Self {
+        let mut body = BlockBody::default();
+        let mut transactions_data = Vec::new();
+        let mut deprecated_classes = Vec::new();
+        let mut casms = Vec::new();
+        for (tx, execution_info) in executed_txs.into_iter().zip(execution_infos.values()) {
+            let output = transaction_output(&tx, execution_info);
+            transactions_data.push(transaction_hashing_data(&tx, &output));
+            if let Transaction::Account(AccountTransaction::Declare(declare_tx)) = &tx {
+                match &declare_tx.class_info.contract_class {
+                    ContractClass::V0(class) => {
+                        deprecated_classes.push((declare_tx.class_hash(), class.clone()))
+                    }
+                    ContractClass::V1(casm) => casms.push((declare_tx.class_hash(), casm.clone())),
+                }
+            }
+            body.transaction_hashes.push(tx.tx_hash());
+            body.transactions.push(tx.into());
+            body.transaction_outputs.push(output);
+        }
+
+        let block_header_without_hash = block_header_without_hash(&block_info);
+        let commitments = calculate_block_commitments(
+            &transactions_data,
+            state_diff,
+            block_header_without_hash.l1_da_mode,
+            &block_header_without_hash.starknet_version,
+        );
+        // The parent hash, the state root and the block hash are set once the block is decided.
+        let header = BlockHeader {
+            block_header_without_hash,
+            state_diff_commitment: Some(commitments.state_diff_commitment),
+            state_diff_length: Some(state_diff.len()),
+            transaction_commitment: Some(commitments.transaction_commitment),
+            event_commitment: Some(commitments.event_commitment),
+            receipt_commitment: Some(commitments.receipt_commitment),
+            n_transactions: body.transactions.len(),
+            n_events: transactions_data
+                .iter()
+                .map(|data| data.transaction_output.events.len())
+                .sum(),
+            ..Default::default()
+        };
+
+        Self { header, body, classes: Vec::new(), deprecated_classes, casms, commitments }
+    }
+
+    /// Returns the hashes of the declare transactions of the block.
+    pub fn declare_tx_hashes(&self) -> Vec<TransactionHash> {
+        self.body
+            .transactions
+            .iter()
+            .zip(&self.body.transaction_hashes)
+            .filter(|(tx, _)| matches!(tx, starknet_api::transaction::Transaction::Declare(_)))
+            .map(|(_, &tx_hash)| tx_hash)
+            .collect()
+    }
+
+    /// Sets the parent hash and the state root of the block, which are known only once it is
+    /// decided and the blocks before it are committed, and computes its hash.
+    pub fn finalize(
+        &mut self,
+        parent_hash: BlockHash,
+        state_root: GlobalRoot,
+    ) -> StarknetApiResult<BlockHash> {
+        let header_without_hash = &mut self.header.block_header_without_hash;
+        header_without_hash.parent_hash = parent_hash;
+        header_without_hash.state_root = state_root;
+        self.header.block_hash =
+            calculate_block_hash(header_without_hash.clone(), self.commitments.clone())?;
+        Ok(self.header.block_hash)
+    }
+}
+
+fn transaction_hashing_data(
+    tx: &Transaction,
+    output: &TransactionOutput,
+) -> TransactionHashingData {
+    let transaction_signature = match tx {
+        Transaction::Account(account_tx) => account_tx.signature(),
+        Transaction::L1Handler(_) => TransactionSignature::default(),
+    };
+    TransactionHashingData {
+        transaction_signature,
+        transaction_output: TransactionOutputForHash {
+            actual_fee: output.actual_fee(),
+            events: output.events().to_vec(),
+            execution_status: output.execution_status().clone(),
+            gas_consumed: output.execution_resources().gas_consumed,
+            messages_sent: output.messages_sent().clone(),
+        },
+        transaction_hash: tx.tx_hash(),
+    }
+}
+
+fn block_header_without_hash(block_info: &BlockInfo) -> BlockHeaderWithoutHash {
+    let BlockInfo { block_number, block_timestamp, sequencer_address, gas_prices, use_kzg_da } =
+        block_info;
+    let (eth_gas_prices, strk_gas_prices) =
+        (&gas_prices.eth_gas_prices, &gas_prices.strk_gas_prices);
+    BlockHeaderWithoutHash {
+        block_number: *block_number,
+        l1_gas_price: GasPricePerToken {
+            price_in_fri: strk_gas_prices.l1_gas_price.get(),
+            price_in_wei: eth_gas_prices.l1_gas_price.get(),
+        },
+        l1_data_gas_price: GasPricePerToken {
+            price_in_fri: strk_gas_prices.l1_data_gas_price.get(),
+            price_in_wei: eth_gas_prices.l1_data_gas_price.get(),
+        },
+        l2_gas_price: GasPricePerToken {
+            price_in_fri: strk_gas_prices.l2_gas_price.get(),
+            price_in_wei: eth_gas_prices.l2_gas_price.get(),
+        },
+        sequencer: SequencerContractAddress(*sequencer_address),
+        timestamp: *block_timestamp,
+        l1_da_mode: if *use_kzg_da {
+            L1DataAvailabilityMode::Blob
+        } else {
+            L1DataAvailabilityMode::Calldata
+        },
+        starknet_version: StarknetVersion::LATEST,
+        ..Default::default()
+    }
+}
+
+fn transaction_output(
+    tx: &Transaction,
+    execution_info: &TransactionExecutionInfo,
+) -> TransactionOutput {
+    let actual_fee = execution_info.receipt.fee;
+    let mut events = Vec::new();
+    let mut messages_sent = Vec::new();
+    for call_info in execution_info.non_optional_call_infos() {
+        events.extend(call_events(call_info));
+        messages_sent.extend(call_messages(call_info));
+    }
+    let execution_status = match &execution_info.revert_error {
+        Some(revert_error) => {
+            TransactionExecutionStatus::Reverted(RevertedTransactionExecutionStatus {
+                revert_reason: revert_error.to_string(),
+            })
+        }
+        None => TransactionExecutionStatus::Succeeded,
+    };
+    let execution_resources = execution_resources(execution_info);
+
+    match tx {
+        Transaction::Account(AccountTransaction::Declare(_)) => {
+            TransactionOutput::Declare(DeclareTransactionOutput {
+                actual_fee,
+                messages_sent,
+                events,
+                execution_status,
+                execution_resources,
+            })
+        }
+        Transaction::Account(AccountTransaction::DeployAccount(deploy_account_tx)) => {
+            TransactionOutput::DeployAccount(DeployAccountTransactionOutput {
+                actual_fee,
+                messages_sent,
+                events,
+                contract_address: deploy_account_tx.contract_address,
+                execution_status,
+                execution_resources,
+            })
+        }
+        Transaction::Account(AccountTransaction::Invoke(_)) => {
+            TransactionOutput::Invoke(InvokeTransactionOutput {
+                actual_fee,
+                messages_sent,
+                events,
+                execution_status,
+                execution_resources,
+            })
+        }
+        Transaction::L1Handler(_) => TransactionOutput::L1Handler(L1HandlerTransactionOutput {
+            actual_fee,
+            messages_sent,
+            events,
+            execution_status,
+            execution_resources,
+        }),
+    }
+}
+
+// The events emitted by the call and its inner calls, in emission order.
+fn call_events(call_info: &CallInfo) -> Vec<Event> {
+    let mut ordered_events: Vec<_> = call_info
+        .iter()
+        .flat_map(|call| {
+            call.execution.events.iter().map(|ordered_event| {
+                (
+                    ordered_event.order,
+                    Event {
+                        from_address: call.call.storage_address,
+                        content: ordered_event.event.clone(),
+                    },
+                )
+            })
+        })
+        .collect();
+    ordered_events.sort_by_key(|(order, _)| *order);
+    ordered_events.into_iter().map(|(_, event)| event).collect()
+}
+
+// The messages to L1 sent by the call and its inner calls, in sending order.
+fn call_messages(call_info: &CallInfo) -> Vec<MessageToL1> {
+    let mut ordered_messages: Vec<_> = call_info
+        .iter()
+        .flat_map(|call| {
+            call.execution.l2_to_l1_messages.iter().map(|ordered_message| {
+                (
+                    ordered_message.order,
+                    MessageToL1 {
+                        from_address: call.call.storage_address,
+                        to_address: ordered_message.message.to_address,
+                        payload: ordered_message.message.payload.clone(),
+                    },
+                )
+            })
+        })
+        .collect();
+    ordered_messages.sort_by_key(|(order, _)| *order);
+    ordered_messages.into_iter().map(|(_, message)| message).collect()
+}
+
+fn execution_resources(execution_info: &TransactionExecutionInfo) -> ExecutionResources {
+    let receipt = &execution_info.receipt;
+    let computation = &receipt.resources.computation;
+    let vm_resources = &computation.vm_resources;
+    let builtin_instance_counter: HashMap<Builtin, u64> = vm_resources
+        .builtin_instance_counter
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .filter_map(|(builtin_name, count)| {
+            Builtin::iter()
+                .find(|builtin| builtin.name() == builtin_name.to_str())
+                .map(|builtin| (builtin, u64_from_usize(*count)))
+        })
+        .collect();
+    ExecutionResources {
+        steps: u64_from_usize(vm_resources.n_steps + computation.n_reverted_steps),
+        builtin_instance_counter,
+        memory_holes: u64_from_usize(vm_resources.n_memory_holes),
+        da_gas_consumed: receipt.da_gas,
+        gas_consumed: receipt.gas,
+    }
+}
@@ -0,0 +1,117 @@
+use blockifier::execution::call_info::{CallExecution, CallInfo, OrderedEvent};
+use blockifier::execution::entry_point::CallEntryPoint;
+use blockifier::fee::receipt::TransactionReceipt;
+use blockifier::test_utils::struct_impls::BlockInfoExt;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use indexmap::{indexmap, IndexMap};
+use starknet_api::block::{BlockHash, BlockInfo};
+use starknet_api::core::{ContractAddress, GlobalRoot};
+use starknet_api::state::ThinStateDiff;
+use starknet_api::transaction::fields::Fee;
+use starknet_api::transaction::{
+    Event,
+    EventContent,
+    EventData,
+    InvokeTransactionOutput,
+    TransactionExecutionStatus,
+    TransactionOutput,
+};
+use starknet_api::{contract_address, tx_hash};
+use starknet_types_core::felt::Felt;
+
+use crate::executed_block::ExecutedBlock;
+use crate::test_utils::test_tx;
+
+fn call_info(
+    storage_address: ContractAddress,
+    event_order: usize,
+    inner_calls: Vec<CallInfo>,
+) -> CallInfo {
+    CallInfo {
+        call: CallEntryPoint { storage_address, ..Default::default() },
+        execution: CallExecution {
+            events: vec![OrderedEvent { order: event_order, event: event_content(event_order) }],
+            ..Default::default()
+        },
+        inner_calls,
+        ..Default::default()
+    }
+}
+
+fn event_content(order: usize) -> EventContent {
+    EventContent { data: EventData(vec![Felt::from(order)]), ..Default::default() }
+}
+
+#[test]
+fn executed_block_from_execution_results() {
+    // Setup.
+    let block_info = BlockInfo::create_for_testing();
+    let tx_hash = tx_hash!(1);
+    let (outer_address, inner_address) = (contract_address!("0x10"), contract_address!("0x20"));
+    // The inner call emits its event before the outer call does.
+    let execution_info = TransactionExecutionInfo {
+        execute_call_info: Some(call_info(
+            outer_address,
+            1,
+            vec![call_info(inner_address, 0, Vec::new())],
+        )),
+        receipt: TransactionReceipt { fee: Fee(100), ..Default::default() },
+        ..Default::default()
+    };
+
+    // Test.
+    let block = ExecutedBlock::new(
+        block_info.clone(),
+        vec![test_tx(tx_hash)],
+        &indexmap! { tx_hash => execution_info },
+        &ThinStateDiff::default(),
+    );
+
+    // Assert.
+    let header = &block.header;
+    assert_eq!(header.block_header_without_hash.block_number, block_info.block_number);
+    assert_eq!(header.block_header_without_hash.timestamp, block_info.block_timestamp);
+    assert_eq!(header.n_transactions, 1);
+    assert_eq!(header.n_events, 2);
+    assert_eq!(block.body.transaction_hashes, vec![tx_hash]);
+    let TransactionOutput::Invoke(InvokeTransactionOutput {
+        actual_fee,
+        events,
+        execution_status,
+        ..
+    }) = &block.body.transaction_outputs[0]
+    else {
+        panic!("Expected an invoke transaction output.");
+    };
+    assert_eq!(*actual_fee, Fee(100));
+    assert_eq!(*execution_status, TransactionExecutionStatus::Succeeded);
+    assert_eq!(
+        *events,
+        vec![
+            Event { from_address: inner_address, content: event_content(0) },
+            Event { from_address: outer_address, content: event_content(1) },
+        ]
+    );
+}
+
+#[test]
+fn finalize_sets_parent_hash_state_root_and_block_hash() {
+    // Setup.
+    let mut block = ExecutedBlock::new(
+        BlockInfo::create_for_testing(),
+        Vec::new(),
+        &IndexMap::new(),
+        &ThinStateDiff::default(),
+    );
+    let (parent_hash, state_root) = (BlockHash(Felt::ONE), GlobalRoot(Felt::TWO));
+
+    // Test.
+    let block_hash = block.finalize(parent_hash, state_root).unwrap();
+
+    // Assert.
+    let header_without_hash = &block.header.block_header_without_hash;
+    assert_eq!(header_without_hash.parent_hash, parent_hash);
+    assert_eq!(header_without_hash.state_root, state_root);
+    assert_eq!(block.header.block_hash, block_hash);
+    assert_ne!(block_hash, BlockHash::default());
+}
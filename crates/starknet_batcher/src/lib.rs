@@ -6,10 +6,16 @@ pub mod block_builder;
 mod block_builder_test;
 pub mod communication;
 pub mod config;
+pub mod executed_block;
+#[cfg(test)]
+mod executed_block_test;
 pub mod fee_market;
 mod proposal_manager;
 #[cfg(test)]
 mod proposal_manager_test;
+pub mod state_committer;
+#[cfg(test)]
+mod state_committer_test;
 #[cfg(test)]
 mod test_utils;
 mod transaction_executor;
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use papyrus_storage::patricia::StateTrieRoots;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::GlobalRoot;
+use starknet_api::state::ThinStateDiff;
+use starknet_committer::block_committer::errors::BlockCommitmentError;
+use starknet_committer::forest::mdbx_forest::{commit_thin_state_diff, ForestUpdate};
+use starknet_patricia::storage::errors::StorageResult;
+use starknet_patricia::storage::storage_trait::{ReadOnlyStorage, StorageKey, StorageValue};
+use thiserror::Error;
+
+use crate::batcher::BatcherStorageReaderTrait;
+
+#[derive(Debug, Error)]
+pub enum StateCommitterError {
+    #[error(transparent)]
+    BlockCommitment(#[from] BlockCommitmentError),
+    #[error("The state tries after block {0} are not stored.")]
+    MissingStateTrieRoots(BlockNumber),
+    #[error(transparent)]
+    Storage(#[from] papyrus_storage::StorageError),
+}
+
+/// Commits the state diffs of blocks to the Patricia forest of the global state, which is kept in
+/// the batcher's storage, and computes the state roots after them.
+pub struct StateCommitter {
+    storage_reader: Arc<dyn BatcherStorageReaderTrait>,
+}
+
+impl StateCommitter {
+    pub fn new(storage_reader: Arc<dyn BatcherStorageReaderTrait>) -> Self {
+        Self { storage_reader }
+    }
+
+    /// Applies the state diff of the block at the given height to the forest after the previous
+    /// block, and returns the state root after the block and the update to write with it. The
+    /// forest in storage is not modified.
+    pub async fn commit(
+        &self,
+        height: BlockNumber,
+        state_diff: &ThinStateDiff,
+    ) -> Result<(GlobalRoot, ForestUpdate), StateCommitterError> {
+        let parent_roots = match height.prev() {
+            Some(parent_height) => self
+                .storage_reader
+                .state_trie_roots(parent_height)?
+                .ok_or(StateCommitterError::MissingStateTrieRoots(parent_height))?,
+            // The state before the genesis block is empty.
+            None => StateTrieRoots::default(),
+        };
+        Ok(commit_thin_state_diff(
+            &ForestReader(self.storage_reader.as_ref()),
+            height,
+            parent_roots,
+            state_diff,
+        )
+        .await?)
+    }
+}
+
+// Reads the nodes of the forest from the batcher's storage.
+struct ForestReader<'a>(&'a dyn BatcherStorageReaderTrait);
+
+impl ReadOnlyStorage for ForestReader<'_> {
+    fn get(&self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        Ok(self.mget(std::slice::from_ref(key))?.pop().flatten())
+    }
+
+    fn mget(&self, keys: &[StorageKey]) -> StorageResult<Vec<Option<StorageValue>>> {
+        Ok(self.0.patricia_nodes(keys)?)
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use indexmap::indexmap;
+use papyrus_storage::patricia::PatriciaStorageReader;
+use papyrus_storage::test_utils::get_test_storage;
+use papyrus_storage::StorageWriter;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::GlobalRoot;
+use starknet_api::state::ThinStateDiff;
+use starknet_api::{class_hash, compiled_class_hash, contract_address, felt, nonce, storage_key};
+use starknet_committer::block_committer::input::{
+    ContractAddress,
+    StarknetStorageKey,
+    StarknetStorageValue,
+};
+use starknet_committer::forest::storage_proof::StorageProof;
+use starknet_committer::hash_function::hash::TreeHashFunctionImpl;
+use starknet_committer::patricia_merkle_tree::types::{ClassHash, CompiledClassHash, Nonce};
+use starknet_patricia::felt::Felt;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::proof::verify_merkle_proof;
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+
+use crate::batcher::BatcherStorageWriterTrait;
+use crate::executed_block::ExecutedBlock;
+use crate::state_committer::{StateCommitter, StateCommitterError};
+
+fn first_state_diff() -> ThinStateDiff {
+    ThinStateDiff {
+        deployed_contracts: indexmap! { contract_address!("0x10") => class_hash!("0x100") },
+        storage_diffs: indexmap! {
+            contract_address!("0x10") => indexmap! { storage_key!("0x1") => felt!("0x2") },
+        },
+        nonces: indexmap! { contract_address!("0x10") => nonce!(1_u8) },
+        ..Default::default()
+    }
+}
+
+fn second_state_diff() -> ThinStateDiff {
+    ThinStateDiff {
+        storage_diffs: indexmap! {
+            contract_address!("0x10") => indexmap! { storage_key!("0x3") => felt!("0x4") },
+        },
+        declared_classes: indexmap! { class_hash!("0x200") => compiled_class_hash!(1_u8) },
+        ..Default::default()
+    }
+}
+
+fn merged_state_diff() -> ThinStateDiff {
+    let mut state_diff = first_state_diff();
+    let second_state_diff = second_state_diff();
+    state_diff.storage_diffs[&contract_address!("0x10")]
+        .extend(second_state_diff.storage_diffs[&contract_address!("0x10")].clone());
+    state_diff.declared_classes = second_state_diff.declared_classes;
+    state_diff
+}
+
+// Commits the state diff of the next block, and stores it as the batcher does.
+async fn commit_and_store(
+    state_committer: &StateCommitter,
+    storage_writer: &mut StorageWriter,
+    height: BlockNumber,
+    state_diff: ThinStateDiff,
+) -> GlobalRoot {
+    let (state_root, forest_update) = state_committer.commit(height, &state_diff).await.unwrap();
+    // Only the state diff of the block matters to the state committer; the blocks are told apart
+    // by their hashes.
+    let mut block = ExecutedBlock::default();
+    block.header.block_hash = BlockHash(felt!(height.0));
+    storage_writer.commit_block(height, state_diff, forest_update, block, None).unwrap();
+    state_root
+}
+
+#[tokio::test]
+async fn empty_state_diff_keeps_empty_root() {
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let state_committer = StateCommitter::new(Arc::new(storage_reader));
+
+    let (state_root, _) =
+        state_committer.commit(BlockNumber(0), &ThinStateDiff::default()).await.unwrap();
+
+    assert_eq!(state_root, GlobalRoot::default());
+}
+
+#[tokio::test]
+async fn incremental_commitment() {
+    // Setup.
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let state_committer = StateCommitter::new(Arc::new(storage_reader));
+    let first_state_root =
+        commit_and_store(&state_committer, &mut storage_writer, BlockNumber(0), first_state_diff())
+            .await;
+
+    // Test.
+    let second_state_root = commit_and_store(
+        &state_committer,
+        &mut storage_writer,
+        BlockNumber(1),
+        second_state_diff(),
+    )
+    .await;
+
+    // Assert.
+    assert_ne!(first_state_root, GlobalRoot::default());
+    assert_ne!(second_state_root, first_state_root);
+    // Committing the blocks one after the other results in the same state as committing them at
+    // once.
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let (merged_state_root, _) = StateCommitter::new(Arc::new(storage_reader))
+        .commit(BlockNumber(0), &merged_state_diff())
+        .await
+        .unwrap();
+    assert_eq!(second_state_root, merged_state_root);
+}
+
+#[tokio::test]
+async fn forest_survives_restart() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    commit_and_store(
+        &StateCommitter::new(Arc::new(storage_reader.clone())),
+        &mut storage_writer,
+        BlockNumber(0),
+        first_state_diff(),
+    )
+    .await;
+
+    // A new committer continues from the forest in storage.
+    let (state_root, _) = StateCommitter::new(Arc::new(storage_reader))
+        .commit(BlockNumber(1), &second_state_diff())
+        .await
+        .unwrap();
+
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let (merged_state_root, _) = StateCommitter::new(Arc::new(storage_reader))
+        .commit(BlockNumber(0), &merged_state_diff())
+        .await
+        .unwrap();
+    assert_eq!(state_root, merged_state_root);
+}
+
+#[tokio::test]
+async fn unstored_commitment_does_not_change_the_forest() {
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let state_committer = StateCommitter::new(Arc::new(storage_reader));
+
+    let (first_state_root, _) =
+        state_committer.commit(BlockNumber(0), &first_state_diff()).await.unwrap();
+    // The block failed to be stored, so it is committed again.
+    let (second_state_root, _) =
+        state_committer.commit(BlockNumber(0), &first_state_diff()).await.unwrap();
+
+    assert_eq!(first_state_root, second_state_root);
+}
+
+#[tokio::test]
+async fn missing_parent_forest() {
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let state_committer = StateCommitter::new(Arc::new(storage_reader));
+
+    let result = state_committer.commit(BlockNumber(1), &first_state_diff()).await;
+
+    assert_matches!(result, Err(StateCommitterError::MissingStateTrieRoots(BlockNumber(0))));
+}
+
+#[tokio::test]
+async fn storage_proof_from_stored_blocks() {
+    // Setup.
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let state_committer = StateCommitter::new(Arc::new(storage_reader.clone()));
+    commit_and_store(&state_committer, &mut storage_writer, BlockNumber(0), first_state_diff())
+        .await;
+    commit_and_store(&state_committer, &mut storage_writer, BlockNumber(1), second_state_diff())
+        .await;
+    let roots = storage_reader
+        .begin_ro_txn()
+        .unwrap()
+        .get_state_trie_roots(BlockNumber(1))
+        .unwrap()
+        .expect("The roots of a stored block should be stored.");
+    let contracts_trie_root_hash = HashOutput(Felt::from(roots.contracts_trie_root));
+    let classes_trie_root_hash = HashOutput(Felt::from(roots.classes_trie_root));
+    let address = ContractAddress(Felt::from(0x10_u8));
+    let class_hash = ClassHash(Felt::from(0x200_u128));
+
+    // Test.
+    let proof = StorageProof::fetch(
+        &storage_reader,
+        contracts_trie_root_hash,
+        classes_trie_root_hash,
+        &[class_hash],
+        &[address],
+        &HashMap::from([(
+            address,
+            vec![StarknetStorageKey(Felt::from(0x1_u8)), StarknetStorageKey(Felt::from(0x3_u8))],
+        )]),
+    )
+    .unwrap();
+
+    // Assert.
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        classes_trie_root_hash,
+        NodeIndex::from(&class_hash),
+        &CompiledClassHash(Felt::ONE),
+        &proof.classes_proof.nodes,
+    )
+    .unwrap();
+    let contract_state = &proof.contracts_proof.leaves[&NodeIndex::from(&address)];
+    assert_eq!(contract_state.class_hash, ClassHash(Felt::from(0x100_u128)));
+    assert_eq!(contract_state.nonce, Nonce(Felt::ONE));
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        contracts_trie_root_hash,
+        NodeIndex::from(&address),
+        contract_state,
+        &proof.contracts_proof.nodes,
+    )
+    .unwrap();
+    // The storage of the contract holds the writes of both blocks.
+    let storage_proof = &proof.contracts_storage_proofs[&address];
+    for (key, value) in [(0x1_u8, 0x2_u8), (0x3, 0x4)] {
+        verify_merkle_proof::<_, TreeHashFunctionImpl>(
+            contract_state.storage_root_hash,
+            NodeIndex::from(&StarknetStorageKey(Felt::from(key))),
+            &StarknetStorageValue(Felt::from(value)),
+            &storage_proof.nodes,
+        )
+        .unwrap();
+    }
+}
@@ -4,26 +4,27 @@ use blockifier::blockifier::transaction_executor::VisitedSegmentsMapping;
 use blockifier::bouncer::BouncerWeights;
 use blockifier::state::cached_state::CommitmentStateDiff;
 use indexmap::IndexMap;
+use starknet_api::block::BlockInfo;
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::test_utils::invoke::{executable_invoke_tx, InvokeTxArgs};
+use starknet_api::transaction::TransactionHash;
 use starknet_api::tx_hash;
 
 use crate::block_builder::BlockExecutionArtifacts;
 
 pub fn test_txs(tx_hash_range: Range<usize>) -> Vec<Transaction> {
-    tx_hash_range
-        .map(|i| {
-            Transaction::Account(executable_invoke_tx(InvokeTxArgs {
-                tx_hash: tx_hash!(i),
-                ..Default::default()
-            }))
-        })
-        .collect()
+    tx_hash_range.map(|i| test_tx(tx_hash!(i))).collect()
+}
+
+pub fn test_tx(tx_hash: TransactionHash) -> Transaction {
+    Transaction::Account(executable_invoke_tx(InvokeTxArgs { tx_hash, ..Default::default() }))
 }
 
 impl BlockExecutionArtifacts {
     pub fn create_for_testing() -> Self {
         Self {
+            block_info: BlockInfo::default(),
+            executed_txs: Vec::new(),
             execution_infos: IndexMap::default(),
             commitment_state_diff: CommitmentStateDiff::default(),
             visited_segments_mapping: VisitedSegmentsMapping::default(),
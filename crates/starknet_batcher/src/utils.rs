@@ -2,10 +2,12 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use blockifier::abi::constants;
+use blockifier::transaction::objects::TransactionExecutionInfo;
 use chrono::Utc;
 use indexmap::IndexMap;
 use starknet_api::block::{BlockHashAndNumber, BlockNumber};
 use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
+use starknet_api::contract_class::EntryPointType;
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::state::ThinStateDiff;
 use starknet_api::transaction::TransactionHash;
@@ -13,6 +15,7 @@ use starknet_batcher_types::batcher_types::{BatcherResult, ProposalCommitment, P
 use starknet_batcher_types::errors::BatcherError;
 
 use crate::block_builder::{BlockBuilderError, BlockExecutionArtifacts};
+use crate::executed_block::ExecutedBlock;
 
 // BlockBuilderError is wrapped in an Arc since it doesn't implement Clone.
 pub(crate) type ProposalResult<T> = Result<T, Arc<BlockBuilderError>>;
@@ -29,6 +32,7 @@ pub(crate) struct ProposalOutput {
     pub commitment: ProposalCommitment,
     pub tx_hashes: HashSet<TransactionHash>,
     pub nonces: HashMap<ContractAddress, Nonce>,
+    pub block: ExecutedBlock,
 }
 
 impl From<BlockExecutionArtifacts> for ProposalOutput {
@@ -42,8 +46,14 @@ impl From<BlockExecutionArtifacts> for ProposalOutput {
         );
 
         // TODO: Get these from the transactions.
-        let deployed_contracts = IndexMap::new();
         let declared_classes = IndexMap::new();
+        // The commitment state diff doesn't tell deployed contracts from replaced classes. A
+        // contract is deployed in the block if its constructor was called.
+        let deployed_addresses = deployed_contract_addresses(&artifacts.execution_infos);
+        let (deployed_contracts, replaced_classes) = commitment_state_diff
+            .address_to_class_hash
+            .into_iter()
+            .partition(|(address, _class_hash)| deployed_addresses.contains(address));
         let state_diff = ThinStateDiff {
             deployed_contracts,
             storage_diffs: commitment_state_diff.storage_updates,
@@ -51,16 +61,34 @@ impl From<BlockExecutionArtifacts> for ProposalOutput {
             nonces: commitment_state_diff.address_to_nonce,
             // TODO: Remove this when the structure of storage diffs changes.
             deprecated_declared_classes: Vec::new(),
-            replaced_classes: IndexMap::new(),
+            replaced_classes,
         };
         let commitment =
             ProposalCommitment { state_diff_commitment: calculate_state_diff_hash(&state_diff) };
         let tx_hashes = HashSet::from_iter(artifacts.execution_infos.keys().copied());
+        let block = ExecutedBlock::new(
+            artifacts.block_info,
+            artifacts.executed_txs,
+            &artifacts.execution_infos,
+            &state_diff,
+        );
 
-        Self { state_diff, commitment, tx_hashes, nonces }
+        Self { state_diff, commitment, tx_hashes, nonces, block }
     }
 }
 
+fn deployed_contract_addresses(
+    execution_infos: &IndexMap<TransactionHash, TransactionExecutionInfo>,
+) -> HashSet<ContractAddress> {
+    execution_infos
+        .values()
+        .flat_map(|execution_info| execution_info.non_optional_call_infos())
+        .flat_map(|call_info| call_info.iter())
+        .filter(|call_info| call_info.call.entry_point_type == EntryPointType::Constructor)
+        .map(|call_info| call_info.call.storage_address)
+        .collect()
+}
+
 pub(crate) fn deadline_as_instant(
     deadline: chrono::DateTime<Utc>,
 ) -> BatcherResult<tokio::time::Instant> {
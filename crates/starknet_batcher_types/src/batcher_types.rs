@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{BlockHashAndNumber, BlockInfo, BlockNumber};
+use starknet_api::block::{BlockHashAndNumber, BlockInfo, BlockNumber, DecisionCertificate};
 use starknet_api::core::StateDiffCommitment;
 use starknet_api::executable_transaction::Transaction;
 use starknet_api::state::ThinStateDiff;
@@ -114,6 +114,8 @@ pub struct StartHeightInput {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DecisionReachedInput {
     pub proposal_id: ProposalId,
+    /// The precommits that decided the proposal, stored next to the block.
+    pub decision_certificate: DecisionCertificate,
 }
 
 pub type BatcherResult<T> = Result<T, BatcherError>;
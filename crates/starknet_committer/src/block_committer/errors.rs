@@ -1,3 +1,4 @@
+use starknet_patricia::storage::errors::StorageError;
 use thiserror::Error;
 
 use crate::forest::forest_errors::ForestError;
@@ -6,4 +7,6 @@ use crate::forest::forest_errors::ForestError;
 pub enum BlockCommitmentError {
     #[error(transparent)]
     ForestError(#[from] ForestError),
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
 }
@@ -1,6 +1,14 @@
 pub mod filled_forest;
 pub mod forest_errors;
+pub mod forest_storage;
+#[cfg(test)]
+pub mod forest_storage_test;
+#[cfg(feature = "mdbx_storage")]
+pub mod mdbx_forest;
 pub mod original_skeleton_forest;
 #[cfg(test)]
 pub mod skeleton_forest_test;
+pub mod storage_proof;
+#[cfg(test)]
+pub mod storage_proof_test;
 pub mod updated_skeleton_forest;
@@ -5,13 +5,14 @@ use starknet_patricia::patricia_merkle_tree::filled_tree::tree::FilledTree;
 use starknet_patricia::patricia_merkle_tree::node_data::leaf::LeafModifications;
 use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
 use starknet_patricia::patricia_merkle_tree::updated_skeleton_tree::tree::UpdatedSkeletonTreeImpl;
-use starknet_patricia::storage::storage_trait::Storage;
+use starknet_patricia::storage::errors::StorageResult;
+use starknet_patricia::storage::storage_trait::{Storage, StorageKey, StorageValue};
 use tracing::info;
 
 use crate::block_committer::input::{ContractAddress, StarknetStorageValue};
 use crate::forest::forest_errors::{ForestError, ForestResult};
 use crate::forest::updated_skeleton_forest::UpdatedSkeletonForest;
-use crate::hash_function::hash::ForestHashFunction;
+use crate::hash_function::hash::{calculate_global_root_hash, ForestHashFunction};
 use crate::patricia_merkle_tree::leaf::leaf_impl::{ContractState, ContractStateInput};
 use crate::patricia_merkle_tree::types::{
     ClassHash,
@@ -29,18 +30,18 @@ pub struct FilledForest {
 }
 
 impl FilledForest {
-    pub fn write_to_storage(&self, storage: &mut impl Storage) {
-        // Serialize all trees to one hash map.
-        let new_db_objects = self
-            .storage_tries
+    pub fn write_to_storage(&self, storage: &mut impl Storage) -> StorageResult<()> {
+        storage.mset(self.new_nodes())
+    }
+
+    /// Returns the nodes of all the trees, serialized to one hash map.
+    pub fn new_nodes(&self) -> HashMap<StorageKey, StorageValue> {
+        self.storage_tries
             .values()
             .flat_map(|tree| tree.serialize().into_iter())
             .chain(self.contracts_trie.serialize())
             .chain(self.classes_trie.serialize())
-            .collect();
-
-        // Store the new hash map
-        storage.mset(new_db_objects);
+            .collect()
     }
 
     pub fn get_contract_root_hash(&self) -> HashOutput {
@@ -51,6 +52,13 @@ impl FilledForest {
         self.classes_trie.get_root_hash()
     }
 
+    pub fn get_global_root_hash(&self) -> HashOutput {
+        calculate_global_root_hash(
+            self.get_contract_root_hash(),
+            self.get_compiled_class_root_hash(),
+        )
+    }
+
     /// Creates a filled forest. Assumes the storage updates and the updated skeletons of the
     /// storage tries include all modified contracts, including those with unmodified storage.
     pub(crate) async fn create<TH: ForestHashFunction + 'static>(
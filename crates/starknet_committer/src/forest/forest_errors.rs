@@ -1,12 +1,15 @@
 use starknet_patricia::patricia_merkle_tree::filled_tree::errors::FilledTreeError;
 use starknet_patricia::patricia_merkle_tree::original_skeleton_tree::errors::OriginalSkeletonTreeError;
+use starknet_patricia::patricia_merkle_tree::traversal::errors::TraversalError;
 use starknet_patricia::patricia_merkle_tree::updated_skeleton_tree::errors::UpdatedSkeletonTreeError;
+use starknet_patricia::storage::errors::StorageError;
+use starknet_patricia::storage::storage_trait::StorageKey;
 use thiserror::Error;
 use tokio::task::JoinError;
 
 use crate::block_committer::input::ContractAddress;
 
-pub(crate) type ForestResult<T> = Result<T, ForestError>;
+pub type ForestResult<T> = Result<T, ForestError>;
 
 #[derive(Debug, Error)]
 pub enum ForestError {
@@ -37,6 +40,14 @@ pub enum ForestError {
     MissingSortedLeafIndices(ContractAddress),
     #[error(transparent)]
     JoinError(#[from] JoinError),
+    #[error(transparent)]
+    Storage(#[from] StorageError),
     #[error("Couldn't create Storage Trie: {0}")]
     StorageTrie(#[source] FilledTreeError),
+    #[error("Couldn't traverse the forest: {0}")]
+    Traversal(#[from] TraversalError),
+    #[error("The forest storage holds an invalid value under the key {key:?}.")]
+    InvalidMetadata { key: StorageKey },
+    #[error("The stale node {0:?} has no references left to release.")]
+    UnreferencedStaleNode(StorageKey),
 }
@@ -0,0 +1,280 @@
+use std::cmp::max;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::ops::Range;
+
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::traversal::tree_diff::diff_trees;
+use starknet_patricia::storage::errors::StorageResult;
+use starknet_patricia::storage::storage_trait::{
+    ReadOnlyStorage,
+    Storage,
+    StorageKey,
+    StorageValue,
+};
+
+use crate::block_committer::input::StarknetStorageValue;
+use crate::forest::filled_forest::FilledForest;
+use crate::forest::forest_errors::{ForestError, ForestResult};
+use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use crate::patricia_merkle_tree::types::CompiledClassHash;
+
+const NODE_REFERENCES_PREFIX: &[u8] = b"node_references:";
+const STALE_NODES_PREFIX: &[u8] = b"stale_nodes:";
+const OLDEST_RETAINED_BLOCK_KEY: &[u8] = b"oldest_retained_block";
+
+/// The roots of the tries of the forest after a block. The default roots are the roots of the
+/// empty forest.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ForestRoots {
+    pub contracts_trie_root_hash: HashOutput,
+    pub classes_trie_root_hash: HashOutput,
+}
+
+impl From<&FilledForest> for ForestRoots {
+    fn from(filled_forest: &FilledForest) -> Self {
+        Self {
+            contracts_trie_root_hash: filled_forest.get_contract_root_hash(),
+            classes_trie_root_hash: filled_forest.get_compiled_class_root_hash(),
+        }
+    }
+}
+
+/// The changes that committing a block makes to the storage of the forest. The written and the
+/// deleted keys are disjoint.
+#[derive(Debug, Default, PartialEq)]
+pub struct ForestWrites {
+    pub writes: HashMap<StorageKey, StorageValue>,
+    pub deletions: Vec<StorageKey>,
+    /// The blocks whose forests left the retention window with the block; their roots should be
+    /// deleted as well.
+    pub pruned_blocks: Range<u64>,
+}
+
+impl ForestWrites {
+    pub fn apply(self, storage: &mut impl Storage) -> StorageResult<()> {
+        storage.mdelete(&self.deletions)?;
+        storage.mset(self.writes)
+    }
+}
+
+/// The storage of the forest, which keeps the forests after the last blocks (the retention
+/// window), and prunes the nodes that only the older forests use.
+///
+/// Nodes are keyed by their hashes, so a node may be used at several indices, by several tries and
+/// by several blocks. Hence, every node counts its references: one for every index it holds in the
+/// latest forest, and one for every index a block in the window removed it from (the forest before
+/// that block still uses it). The nodes that a block removes are recorded with the block, and their
+/// references are released once the forest before the block leaves the window; a node without
+/// references is deleted.
+/// The references, the records and the window are kept in the storage of the nodes, and are
+/// written together with the nodes of the block.
+pub struct ForestStorage<'a, S: ReadOnlyStorage> {
+    storage: &'a S,
+    retained_blocks: NonZeroU64,
+}
+
+impl<'a, S: ReadOnlyStorage> ForestStorage<'a, S> {
+    pub fn new(storage: &'a S, retained_blocks: NonZeroU64) -> Self {
+        Self { storage, retained_blocks }
+    }
+
+    /// Returns the oldest block whose forest is in the retention window (or whose forest is not
+    /// stored, if no block was committed since it).
+    pub fn oldest_retained_block(&self) -> ForestResult<u64> {
+        let key = StorageKey(OLDEST_RETAINED_BLOCK_KEY.to_vec());
+        match self.storage.get(&key)? {
+            Some(value) => decode_u64(&key, &value),
+            None => Ok(0),
+        }
+    }
+
+    /// Returns the changes to write to the storage with the forest after the given block, whose
+    /// parent forest is in the storage: the new nodes of the block and their references, and the
+    /// pruning of the forests that leave the retention window. The storage is not modified.
+    pub fn write_block(
+        &self,
+        block_number: u64,
+        parent_roots: ForestRoots,
+        filled_forest: &FilledForest,
+    ) -> ForestResult<ForestWrites> {
+        let new_nodes = filled_forest.new_nodes();
+        let (removed_keys, inserted_keys) = diff_forests(
+            &OverlayStorage { nodes: &new_nodes, storage: self.storage },
+            parent_roots,
+            ForestRoots::from(filled_forest),
+        )?;
+        let mut forest_writes = ForestWrites { writes: new_nodes, ..Default::default() };
+        let mut references = HashMap::new();
+        for key in inserted_keys {
+            *self.references_mut(&mut references, key)? += 1;
+        }
+
+        // The forest before a block is retained as long as the block is in the window.
+        let oldest_retained_block = self.oldest_retained_block()?;
+        let new_oldest_retained_block =
+            max(oldest_retained_block, block_number.saturating_sub(self.retained_blocks.get() - 1));
+        let mut removed_keys = Some(removed_keys);
+        for released_block_number in oldest_retained_block + 1..=new_oldest_retained_block {
+            let stale_keys = match released_block_number == block_number {
+                true => removed_keys.take().expect("The block is released once."),
+                false => {
+                    let stale_nodes_key = stale_nodes_key(released_block_number);
+                    let stale_keys = match self.storage.get(&stale_nodes_key)? {
+                        Some(value) => decode_keys(&stale_nodes_key, &value)?,
+                        None => Vec::new(),
+                    };
+                    forest_writes.deletions.push(stale_nodes_key);
+                    stale_keys
+                }
+            };
+            for key in stale_keys {
+                let count = self.references_mut(&mut references, key.clone())?;
+                *count = count.checked_sub(1).ok_or(ForestError::UnreferencedStaleNode(key))?;
+            }
+        }
+        if let Some(removed_keys) = removed_keys {
+            forest_writes.writes.insert(stale_nodes_key(block_number), encode_keys(&removed_keys));
+        }
+
+        for (key, count) in references {
+            let references_key = node_references_key(&key);
+            match count {
+                0 => {
+                    forest_writes.writes.remove(&key);
+                    forest_writes.deletions.extend([key, references_key]);
+                }
+                _ => {
+                    forest_writes
+                        .writes
+                        .insert(references_key, StorageValue(count.to_be_bytes().to_vec()));
+                }
+            }
+        }
+        if new_oldest_retained_block != oldest_retained_block {
+            forest_writes.writes.insert(
+                StorageKey(OLDEST_RETAINED_BLOCK_KEY.to_vec()),
+                StorageValue(new_oldest_retained_block.to_be_bytes().to_vec()),
+            );
+        }
+        forest_writes.pruned_blocks = oldest_retained_block..new_oldest_retained_block;
+        Ok(forest_writes)
+    }
+
+    fn references_mut<'m>(
+        &self,
+        references: &'m mut HashMap<StorageKey, u64>,
+        key: StorageKey,
+    ) -> ForestResult<&'m mut u64> {
+        Ok(match references.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let references_key = node_references_key(entry.key());
+                let count = match self.storage.get(&references_key)? {
+                    Some(value) => decode_u64(&references_key, &value)?,
+                    None => 0,
+                };
+                entry.insert(count)
+            }
+        })
+    }
+}
+
+/// Returns the keys of the nodes that were removed from the forest with the old roots, and the keys
+/// of the nodes that were inserted to the forest with the new roots, once for every index.
+fn diff_forests(
+    storage: &impl ReadOnlyStorage,
+    old_roots: ForestRoots,
+    new_roots: ForestRoots,
+) -> ForestResult<(Vec<StorageKey>, Vec<StorageKey>)> {
+    let classes_trie_diff = diff_trees::<CompiledClassHash>(
+        storage,
+        old_roots.classes_trie_root_hash,
+        new_roots.classes_trie_root_hash,
+    )?;
+    let contracts_trie_diff = diff_trees::<ContractState>(
+        storage,
+        old_roots.contracts_trie_root_hash,
+        new_roots.contracts_trie_root_hash,
+    )?;
+    let mut removed_keys =
+        [classes_trie_diff.removed_keys, contracts_trie_diff.removed_keys].concat();
+    let mut inserted_keys =
+        [classes_trie_diff.inserted_keys, contracts_trie_diff.inserted_keys].concat();
+    for (_, old_contract_state, new_contract_state) in contracts_trie_diff.modified_leaves {
+        let storage_trie_diff = diff_trees::<StarknetStorageValue>(
+            storage,
+            old_contract_state.storage_root_hash,
+            new_contract_state.storage_root_hash,
+        )?;
+        removed_keys.extend(storage_trie_diff.removed_keys);
+        inserted_keys.extend(storage_trie_diff.inserted_keys);
+    }
+    Ok((removed_keys, inserted_keys))
+}
+
+// Reads the new nodes of a block before they are written.
+struct OverlayStorage<'a, S: ReadOnlyStorage> {
+    nodes: &'a HashMap<StorageKey, StorageValue>,
+    storage: &'a S,
+}
+
+impl<S: ReadOnlyStorage> ReadOnlyStorage for OverlayStorage<'_, S> {
+    fn get(&self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        match self.nodes.get(key) {
+            Some(value) => Ok(Some(value.clone())),
+            None => self.storage.get(key),
+        }
+    }
+
+    fn mget(&self, keys: &[StorageKey]) -> StorageResult<Vec<Option<StorageValue>>> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+}
+
+fn node_references_key(key: &StorageKey) -> StorageKey {
+    StorageKey([NODE_REFERENCES_PREFIX, &key.0].concat())
+}
+
+fn stale_nodes_key(block_number: u64) -> StorageKey {
+    StorageKey([STALE_NODES_PREFIX, &block_number.to_be_bytes()].concat())
+}
+
+fn decode_u64(key: &StorageKey, value: &StorageValue) -> ForestResult<u64> {
+    let bytes = value
+        .0
+        .as_slice()
+        .try_into()
+        .map_err(|_| ForestError::InvalidMetadata { key: key.clone() })?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+// Every key is encoded as its length (4 bytes, big-endian) followed by its bytes.
+fn encode_keys(keys: &[StorageKey]) -> StorageValue {
+    let mut value = Vec::new();
+    for key in keys {
+        let length = u32::try_from(key.0.len()).expect("Node keys should be short.");
+        value.extend(length.to_be_bytes());
+        value.extend(&key.0);
+    }
+    StorageValue(value)
+}
+
+fn decode_keys(key: &StorageKey, value: &StorageValue) -> ForestResult<Vec<StorageKey>> {
+    let invalid_metadata = || ForestError::InvalidMetadata { key: key.clone() };
+    let mut keys = Vec::new();
+    let mut remaining = value.0.as_slice();
+    while !remaining.is_empty() {
+        let (length, rest) = remaining.split_first_chunk::<4>().ok_or_else(invalid_metadata)?;
+        let length =
+            usize::try_from(u32::from_be_bytes(*length)).map_err(|_| invalid_metadata())?;
+        if rest.len() < length {
+            return Err(invalid_metadata());
+        }
+        let (node_key, rest) = rest.split_at(length);
+        keys.push(StorageKey(node_key.to_vec()));
+        remaining = rest;
+    }
+    Ok(keys)
+}
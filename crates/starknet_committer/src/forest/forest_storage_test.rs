@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::ops::Range;
+
+use starknet_patricia::felt::Felt;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::errors::MerkleProofError;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::proof::verify_merkle_proof;
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_patricia::storage::map_storage::MapStorage;
+use starknet_patricia::storage::storage_trait::StorageValue;
+use tracing::level_filters::LevelFilter;
+
+use crate::block_committer::commit::commit_state_diff;
+use crate::block_committer::input::{
+    ConfigImpl,
+    ContractAddress,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff,
+};
+use crate::forest::forest_storage::{ForestRoots, ForestStorage};
+use crate::forest::storage_proof::StorageProof;
+use crate::hash_function::hash::TreeHashFunctionImpl;
+use crate::patricia_merkle_tree::types::{ClassHash, CompiledClassHash};
+
+const CONTRACT_ADDRESS: ContractAddress = ContractAddress(Felt::ONE);
+
+fn storage_key(value: u8) -> StarknetStorageKey {
+    StarknetStorageKey(Felt::from(value))
+}
+
+fn storage_updates(updates: &[(u8, u8)]) -> StateDiff {
+    StateDiff {
+        storage_updates: HashMap::from([(
+            CONTRACT_ADDRESS,
+            updates
+                .iter()
+                .map(|(key, value)| (storage_key(*key), StarknetStorageValue(Felt::from(*value))))
+                .collect(),
+        )]),
+        ..Default::default()
+    }
+}
+
+/// Commits the block on top of the forest with the given roots, writes it to the storage and
+/// returns the roots after the block and the pruned blocks.
+async fn commit_block(
+    storage: &mut MapStorage,
+    retained_blocks: u64,
+    block_number: u64,
+    parent_roots: ForestRoots,
+    state_diff: &StateDiff,
+) -> (ForestRoots, Range<u64>) {
+    let filled_forest = commit_state_diff(
+        &*storage,
+        parent_roots.contracts_trie_root_hash,
+        parent_roots.classes_trie_root_hash,
+        state_diff,
+        &ConfigImpl::new(false, LevelFilter::INFO),
+    )
+    .await
+    .unwrap();
+    let forest_writes = ForestStorage::new(&*storage, NonZeroU64::new(retained_blocks).unwrap())
+        .write_block(block_number, parent_roots, &filled_forest)
+        .unwrap();
+    let pruned_blocks = forest_writes.pruned_blocks.clone();
+    forest_writes.apply(storage).unwrap();
+    (ForestRoots::from(&filled_forest), pruned_blocks)
+}
+
+/// Commits the blocks from the genesis, and returns the roots after every block.
+async fn commit_blocks(
+    storage: &mut MapStorage,
+    retained_blocks: u64,
+    state_diffs: &[StateDiff],
+) -> Vec<ForestRoots> {
+    let mut roots = Vec::new();
+    for (block_number, state_diff) in (0..).zip(state_diffs) {
+        let parent_roots = roots.last().copied().unwrap_or_default();
+        roots.push(
+            commit_block(storage, retained_blocks, block_number, parent_roots, state_diff).await.0,
+        );
+    }
+    roots
+}
+
+/// Proves the value of the given storage key of the contract in the forest with the given roots.
+fn prove_storage_value(
+    storage: &MapStorage,
+    roots: &ForestRoots,
+    key: u8,
+    value: u8,
+) -> Result<(), MerkleProofError> {
+    let proof = StorageProof::fetch(
+        storage,
+        roots.contracts_trie_root_hash,
+        roots.classes_trie_root_hash,
+        &[],
+        &[],
+        &HashMap::from([(CONTRACT_ADDRESS, vec![storage_key(key)])]),
+    )?;
+    let contract_state = &proof.contracts_proof.leaves[&NodeIndex::from(&CONTRACT_ADDRESS)];
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        contract_state.storage_root_hash,
+        NodeIndex::from(&storage_key(key)),
+        &StarknetStorageValue(Felt::from(value)),
+        &proof.contracts_storage_proofs[&CONTRACT_ADDRESS].nodes,
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_blocks_leave_the_retention_window() {
+    let mut storage = MapStorage::default();
+    let first_block = StateDiff {
+        address_to_class_hash: HashMap::from([(CONTRACT_ADDRESS, ClassHash(Felt::from(10_u8)))]),
+        class_hash_to_compiled_class_hash: HashMap::from([(
+            ClassHash(Felt::from(10_u8)),
+            CompiledClassHash(Felt::from(100_u8)),
+        )]),
+        ..storage_updates(&[(2, 20), (3, 30)])
+    };
+    let [first_block_roots, second_block_roots]: [ForestRoots; 2] =
+        commit_blocks(&mut storage, 2, &[first_block, storage_updates(&[(2, 21)])])
+            .await
+            .try_into()
+            .unwrap();
+    prove_storage_value(&storage, &first_block_roots, 2, 20).unwrap();
+
+    // The third block pushes the first block out of the window.
+    let (third_block_roots, pruned_blocks) =
+        commit_block(&mut storage, 2, 2, second_block_roots, &storage_updates(&[(3, 31)])).await;
+
+    assert_eq!(pruned_blocks, 0..1);
+    assert_eq!(
+        ForestStorage::new(&storage, NonZeroU64::new(2).unwrap()).oldest_retained_block().unwrap(),
+        1
+    );
+    prove_storage_value(&storage, &second_block_roots, 2, 21).unwrap();
+    prove_storage_value(&storage, &second_block_roots, 3, 30).unwrap();
+    prove_storage_value(&storage, &third_block_roots, 2, 21).unwrap();
+    prove_storage_value(&storage, &third_block_roots, 3, 31).unwrap();
+    assert!(matches!(
+        prove_storage_value(&storage, &first_block_roots, 2, 20),
+        Err(MerkleProofError::MissingNode(_))
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_no_pruning_within_retention_window() {
+    let mut storage = MapStorage::default();
+    let roots =
+        commit_blocks(&mut storage, 3, &[storage_updates(&[(2, 20)]), storage_updates(&[(2, 21)])])
+            .await;
+
+    let (latest_roots, pruned_blocks) =
+        commit_block(&mut storage, 3, 2, roots[1], &storage_updates(&[(2, 22)])).await;
+
+    assert!(pruned_blocks.is_empty());
+    prove_storage_value(&storage, &roots[0], 2, 20).unwrap();
+    prove_storage_value(&storage, &roots[1], 2, 21).unwrap();
+    prove_storage_value(&storage, &latest_roots, 2, 22).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_storage_does_not_grow_with_the_history() {
+    let mut storage = MapStorage::default();
+    let mut roots = ForestRoots::default();
+    let mut n_keys = Vec::new();
+
+    // Every block replaces the same leaf.
+    for block_number in 0..10 {
+        let value = u8::try_from(block_number).unwrap() + 1;
+        roots = commit_block(&mut storage, 2, block_number, roots, &storage_updates(&[(2, value)]))
+            .await
+            .0;
+        n_keys.push(storage.storage.len());
+    }
+
+    // Once the window is full, every block prunes as many nodes as it adds.
+    assert!(n_keys[3..].iter().all(|n| *n == n_keys[2]));
+    prove_storage_value(&storage, &roots, 2, 10).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shared_nodes_are_kept_while_used() {
+    let mut storage = MapStorage::default();
+    // The leaves of both keys are the same node.
+    let roots = commit_blocks(
+        &mut storage,
+        1,
+        &[storage_updates(&[(2, 20), (3, 20)]), storage_updates(&[(2, 21)])],
+    )
+    .await;
+
+    prove_storage_value(&storage, &roots[1], 2, 21).unwrap();
+    prove_storage_value(&storage, &roots[1], 3, 20).unwrap();
+    assert!(prove_storage_value(&storage, &roots[0], 2, 20).is_err());
+    let is_leaf_of_20 = |value: &StorageValue| value.0 == Felt::from(20_u8).to_bytes_be();
+    assert!(storage.storage.values().any(is_leaf_of_20));
+
+    // Once no key uses the node, it is pruned with the rest of the old forest.
+    let (latest_roots, _) =
+        commit_block(&mut storage, 1, 2, roots[1], &storage_updates(&[(3, 31)])).await;
+    prove_storage_value(&storage, &latest_roots, 3, 31).unwrap();
+    assert!(!storage.storage.values().any(is_leaf_of_20));
+}
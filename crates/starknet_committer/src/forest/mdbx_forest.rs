@@ -0,0 +1,171 @@
+use std::num::NonZeroU64;
+
+use papyrus_storage::db::RW;
+use papyrus_storage::patricia::{PatriciaStorageReader, PatriciaStorageWriter, StateTrieRoots};
+use papyrus_storage::{StorageReader, StorageTxn};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::GlobalRoot;
+use starknet_api::state::ThinStateDiff;
+use starknet_patricia::felt::Felt;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::storage::errors::StorageError;
+use starknet_patricia::storage::storage_trait::ReadOnlyStorage;
+use tracing::debug;
+use tracing::level_filters::LevelFilter;
+
+use crate::block_committer::commit::commit_state_diff;
+use crate::block_committer::errors::BlockCommitmentError;
+use crate::block_committer::input::{
+    ConfigImpl,
+    ContractAddress,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff,
+};
+use crate::forest::forest_storage::{ForestRoots, ForestStorage, ForestWrites};
+use crate::patricia_merkle_tree::types::{ClassHash, CompiledClassHash, Nonce};
+
+/// The number of the last blocks whose forests are kept in a papyrus storage; the nodes that only
+/// older forests use are pruned.
+pub const RETAINED_BLOCKS: u64 = 128;
+
+/// The changes that committing the state diff of a block makes to the forest of the global state,
+/// which is kept in the Patricia tables of a papyrus storage.
+///
+/// The update is written in the same transaction as the state diff of the block, so the stored
+/// forest is always the forest after the last stored state diff.
+#[derive(Debug, Default, PartialEq)]
+pub struct ForestUpdate {
+    pub forest_writes: ForestWrites,
+    pub roots: StateTrieRoots,
+}
+
+impl ForestUpdate {
+    /// Writes the changes to the nodes and the roots of the forest after the given block, and
+    /// deletes the roots of the blocks that left the retention window.
+    pub fn write<'env>(
+        &self,
+        mut txn: StorageTxn<'env, RW>,
+        block_number: BlockNumber,
+    ) -> papyrus_storage::StorageResult<StorageTxn<'env, RW>> {
+        for key in &self.forest_writes.deletions {
+            txn = txn.delete_patricia_node(&key.0)?;
+        }
+        for (key, value) in &self.forest_writes.writes {
+            txn = txn.set_patricia_node(&key.0, &value.0)?;
+        }
+        for pruned_block_number in self.forest_writes.pruned_blocks.clone() {
+            txn = txn.delete_state_trie_roots(BlockNumber(pruned_block_number))?;
+        }
+        txn.set_state_trie_roots(block_number, &self.roots)
+    }
+}
+
+/// Applies the state diff of the given block to the forest with the given roots (the forest after
+/// the previous block), read from the given storage, and returns the state root after the block
+/// together with the update of the forest. The storage is not modified.
+pub async fn commit_thin_state_diff(
+    storage: &impl ReadOnlyStorage,
+    block_number: BlockNumber,
+    parent_roots: StateTrieRoots,
+    state_diff: &ThinStateDiff,
+) -> Result<(GlobalRoot, ForestUpdate), BlockCommitmentError> {
+    let parent_roots = ForestRoots {
+        contracts_trie_root_hash: HashOutput(Felt::from(parent_roots.contracts_trie_root)),
+        classes_trie_root_hash: HashOutput(Felt::from(parent_roots.classes_trie_root)),
+    };
+    let filled_forest = commit_state_diff(
+        storage,
+        parent_roots.contracts_trie_root_hash,
+        parent_roots.classes_trie_root_hash,
+        &committer_state_diff(state_diff),
+        &ConfigImpl::new(false, LevelFilter::INFO),
+    )
+    .await?;
+    let retained_blocks = NonZeroU64::new(RETAINED_BLOCKS).expect("The window is not empty.");
+    let forest_writes = ForestStorage::new(storage, retained_blocks).write_block(
+        block_number.0,
+        parent_roots,
+        &filled_forest,
+    )?;
+    let roots = StateTrieRoots {
+        contracts_trie_root: filled_forest.get_contract_root_hash().0.into(),
+        classes_trie_root: filled_forest.get_compiled_class_root_hash().0.into(),
+    };
+    let state_root = GlobalRoot(filled_forest.get_global_root_hash().0.into());
+    Ok((state_root, ForestUpdate { forest_writes, roots }))
+}
+
+/// Applies the state diff of a block to the forest kept in the given storage, and returns the
+/// update of the forest, or None if the forest before the block is not stored (e.g., the storage
+/// was synced before the forest was kept). The storage is not modified.
+pub async fn commit_to_stored_forest(
+    storage_reader: &StorageReader,
+    block_number: BlockNumber,
+    state_diff: &ThinStateDiff,
+) -> Result<Option<ForestUpdate>, BlockCommitmentError> {
+    let parent_roots = match block_number.prev() {
+        Some(parent_block_number) => storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_state_trie_roots(parent_block_number))
+            .map_err(StorageError::from)?,
+        // The forest before the genesis block is empty.
+        None => Some(StateTrieRoots::default()),
+    };
+    let Some(parent_roots) = parent_roots else {
+        debug!("The forest before block {} is not stored; not committing to it.", block_number);
+        return Ok(None);
+    };
+    let (_, forest_update) =
+        commit_thin_state_diff(storage_reader, block_number, parent_roots, state_diff).await?;
+    Ok(Some(forest_update))
+}
+
+fn committer_state_diff(state_diff: &ThinStateDiff) -> StateDiff {
+    // Deprecated classes are not part of the classes trie.
+    StateDiff {
+        address_to_class_hash: state_diff
+            .deployed_contracts
+            .iter()
+            .chain(state_diff.replaced_classes.iter())
+            .map(|(address, class_hash)| {
+                (ContractAddress(Felt::from(*address.key())), ClassHash(Felt::from(class_hash.0)))
+            })
+            .collect(),
+        address_to_nonce: state_diff
+            .nonces
+            .iter()
+            .map(|(address, nonce)| {
+                (ContractAddress(Felt::from(*address.key())), Nonce(Felt::from(nonce.0)))
+            })
+            .collect(),
+        class_hash_to_compiled_class_hash: state_diff
+            .declared_classes
+            .iter()
+            .map(|(class_hash, compiled_class_hash)| {
+                (
+                    ClassHash(Felt::from(class_hash.0)),
+                    CompiledClassHash(Felt::from(compiled_class_hash.0)),
+                )
+            })
+            .collect(),
+        storage_updates: state_diff
+            .storage_diffs
+            .iter()
+            .map(|(address, storage_diff)| {
+                (
+                    ContractAddress(Felt::from(*address.key())),
+                    storage_diff
+                        .iter()
+                        .map(|(key, value)| {
+                            (
+                                StarknetStorageKey(Felt::from(*key.key())),
+                                StarknetStorageValue(Felt::from(*value)),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    }
+}
@@ -7,7 +7,7 @@ use starknet_patricia::patricia_merkle_tree::original_skeleton_tree::tree::{
     OriginalSkeletonTreeImpl,
 };
 use starknet_patricia::patricia_merkle_tree::types::{NodeIndex, SortedLeafIndices};
-use starknet_patricia::storage::storage_trait::Storage;
+use starknet_patricia::storage::storage_trait::ReadOnlyStorage;
 
 use crate::block_committer::input::{Config, ContractAddress, StarknetStorageValue};
 use crate::forest::forest_errors::{ForestError, ForestResult};
@@ -31,7 +31,7 @@ impl<'a> OriginalSkeletonForest<'a> {
     /// contracts, the classes trie and the contracts trie. Additionally, returns the original
     /// contract states that are needed to compute the contract state tree.
     pub(crate) fn create(
-        storage: impl Storage,
+        storage: &impl ReadOnlyStorage,
         contracts_trie_root_hash: HashOutput,
         classes_trie_root_hash: HashOutput,
         storage_updates: &HashMap<ContractAddress, LeafModifications<StarknetStorageValue>>,
@@ -44,20 +44,20 @@ impl<'a> OriginalSkeletonForest<'a> {
     {
         let (contracts_trie, original_contracts_trie_leaves) = Self::create_contracts_trie(
             contracts_trie_root_hash,
-            &storage,
+            storage,
             forest_sorted_indices.contracts_trie_sorted_indices,
         )?;
         let storage_tries = Self::create_storage_tries(
             storage_updates,
             &original_contracts_trie_leaves,
-            &storage,
+            storage,
             config,
             &forest_sorted_indices.storage_tries_sorted_indices,
         )?;
         let classes_trie = Self::create_classes_trie(
             classes_updates,
             classes_trie_root_hash,
-            &storage,
+            storage,
             config,
             forest_sorted_indices.classes_trie_sorted_indices,
         )?;
@@ -69,7 +69,7 @@ impl<'a> OriginalSkeletonForest<'a> {
     /// Also returns the previous contracts state of the modified contracts.
     fn create_contracts_trie(
         contracts_trie_root_hash: HashOutput,
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         contracts_trie_sorted_indices: SortedLeafIndices<'a>,
     ) -> ForestResult<(OriginalSkeletonTreeImpl<'a>, HashMap<NodeIndex, ContractState>)> {
         Ok(OriginalSkeletonTreeImpl::create_and_get_previous_leaves(
@@ -84,7 +84,7 @@ impl<'a> OriginalSkeletonForest<'a> {
     fn create_storage_tries(
         actual_storage_updates: &HashMap<ContractAddress, LeafModifications<StarknetStorageValue>>,
         original_contracts_trie_leaves: &HashMap<NodeIndex, ContractState>,
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         config: &impl Config,
         storage_tries_sorted_indices: &HashMap<ContractAddress, SortedLeafIndices<'a>>,
     ) -> ForestResult<HashMap<ContractAddress, OriginalSkeletonTreeImpl<'a>>> {
@@ -114,7 +114,7 @@ impl<'a> OriginalSkeletonForest<'a> {
     fn create_classes_trie(
         actual_classes_updates: &LeafModifications<CompiledClassHash>,
         classes_trie_root_hash: HashOutput,
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         config: &impl Config,
         contracts_trie_sorted_indices: SortedLeafIndices<'a>,
     ) -> ForestResult<OriginalSkeletonTreeImpl<'a>> {
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::proof::{
+    MerkleProof,
+    MerkleProofResult,
+};
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_patricia::storage::storage_trait::ReadOnlyStorage;
+
+use crate::block_committer::input::{ContractAddress, StarknetStorageKey, StarknetStorageValue};
+use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use crate::patricia_merkle_tree::types::{ClassHash, CompiledClassHash};
+
+/// Proofs of leaves of the tries of the state: the classes trie, the contracts trie and the
+/// storage tries of some contracts.
+#[derive(Debug, Default, PartialEq)]
+pub struct StorageProof {
+    pub classes_proof: MerkleProof<CompiledClassHash>,
+    pub contracts_proof: MerkleProof<ContractState>,
+    pub contracts_storage_proofs: HashMap<ContractAddress, MerkleProof<StarknetStorageValue>>,
+}
+
+impl StorageProof {
+    /// Reads the proofs of the given classes, contracts and storage keys from the storage of the
+    /// tries with the given roots.
+    /// The contracts of the storage keys are proven as well, since their states hold the roots of
+    /// their storage tries.
+    pub fn fetch(
+        storage: &impl ReadOnlyStorage,
+        contracts_trie_root_hash: HashOutput,
+        classes_trie_root_hash: HashOutput,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[ContractAddress],
+        contracts_storage_keys: &HashMap<ContractAddress, Vec<StarknetStorageKey>>,
+    ) -> MerkleProofResult<Self> {
+        let classes_proof = MerkleProof::fetch(
+            storage,
+            classes_trie_root_hash,
+            &class_hashes.iter().map(NodeIndex::from).collect::<Vec<_>>(),
+        )?;
+        let contracts_proof = MerkleProof::<ContractState>::fetch(
+            storage,
+            contracts_trie_root_hash,
+            &contract_addresses
+                .iter()
+                .chain(contracts_storage_keys.keys())
+                .map(NodeIndex::from)
+                .collect::<Vec<_>>(),
+        )?;
+
+        let mut contracts_storage_proofs = HashMap::new();
+        for (address, keys) in contracts_storage_keys {
+            // The storage trie of a contract that is not deployed is empty.
+            let storage_root_hash = contracts_proof
+                .leaves
+                .get(&NodeIndex::from(address))
+                .map_or(HashOutput::ROOT_OF_EMPTY_TREE, |state| state.storage_root_hash);
+            let storage_proof = MerkleProof::fetch(
+                storage,
+                storage_root_hash,
+                &keys.iter().map(NodeIndex::from).collect::<Vec<_>>(),
+            )?;
+            contracts_storage_proofs.insert(*address, storage_proof);
+        }
+
+        Ok(Self { classes_proof, contracts_proof, contracts_storage_proofs })
+    }
+}
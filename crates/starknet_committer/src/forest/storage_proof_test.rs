@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use starknet_patricia::felt::Felt;
+use starknet_patricia::hash::hash_trait::HashOutput;
+use starknet_patricia::patricia_merkle_tree::merkle_proof::proof::verify_merkle_proof;
+use starknet_patricia::patricia_merkle_tree::types::NodeIndex;
+use starknet_patricia::storage::map_storage::MapStorage;
+use tracing::level_filters::LevelFilter;
+
+use crate::block_committer::commit::commit_state_diff;
+use crate::block_committer::input::{
+    ConfigImpl,
+    ContractAddress,
+    StarknetStorageKey,
+    StarknetStorageValue,
+    StateDiff,
+};
+use crate::forest::storage_proof::StorageProof;
+use crate::hash_function::hash::TreeHashFunctionImpl;
+use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
+use crate::patricia_merkle_tree::types::{ClassHash, CompiledClassHash, Nonce};
+
+fn address(value: u8) -> ContractAddress {
+    ContractAddress(Felt::from(value))
+}
+
+fn storage_key(value: u8) -> StarknetStorageKey {
+    StarknetStorageKey(Felt::from(value))
+}
+
+fn class_hash(value: u8) -> ClassHash {
+    ClassHash(Felt::from(value))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fetch_and_verify_storage_proof() {
+    let state_diff = StateDiff {
+        address_to_class_hash: HashMap::from([(address(1), class_hash(10))]),
+        address_to_nonce: HashMap::from([(address(1), Nonce(Felt::ONE))]),
+        class_hash_to_compiled_class_hash: HashMap::from([(
+            class_hash(10),
+            CompiledClassHash(Felt::from(100_u8)),
+        )]),
+        storage_updates: HashMap::from([(
+            address(1),
+            HashMap::from([
+                (storage_key(2), StarknetStorageValue(Felt::from(20_u8))),
+                (storage_key(3), StarknetStorageValue(Felt::from(30_u8))),
+            ]),
+        )]),
+    };
+    let mut storage = MapStorage::default();
+    let filled_forest = commit_state_diff(
+        &storage,
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        &state_diff,
+        &ConfigImpl::new(false, LevelFilter::INFO),
+    )
+    .await
+    .unwrap();
+    filled_forest.write_to_storage(&mut storage).unwrap();
+    let contracts_trie_root_hash = filled_forest.get_contract_root_hash();
+    let classes_trie_root_hash = filled_forest.get_compiled_class_root_hash();
+
+    // Class 11, contract 4 and storage key 5 are not in the state.
+    let proof = StorageProof::fetch(
+        &storage,
+        contracts_trie_root_hash,
+        classes_trie_root_hash,
+        &[class_hash(10), class_hash(11)],
+        &[address(4)],
+        &HashMap::from([
+            (address(1), vec![storage_key(2), storage_key(5)]),
+            (address(4), vec![storage_key(2)]),
+        ]),
+    )
+    .unwrap();
+
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        classes_trie_root_hash,
+        NodeIndex::from(&class_hash(10)),
+        &CompiledClassHash(Felt::from(100_u8)),
+        &proof.classes_proof.nodes,
+    )
+    .unwrap();
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        classes_trie_root_hash,
+        NodeIndex::from(&class_hash(11)),
+        &CompiledClassHash::default(),
+        &proof.classes_proof.nodes,
+    )
+    .unwrap();
+
+    let contract_state = &proof.contracts_proof.leaves[&NodeIndex::from(&address(1))];
+    assert_eq!(contract_state.class_hash, class_hash(10));
+    assert_eq!(contract_state.nonce, Nonce(Felt::ONE));
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        contracts_trie_root_hash,
+        NodeIndex::from(&address(1)),
+        contract_state,
+        &proof.contracts_proof.nodes,
+    )
+    .unwrap();
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        contracts_trie_root_hash,
+        NodeIndex::from(&address(4)),
+        &ContractState::default(),
+        &proof.contracts_proof.nodes,
+    )
+    .unwrap();
+
+    let storage_proof = &proof.contracts_storage_proofs[&address(1)];
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        contract_state.storage_root_hash,
+        NodeIndex::from(&storage_key(2)),
+        &StarknetStorageValue(Felt::from(20_u8)),
+        &storage_proof.nodes,
+    )
+    .unwrap();
+    verify_merkle_proof::<_, TreeHashFunctionImpl>(
+        contract_state.storage_root_hash,
+        NodeIndex::from(&storage_key(5)),
+        &StarknetStorageValue::default(),
+        &storage_proof.nodes,
+    )
+    .unwrap();
+
+    // The storage trie of an undeployed contract is empty.
+    assert!(proof.contracts_storage_proofs[&address(4)].nodes.is_empty());
+}
@@ -36,6 +36,32 @@ impl TreeHashFunctionImpl {
     // The hex string corresponding to b'CONTRACT_CLASS_LEAF_V0' in big-endian.
     pub const CONTRACT_CLASS_LEAF_V0: &'static str =
         "0x434f4e54524143545f434c4153535f4c4541465f5630";
+
+    // The hex string corresponding to b'STARKNET_STATE_V0' in big-endian.
+    pub const STARKNET_STATE_V0: &'static str = "0x535441524b4e45545f53544154455f5630";
+}
+
+/// Computes the global state root from the roots of the contracts trie and the classes trie.
+/// The implementation is based on the following reference:
+/// <https://docs.starknet.io/architecture-and-concepts/network-architecture/starknet-state/#state_commitment>
+pub fn calculate_global_root_hash(
+    contracts_trie_root_hash: HashOutput,
+    classes_trie_root_hash: HashOutput,
+) -> HashOutput {
+    // States without declared Cairo 1 classes are committed to by the contracts trie alone.
+    if classes_trie_root_hash == HashOutput::ROOT_OF_EMPTY_TREE {
+        return contracts_trie_root_hash;
+    }
+    let global_state_version = Felt::from_hex(TreeHashFunctionImpl::STARKNET_STATE_V0)
+        .expect("could not parse hex string corresponding to b'STARKNET_STATE_V0' to Felt");
+    HashOutput(
+        Poseidon::hash_array(&[
+            global_state_version.into(),
+            contracts_trie_root_hash.0.into(),
+            classes_trie_root_hash.0.into(),
+        ])
+        .into(),
+    )
 }
 
 /// Implementation of TreeHashFunction for contracts trie.
@@ -10,10 +10,10 @@ use starknet_patricia::patricia_merkle_tree::node_data::inner_node::{
     PathToBottom,
 };
 use starknet_patricia::patricia_merkle_tree::updated_skeleton_tree::hash_function::TreeHashFunction;
-use starknet_types_core::hash::Pedersen;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
 
 use crate::block_committer::input::StarknetStorageValue;
-use crate::hash_function::hash::TreeHashFunctionImpl;
+use crate::hash_function::hash::{calculate_global_root_hash, TreeHashFunctionImpl};
 use crate::patricia_merkle_tree::leaf::leaf_impl::ContractState;
 use crate::patricia_merkle_tree::types::{ClassHash, CompiledClassHash, Nonce};
 
@@ -91,8 +91,6 @@ fn test_tree_hash_function_impl_binary_node(
     #[case] right_hash: Felt,
     #[case] expected_hash: Felt,
 ) {
-    use starknet_types_core::hash::StarkHash;
-
     let hash_output =
         TreeHashFunctionImpl::compute_node_hash(&NodeData::<StarknetStorageValue>::Binary(
             BinaryData { left_hash: HashOutput(left_hash), right_hash: HashOutput(right_hash) },
@@ -114,8 +112,6 @@ fn test_tree_hash_function_impl_edge_node(
     #[case] length: u8,
     #[case] expected_hash: Felt,
 ) {
-    use starknet_types_core::hash::StarkHash;
-
     let hash_output = TreeHashFunctionImpl::compute_node_hash(
         &NodeData::<StarknetStorageValue>::Edge(EdgeData {
             bottom_hash: HashOutput(bottom_hash),
@@ -140,3 +136,32 @@ fn test_constant_contract_class_leaf_v0() {
         b"CONTRACT_CLASS_LEAF_V0".as_slice()
     );
 }
+
+#[rstest]
+fn test_constant_starknet_state_v0() {
+    assert_eq!(
+        hex::decode(TreeHashFunctionImpl::STARKNET_STATE_V0.trim_start_matches("0x")).unwrap(),
+        b"STARKNET_STATE_V0".as_slice()
+    );
+}
+
+#[rstest]
+#[case::empty_classes_trie(HashOutput::ROOT_OF_EMPTY_TREE, HashOutput(Felt::from(0xC0FFEE_u128)))]
+#[case::non_empty_classes_trie(
+    HashOutput(Felt::from(0xBEEF_u128)),
+    HashOutput(
+        Poseidon::hash_array(&[
+            Felt::from_hex(TreeHashFunctionImpl::STARKNET_STATE_V0).unwrap().into(),
+            Felt::from(0xC0FFEE_u128).into(),
+            Felt::from(0xBEEF_u128).into(),
+        ])
+        .into()
+    )
+)]
+fn test_global_root_hash(#[case] classes_trie_root_hash: HashOutput, #[case] expected: HashOutput) {
+    let contracts_trie_root_hash = HashOutput(Felt::from(0xC0FFEE_u128));
+    assert_eq!(
+        calculate_global_root_hash(contracts_trie_root_hash, classes_trie_root_hash),
+        expected
+    );
+}
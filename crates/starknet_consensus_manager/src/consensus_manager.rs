@@ -2,8 +2,10 @@ use std::any::type_name;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use papyrus_consensus::signing::{create_signer, ConsensusSigner};
 use papyrus_consensus::stream_handler::StreamHandler;
 use papyrus_consensus::types::ConsensusError;
+use papyrus_consensus::wal::Wal;
 use papyrus_consensus_orchestrator::sequencer_consensus_context::SequencerConsensusContext;
 use papyrus_network::gossipsub_impl::Topic;
 use papyrus_network::network_manager::{BroadcastTopicChannels, NetworkManager};
@@ -13,6 +15,7 @@ use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sequencer_infra::errors::ComponentError;
 use starknet_state_sync_types::communication::SharedStateSyncClient;
 use tracing::{error, info};
+use validator::Validate;
 
 use crate::config::ConsensusManagerConfig;
 
@@ -38,6 +41,12 @@ impl ConsensusManager {
     }
 
     pub async fn run(&self) -> Result<(), ConsensusError> {
+        // The consensus config is only used, and thus validated, once consensus runs.
+        self.config
+            .consensus_config
+            .validate()
+            .map_err(|e| ConsensusError::InvalidConfig(e.to_string()))?;
+
         let mut network_manager =
             NetworkManager::new(self.config.consensus_config.network_config.clone(), None);
 
@@ -63,11 +72,18 @@ impl ConsensusManager {
         let (outbound_internal_sender, inbound_internal_receiver, mut stream_handler_task_handle) =
             StreamHandler::get_channels(inbound_network_receiver, outbound_network_sender);
 
+        let signer = create_signer(&self.config.consensus_config.signer)
+            .map_err(|e| ConsensusError::SigningError(e.to_string()))?;
+        let signer = ConsensusSigner::new(self.config.consensus_config.chain_id.clone(), signer);
+        let wal = self.config.consensus_config.wal_path.as_deref().map(Wal::open).transpose()?;
         let context = SequencerConsensusContext::new(
             Arc::clone(&self.batcher_client),
             outbound_internal_sender,
             votes_broadcast_channels.broadcast_topic_client.clone(),
             self.config.consensus_config.num_validators,
+            self.config.consensus_config.validator_public_keys.clone(),
+            self.config.consensus_config.validator_weights.clone(),
+            signer.clone(),
             self.config.consensus_config.chain_id.clone(),
         );
 
@@ -80,6 +96,8 @@ impl ConsensusManager {
             self.config.consensus_config.validator_id,
             self.config.consensus_config.consensus_delay,
             self.config.consensus_config.timeouts.clone(),
+            signer,
+            wal,
             votes_broadcast_channels.into(),
             inbound_internal_receiver,
             futures::stream::pending(),
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, RwLock};
+
+use blockifier::context::BlockContext;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::metrics::{GATEWAY_STATE_CACHE_HITS, GATEWAY_STATE_CACHE_MISSES};
+use crate::state_reader::MempoolStateReader;
+
+#[cfg(test)]
+#[path = "block_state_cache_test.rs"]
+mod block_state_cache_test;
+
+/// The block context and the state read at a single block, shared by all the transactions
+/// validated on top of it.
+pub struct BlockCache {
+    block_number: BlockNumber,
+    block_context: BlockContext,
+    nonces: RwLock<HashMap<ContractAddress, Nonce>>,
+    class_hashes: RwLock<HashMap<ContractAddress, ClassHash>>,
+    compiled_classes: RwLock<HashMap<ClassHash, RunnableCompiledClass>>,
+}
+
+impl BlockCache {
+    fn new(block_number: BlockNumber, block_context: BlockContext) -> Self {
+        Self {
+            block_number,
+            block_context,
+            nonces: Default::default(),
+            class_hashes: Default::default(),
+            compiled_classes: Default::default(),
+        }
+    }
+
+    pub fn block_context(&self) -> &BlockContext {
+        &self.block_context
+    }
+}
+
+/// Caches the block context and hot state of the latest block across gateway requests. The cache
+/// is invalidated once a different latest block is observed.
+#[derive(Default)]
+pub struct BlockStateCache {
+    latest_block_cache: Mutex<Option<Arc<BlockCache>>>,
+}
+
+impl BlockStateCache {
+    /// Returns the cache of the given block, creating it, and dropping the cache of the previously
+    /// observed block, if it is not cached yet.
+    pub fn get_or_create(
+        &self,
+        block_number: BlockNumber,
+        create_block_context: impl FnOnce() -> BlockContext,
+    ) -> Arc<BlockCache> {
+        let mut latest_block_cache =
+            self.latest_block_cache.lock().expect("Block state cache lock should not be poisoned.");
+        if let Some(block_cache) = latest_block_cache.as_ref() {
+            if block_cache.block_number == block_number {
+                record_cache_access("block_context", true);
+                return block_cache.clone();
+            }
+        }
+
+        record_cache_access("block_context", false);
+        let block_cache = Arc::new(BlockCache::new(block_number, create_block_context()));
+        *latest_block_cache = Some(block_cache.clone());
+        block_cache
+    }
+}
+
+/// A read-through cache on top of a state reader of the cached block. Storage reads are not cached.
+pub struct CachingStateReader {
+    state_reader: Box<dyn MempoolStateReader>,
+    block_cache: Arc<BlockCache>,
+}
+
+impl CachingStateReader {
+    pub fn new(state_reader: Box<dyn MempoolStateReader>, block_cache: Arc<BlockCache>) -> Self {
+        Self { state_reader, block_cache }
+    }
+}
+
+impl MempoolStateReader for CachingStateReader {
+    fn get_block_info(&self) -> StateResult<BlockInfo> {
+        self.state_reader.get_block_info()
+    }
+}
+
+impl BlockifierStateReader for CachingStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.state_reader.get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        read_through(&self.block_cache.nonces, contract_address, "nonce", || {
+            self.state_reader.get_nonce_at(contract_address)
+        })
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        read_through(&self.block_cache.class_hashes, contract_address, "class_hash", || {
+            self.state_reader.get_class_hash_at(contract_address)
+        })
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        read_through(&self.block_cache.compiled_classes, class_hash, "compiled_class", || {
+            self.state_reader.get_compiled_class(class_hash)
+        })
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.state_reader.get_compiled_class_hash(class_hash)
+    }
+}
+
+/// Returns the cached value of the given key, or reads and caches it on a miss. Failed reads are
+/// not cached.
+fn read_through<K: Eq + Hash, V: Clone>(
+    cache: &RwLock<HashMap<K, V>>,
+    key: K,
+    entry: &'static str,
+    read: impl FnOnce() -> StateResult<V>,
+) -> StateResult<V> {
+    if let Some(value) =
+        cache.read().expect("Block state cache lock should not be poisoned.").get(&key)
+    {
+        record_cache_access(entry, true);
+        return Ok(value.clone());
+    }
+
+    record_cache_access(entry, false);
+    let value = read()?;
+    cache
+        .write()
+        .expect("Block state cache lock should not be poisoned.")
+        .insert(key, value.clone());
+    Ok(value)
+}
+
+fn record_cache_access(entry: &'static str, hit: bool) {
+    let metric_name = if hit { GATEWAY_STATE_CACHE_HITS } else { GATEWAY_STATE_CACHE_MISSES };
+    metrics::counter!(metric_name, 1, "entry" => entry);
+}
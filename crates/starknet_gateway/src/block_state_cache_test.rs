@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use blockifier::context::BlockContext;
+use blockifier::state::state_api::StateReader as BlockifierStateReader;
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::{CairoVersion, RunnableCairo1};
+use rstest::rstest;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::Nonce;
+use starknet_api::nonce;
+
+use crate::block_state_cache::{BlockStateCache, CachingStateReader};
+use crate::state_reader::StateReaderFactory;
+use crate::state_reader_test_utils::local_test_state_reader_factory;
+
+#[rstest]
+fn test_block_cache_reused_within_block() {
+    // Setup.
+    let block_state_cache = BlockStateCache::default();
+    let block_cache =
+        block_state_cache.get_or_create(BlockNumber(1), BlockContext::create_for_testing);
+
+    // Test.
+    let same_block_cache = block_state_cache
+        .get_or_create(BlockNumber(1), || panic!("Block context should not be recreated."));
+
+    // Assert.
+    assert!(Arc::ptr_eq(&block_cache, &same_block_cache));
+}
+
+#[rstest]
+fn test_block_cache_invalidated_on_new_block() {
+    // Setup.
+    let block_state_cache = BlockStateCache::default();
+    let block_cache =
+        block_state_cache.get_or_create(BlockNumber(1), BlockContext::create_for_testing);
+    block_cache.nonces.write().unwrap().insert(Default::default(), Nonce::default());
+
+    // Test.
+    let new_block_cache =
+        block_state_cache.get_or_create(BlockNumber(2), BlockContext::create_for_testing);
+
+    // Assert.
+    assert!(!Arc::ptr_eq(&block_cache, &new_block_cache));
+    assert_eq!(new_block_cache.block_number, BlockNumber(2));
+    assert!(new_block_cache.nonces.read().unwrap().is_empty());
+}
+
+#[rstest]
+fn test_caching_state_reader_reads_through() {
+    // Setup.
+    let cairo_version = CairoVersion::Cairo1(RunnableCairo1::Casm);
+    let state_reader_factory = local_test_state_reader_factory(cairo_version, false);
+    let block_number = state_reader_factory.state_reader.block_info.block_number;
+    let block_cache =
+        BlockStateCache::default().get_or_create(block_number, BlockContext::create_for_testing);
+    let account_contract = FeatureContract::AccountWithoutValidations(cairo_version);
+    let address = account_contract.get_instance_address(0);
+    let class_hash = account_contract.get_class_hash();
+
+    // Test.
+    let caching_state_reader = CachingStateReader::new(
+        state_reader_factory.get_state_reader(block_number),
+        block_cache.clone(),
+    );
+    let nonce = caching_state_reader.get_nonce_at(address).unwrap();
+    let read_class_hash = caching_state_reader.get_class_hash_at(address).unwrap();
+    let compiled_class = caching_state_reader.get_compiled_class(class_hash).unwrap();
+
+    // Assert.
+    assert_eq!(read_class_hash, class_hash);
+    assert_eq!(block_cache.nonces.read().unwrap().get(&address), Some(&nonce));
+    assert_eq!(block_cache.class_hashes.read().unwrap().get(&address), Some(&class_hash));
+    assert_eq!(
+        block_cache.compiled_classes.read().unwrap().get(&class_hash),
+        Some(&compiled_class)
+    );
+
+    // A reader of a following request is served from the cache.
+    let cached_nonce = nonce!(7_u8);
+    block_cache.nonces.write().unwrap().insert(address, cached_nonce);
+    let caching_state_reader =
+        CachingStateReader::new(state_reader_factory.get_state_reader(block_number), block_cache);
+    assert_eq!(caching_state_reader.get_nonce_at(address).unwrap(), cached_nonce);
+}
@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 use blockifier::context::ChainInfo;
 use blockifier::versioned_constants::VersionedConstantsOverrides;
+use papyrus_config::converters::deserialize_seconds_to_duration;
 use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
@@ -18,11 +20,20 @@ pub struct GatewayConfig {
     pub stateless_tx_validator_config: StatelessTransactionValidatorConfig,
     pub stateful_tx_validator_config: StatefulTransactionValidatorConfig,
     pub chain_info: ChainInfo,
+    pub state_reader_source: StateReaderSource,
+    pub congestion_control_config: CongestionControlConfig,
 }
 
 impl SerializeConfig for GatewayConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
         vec![
+            BTreeMap::from_iter([ser_param(
+                "state_reader_source",
+                &self.state_reader_source,
+                "The source of the state the transactions are validated against: Rpc, StateSync \
+                 or Storage (requires the state sync to run in the same process).",
+                ParamPrivacyInput::Public,
+            )]),
             append_sub_config_name(
                 self.stateless_tx_validator_config.dump(),
                 "stateless_tx_validator_config",
@@ -32,6 +43,10 @@ impl SerializeConfig for GatewayConfig {
                 "stateful_tx_validator_config",
             ),
             append_sub_config_name(self.chain_info.dump(), "chain_info"),
+            append_sub_config_name(
+                self.congestion_control_config.dump(),
+                "congestion_control_config",
+            ),
         ]
         .into_iter()
         .flatten()
@@ -39,6 +54,80 @@ impl SerializeConfig for GatewayConfig {
     }
 }
 
+/// The source of the state the gateway validates transactions against.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum StateReaderSource {
+    // A JSON-RPC node, configured by `RpcStateReaderConfig`.
+    #[default]
+    Rpc,
+    // The state sync component, through its client.
+    StateSync,
+    // The storage of a state sync component running in the same process.
+    Storage,
+}
+
+/// Limits on the load a gateway accepts; requests over a limit are rejected before being processed.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct CongestionControlConfig {
+    pub max_concurrent_validations: usize,
+    // The following limits are counted over fixed windows of `window_duration`.
+    pub max_declares_per_sender_per_window: usize,
+    pub max_txs_per_sender_per_window: usize,
+    pub max_txs_per_peer_per_window: usize,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub window_duration: Duration,
+}
+
+impl Default for CongestionControlConfig {
+    fn default() -> Self {
+        CongestionControlConfig {
+            max_concurrent_validations: 64,
+            max_declares_per_sender_per_window: 10,
+            max_txs_per_sender_per_window: 100,
+            max_txs_per_peer_per_window: 1000,
+            window_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+impl SerializeConfig for CongestionControlConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "max_concurrent_validations",
+                &self.max_concurrent_validations,
+                "Maximal number of transactions processed concurrently.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_declares_per_sender_per_window",
+                &self.max_declares_per_sender_per_window,
+                "Maximal number of declare transactions accepted from a single sender address in \
+                 a window.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_txs_per_sender_per_window",
+                &self.max_txs_per_sender_per_window,
+                "Maximal number of transactions accepted from a single sender address in a window.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_txs_per_peer_per_window",
+                &self.max_txs_per_peer_per_window,
+                "Maximal number of transactions accepted from a single p2p peer in a window.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "window_duration",
+                &self.window_duration.as_secs(),
+                "Duration in seconds of the windows the rate limits are counted over.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct StatelessTransactionValidatorConfig {
     // If true, validates that the resource bounds are not zero.
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use papyrus_network_types::network_types::{BroadcastedMessageMetadata, OpaquePeerId};
+use starknet_api::core::ContractAddress;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_gateway_types::errors::GatewaySpecError;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+
+use crate::config::CongestionControlConfig;
+use crate::errors::{GatewayResult, StatelessTransactionValidatorError};
+use crate::metrics::GATEWAY_CONGESTION_REJECTIONS;
+
+#[cfg(test)]
+#[path = "congestion_control_test.rs"]
+mod congestion_control_test;
+
+/// Rejects transactions over the limits of the gateway, before they are processed. A rejected
+/// transaction is not counted toward any limit.
+///
+/// Note: a transaction received over p2p that is rejected by the per-peer limit is reported to the
+/// peer manager along with any other gateway error, except for rejections by the other limits.
+pub struct CongestionController {
+    config: CongestionControlConfig,
+    validation_permits: Arc<Semaphore>,
+    // All counters are locked in the order of declaration, to check all limits before charging
+    // any.
+    peer_txs: Mutex<WindowCounter<OpaquePeerId>>,
+    sender_declares: Mutex<WindowCounter<ContractAddress>>,
+    sender_txs: Mutex<WindowCounter<ContractAddress>>,
+}
+
+impl CongestionController {
+    pub fn new(config: CongestionControlConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            validation_permits: Arc::new(Semaphore::new(config.max_concurrent_validations)),
+            peer_txs: Mutex::new(WindowCounter::new(now)),
+            sender_declares: Mutex::new(WindowCounter::new(now)),
+            sender_txs: Mutex::new(WindowCounter::new(now)),
+            config,
+        }
+    }
+
+    /// Admits the given transaction for processing, returning a permit that must be held until
+    /// its processing ends.
+    pub fn admit(
+        &self,
+        tx: &RpcTransaction,
+        p2p_message_metadata: Option<&BroadcastedMessageMetadata>,
+    ) -> GatewayResult<OwnedSemaphorePermit> {
+        self.admit_at(tx, p2p_message_metadata, Instant::now())
+    }
+
+    fn admit_at(
+        &self,
+        tx: &RpcTransaction,
+        p2p_message_metadata: Option<&BroadcastedMessageMetadata>,
+        now: Instant,
+    ) -> GatewayResult<OwnedSemaphorePermit> {
+        let sender_address =
+            tx.calculate_sender_address().map_err(StatelessTransactionValidatorError::from)?;
+        let is_declare = matches!(tx, RpcTransaction::Declare(_));
+        let window = self.config.window_duration;
+
+        let mut peer_txs = lock(&self.peer_txs);
+        let mut sender_declares = lock(&self.sender_declares);
+        let mut sender_txs = lock(&self.sender_txs);
+
+        if let Some(metadata) = p2p_message_metadata {
+            let limit = self.config.max_txs_per_peer_per_window;
+            if !peer_txs.has_room(&metadata.originator_id, limit, window, now) {
+                return Err(reject_peer(limit, window));
+            }
+        }
+        if is_declare {
+            let limit = self.config.max_declares_per_sender_per_window;
+            if !sender_declares.has_room(&sender_address, limit, window, now) {
+                return Err(reject_limit("declare", limit, window));
+            }
+        }
+        let limit = self.config.max_txs_per_sender_per_window;
+        if !sender_txs.has_room(&sender_address, limit, window, now) {
+            return Err(reject_limit("sender", limit, window));
+        }
+
+        let permit = self.validation_permits.clone().try_acquire_owned().map_err(|_| {
+            reject("concurrent_validations", "Too many transactions are being processed.")
+        })?;
+
+        // All limits were checked; charge them.
+        if let Some(metadata) = p2p_message_metadata {
+            peer_txs.increment(metadata.originator_id.clone());
+        }
+        if is_declare {
+            sender_declares.increment(sender_address);
+        }
+        sender_txs.increment(sender_address);
+
+        Ok(permit)
+    }
+}
+
+fn lock<K>(counter: &Mutex<WindowCounter<K>>) -> MutexGuard<'_, WindowCounter<K>> {
+    counter.lock().expect("Congestion control lock should not be poisoned.")
+}
+
+fn reject_peer(limit: usize, window: Duration) -> GatewaySpecError {
+    let reason = format!("Exceeded the peer limit of {limit} transactions per {window:?}.");
+    debug!("Rejecting transaction: {reason}");
+    metrics::counter!(GATEWAY_CONGESTION_REJECTIONS, 1, "limit" => "peer");
+    GatewaySpecError::PeerRateLimitExceeded { data: reason }
+}
+
+fn reject_limit(limit_name: &'static str, limit: usize, window: Duration) -> GatewaySpecError {
+    reject(
+        limit_name,
+        &format!("Exceeded the {limit_name} limit of {limit} transactions per {window:?}."),
+    )
+}
+
+fn reject(limit_name: &'static str, reason: &str) -> GatewaySpecError {
+    debug!("Rejecting transaction: {reason}");
+    metrics::counter!(GATEWAY_CONGESTION_REJECTIONS, 1, "limit" => limit_name);
+    GatewaySpecError::RateLimitExceeded { data: reason.to_string() }
+}
+
+/// Counts occurrences per key over fixed time windows.
+struct WindowCounter<K> {
+    window_start: Instant,
+    counts: HashMap<K, usize>,
+}
+
+impl<K: Eq + Hash> WindowCounter<K> {
+    fn new(now: Instant) -> Self {
+        Self { window_start: now, counts: HashMap::new() }
+    }
+
+    /// Returns whether the given key may be counted again without exceeding the limit in the
+    /// current window, starting a new window if the current one ended.
+    fn has_room(&mut self, key: &K, limit: usize, window: Duration, now: Instant) -> bool {
+        if now.duration_since(self.window_start) >= window {
+            self.window_start = now;
+            self.counts.clear();
+        }
+
+        self.counts.get(key).copied().unwrap_or_default() < limit
+    }
+
+    fn increment(&mut self, key: K) {
+        *self.counts.entry(key).or_default() += 1;
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::{Duration, Instant};
+
+use assert_matches::assert_matches;
+use blockifier::test_utils::{CairoVersion, RunnableCairo1};
+use mempool_test_utils::starknet_api_test_utils::{declare_tx, invoke_tx};
+use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_test_utils::{get_rng, GetTestInstance};
+use rstest::{fixture, rstest};
+use starknet_api::contract_address;
+use starknet_api::core::ContractAddress;
+use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
+use starknet_gateway_types::errors::GatewaySpecError;
+
+use crate::config::CongestionControlConfig;
+use crate::congestion_control::CongestionController;
+
+const WINDOW_DURATION: Duration = Duration::from_secs(10);
+
+#[fixture]
+fn config() -> CongestionControlConfig {
+    CongestionControlConfig {
+        max_concurrent_validations: 10,
+        max_declares_per_sender_per_window: 10,
+        max_txs_per_sender_per_window: 10,
+        max_txs_per_peer_per_window: 10,
+        window_duration: WINDOW_DURATION,
+    }
+}
+
+fn tx() -> RpcTransaction {
+    invoke_tx(CairoVersion::Cairo1(RunnableCairo1::Casm))
+}
+
+fn declare_tx_of(sender_address: ContractAddress) -> RpcTransaction {
+    let mut tx = declare_tx();
+    let RpcTransaction::Declare(RpcDeclareTransaction::V3(declare_tx)) = &mut tx else {
+        unreachable!("Expected a declare transaction.");
+    };
+    declare_tx.sender_address = sender_address;
+    tx
+}
+
+#[rstest]
+fn test_concurrent_validations_limit(config: CongestionControlConfig) {
+    // Setup.
+    let congestion_controller = CongestionController::new(CongestionControlConfig {
+        max_concurrent_validations: 1,
+        ..config
+    });
+    let now = Instant::now();
+    let permit = congestion_controller.admit_at(&tx(), None, now).unwrap();
+
+    // Test and assert.
+    assert_matches!(
+        congestion_controller.admit_at(&declare_tx(), None, now),
+        Err(GatewaySpecError::RateLimitExceeded { .. })
+    );
+
+    drop(permit);
+    assert!(congestion_controller.admit_at(&declare_tx(), None, now).is_ok());
+}
+
+#[rstest]
+#[case::declares(CongestionControlConfig { max_declares_per_sender_per_window: 1, ..config() }, declare_tx())]
+#[case::sender(CongestionControlConfig { max_txs_per_sender_per_window: 1, ..config() }, tx())]
+fn test_tx_rate_limit(#[case] config: CongestionControlConfig, #[case] tx: RpcTransaction) {
+    // Setup.
+    let congestion_controller = CongestionController::new(config);
+    let now = Instant::now();
+    congestion_controller.admit_at(&tx, None, now).unwrap();
+
+    // Test and assert.
+    assert_matches!(
+        congestion_controller.admit_at(&tx, None, now),
+        Err(GatewaySpecError::RateLimitExceeded { .. })
+    );
+
+    // The limit is reset in the following window.
+    assert!(congestion_controller.admit_at(&tx, None, now + WINDOW_DURATION).is_ok());
+}
+
+#[rstest]
+fn test_peer_rate_limit(config: CongestionControlConfig) {
+    // Setup.
+    let congestion_controller = CongestionController::new(CongestionControlConfig {
+        max_txs_per_peer_per_window: 1,
+        ..config
+    });
+    let mut rng = get_rng();
+    let peer_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    let other_peer_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    let now = Instant::now();
+    congestion_controller.admit_at(&tx(), Some(&peer_metadata), now).unwrap();
+
+    // Test and assert.
+    assert_matches!(
+        congestion_controller.admit_at(&tx(), Some(&peer_metadata), now),
+        Err(GatewaySpecError::PeerRateLimitExceeded { .. })
+    );
+    assert!(congestion_controller.admit_at(&tx(), Some(&other_peer_metadata), now).is_ok());
+    // Transactions that were not received over p2p are not limited per peer.
+    assert!(congestion_controller.admit_at(&tx(), None, now).is_ok());
+}
+
+#[rstest]
+fn test_declare_rate_limit_is_per_sender(config: CongestionControlConfig) {
+    // Setup.
+    let congestion_controller = CongestionController::new(CongestionControlConfig {
+        max_declares_per_sender_per_window: 1,
+        ..config
+    });
+    let now = Instant::now();
+    let declare_tx = declare_tx_of(contract_address!("0x1"));
+    congestion_controller.admit_at(&declare_tx, None, now).unwrap();
+
+    // Test and assert.
+    assert_matches!(
+        congestion_controller.admit_at(&declare_tx, None, now),
+        Err(GatewaySpecError::RateLimitExceeded { .. })
+    );
+    let other_sender_declare_tx = declare_tx_of(contract_address!("0x2"));
+    assert!(congestion_controller.admit_at(&other_sender_declare_tx, None, now).is_ok());
+}
+
+#[rstest]
+fn test_rejected_tx_is_not_counted(config: CongestionControlConfig) {
+    // Setup.
+    let congestion_controller = CongestionController::new(CongestionControlConfig {
+        max_concurrent_validations: 1,
+        max_txs_per_sender_per_window: 2,
+        max_txs_per_peer_per_window: 2,
+        ..config
+    });
+    let peer_metadata = BroadcastedMessageMetadata::get_test_instance(&mut get_rng());
+    let now = Instant::now();
+    let permit = congestion_controller.admit_at(&tx(), Some(&peer_metadata), now).unwrap();
+
+    // Test: rejected by the concurrency limit, after passing the rate limits.
+    assert_matches!(
+        congestion_controller.admit_at(&tx(), Some(&peer_metadata), now),
+        Err(GatewaySpecError::RateLimitExceeded { .. })
+    );
+
+    // Assert: the rejected transaction was not counted toward the rate limits.
+    drop(permit);
+    assert!(congestion_controller.admit_at(&tx(), Some(&peer_metadata), now).is_ok());
+}
@@ -3,23 +3,33 @@ use std::sync::Arc;
 
 use blockifier::context::ChainInfo;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_rpc::{
+    AddDeclareOkResultRPC0_8 as AddDeclareOkResult,
+    AddDeployAccountOkResultRPC0_8 as AddDeployAccountOkResult,
+    AddInvokeOkResultRPC0_8 as AddInvokeOkResult,
+};
+use papyrus_storage::StorageReader;
 use starknet_api::executable_transaction::AccountTransaction;
-use starknet_api::rpc_transaction::RpcTransaction;
-use starknet_api::transaction::TransactionHash;
+use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
 use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_gateway_types::gateway_types::GatewayOutput;
 use starknet_mempool_types::communication::{AddTransactionArgsWrapper, SharedMempoolClient};
 use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
+use starknet_state_sync_types::communication::SharedStateSyncClient;
 use tracing::{error, instrument, Span};
 
 use crate::compilation::GatewayCompiler;
-use crate::config::{GatewayConfig, RpcStateReaderConfig};
+use crate::config::{GatewayConfig, RpcStateReaderConfig, StateReaderSource};
+use crate::congestion_control::CongestionController;
 use crate::errors::GatewayResult;
 use crate::rpc_state_reader::RpcStateReaderFactory;
 use crate::state_reader::StateReaderFactory;
 use crate::stateful_transaction_validator::StatefulTransactionValidator;
 use crate::stateless_transaction_validator::StatelessTransactionValidator;
+use crate::storage_state_reader::StorageStateReaderFactory;
+use crate::sync_state_reader::SyncStateReaderFactory;
 use crate::utils::compile_contract_and_build_executable_tx;
 
 #[cfg(test)]
@@ -34,6 +44,7 @@ pub struct Gateway {
     pub gateway_compiler: Arc<GatewayCompiler>,
     pub mempool_client: SharedMempoolClient,
     pub chain_info: ChainInfo,
+    congestion_controller: CongestionController,
 }
 
 impl Gateway {
@@ -48,13 +59,16 @@ impl Gateway {
             stateless_tx_validator: Arc::new(StatelessTransactionValidator {
                 config: config.stateless_tx_validator_config.clone(),
             }),
-            stateful_tx_validator: Arc::new(StatefulTransactionValidator {
-                config: config.stateful_tx_validator_config.clone(),
-            }),
+            stateful_tx_validator: Arc::new(StatefulTransactionValidator::new(
+                config.stateful_tx_validator_config.clone(),
+            )),
             state_reader_factory,
             gateway_compiler: Arc::new(gateway_compiler),
             mempool_client,
             chain_info: config.chain_info.clone(),
+            congestion_controller: CongestionController::new(
+                config.congestion_control_config.clone(),
+            ),
         }
     }
 
@@ -63,27 +77,51 @@ impl Gateway {
         &self,
         tx: RpcTransaction,
         p2p_message_metadata: Option<BroadcastedMessageMetadata>,
-    ) -> GatewayResult<TransactionHash> {
+    ) -> GatewayResult<GatewayOutput> {
+        // Reject transactions over the gateway limits before spending any resources on them.
+        let validation_permit =
+            self.congestion_controller.admit(&tx, p2p_message_metadata.as_ref())?;
+
         let blocking_task = ProcessTxBlockingTask::new(self, tx);
         // Run the blocking task in the current span.
         let curr_span = Span::current();
-        let add_tx_args =
-            tokio::task::spawn_blocking(move || curr_span.in_scope(|| blocking_task.process_tx()))
-                .await
-                .map_err(|join_err| {
-                    error!("Failed to process tx: {}", join_err);
-                    GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
-                })??;
+        let add_tx_args = tokio::task::spawn_blocking(move || {
+            let _validation_permit = validation_permit;
+            curr_span.in_scope(|| blocking_task.process_tx())
+        })
+        .await
+        .map_err(|join_err| {
+            error!("Failed to process tx: {}", join_err);
+            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        })??;
 
-        let tx_hash = add_tx_args.tx.tx_hash();
+        let gateway_output = create_gateway_output(&add_tx_args.tx);
 
         let add_tx_args = AddTransactionArgsWrapper { args: add_tx_args, p2p_message_metadata };
         self.mempool_client.add_tx(add_tx_args).await.map_err(|e| {
             error!("Failed to send tx to mempool: {}", e);
             GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
         })?;
-        // TODO: Also return `ContractAddress` for deploy and `ClassHash` for Declare.
-        Ok(tx_hash)
+        Ok(gateway_output)
+    }
+}
+
+fn create_gateway_output(tx: &AccountTransaction) -> GatewayOutput {
+    let transaction_hash = tx.tx_hash();
+    match tx {
+        AccountTransaction::Declare(declare_tx) => GatewayOutput::Declare(AddDeclareOkResult {
+            transaction_hash,
+            class_hash: declare_tx.class_hash(),
+        }),
+        AccountTransaction::DeployAccount(deploy_account_tx) => {
+            GatewayOutput::DeployAccount(AddDeployAccountOkResult {
+                transaction_hash,
+                contract_address: deploy_account_tx.contract_address,
+            })
+        }
+        AccountTransaction::Invoke(_) => {
+            GatewayOutput::Invoke(AddInvokeOkResult { transaction_hash })
+        }
     }
 }
 
@@ -111,11 +149,16 @@ impl ProcessTxBlockingTask {
     }
 
     fn process_tx(self) -> GatewayResult<AddTransactionArgs> {
-        // TODO(Arni, 1/5/2024): Perform congestion control.
-
         // Perform stateless validations.
         self.stateless_tx_validator.validate(&self.tx)?;
 
+        // The Sierra class is not needed for execution, and is passed next to the transaction.
+        let sierra_contract_class = match &self.tx {
+            RpcTransaction::Declare(RpcDeclareTransaction::V3(tx)) => {
+                Some(tx.contract_class.clone())
+            }
+            RpcTransaction::DeployAccount(_) | RpcTransaction::Invoke(_) => None,
+        };
         let executable_tx = compile_contract_and_build_executable_tx(
             self.tx,
             self.gateway_compiler.as_ref(),
@@ -140,18 +183,39 @@ impl ProcessTxBlockingTask {
 
         self.stateful_tx_validator.run_validate(&executable_tx, nonce, validator)?;
 
-        // TODO(Arni): Add the Sierra and the Casm to the mempool input.
-        Ok(AddTransactionArgs { tx: executable_tx, account_state: AccountState { address, nonce } })
+        Ok(AddTransactionArgs {
+            tx: executable_tx,
+            account_state: AccountState { address, nonce },
+            sierra_contract_class,
+        })
     }
 }
 
+/// Creates a gateway whose state reader is selected by `config.state_reader_source`; the state sync
+/// client and the storage reader are only required by the respective sources.
 pub fn create_gateway(
     config: GatewayConfig,
     rpc_state_reader_config: RpcStateReaderConfig,
     compiler_config: SierraToCasmCompilationConfig,
     mempool_client: SharedMempoolClient,
+    state_sync_client: Option<SharedStateSyncClient>,
+    storage_reader: Option<StorageReader>,
 ) -> Gateway {
-    let state_reader_factory = Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config });
+    let state_reader_factory: Arc<dyn StateReaderFactory> = match config.state_reader_source {
+        StateReaderSource::Rpc => {
+            Arc::new(RpcStateReaderFactory { config: rpc_state_reader_config })
+        }
+        StateReaderSource::StateSync => {
+            let state_sync_client = state_sync_client
+                .expect("Reading the state through state sync requires a state sync client.");
+            Arc::new(SyncStateReaderFactory { state_sync_client })
+        }
+        StateReaderSource::Storage => {
+            let storage_reader = storage_reader
+                .expect("Reading the state from storage requires a local state sync component.");
+            Arc::new(StorageStateReaderFactory::new(storage_reader))
+        }
+    };
     let gateway_compiler = GatewayCompiler::new_command_line_compiler(compiler_config);
 
     Gateway::new(config, state_reader_factory, gateway_compiler, mempool_client)
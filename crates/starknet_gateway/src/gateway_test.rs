@@ -6,19 +6,23 @@ use blockifier::test_utils::{CairoVersion, RunnableCairo1};
 use mempool_test_utils::starknet_api_test_utils::{declare_tx, invoke_tx};
 use mockall::predicate::eq;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_rpc::AddInvokeOkResultRPC0_8 as AddInvokeOkResult;
 use papyrus_test_utils::{get_rng, GetTestInstance};
 use rstest::{fixture, rstest};
 use starknet_api::core::{ChainId, CompiledClassHash, ContractAddress};
 use starknet_api::executable_transaction::{AccountTransaction, InvokeTransaction};
 use starknet_api::rpc_transaction::{RpcDeclareTransaction, RpcTransaction};
 use starknet_gateway_types::errors::GatewaySpecError;
+use starknet_gateway_types::gateway_types::GatewayOutput;
 use starknet_mempool_types::communication::{AddTransactionArgsWrapper, MockMempoolClient};
 use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
 use starknet_sierra_compile::config::SierraToCasmCompilationConfig;
 
 use crate::compilation::GatewayCompiler;
 use crate::config::{
+    CongestionControlConfig,
     GatewayConfig,
+    StateReaderSource,
     StatefulTransactionValidatorConfig,
     StatelessTransactionValidatorConfig,
 };
@@ -31,6 +35,8 @@ fn config() -> GatewayConfig {
         stateless_tx_validator_config: StatelessTransactionValidatorConfig::default(),
         stateful_tx_validator_config: StatefulTransactionValidatorConfig::default(),
         chain_info: ChainInfo::create_for_testing(),
+        state_reader_source: StateReaderSource::default(),
+        congestion_control_config: CongestionControlConfig::default(),
     }
 }
 
@@ -106,6 +112,7 @@ async fn test_add_tx(mut mock_dependencies: MockDependencies) {
     let add_tx_args = AddTransactionArgs {
         tx: executable_tx,
         account_state: AccountState { address, nonce: *rpc_tx.nonce() },
+        sierra_contract_class: None,
     };
     mock_dependencies.expect_add_tx(AddTransactionArgsWrapper {
         args: add_tx_args,
@@ -114,9 +121,12 @@ async fn test_add_tx(mut mock_dependencies: MockDependencies) {
 
     let gateway = mock_dependencies.gateway();
 
-    let response_tx_hash = gateway.add_tx(rpc_tx, p2p_message_metadata).await.unwrap();
+    let gateway_output = gateway.add_tx(rpc_tx, p2p_message_metadata).await.unwrap();
 
-    assert_eq!(tx_hash, response_tx_hash);
+    assert_eq!(
+        gateway_output,
+        GatewayOutput::Invoke(AddInvokeOkResult { transaction_hash: tx_hash })
+    );
 }
 
 // Gateway spec errors tests.
@@ -1,9 +1,12 @@
+mod block_state_cache;
 pub mod communication;
 pub mod compilation;
 mod compiler_version;
 pub mod config;
+mod congestion_control;
 pub mod errors;
 pub mod gateway;
+pub mod metrics;
 pub mod rpc_objects;
 pub mod rpc_state_reader;
 #[cfg(test)]
@@ -13,6 +16,8 @@ pub mod state_reader;
 mod state_reader_test_utils;
 mod stateful_transaction_validator;
 mod stateless_transaction_validator;
+pub mod storage_state_reader;
+pub mod sync_state_reader;
 #[cfg(test)]
 mod test_utils;
 mod utils;
@@ -0,0 +1,8 @@
+/// The number of state reads served by the gateway block state cache, labeled by the cached entry.
+pub const GATEWAY_STATE_CACHE_HITS: &str = "gateway_state_cache_hits";
+/// The number of state reads that missed the gateway block state cache and were read from the
+/// underlying state reader, labeled by the cached entry.
+pub const GATEWAY_STATE_CACHE_MISSES: &str = "gateway_state_cache_misses";
+/// The number of transactions rejected by the gateway congestion control, labeled by the exceeded
+/// limit.
+pub const GATEWAY_CONGESTION_REJECTIONS: &str = "gateway_congestion_rejections";
@@ -1,10 +1,18 @@
+use blockifier::blockifier::block::validated_gas_prices;
 use blockifier::execution::contract_class::RunnableCompiledClass;
 use blockifier::state::errors::StateError;
 use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
 #[cfg(test)]
 use mockall::automock;
-use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::block::{
+    BlockHeaderWithoutHash,
+    BlockInfo,
+    BlockNumber,
+    GasPrice,
+    NonzeroGasPrice,
+};
 use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::state::StorageKey;
 use starknet_types_core::felt::Felt;
 
@@ -52,3 +60,27 @@ impl BlockifierStateReader for Box<dyn MempoolStateReader> {
         self.as_ref().get_compiled_class_hash(class_hash)
     }
 }
+
+/// Returns the block info of the block with the given header, for readers backed by a synced
+/// storage.
+pub(crate) fn block_info_from_header(header: BlockHeaderWithoutHash) -> StateResult<BlockInfo> {
+    Ok(BlockInfo {
+        block_number: header.block_number,
+        sequencer_address: header.sequencer.0,
+        block_timestamp: header.timestamp,
+        gas_prices: validated_gas_prices(
+            parse_gas_price(header.l1_gas_price.price_in_wei)?,
+            parse_gas_price(header.l1_gas_price.price_in_fri)?,
+            parse_gas_price(header.l1_data_gas_price.price_in_wei)?,
+            parse_gas_price(header.l1_data_gas_price.price_in_fri)?,
+            parse_gas_price(header.l2_gas_price.price_in_wei)?,
+            parse_gas_price(header.l2_gas_price.price_in_fri)?,
+        ),
+        use_kzg_da: matches!(header.l1_da_mode, L1DataAvailabilityMode::Blob),
+    })
+}
+
+fn parse_gas_price(gas_price: GasPrice) -> StateResult<NonzeroGasPrice> {
+    NonzeroGasPrice::new(gas_price)
+        .map_err(|_| StateError::StateReadError(format!("Failed to parse gas price {gas_price:?}")))
+}
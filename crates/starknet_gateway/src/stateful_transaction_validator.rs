@@ -20,6 +20,7 @@ use starknet_gateway_types::errors::GatewaySpecError;
 use starknet_types_core::felt::Felt;
 use tracing::error;
 
+use crate::block_state_cache::{BlockStateCache, CachingStateReader};
 use crate::config::StatefulTransactionValidatorConfig;
 use crate::errors::StatefulTransactionValidatorResult;
 use crate::state_reader::{MempoolStateReader, StateReaderFactory};
@@ -30,6 +31,9 @@ mod stateful_transaction_validator_test;
 
 pub struct StatefulTransactionValidator {
     pub config: StatefulTransactionValidatorConfig,
+    // Shared across requests, to avoid rebuilding the block context and re-reading hot state
+    // until a new block is observed.
+    block_state_cache: BlockStateCache,
 }
 
 type BlockifierStatefulValidator = StatefulValidator<Box<dyn MempoolStateReader>>;
@@ -67,6 +71,10 @@ impl StatefulTransactionValidatorTrait for BlockifierStatefulValidator {
 }
 
 impl StatefulTransactionValidator {
+    pub fn new(config: StatefulTransactionValidatorConfig) -> Self {
+        Self { config, block_state_cache: BlockStateCache::default() }
+    }
+
     // TODO(Arni): consider separating validation from transaction conversion, as transaction
     // conversion is also relevant for the Mempool.
     pub fn run_validate<V: StatefulTransactionValidatorTrait>(
@@ -92,11 +100,27 @@ impl StatefulTransactionValidator {
         state_reader_factory: &dyn StateReaderFactory,
         chain_info: &ChainInfo,
     ) -> StatefulTransactionValidatorResult<BlockifierStatefulValidator> {
-        // TODO(yael 6/5/2024): consider storing the block_info as part of the
-        // StatefulTransactionValidator and update it only once a new block is created.
         let latest_block_info = get_latest_block_info(state_reader_factory)?;
-        let state_reader = state_reader_factory.get_state_reader(latest_block_info.block_number);
+        let latest_block_number = latest_block_info.block_number;
+        let block_cache = self.block_state_cache.get_or_create(latest_block_number, || {
+            self.create_block_context(latest_block_info, chain_info)
+        });
+        let state_reader = state_reader_factory.get_state_reader(latest_block_number);
+        let state_reader: Box<dyn MempoolStateReader> =
+            Box::new(CachingStateReader::new(state_reader, block_cache.clone()));
         let state = CachedState::new(state_reader);
+        let block_context = block_cache.block_context().clone();
+
+        Ok(BlockifierStatefulValidator::create(state, block_context))
+    }
+
+    /// Creates the context of the block following the latest block, on top of which transactions
+    /// are validated.
+    fn create_block_context(
+        &self,
+        latest_block_info: BlockInfo,
+        chain_info: &ChainInfo,
+    ) -> BlockContext {
         let versioned_constants = VersionedConstants::get_versioned_constants(
             self.config.versioned_constants_overrides.clone(),
         );
@@ -104,14 +128,7 @@ impl StatefulTransactionValidator {
         block_info.block_number = block_info.block_number.unchecked_next();
         // TODO(yael 21/4/24): create the block context using pre_process_block once we will be
         // able to read the block_hash of 10 blocks ago from papyrus.
-        let block_context = BlockContext::new(
-            block_info,
-            chain_info.clone(),
-            versioned_constants,
-            BouncerConfig::max(),
-        );
-
-        Ok(BlockifierStatefulValidator::create(state, block_context))
+        BlockContext::new(block_info, chain_info.clone(), versioned_constants, BouncerConfig::max())
     }
 }
 
@@ -122,8 +139,8 @@ fn skip_stateful_validations(tx: &ExecutableTransaction, account_nonce: Nonce) -
     match tx {
         ExecutableTransaction::Invoke(ExecutableInvokeTransaction { tx, .. }) => {
             // check if the transaction nonce is 1, meaning it is post deploy_account, and the
-            // account nonce is zero, meaning the account was not deployed yet. The mempool also
-            // verifies that the deploy_account transaction exists.
+            // account nonce is zero, meaning the account was not deployed yet. The mempool
+            // suspends such a transaction until the deploy_account transaction arrives.
             tx.nonce() == Nonce(Felt::ONE) && account_nonce == Nonce(Felt::ZERO)
         }
         ExecutableTransaction::DeployAccount(_) | ExecutableTransaction::Declare(_) => false,
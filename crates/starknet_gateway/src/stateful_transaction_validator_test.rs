@@ -48,7 +48,7 @@ pub const STATEFUL_VALIDATOR_FEE_ERROR: BlockifierStatefulValidatorError =
 
 #[fixture]
 fn stateful_validator() -> StatefulTransactionValidator {
-    StatefulTransactionValidator { config: StatefulTransactionValidatorConfig::default() }
+    StatefulTransactionValidator::new(StatefulTransactionValidatorConfig::default())
 }
 
 // TODO(Arni): consider testing declare and deploy account.
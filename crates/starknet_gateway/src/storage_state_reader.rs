@@ -0,0 +1,165 @@
+use blockifier::execution::contract_class::{
+    RunnableCompiledClass,
+    VersionedRunnableCompiledClass,
+};
+use blockifier::state::errors::StateError;
+use blockifier::state::global_cache::GlobalContractCache;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use papyrus_state_reader::papyrus_state::PapyrusReader;
+use papyrus_storage::header::HeaderStorageReader;
+use papyrus_storage::state::StateStorageReader;
+use papyrus_storage::{StorageReader, StorageResult};
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::state_reader::{block_info_from_header, MempoolStateReader, StateReaderFactory};
+
+// The number of compiled classes cached across the readers of a factory.
+const GLOBAL_CONTRACT_CACHE_SIZE: usize = 400;
+
+/// Reads the state directly from a storage shared with the state sync component, when both run in
+/// the same process.
+pub struct StorageStateReader {
+    // The block after which the state is read, pinned when the reader is created so all reads are
+    // consistent; holds the error message if it could not be resolved.
+    block_number: Result<BlockNumber, String>,
+    storage_reader: StorageReader,
+    global_class_hash_to_class: GlobalContractCache<VersionedRunnableCompiledClass>,
+}
+
+impl StorageStateReader {
+    fn block_number(&self) -> StateResult<BlockNumber> {
+        self.block_number.clone().map_err(StateError::StateReadError)
+    }
+
+    /// Returns the number of the first block whose state was not synced yet.
+    fn state_marker(&self) -> StateResult<BlockNumber> {
+        state_marker(&self.storage_reader)
+            .map_err(|err| StateError::StateReadError(err.to_string()))
+    }
+
+    /// Returns a reader of the state right after the block of this reader.
+    fn papyrus_reader(&self) -> StateResult<PapyrusReader> {
+        let block_number = self.block_number()?;
+        if block_number >= self.state_marker()? {
+            return Err(StateError::StateReadError(format!(
+                "Block number {block_number} was not synced yet."
+            )));
+        }
+
+        Ok(PapyrusReader::new(
+            self.storage_reader.clone(),
+            block_number.unchecked_next(),
+            self.global_class_hash_to_class.clone(),
+        ))
+    }
+}
+
+impl MempoolStateReader for StorageStateReader {
+    fn get_block_info(&self) -> StateResult<BlockInfo> {
+        let block_number = self.block_number()?;
+        let block_header = self
+            .storage_reader
+            .begin_ro_txn()
+            .and_then(|txn| txn.get_block_header(block_number))
+            .map_err(|err| StateError::StateReadError(err.to_string()))?
+            .ok_or_else(|| {
+                StateError::StateReadError(format!("Block number {block_number} was not found."))
+            })?;
+
+        block_info_from_header(block_header.block_header_without_hash)
+    }
+}
+
+impl BlockifierStateReader for StorageStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.papyrus_reader()?.get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.papyrus_reader()?.get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.papyrus_reader()?.get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.papyrus_reader()?.get_compiled_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        let block_number = self.block_number()?;
+        let txn = self
+            .storage_reader
+            .begin_ro_txn()
+            .map_err(|err| StateError::StateReadError(err.to_string()))?;
+        let declaration_block_number = txn
+            .get_state_reader()
+            .and_then(|state_reader| state_reader.get_class_definition_block_number(&class_hash))
+            .map_err(|err| StateError::StateReadError(err.to_string()))?;
+        // Classes that were not declared as Cairo 1 classes by the block have the default hash.
+        let Some(declaration_block_number) = declaration_block_number
+            .filter(|&declaration_block_number| declaration_block_number <= block_number)
+        else {
+            return Ok(CompiledClassHash::default());
+        };
+
+        let state_diff = txn
+            .get_state_diff(declaration_block_number)
+            .map_err(|err| StateError::StateReadError(err.to_string()))?
+            .ok_or_else(|| {
+                StateError::StateReadError(format!(
+                    "State diff of block number {declaration_block_number} was not found."
+                ))
+            })?;
+        Ok(state_diff.declared_classes.get(&class_hash).copied().unwrap_or_default())
+    }
+}
+
+pub struct StorageStateReaderFactory {
+    storage_reader: StorageReader,
+    global_class_hash_to_class: GlobalContractCache<VersionedRunnableCompiledClass>,
+}
+
+impl StorageStateReaderFactory {
+    pub fn new(storage_reader: StorageReader) -> Self {
+        Self {
+            storage_reader,
+            global_class_hash_to_class: GlobalContractCache::new(GLOBAL_CONTRACT_CACHE_SIZE),
+        }
+    }
+
+    fn create_reader(&self, block_number: Result<BlockNumber, String>) -> StorageStateReader {
+        StorageStateReader {
+            block_number,
+            storage_reader: self.storage_reader.clone(),
+            global_class_hash_to_class: self.global_class_hash_to_class.clone(),
+        }
+    }
+}
+
+impl StateReaderFactory for StorageStateReaderFactory {
+    fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
+        let block_number = state_marker(&self.storage_reader)
+            .map_err(|err| err.to_string())
+            .and_then(|state_marker| {
+                state_marker.prev().ok_or_else(|| "No block was synced yet.".to_string())
+            });
+        Box::new(self.create_reader(block_number))
+    }
+
+    fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        Box::new(self.create_reader(Ok(block_number)))
+    }
+}
+
+fn state_marker(storage_reader: &StorageReader) -> StorageResult<BlockNumber> {
+    storage_reader.begin_ro_txn().and_then(|txn| txn.get_state_marker())
+}
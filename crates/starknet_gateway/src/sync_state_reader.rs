@@ -0,0 +1,134 @@
+use std::future::Future;
+
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_state_sync_types::communication::{
+    SharedStateSyncClient,
+    StateSyncClientError,
+    StateSyncClientResult,
+};
+use starknet_state_sync_types::errors::StateSyncError;
+use starknet_types_core::felt::Felt;
+use tokio::runtime::Handle;
+
+use crate::state_reader::{block_info_from_header, MempoolStateReader, StateReaderFactory};
+
+/// Reads the state from the storage of the state sync component, through its client.
+///
+/// Note: the reader blocks on the client requests, hence it must be used outside of an async
+/// context, e.g., in a blocking task.
+pub struct SyncStateReader {
+    // The block after which the state is read, pinned when the reader is created so all reads are
+    // consistent; holds the error message if it could not be resolved.
+    block_number: Result<BlockNumber, String>,
+    state_sync_client: SharedStateSyncClient,
+    runtime: Handle,
+}
+
+impl SyncStateReader {
+    /// Creates a reader of the state right after the given block; must be called within a Tokio
+    /// runtime.
+    pub fn from_number(
+        state_sync_client: SharedStateSyncClient,
+        block_number: BlockNumber,
+    ) -> Self {
+        Self { block_number: Ok(block_number), state_sync_client, runtime: Handle::current() }
+    }
+
+    /// Creates a reader of the state right after the latest synced block; must be called within a
+    /// Tokio runtime, outside of an async context.
+    pub fn from_latest(state_sync_client: SharedStateSyncClient) -> Self {
+        let runtime = Handle::current();
+        let block_number = runtime
+            .block_on(state_sync_client.get_latest_block_number())
+            .map_err(|err| err.to_string())
+            .and_then(|block_number| {
+                block_number.ok_or_else(|| "No block was synced yet.".to_string())
+            });
+        Self { block_number, state_sync_client, runtime }
+    }
+
+    fn block_on<T>(
+        &self,
+        request: impl Future<Output = StateSyncClientResult<T>>,
+    ) -> StateResult<T> {
+        self.runtime.block_on(request).map_err(state_sync_client_err_to_state_err)
+    }
+
+    fn block_number(&self) -> StateResult<BlockNumber> {
+        self.block_number.clone().map_err(StateError::StateReadError)
+    }
+}
+
+impl MempoolStateReader for SyncStateReader {
+    fn get_block_info(&self) -> StateResult<BlockInfo> {
+        let block_number = self.block_number()?;
+        let block_header = self
+            .block_on(self.state_sync_client.get_block_header(block_number))?
+            .ok_or_else(|| {
+                StateError::StateReadError(format!("Block number {block_number} was not found."))
+            })?;
+
+        block_info_from_header(block_header)
+    }
+}
+
+impl BlockifierStateReader for SyncStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        let block_number = self.block_number()?;
+        self.block_on(self.state_sync_client.get_storage_at(block_number, contract_address, key))
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let block_number = self.block_number()?;
+        self.block_on(self.state_sync_client.get_nonce_at(block_number, contract_address))
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let block_number = self.block_number()?;
+        self.block_on(self.state_sync_client.get_class_hash_at(block_number, contract_address))
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        let block_number = self.block_number()?;
+        let contract_class =
+            self.block_on(self.state_sync_client.get_compiled_class(block_number, class_hash))?;
+        RunnableCompiledClass::try_from(contract_class).map_err(StateError::ProgramError)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        let block_number = self.block_number()?;
+        self.block_on(self.state_sync_client.get_compiled_class_hash(block_number, class_hash))
+    }
+}
+
+pub struct SyncStateReaderFactory {
+    pub state_sync_client: SharedStateSyncClient,
+}
+
+impl StateReaderFactory for SyncStateReaderFactory {
+    fn get_state_reader_from_latest_block(&self) -> Box<dyn MempoolStateReader> {
+        Box::new(SyncStateReader::from_latest(self.state_sync_client.clone()))
+    }
+
+    fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        Box::new(SyncStateReader::from_number(self.state_sync_client.clone(), block_number))
+    }
+}
+
+fn state_sync_client_err_to_state_err(err: StateSyncClientError) -> StateError {
+    match err {
+        StateSyncClientError::StateSyncError(StateSyncError::ClassNotFound(class_hash)) => {
+            StateError::UndeclaredClassHash(class_hash)
+        }
+        _ => StateError::StateReadError(err.to_string()),
+    }
+}
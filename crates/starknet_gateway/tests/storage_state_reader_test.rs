@@ -0,0 +1,150 @@
+use assert_matches::assert_matches;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::StateReader;
+use blockifier::test_utils::contracts::FeatureContract;
+use blockifier::test_utils::CairoVersion;
+use indexmap::IndexMap;
+use papyrus_storage::class::ClassStorageWriter;
+use papyrus_storage::header::HeaderStorageWriter;
+use papyrus_storage::state::StateStorageWriter;
+use papyrus_storage::test_utils::get_test_storage;
+use starknet_api::block::{
+    BlockHeader,
+    BlockHeaderWithoutHash,
+    BlockNumber,
+    BlockTimestamp,
+    GasPricePerToken,
+};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ClassHash, CompiledClassHash, Nonce, SequencerContractAddress};
+use starknet_api::state::{StateDiff, ThinStateDiff};
+use starknet_api::{contract_address, felt, nonce, storage_key};
+use starknet_gateway::state_reader::StateReaderFactory;
+use starknet_gateway::storage_state_reader::StorageStateReaderFactory;
+
+fn block_header(block_number: BlockNumber) -> BlockHeader {
+    // Gas prices must be non-zero.
+    let gas_price = GasPricePerToken { price_in_fri: 1_u8.into(), price_in_wei: 1_u8.into() };
+    BlockHeader {
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number,
+            l1_gas_price: gas_price,
+            l1_data_gas_price: gas_price,
+            l2_gas_price: gas_price,
+            sequencer: SequencerContractAddress(contract_address!("0x10")),
+            timestamp: BlockTimestamp(1000 + block_number.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_storage_state_reader() {
+    // Setup.
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let test_class_hash = test_contract.get_class_hash();
+    let test_class = assert_matches!(
+        test_contract.get_class(), ContractClass::V0(contract_class) => contract_class
+    );
+    let test_address = test_contract.get_instance_address(0);
+    let (key, value) = (storage_key!(7_u8), felt!(18_u8));
+    let cairo_1_class_hash = ClassHash(felt!(0x1234_u16));
+    let compiled_class_hash = CompiledClassHash(felt!(0x4321_u16));
+
+    // Block 0 declares and deploys the test contract; block 1 writes to its storage, and declares
+    // a Cairo 1 class.
+    let block_0_state_diff = StateDiff {
+        deployed_contracts: IndexMap::from([(test_address, test_class_hash)]),
+        deprecated_declared_classes: IndexMap::from([(test_class_hash, test_class.clone())]),
+        ..Default::default()
+    };
+    let block_1_state_diff = ThinStateDiff {
+        storage_diffs: IndexMap::from([(test_address, IndexMap::from([(key, value)]))]),
+        nonces: IndexMap::from([(test_address, nonce!(1_u8))]),
+        declared_classes: IndexMap::from([(cairo_1_class_hash, compiled_class_hash)]),
+        ..Default::default()
+    };
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &block_header(BlockNumber(0)))
+        .unwrap()
+        .append_state_diff(BlockNumber(0), block_0_state_diff.into())
+        .unwrap()
+        .append_classes(BlockNumber(0), &[], &[(test_class_hash, &test_class)])
+        .unwrap()
+        .append_header(BlockNumber(1), &block_header(BlockNumber(1)))
+        .unwrap()
+        .append_state_diff(BlockNumber(1), block_1_state_diff)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let factory = StorageStateReaderFactory::new(storage_reader);
+
+    // Test and assert: the latest reader views the latest synced block.
+    let latest_state_reader = factory.get_state_reader_from_latest_block();
+    let latest_block_info = latest_state_reader.get_block_info().unwrap();
+    assert_eq!(latest_block_info.block_number, BlockNumber(1));
+    assert_eq!(latest_block_info.block_timestamp, BlockTimestamp(1001));
+    assert_eq!(latest_block_info.sequencer_address, contract_address!("0x10"));
+
+    // The state right after block 0.
+    let state_reader = factory.get_state_reader(BlockNumber(0));
+    assert_eq!(state_reader.get_class_hash_at(test_address).unwrap(), test_class_hash);
+    assert_eq!(state_reader.get_nonce_at(test_address).unwrap(), Nonce::default());
+    assert_eq!(state_reader.get_storage_at(test_address, key).unwrap(), felt!(0_u8));
+    assert_matches!(
+        state_reader.get_compiled_class(test_class_hash).unwrap(),
+        RunnableCompiledClass::V0(_)
+    );
+    assert_eq!(
+        state_reader.get_compiled_class_hash(cairo_1_class_hash).unwrap(),
+        CompiledClassHash::default()
+    );
+
+    // The state right after block 1.
+    let state_reader = factory.get_state_reader(BlockNumber(1));
+    assert_eq!(state_reader.get_nonce_at(test_address).unwrap(), nonce!(1_u8));
+    assert_eq!(state_reader.get_storage_at(test_address, key).unwrap(), value);
+    assert_eq!(
+        state_reader.get_compiled_class_hash(cairo_1_class_hash).unwrap(),
+        compiled_class_hash
+    );
+    // Cairo 0 classes have no compiled class hash.
+    assert_eq!(
+        state_reader.get_compiled_class_hash(test_class_hash).unwrap(),
+        CompiledClassHash::default()
+    );
+
+    // Undeployed contracts and undeclared classes.
+    let undeployed_address = contract_address!("0x1234");
+    assert_eq!(state_reader.get_class_hash_at(undeployed_address).unwrap(), ClassHash::default());
+    assert_eq!(state_reader.get_nonce_at(undeployed_address).unwrap(), Nonce::default());
+    let undeclared_class_hash = ClassHash(felt!(0x5678_u16));
+    assert_matches!(
+        state_reader.get_compiled_class(undeclared_class_hash),
+        Err(StateError::UndeclaredClassHash(class_hash)) if class_hash == undeclared_class_hash
+    );
+
+    // A block that was not synced yet.
+    let state_reader = factory.get_state_reader(BlockNumber(2));
+    assert_matches!(state_reader.get_nonce_at(test_address), Err(StateError::StateReadError(_)));
+
+    // The latest reader remains pinned to the block that was latest upon its creation.
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(2), &block_header(BlockNumber(2)))
+        .unwrap()
+        .append_state_diff(BlockNumber(2), ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(latest_state_reader.get_block_info().unwrap().block_number, BlockNumber(1));
+    assert_eq!(latest_state_reader.get_nonce_at(test_address).unwrap(), nonce!(1_u8));
+}
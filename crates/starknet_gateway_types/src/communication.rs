@@ -5,7 +5,6 @@ use async_trait::async_trait;
 use mockall::automock;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
-use starknet_api::transaction::TransactionHash;
 use starknet_sequencer_infra::component_client::{
     ClientError,
     LocalComponentClient,
@@ -18,7 +17,7 @@ use starknet_sequencer_infra::component_definitions::{
 use thiserror::Error;
 
 use crate::errors::GatewayError;
-use crate::gateway_types::{GatewayInput, GatewayResult};
+use crate::gateway_types::{GatewayInput, GatewayOutput, GatewayResult};
 
 pub type LocalGatewayClient = LocalComponentClient<GatewayRequest, GatewayResponse>;
 pub type RemoteGatewayClient = RemoteComponentClient<GatewayRequest, GatewayResponse>;
@@ -33,7 +32,7 @@ use tracing::{error, instrument};
 #[cfg_attr(any(feature = "testing", test), automock)]
 #[async_trait]
 pub trait GatewayClient: Send + Sync {
-    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<TransactionHash>;
+    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<GatewayOutput>;
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -43,7 +42,7 @@ pub enum GatewayRequest {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum GatewayResponse {
-    AddTransaction(GatewayResult<TransactionHash>),
+    AddTransaction(GatewayResult<GatewayOutput>),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -60,7 +59,7 @@ where
     ComponentClientType: Send + Sync + ComponentClient<GatewayRequest, GatewayResponse>,
 {
     #[instrument(skip(self))]
-    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<TransactionHash> {
+    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<GatewayOutput> {
         let request = GatewayRequest::AddTransaction(gateway_input);
         let response = self.send(request).await;
         handle_response_variants!(GatewayResponse, AddTransaction, GatewayClientError, GatewayError)
@@ -49,6 +49,11 @@ pub enum GatewaySpecError {
     InvalidTransactionNonce,
     #[assoc(into_rpc = NON_ACCOUNT)]
     NonAccount,
+    // Distinguished from other rate limits, as it is the only one a p2p peer is accountable for.
+    #[assoc(into_rpc = rate_limit_exceeded(_data))]
+    PeerRateLimitExceeded { data: String },
+    #[assoc(into_rpc = rate_limit_exceeded(_data))]
+    RateLimitExceeded { data: String },
     #[assoc(into_rpc = unexpected_error(_data))]
     UnexpectedError { data: String },
     #[assoc(into_rpc = UNSUPPORTED_CONTRACT_CLASS_VERSION)]
@@ -59,6 +64,12 @@ pub enum GatewaySpecError {
     ValidationFailure { data: String },
 }
 
+// Not part of the Starknet RPC specification; uses the "limit exceeded" code of the JSON-RPC server
+// errors range.
+fn rate_limit_exceeded(data: String) -> JsonRpcError<String> {
+    JsonRpcError { code: -32005, message: "Rate limit exceeded", data: Some(data) }
+}
+
 impl IntoResponse for GatewaySpecError {
     fn into_response(self) -> Response {
         let as_rpc = self.into_rpc();
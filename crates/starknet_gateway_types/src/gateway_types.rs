@@ -1,6 +1,12 @@
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use papyrus_rpc::{
+    AddDeclareOkResultRPC0_8,
+    AddDeployAccountOkResultRPC0_8,
+    AddInvokeOkResultRPC0_8,
+};
 use serde::{Deserialize, Serialize};
 use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::transaction::TransactionHash;
 
 use crate::errors::GatewayError;
 
@@ -10,4 +16,22 @@ pub struct GatewayInput {
     pub message_metadata: Option<BroadcastedMessageMetadata>,
 }
 
+/// The result of adding a transaction, per transaction kind, as defined by the JSON-RPC write API.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GatewayOutput {
+    Declare(AddDeclareOkResultRPC0_8),
+    DeployAccount(AddDeployAccountOkResultRPC0_8),
+    Invoke(AddInvokeOkResultRPC0_8),
+}
+
+impl GatewayOutput {
+    pub fn transaction_hash(&self) -> TransactionHash {
+        match self {
+            GatewayOutput::Declare(output) => output.transaction_hash,
+            GatewayOutput::DeployAccount(output) => output.transaction_hash,
+            GatewayOutput::Invoke(output) => output.transaction_hash,
+        }
+    }
+}
+
 pub type GatewayResult<T> = Result<T, GatewayError>;
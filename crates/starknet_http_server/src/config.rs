@@ -11,6 +11,8 @@ use validator::Validate;
 pub struct HttpServerConfig {
     pub ip: IpAddr,
     pub port: u16,
+    // Maximal number of requests in a JSON-RPC batch; larger batches are rejected as a whole.
+    pub max_batch_size: usize,
 }
 
 impl SerializeConfig for HttpServerConfig {
@@ -18,12 +20,18 @@ impl SerializeConfig for HttpServerConfig {
         BTreeMap::from_iter([
             ser_param("ip", &self.ip.to_string(), "The http server ip.", ParamPrivacyInput::Public),
             ser_param("port", &self.port, "The http server port.", ParamPrivacyInput::Public),
+            ser_param(
+                "max_batch_size",
+                &self.max_batch_size,
+                "Maximal number of requests in a JSON-RPC batch.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
 
 impl Default for HttpServerConfig {
     fn default() -> Self {
-        Self { ip: "0.0.0.0".parse().unwrap(), port: 8080 }
+        Self { ip: "0.0.0.0".parse().unwrap(), port: 8080, max_batch_size: 100 }
     }
 }
@@ -2,20 +2,24 @@ use std::any::type_name;
 use std::clone::Clone;
 use std::net::SocketAddr;
 
+use axum::body::Bytes;
 use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::routing::post;
 use axum::{async_trait, Json, Router};
+use serde_json::Value;
 use starknet_api::rpc_transaction::RpcTransaction;
-use starknet_api::transaction::TransactionHash;
 use starknet_gateway_types::communication::SharedGatewayClient;
 use starknet_gateway_types::errors::GatewaySpecError;
-use starknet_gateway_types::gateway_types::GatewayInput;
+use starknet_gateway_types::gateway_types::{GatewayInput, GatewayOutput};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sequencer_infra::errors::ComponentError;
 use tracing::{error, info, instrument};
 
 use crate::config::HttpServerConfig;
 use crate::errors::HttpServerRunError;
+use crate::json_rpc::{gateway_output_to_json, handle_json_rpc};
 
 #[cfg(test)]
 #[path = "http_server_test.rs"]
@@ -31,17 +35,18 @@ pub struct HttpServer {
 #[derive(Clone)]
 pub struct AppState {
     pub gateway_client: SharedGatewayClient,
+    pub max_batch_size: usize,
 }
 
 impl HttpServer {
     pub fn new(config: HttpServerConfig, gateway_client: SharedGatewayClient) -> Self {
-        let app_state = AppState { gateway_client };
+        let app_state = AppState { gateway_client, max_batch_size: config.max_batch_size };
         HttpServer { config, app_state }
     }
 
     pub async fn run(&mut self) -> Result<(), HttpServerRunError> {
         // Parses the bind address from HttpServerConfig, returning an error for invalid addresses.
-        let HttpServerConfig { ip, port } = self.config;
+        let HttpServerConfig { ip, port, .. } = self.config;
         let addr = SocketAddr::new(ip, port);
         let app = self.app();
         info!("HttpServer running using socket: {}", addr);
@@ -51,7 +56,11 @@ impl HttpServer {
     }
 
     pub fn app(&self) -> Router {
-        Router::new().route("/add_tx", post(add_tx)).with_state(self.app_state.clone())
+        Router::new()
+            // Kept for backwards compatibility; prefer the JSON-RPC write API.
+            .route("/add_tx", post(add_tx))
+            .route("/rpc", post(json_rpc))
+            .with_state(self.app_state.clone())
     }
 }
 
@@ -61,7 +70,7 @@ impl HttpServer {
 async fn add_tx(
     State(app_state): State<AppState>,
     Json(tx): Json<RpcTransaction>,
-) -> HttpServerResult<Json<TransactionHash>> {
+) -> HttpServerResult<Json<Value>> {
     let gateway_input: GatewayInput = GatewayInput { rpc_tx: tx.clone(), message_metadata: None };
 
     let add_tx_result = app_state.gateway_client.add_tx(gateway_input).await.map_err(|join_err| {
@@ -73,10 +82,20 @@ async fn add_tx(
 }
 
 pub(crate) fn add_tx_result_as_json(
-    result: Result<TransactionHash, GatewaySpecError>,
-) -> HttpServerResult<Json<TransactionHash>> {
-    let tx_hash = result?;
-    Ok(Json(tx_hash))
+    result: Result<GatewayOutput, GatewaySpecError>,
+) -> HttpServerResult<Json<Value>> {
+    let gateway_output = result?;
+    Ok(Json(gateway_output_to_json(gateway_output)))
+}
+
+/// Serves the Starknet JSON-RPC write API, including batch requests.
+#[instrument(skip(app_state, body))]
+async fn json_rpc(State(app_state): State<AppState>, body: Bytes) -> Response {
+    match handle_json_rpc(&app_state.gateway_client, &body, app_state.max_batch_size).await {
+        Some(response) => Json(response).into_response(),
+        // Notifications are not responded to.
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
 }
 
 pub fn create_http_server(
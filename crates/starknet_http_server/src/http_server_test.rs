@@ -1,20 +1,46 @@
 use axum::body::{Bytes, HttpBody};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use papyrus_rpc::{
+    AddDeclareOkResultRPC0_8 as AddDeclareOkResult,
+    AddDeployAccountOkResultRPC0_8 as AddDeployAccountOkResult,
+    AddInvokeOkResultRPC0_8 as AddInvokeOkResult,
+};
+use rstest::rstest;
+use serde_json::Value;
+use starknet_api::core::{ClassHash, ContractAddress};
 use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::gateway_types::GatewayOutput;
 
 use crate::http_server::add_tx_result_as_json;
 
+#[rstest]
+#[case::declare(GatewayOutput::Declare(AddDeclareOkResult {
+    transaction_hash: TransactionHash::default(),
+    class_hash: ClassHash::default(),
+}))]
+#[case::deploy_account(GatewayOutput::DeployAccount(AddDeployAccountOkResult {
+    transaction_hash: TransactionHash::default(),
+    contract_address: ContractAddress::default(),
+}))]
+#[case::invoke(GatewayOutput::Invoke(AddInvokeOkResult {
+    transaction_hash: TransactionHash::default(),
+}))]
 #[tokio::test]
-async fn test_tx_hash_json_conversion() {
-    let tx_hash = TransactionHash::default();
-    let response = add_tx_result_as_json(Ok(tx_hash)).into_response();
+async fn test_gateway_output_json_conversion(#[case] gateway_output: GatewayOutput) {
+    let response = add_tx_result_as_json(Ok(gateway_output.clone())).into_response();
 
     let status_code = response.status();
     let response_bytes = &to_bytes(response).await;
 
     assert_eq!(status_code, StatusCode::OK, "{response_bytes:?}");
-    assert_eq!(tx_hash, serde_json::from_slice(response_bytes).unwrap());
+    let expected_json = match gateway_output {
+        GatewayOutput::Declare(output) => serde_json::to_value(output),
+        GatewayOutput::DeployAccount(output) => serde_json::to_value(output),
+        GatewayOutput::Invoke(output) => serde_json::to_value(output),
+    }
+    .unwrap();
+    assert_eq!(expected_json, serde_json::from_slice::<Value>(response_bytes).unwrap());
 }
 
 async fn to_bytes(res: Response) -> Bytes {
@@ -0,0 +1,245 @@
+use futures::future::join_all;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_gateway_types::communication::{GatewayClientError, SharedGatewayClient};
+use starknet_gateway_types::errors::{GatewayError, GatewaySpecError};
+use starknet_gateway_types::gateway_types::{GatewayInput, GatewayOutput};
+use tracing::{debug, error};
+
+#[cfg(test)]
+#[path = "json_rpc_test.rs"]
+mod json_rpc_test;
+
+const JSON_RPC_VERSION: &str = "2.0";
+
+// Standard JSON-RPC 2.0 error codes.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    // A request without an id is a notification, which is not responded to; a null id is not.
+    #[serde(default, deserialize_with = "deserialize_present_id")]
+    id: Option<Value>,
+}
+
+// Distinguishes a null id from a missing one, both of which serde deserializes to `None` by
+// default.
+fn deserialize_present_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcErrorObject {
+    pub code: i32,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRpcErrorObject {
+    fn new(code: i32, message: &str) -> Self {
+        Self { code, message: message.to_string(), data: None }
+    }
+}
+
+impl From<GatewaySpecError> for JsonRpcErrorObject {
+    fn from(err: GatewaySpecError) -> Self {
+        let rpc_error = err.into_rpc();
+        Self {
+            code: rpc_error.code,
+            message: rpc_error.message.to_string(),
+            data: rpc_error.data.map(Value::String),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcErrorObject>,
+    pub id: Value,
+}
+
+impl JsonRpcResponse {
+    fn new(id: Value, result: Result<Value, JsonRpcErrorObject>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self { jsonrpc: JSON_RPC_VERSION.to_string(), result, error, id }
+    }
+}
+
+/// The write methods of the Starknet JSON-RPC API, each with the name of its transaction param.
+#[derive(Clone, Copy, Debug)]
+enum WriteMethod {
+    AddDeclareTransaction,
+    AddDeployAccountTransaction,
+    AddInvokeTransaction,
+}
+
+impl WriteMethod {
+    fn from_name(method: &str) -> Option<Self> {
+        match method {
+            "starknet_addDeclareTransaction" => Some(Self::AddDeclareTransaction),
+            "starknet_addDeployAccountTransaction" => Some(Self::AddDeployAccountTransaction),
+            "starknet_addInvokeTransaction" => Some(Self::AddInvokeTransaction),
+            _ => None,
+        }
+    }
+
+    fn param_name(&self) -> &'static str {
+        match self {
+            Self::AddDeclareTransaction => "declare_transaction",
+            Self::AddDeployAccountTransaction => "deploy_account_transaction",
+            Self::AddInvokeTransaction => "invoke_transaction",
+        }
+    }
+
+    fn accepts(&self, tx: &RpcTransaction) -> bool {
+        matches!(
+            (self, tx),
+            (Self::AddDeclareTransaction, RpcTransaction::Declare(_))
+                | (Self::AddDeployAccountTransaction, RpcTransaction::DeployAccount(_))
+                | (Self::AddInvokeTransaction, RpcTransaction::Invoke(_))
+        )
+    }
+
+    /// Extracts the transaction from the params, given either by name or by position.
+    fn parse_params(&self, params: Value) -> Result<RpcTransaction, JsonRpcErrorObject> {
+        let tx_value = match params {
+            Value::Object(mut params) if params.len() == 1 => params.remove(self.param_name()),
+            Value::Array(mut params) if params.len() == 1 => params.pop(),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            JsonRpcErrorObject::new(
+                INVALID_PARAMS,
+                &format!("Expected a single param: {}.", self.param_name()),
+            )
+        })?;
+
+        let tx: RpcTransaction =
+            serde_json::from_value(tx_value).map_err(|err| JsonRpcErrorObject {
+                data: Some(Value::String(err.to_string())),
+                ..JsonRpcErrorObject::new(INVALID_PARAMS, "Invalid params")
+            })?;
+        if !self.accepts(&tx) {
+            return Err(JsonRpcErrorObject::new(
+                INVALID_PARAMS,
+                &format!("Unexpected transaction type for {}.", self.param_name()),
+            ));
+        }
+
+        Ok(tx)
+    }
+}
+
+/// Handles a JSON-RPC 2.0 request or batch of requests, returning the response to send, if any.
+pub(crate) async fn handle_json_rpc(
+    gateway_client: &SharedGatewayClient,
+    body: &[u8],
+    max_batch_size: usize,
+) -> Option<Value> {
+    let body: Value = match serde_json::from_slice(body) {
+        Ok(body) => body,
+        Err(err) => {
+            debug!("Failed to parse JSON-RPC request: {err}");
+            let error = JsonRpcErrorObject::new(PARSE_ERROR, "Parse error");
+            return Some(json!(JsonRpcResponse::new(Value::Null, Err(error))));
+        }
+    };
+
+    match body {
+        Value::Array(requests) if requests.is_empty() => {
+            let error = JsonRpcErrorObject::new(INVALID_REQUEST, "Invalid Request");
+            Some(json!(JsonRpcResponse::new(Value::Null, Err(error))))
+        }
+        Value::Array(requests) if requests.len() > max_batch_size => {
+            let error = JsonRpcErrorObject {
+                data: Some(Value::String(format!(
+                    "The batch exceeds the maximal size of {max_batch_size} requests."
+                ))),
+                ..JsonRpcErrorObject::new(INVALID_REQUEST, "Invalid Request")
+            };
+            Some(json!(JsonRpcResponse::new(Value::Null, Err(error))))
+        }
+        Value::Array(requests) => {
+            let responses: Vec<JsonRpcResponse> = join_all(
+                requests.into_iter().map(|request| handle_request(gateway_client, request)),
+            )
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+            // A batch of notifications is not responded to.
+            (!responses.is_empty()).then(|| json!(responses))
+        }
+        request => handle_request(gateway_client, request).await.map(|response| json!(response)),
+    }
+}
+
+async fn handle_request(
+    gateway_client: &SharedGatewayClient,
+    request: Value,
+) -> Option<JsonRpcResponse> {
+    let request: JsonRpcRequest = match serde_json::from_value(request) {
+        Ok(request) if request.jsonrpc == JSON_RPC_VERSION => request,
+        _ => {
+            let error = JsonRpcErrorObject::new(INVALID_REQUEST, "Invalid Request");
+            return Some(JsonRpcResponse::new(Value::Null, Err(error)));
+        }
+    };
+
+    let result = add_tx(gateway_client, &request.method, request.params).await;
+    request.id.map(|id| JsonRpcResponse::new(id, result))
+}
+
+async fn add_tx(
+    gateway_client: &SharedGatewayClient,
+    method: &str,
+    params: Value,
+) -> Result<Value, JsonRpcErrorObject> {
+    let write_method = WriteMethod::from_name(method)
+        .ok_or_else(|| JsonRpcErrorObject::new(METHOD_NOT_FOUND, "Method not found"))?;
+    let rpc_tx = write_method.parse_params(params)?;
+
+    let gateway_input = GatewayInput { rpc_tx, message_metadata: None };
+    let gateway_output =
+        gateway_client.add_tx(gateway_input).await.map_err(gateway_client_err_to_spec_err)?;
+
+    Ok(gateway_output_to_json(gateway_output))
+}
+
+fn gateway_client_err_to_spec_err(err: GatewayClientError) -> GatewaySpecError {
+    match err {
+        GatewayClientError::GatewayError(GatewayError::GatewaySpecError { source, .. }) => source,
+        GatewayClientError::ClientError(err) => {
+            error!("Failed to send tx to the gateway: {}", err);
+            GatewaySpecError::UnexpectedError { data: "Internal server error".to_owned() }
+        }
+    }
+}
+
+/// Returns the result of the added transaction, in the shape of the JSON-RPC write API.
+pub(crate) fn gateway_output_to_json(gateway_output: GatewayOutput) -> Value {
+    match gateway_output {
+        GatewayOutput::Declare(output) => json!(output),
+        GatewayOutput::DeployAccount(output) => json!(output),
+        GatewayOutput::Invoke(output) => json!(output),
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use papyrus_rpc::AddInvokeOkResultRPC0_8 as AddInvokeOkResult;
+use rstest::rstest;
+use serde_json::{json, Value};
+use starknet_api::invoke_tx_args;
+use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::test_utils::invoke::rpc_invoke_tx;
+use starknet_api::transaction::TransactionHash;
+use starknet_gateway_types::communication::{
+    GatewayClientError,
+    MockGatewayClient,
+    SharedGatewayClient,
+};
+use starknet_gateway_types::errors::{GatewayError, GatewaySpecError};
+use starknet_gateway_types::gateway_types::GatewayOutput;
+
+use crate::json_rpc::{handle_json_rpc, JsonRpcResponse};
+
+const MAX_BATCH_SIZE: usize = 3;
+
+fn invoke_tx() -> RpcTransaction {
+    rpc_invoke_tx(invoke_tx_args!())
+}
+
+fn add_invoke_request(id: u64, params: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "starknet_addInvokeTransaction",
+        "params": params,
+        "id": id,
+    })
+}
+
+fn successful_gateway_client() -> MockGatewayClient {
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client.expect_add_tx().returning(|gateway_input| {
+        assert_eq!(gateway_input.rpc_tx, invoke_tx());
+        Ok(GatewayOutput::Invoke(AddInvokeOkResult {
+            transaction_hash: TransactionHash::default(),
+        }))
+    });
+    mock_gateway_client
+}
+
+async fn handle(mock_gateway_client: MockGatewayClient, body: Value) -> Option<Value> {
+    let gateway_client: SharedGatewayClient = Arc::new(mock_gateway_client);
+    handle_json_rpc(&gateway_client, body.to_string().as_bytes(), MAX_BATCH_SIZE).await
+}
+
+fn parse_response(response: Value) -> JsonRpcResponse {
+    serde_json::from_value(response).unwrap()
+}
+
+#[rstest]
+#[case::named_params(json!({ "invoke_transaction": invoke_tx() }))]
+#[case::positional_params(json!([invoke_tx()]))]
+#[tokio::test]
+async fn test_add_invoke_transaction(#[case] params: Value) {
+    let response = handle(successful_gateway_client(), add_invoke_request(1, params)).await;
+
+    let response = parse_response(response.unwrap());
+    assert_eq!(response.id, json!(1));
+    assert_eq!(response.error, None);
+    assert_eq!(response.result, Some(json!({ "transaction_hash": TransactionHash::default() })));
+}
+
+#[rstest]
+#[case::unknown_method(
+    json!({ "jsonrpc": "2.0", "method": "starknet_addL1Transaction", "params": [], "id": 1 }),
+    -32601
+)]
+#[case::wrong_transaction_type(
+    json!({
+        "jsonrpc": "2.0",
+        "method": "starknet_addDeclareTransaction",
+        "params": { "declare_transaction": invoke_tx() },
+        "id": 1,
+    }),
+    -32602
+)]
+#[case::missing_params(add_invoke_request(1, json!({})), -32602)]
+#[case::invalid_version(json!({ "jsonrpc": "1.0", "method": "starknet_addInvokeTransaction" }), -32600)]
+#[tokio::test]
+async fn test_invalid_request(#[case] request: Value, #[case] expected_code: i32) {
+    let response = handle(MockGatewayClient::new(), request).await;
+
+    let error = parse_response(response.unwrap()).error.unwrap();
+    assert_eq!(error.code, expected_code);
+}
+
+#[tokio::test]
+async fn test_parse_error() {
+    let gateway_client: SharedGatewayClient = Arc::new(MockGatewayClient::new());
+    let response = handle_json_rpc(&gateway_client, b"{ not json", MAX_BATCH_SIZE).await;
+
+    let response = parse_response(response.unwrap());
+    assert_eq!(response.id, Value::Null);
+    assert_eq!(response.error.unwrap().code, -32700);
+}
+
+#[tokio::test]
+async fn test_gateway_spec_error_maps_to_spec_code() {
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client.expect_add_tx().returning(|_| {
+        Err(GatewayClientError::GatewayError(GatewayError::GatewaySpecError {
+            source: GatewaySpecError::InvalidTransactionNonce,
+            p2p_message_metadata: None,
+        }))
+    });
+
+    let response =
+        handle(mock_gateway_client, add_invoke_request(1, json!([invoke_tx()]))).await.unwrap();
+
+    let error = parse_response(response).error.unwrap();
+    assert_eq!(error.code, GatewaySpecError::InvalidTransactionNonce.into_rpc().code);
+}
+
+#[tokio::test]
+async fn test_batch_request() {
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "starknet_addInvokeTransaction",
+        "params": [invoke_tx()],
+    });
+    let batch = json!([
+        add_invoke_request(1, json!([invoke_tx()])),
+        notification,
+        { "jsonrpc": "2.0", "method": "starknet_unknownMethod", "id": 2 },
+    ]);
+
+    let response = handle(successful_gateway_client(), batch).await.unwrap();
+
+    // The notification is not responded to.
+    let responses: Vec<JsonRpcResponse> = serde_json::from_value(response).unwrap();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0].id, json!(1));
+    assert!(responses[0].result.is_some());
+    assert_eq!(responses[1].id, json!(2));
+    assert_eq!(responses[1].error.as_ref().unwrap().code, -32601);
+}
+
+#[tokio::test]
+async fn test_batch_of_notifications_is_not_responded_to() {
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "starknet_addInvokeTransaction",
+        "params": [invoke_tx()],
+    });
+
+    let response = handle(successful_gateway_client(), json!([notification])).await;
+
+    assert_eq!(response, None);
+}
+
+#[tokio::test]
+async fn test_null_id_is_responded_to() {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "method": "starknet_addInvokeTransaction",
+        "params": [invoke_tx()],
+        "id": null,
+    });
+
+    let response = handle(successful_gateway_client(), request).await.unwrap();
+
+    let response = parse_response(response);
+    assert_eq!(response.id, Value::Null);
+    assert!(response.result.is_some());
+}
+
+#[tokio::test]
+async fn test_batch_over_max_size_is_rejected() {
+    let batch: Vec<Value> = (0..=u64::try_from(MAX_BATCH_SIZE).unwrap())
+        .map(|id| add_invoke_request(id, json!([invoke_tx()])))
+        .collect();
+
+    // The gateway is not called.
+    let response = handle(MockGatewayClient::new(), json!(batch)).await.unwrap();
+
+    let response = parse_response(response);
+    assert_eq!(response.id, Value::Null);
+    assert_eq!(response.error.unwrap().code, -32600);
+}
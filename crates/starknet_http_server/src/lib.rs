@@ -2,5 +2,6 @@ pub mod communication;
 pub mod config;
 pub mod errors;
 pub mod http_server;
+pub mod json_rpc;
 #[cfg(feature = "testing")]
 pub mod test_utils;
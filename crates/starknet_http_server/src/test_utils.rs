@@ -2,6 +2,7 @@ use std::net::SocketAddr;
 
 use axum::body::Body;
 use reqwest::{Client, Response};
+use serde::Deserialize;
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::test_utils::rpc_tx_to_json;
 use starknet_api::transaction::TransactionHash;
@@ -10,6 +11,12 @@ use starknet_sequencer_infra::test_utils::get_available_socket;
 
 use crate::config::HttpServerConfig;
 
+// The fields shared by the results of all the transaction kinds.
+#[derive(Deserialize)]
+struct AddTxResult {
+    transaction_hash: TransactionHash,
+}
+
 /// A test utility client for interacting with an http server.
 pub struct HttpTestClient {
     socket: SocketAddr,
@@ -26,7 +33,8 @@ impl HttpTestClient {
         let response = self.add_tx(rpc_tx).await;
         assert!(response.status().is_success());
 
-        response.json().await.unwrap()
+        let add_tx_result: AddTxResult = response.json().await.unwrap();
+        add_tx_result.transaction_hash
     }
 
     // TODO: implement when usage eventually arises.
@@ -51,5 +59,5 @@ impl HttpTestClient {
 pub async fn create_http_server_config() -> HttpServerConfig {
     // TODO(Tsabary): use ser_generated_param.
     let socket = get_available_socket().await;
-    HttpServerConfig { ip: socket.ip(), port: socket.port() }
+    HttpServerConfig { ip: socket.ip(), port: socket.port(), ..Default::default() }
 }
@@ -3,6 +3,7 @@ use std::io::Write;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 
+use papyrus_consensus::config::{serialize_public_keys, serialize_voting_weights};
 use serde_json::{json, Value};
 use starknet_sequencer_infra::component_definitions::{
     LocalServerConfig,
@@ -55,7 +56,7 @@ pub(crate) fn dump_config_file_changes(
 ) -> PathBuf {
     // Dump config changes file for the sequencer node.
     // TODO(Tsabary): auto dump the entirety of RequiredParams fields.
-    let json_data = config_fields_to_json!(
+    let mut json_data = config_fields_to_json!(
         required_params.chain_id,
         required_params.eth_fee_token_address,
         required_params.strk_fee_token_address,
@@ -65,9 +66,19 @@ pub(crate) fn dump_config_file_changes(
         config.http_server_config.ip,
         config.http_server_config.port,
         config.consensus_manager_config.consensus_config.start_height,
+        config.consensus_manager_config.consensus_config.num_validators,
         config.state_sync_config.storage_config.db_config.path_prefix,
         config.state_sync_config.network_config.tcp_port,
+        config.consensus_manager_config.consensus_config.signer.private_key_file,
     );
+    // Params which are not dumped as their values.
+    let consensus_config = &config.consensus_manager_config.consensus_config;
+    json_data["consensus_manager_config.consensus_config.signer.private_key_file.#is_none"] =
+        json!(consensus_config.signer.private_key_file.is_none());
+    json_data["consensus_manager_config.consensus_config.validator_public_keys"] =
+        json!(serialize_public_keys(&consensus_config.validator_public_keys));
+    json_data["consensus_manager_config.consensus_config.validator_weights"] =
+        json!(serialize_voting_weights(&consensus_config.validator_weights));
     let node_config_path = dump_json_data(json_data, NODE_CONFIG_CHANGES_FILE_PATH, dir);
     assert!(node_config_path.exists(), "File does not exist: {:?}", node_config_path);
 
@@ -123,7 +134,7 @@ pub fn get_local_with_remote_enabled_component_config(
         execution_mode: ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled,
         local_server_config: Some(LocalServerConfig::default()),
         remote_client_config: None,
-        remote_server_config: Some(RemoteServerConfig { socket }),
+        remote_server_config: Some(RemoteServerConfig { socket, ..RemoteServerConfig::default() }),
     }
 }
 
@@ -16,7 +16,7 @@ use starknet_monitoring_endpoint::test_utils::IsAliveClient;
 use starknet_sequencer_node::config::node_config::SequencerNodeConfig;
 use starknet_sequencer_node::servers::run_component_servers;
 use starknet_sequencer_node::utils::create_node_modules;
-use tempfile::TempDir;
+use tempfile::{NamedTempFile, TempDir};
 use tokio::task::JoinHandle;
 use tracing::{debug, instrument};
 
@@ -89,10 +89,11 @@ pub struct SequencerSetup {
     // Client for adding transactions to the sequencer node.
     pub add_tx_http_client: HttpTestClient,
 
-    // Handlers for the storage files, maintained so the files are not deleted.
+    // Handlers for the storage and signer key files, maintained so the files are not deleted.
     pub batcher_storage_file_handle: TempDir,
     pub rpc_storage_file_handle: TempDir,
     pub state_sync_storage_file_handle: TempDir,
+    pub signer_key_file_handle: NamedTempFile,
 
     // Handle of the sequencer node.
     pub sequencer_node_handle: JoinHandle<Result<(), anyhow::Error>>,
@@ -122,7 +123,7 @@ impl SequencerSetup {
         .await;
 
         // Derive the configuration for the sequencer node.
-        let (config, _required_params) = create_config(
+        let (config, _required_params, signer_key_file_handle) = create_config(
             sequencer_index,
             chain_info,
             rpc_server_addr,
@@ -139,7 +140,7 @@ impl SequencerSetup {
         let MonitoringEndpointConfig { ip, port, .. } = config.monitoring_endpoint_config;
         let is_alive_test_client = IsAliveClient::new(SocketAddr::from((ip, port)));
 
-        let HttpServerConfig { ip, port } = config.http_server_config;
+        let HttpServerConfig { ip, port, .. } = config.http_server_config;
         let add_tx_http_client = HttpTestClient::new(SocketAddr::from((ip, port)));
 
         // Build and run the sequencer node.
@@ -152,6 +153,7 @@ impl SequencerSetup {
             batcher_storage_file_handle: storage_for_test.batcher_storage_handle,
             rpc_storage_file_handle: storage_for_test.rpc_storage_handle,
             state_sync_storage_file_handle: storage_for_test.state_sync_storage_handle,
+            signer_key_file_handle,
             sequencer_node_handle,
             config,
             is_alive_test_client,
@@ -7,7 +7,7 @@ use starknet_http_server::config::HttpServerConfig;
 use starknet_http_server::test_utils::HttpTestClient;
 use starknet_monitoring_endpoint::config::MonitoringEndpointConfig;
 use starknet_monitoring_endpoint::test_utils::IsAliveClient;
-use tempfile::{tempdir, TempDir};
+use tempfile::{tempdir, NamedTempFile, TempDir};
 
 use crate::config_utils::dump_config_file_changes;
 use crate::state_reader::{spawn_test_rpc_state_reader, StorageTestSetup};
@@ -43,6 +43,8 @@ pub struct IntegrationTestSetup {
     node_config_dir_handle: TempDir,
     #[allow(dead_code)]
     state_sync_storage_handle: TempDir,
+    #[allow(dead_code)]
+    signer_key_file_handle: NamedTempFile,
 }
 
 impl IntegrationTestSetup {
@@ -64,7 +66,7 @@ impl IntegrationTestSetup {
             create_mempool_p2p_configs(SEQUENCER_INDICES.len(), chain_info.chain_id.clone());
 
         // Derive the configuration for the sequencer node.
-        let (config, required_params) = create_config(
+        let (config, required_params, signer_key_file_handle) = create_config(
             SEQUENCER_INDEX,
             chain_info,
             rpc_server_addr,
@@ -86,7 +88,7 @@ impl IntegrationTestSetup {
         let MonitoringEndpointConfig { ip, port, .. } = config.monitoring_endpoint_config;
         let is_alive_test_client = IsAliveClient::new(SocketAddr::from((ip, port)));
 
-        let HttpServerConfig { ip, port } = config.http_server_config;
+        let HttpServerConfig { ip, port, .. } = config.http_server_config;
         let add_tx_http_client = HttpTestClient::new(SocketAddr::from((ip, port)));
 
         IntegrationTestSetup {
@@ -99,6 +101,7 @@ impl IntegrationTestSetup {
             node_config_path,
             state_sync_storage_handle: storage_for_test.state_sync_storage_handle,
             state_sync_storage_config: config.state_sync_config.storage_config,
+            signer_key_file_handle,
         }
     }
 }
@@ -1,4 +1,5 @@
 use std::future::Future;
+use std::io::Write;
 use std::net::SocketAddr;
 use std::time::Duration;
 
@@ -18,14 +19,17 @@ use papyrus_protobuf::consensus::{ProposalPart, StreamMessage};
 use papyrus_storage::StorageConfig;
 use starknet_api::block::BlockNumber;
 use starknet_api::core::{ChainId, ContractAddress};
+use starknet_api::crypto::utils::get_public_key;
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
 use starknet_batcher::block_builder::BlockBuilderConfig;
 use starknet_batcher::config::BatcherConfig;
 use starknet_consensus_manager::config::ConsensusManagerConfig;
 use starknet_gateway::config::{
+    CongestionControlConfig,
     GatewayConfig,
     RpcStateReaderConfig,
+    StateReaderSource,
     StatefulTransactionValidatorConfig,
     StatelessTransactionValidatorConfig,
 };
@@ -37,6 +41,7 @@ use starknet_sequencer_node::config::node_config::SequencerNodeConfig;
 use starknet_sequencer_node::config::test_utils::RequiredParams;
 use starknet_state_sync::config::StateSyncConfig;
 use starknet_types_core::felt::Felt;
+use tempfile::NamedTempFile;
 
 // TODO(Tsabary): Get rid of this constant once we have a better way to set the port for testing.
 const STATE_SYNC_NETWORK_CONFIG_TCP_PORT_FOR_TESTING: u16 = 12345;
@@ -59,8 +64,9 @@ pub async fn create_config(
     state_sync_storage_config: StorageConfig,
     mut consensus_manager_config: ConsensusManagerConfig,
     mempool_p2p_config: MempoolP2pConfig,
-) -> (SequencerNodeConfig, RequiredParams) {
+) -> (SequencerNodeConfig, RequiredParams, NamedTempFile) {
     set_validator_id(&mut consensus_manager_config, sequencer_index);
+    let signer_key_file_handle = set_signer_key_file(&mut consensus_manager_config);
     let fee_token_addresses = chain_info.fee_token_addresses.clone();
     let batcher_config = create_batcher_config(batcher_storage_config, chain_info.clone());
     let gateway_config = create_gateway_config(chain_info.clone()).await;
@@ -87,6 +93,7 @@ pub async fn create_config(
             strk_fee_token_address: fee_token_addresses.strk_fee_token_address,
             validator_id: ContractAddress::from(DEFAULT_VALIDATOR_ID),
         },
+        signer_key_file_handle,
     )
 }
 
@@ -108,6 +115,12 @@ pub fn create_consensus_manager_configs_and_channels(
     timeouts.prevote_timeout *= 3;
     timeouts.proposal_timeout *= 3;
 
+    let validator_public_keys = (0..u64::try_from(n_managers).unwrap())
+        .map(|i| {
+            get_public_key(&validator_private_key(ValidatorId::from(DEFAULT_VALIDATOR_ID + i)))
+        })
+        .collect::<Vec<_>>();
+
     let consensus_manager_configs = network_configs
         .into_iter()
         // TODO(Matan): Get config from default config file.
@@ -118,6 +131,8 @@ pub fn create_consensus_manager_configs_and_channels(
                 consensus_delay: Duration::from_secs(5),
                 network_config,
                 num_validators: u64::try_from(n_managers).unwrap(),
+                validator_public_keys: validator_public_keys.clone(),
+                validator_weights: vec![1; n_managers],
                 timeouts: timeouts.clone(),
                 ..Default::default()
             },
@@ -245,13 +260,19 @@ pub async fn create_gateway_config(chain_info: ChainInfo) -> GatewayConfig {
     };
     let stateful_tx_validator_config = StatefulTransactionValidatorConfig::default();
 
-    GatewayConfig { stateless_tx_validator_config, stateful_tx_validator_config, chain_info }
+    GatewayConfig {
+        stateless_tx_validator_config,
+        stateful_tx_validator_config,
+        chain_info,
+        state_reader_source: StateReaderSource::Rpc,
+        congestion_control_config: CongestionControlConfig::default(),
+    }
 }
 
 pub async fn create_http_server_config() -> HttpServerConfig {
     // TODO(Tsabary): use ser_generated_param.
     let socket = get_available_socket().await;
-    HttpServerConfig { ip: socket.ip(), port: socket.port() }
+    HttpServerConfig { ip: socket.ip(), port: socket.port(), ..Default::default() }
 }
 
 pub fn create_batcher_config(
@@ -274,6 +295,22 @@ fn set_validator_id(consensus_manager_config: &mut ConsensusManagerConfig, seque
     .unwrap();
 }
 
+// The private key of a validator in tests is its id.
+fn validator_private_key(validator_id: ValidatorId) -> Felt {
+    Felt::from(validator_id)
+}
+
+// Writes the private key of the validator to a file, which consensus signs with. The file is
+// deleted once the returned handle is dropped.
+fn set_signer_key_file(consensus_manager_config: &mut ConsensusManagerConfig) -> NamedTempFile {
+    let consensus_config = &mut consensus_manager_config.consensus_config;
+    let mut private_key_file = NamedTempFile::new().unwrap();
+    writeln!(private_key_file, "{:#x}", validator_private_key(consensus_config.validator_id))
+        .unwrap();
+    consensus_config.signer.private_key_file = Some(private_key_file.path().to_path_buf());
+    private_key_file
+}
+
 fn create_monitoring_endpoint_config(sequencer_index: usize) -> MonitoringEndpointConfig {
     let mut config = MonitoringEndpointConfig::default();
     config.port += u16::try_from(sequencer_index).unwrap();
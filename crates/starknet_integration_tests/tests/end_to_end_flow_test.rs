@@ -2,15 +2,10 @@ use std::collections::HashSet;
 
 use futures::StreamExt;
 use mempool_test_utils::starknet_api_test_utils::MultiAccountTransactionGenerator;
-use papyrus_consensus::types::ValidatorId;
+use papyrus_consensus::config::ConsensusConfig;
+use papyrus_consensus::signing::{create_signer, ConsensusSigner};
 use papyrus_network::network_manager::BroadcastTopicChannels;
-use papyrus_protobuf::consensus::{
-    ProposalFin,
-    ProposalInit,
-    ProposalPart,
-    StreamMessage,
-    StreamMessageBody,
-};
+use papyrus_protobuf::consensus::{ProposalInit, ProposalPart, StreamMessage, StreamMessageBody};
 use papyrus_storage::test_utils::CHAIN_ID_FOR_TESTS;
 use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
@@ -75,13 +70,13 @@ async fn end_to_end_flow(mut tx_generator: MultiAccountTransactionGenerator) {
                 sequencer_to_add_txs.assert_add_tx_success(tx)
             })
             .await;
-        let expected_validator_id = expected_proposer_iter
+        let expected_proposer_config = expected_proposer_iter
             .next()
             .unwrap()
             .config
             .consensus_manager_config
             .consensus_config
-            .validator_id;
+            .clone();
         // TODO(Dan, Itay): Consider adding a utility function that waits for something to happen.
         tokio::time::timeout(
             LISTEN_TO_BROADCAST_MESSAGES_TIMEOUT,
@@ -90,7 +85,7 @@ async fn end_to_end_flow(mut tx_generator: MultiAccountTransactionGenerator) {
                 &expected_batched_tx_hashes,
                 height,
                 expected_content_id,
-                expected_validator_id,
+                &expected_proposer_config,
             ),
         )
         .await
@@ -107,7 +102,7 @@ async fn listen_to_broadcasted_messages(
     expected_batched_tx_hashes: &[TransactionHash],
     expected_height: BlockNumber,
     expected_content_id: Felt,
-    expected_proposer_id: ValidatorId,
+    expected_proposer_config: &ConsensusConfig,
 ) {
     let chain_id = CHAIN_ID_FOR_TESTS.clone();
     let broadcasted_messages_receiver =
@@ -117,9 +112,18 @@ async fn listen_to_broadcasted_messages(
         height: expected_height,
         round: 0,
         valid_round: None,
-        proposer: expected_proposer_id,
+        proposer: expected_proposer_config.validator_id,
     };
-    let expected_proposal_fin = ProposalFin { proposal_content_id: BlockHash(expected_content_id) };
+    // Signatures are deterministic, so the fin is expected to be signed exactly as the proposer
+    // would sign it.
+    let expected_proposer_signer = ConsensusSigner::new(
+        expected_proposer_config.chain_id.clone(),
+        create_signer(&expected_proposer_config.signer).unwrap(),
+    );
+    let expected_proposal_fin = expected_proposer_signer
+        .sign_proposal_fin(&expected_proposal_init, BlockHash(expected_content_id))
+        .await
+        .unwrap();
 
     let StreamMessage {
         stream_id: first_stream_id,
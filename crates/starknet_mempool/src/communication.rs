@@ -1,11 +1,20 @@
+use std::any::type_name;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
 use async_trait::async_trait;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
+use serde_json::json;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::rpc_transaction::{
     RpcDeployAccountTransaction,
     RpcInvokeTransaction,
     RpcTransaction,
 };
+use starknet_api::state::SierraContractClass;
+use starknet_api::transaction::TransactionHash;
 use starknet_mempool_p2p_types::communication::SharedMempoolP2pPropagatorClient;
 use starknet_mempool_types::communication::{
     AddTransactionArgsWrapper,
@@ -13,26 +22,43 @@ use starknet_mempool_types::communication::{
     MempoolResponse,
 };
 use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::{CommitBlockArgs, MempoolResult};
+use starknet_mempool_types::mempool_types::{
+    CommitBlockArgs,
+    GetQueueSnapshotArgs,
+    MempoolResult,
+    RevertBlockArgs,
+    TransactionWithStatus,
+};
 use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
+use starknet_sequencer_infra::component_health::{ComponentHealth, HealthReport, HealthStatus};
 use starknet_sequencer_infra::component_server::{LocalComponentServer, RemoteComponentServer};
+use starknet_sequencer_infra::errors::ComponentError;
+use tracing::info;
 
-use crate::mempool::Mempool;
+use crate::mempool::{Mempool, MempoolConfig};
+use crate::utils::InstantClock;
 
 pub type LocalMempoolServer =
     LocalComponentServer<MempoolCommunicationWrapper, MempoolRequest, MempoolResponse>;
 pub type RemoteMempoolServer = RemoteComponentServer<MempoolRequest, MempoolResponse>;
 
 pub fn create_mempool(
+    config: MempoolConfig,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
-) -> MempoolCommunicationWrapper {
-    MempoolCommunicationWrapper::new(Mempool::default(), mempool_p2p_propagator_client)
+) -> io::Result<MempoolCommunicationWrapper> {
+    Ok(MempoolCommunicationWrapper::new(
+        Mempool::new(config, Arc::new(InstantClock))?,
+        mempool_p2p_propagator_client,
+    ))
 }
 
 /// Wraps the mempool to enable inbound async communication from other components.
 pub struct MempoolCommunicationWrapper {
-    mempool: Mempool,
+    // Shared with the task that periodically removes expired transactions.
+    mempool: Arc<Mutex<Mempool>>,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+    // The number of transactions in the mempool, as of the last handled request.
+    n_txs: Arc<AtomicUsize>,
 }
 
 impl MempoolCommunicationWrapper {
@@ -40,7 +66,25 @@ impl MempoolCommunicationWrapper {
         mempool: Mempool,
         mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
     ) -> Self {
-        MempoolCommunicationWrapper { mempool, mempool_p2p_propagator_client }
+        let n_txs = Arc::new(AtomicUsize::new(mempool.n_txs()));
+        MempoolCommunicationWrapper {
+            mempool: Arc::new(Mutex::new(mempool)),
+            mempool_p2p_propagator_client,
+            n_txs,
+        }
+    }
+
+    /// Returns a handle reporting the size of the mempool, which outlives moving the mempool into
+    /// its server.
+    pub fn health_reporter(&self) -> MempoolHealth {
+        MempoolHealth {
+            n_txs: self.n_txs.clone(),
+            capacity_in_txs: self.mempool().capacity_in_txs(),
+        }
+    }
+
+    fn mempool(&self) -> MutexGuard<'_, Mempool> {
+        lock(&self.mempool)
     }
 
     async fn send_tx_to_p2p(
@@ -82,7 +126,7 @@ impl MempoolCommunicationWrapper {
         &mut self,
         args_wrapper: AddTransactionArgsWrapper,
     ) -> MempoolResult<()> {
-        self.mempool.add_tx(args_wrapper.args.clone())?;
+        self.mempool().add_tx(args_wrapper.args.clone())?;
         // TODO: Verify that only transactions that were added to the mempool are sent.
         // TODO: handle declare correctly and remove this match.
         match args_wrapper.args.tx {
@@ -92,29 +136,116 @@ impl MempoolCommunicationWrapper {
     }
 
     fn commit_block(&mut self, args: CommitBlockArgs) -> MempoolResult<()> {
-        self.mempool.commit_block(args)
+        self.mempool().commit_block(args)
+    }
+
+    fn revert_block(&mut self, args: RevertBlockArgs) -> MempoolResult<()> {
+        self.mempool().revert_block(args)
     }
 
     fn get_txs(&mut self, n_txs: usize) -> MempoolResult<Vec<AccountTransaction>> {
-        self.mempool.get_txs(n_txs)
+        self.mempool().get_txs(n_txs)
+    }
+
+    fn get_sierra_classes(
+        &self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> Vec<(ClassHash, SierraContractClass)> {
+        self.mempool().get_sierra_classes(&tx_hashes)
+    }
+
+    fn get_tx(&self, tx_hash: TransactionHash) -> MempoolResult<TransactionWithStatus> {
+        self.mempool().get_tx(tx_hash)
+    }
+
+    fn get_account_next_nonce(&self, address: ContractAddress) -> MempoolResult<Option<Nonce>> {
+        self.mempool().get_account_next_nonce(address)
+    }
+
+    fn get_queue_snapshot(&self, args: GetQueueSnapshotArgs) -> Vec<TransactionHash> {
+        self.mempool().get_queue_snapshot(args)
     }
 }
 
 #[async_trait]
 impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommunicationWrapper {
     async fn handle_request(&mut self, request: MempoolRequest) -> MempoolResponse {
-        match request {
+        let response = match request {
             MempoolRequest::AddTransaction(args) => {
                 MempoolResponse::AddTransaction(self.add_tx(args).await)
             }
             MempoolRequest::CommitBlock(args) => {
                 MempoolResponse::CommitBlock(self.commit_block(args))
             }
+            MempoolRequest::RevertBlock(args) => {
+                MempoolResponse::RevertBlock(self.revert_block(args))
+            }
             MempoolRequest::GetTransactions(n_txs) => {
                 MempoolResponse::GetTransactions(self.get_txs(n_txs))
             }
-        }
+            MempoolRequest::GetSierraClasses(tx_hashes) => {
+                MempoolResponse::GetSierraClasses(Ok(self.get_sierra_classes(tx_hashes)))
+            }
+            MempoolRequest::GetTransaction(tx_hash) => {
+                MempoolResponse::GetTransaction(self.get_tx(tx_hash))
+            }
+            MempoolRequest::GetAccountNextNonce(address) => {
+                MempoolResponse::GetAccountNextNonce(self.get_account_next_nonce(address))
+            }
+            MempoolRequest::GetQueueSnapshot(args) => {
+                MempoolResponse::GetQueueSnapshot(Ok(self.get_queue_snapshot(args)))
+            }
+        };
+        self.n_txs.store(self.mempool().n_txs(), Ordering::Relaxed);
+        response
+    }
+}
+
+#[async_trait]
+impl ComponentStarter for MempoolCommunicationWrapper {
+    /// Spawns the task that periodically removes expired transactions, so they are removed even if
+    /// the mempool receives no requests.
+    async fn start(&mut self) -> Result<(), ComponentError> {
+        info!("Starting component {}.", type_name::<Self>());
+        let mempool = self.mempool.clone();
+        let n_txs = self.n_txs.clone();
+        let expiry_sweep_interval = self.mempool().expiry_sweep_interval();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(expiry_sweep_interval);
+            loop {
+                interval.tick().await;
+                let mut mempool = lock(&mempool);
+                mempool.remove_expired_txs();
+                n_txs.store(mempool.n_txs(), Ordering::Relaxed);
+            }
+        });
+        Ok(())
     }
 }
 
-impl ComponentStarter for MempoolCommunicationWrapper {}
+fn lock(mempool: &Mutex<Mempool>) -> MutexGuard<'_, Mempool> {
+    mempool.lock().expect("Lock on the mempool was poisoned due to a previous panic")
+}
+
+/// Reports the size of the mempool; a full mempool evicts transactions, and is reported degraded.
+#[derive(Clone, Debug)]
+pub struct MempoolHealth {
+    n_txs: Arc<AtomicUsize>,
+    capacity_in_txs: usize,
+}
+
+#[async_trait]
+impl ComponentHealth for MempoolHealth {
+    async fn health(&self) -> HealthReport {
+        let n_txs = self.n_txs.load(Ordering::Relaxed);
+        let status = if n_txs < self.capacity_in_txs {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Degraded
+        };
+        HealthReport::new(
+            status,
+            json!({ "n_txs": n_txs, "capacity_in_txs": self.capacity_in_txs }),
+        )
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::TransactionHash;
+use starknet_mempool_types::mempool_types::{
+    AccountState,
+    AddTransactionArgs,
+    CommitBlockArgs,
+    RevertBlockArgs,
+};
+
+// The minimal number of entries in the journal before it is compacted.
+const MIN_ENTRIES_TO_COMPACT: usize = 1000;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) enum JournalEntry {
+    CommittedNonces(HashMap<ContractAddress, Nonce>),
+    AddTransaction { args: AddTransactionArgs, submission_time: SystemTime },
+    CommitBlock(CommitBlockArgs),
+    RevertBlock(RevertBlockArgs),
+}
+
+/// An append-only log of the changes to the mempool, enabling their replay after a restart.
+/// It is occasionally compacted to the content of the mempool, once it is large enough compared to
+/// that content.
+#[derive(Debug)]
+pub(crate) struct MempoolJournal {
+    path: PathBuf,
+    file: File,
+    n_entries: usize,
+    // The account state each journaled transaction was admitted with.
+    account_states: HashMap<TransactionHash, AccountState>,
+}
+
+impl MempoolJournal {
+    /// Opens the journal at the given path, creating it if missing, and returns it along with its
+    /// existing entries.
+    pub fn open(path: PathBuf) -> io::Result<(Self, Vec<JournalEntry>)> {
+        let entries = match File::open(&path) {
+            Ok(file) => read_entries(file)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error),
+        };
+        let account_states = entries
+            .iter()
+            .filter_map(|entry| match entry {
+                JournalEntry::AddTransaction { args, .. } => {
+                    Some((args.tx.tx_hash(), args.account_state))
+                }
+                _ => None,
+            })
+            .collect();
+
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok((MempoolJournal { path, file, n_entries: entries.len(), account_states }, entries))
+    }
+
+    pub fn append(&mut self, entry: &JournalEntry) -> io::Result<()> {
+        write_entry(&mut self.file, entry)?;
+        self.n_entries += 1;
+        if let JournalEntry::AddTransaction { args, .. } = entry {
+            self.account_states.insert(args.tx.tx_hash(), args.account_state);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the account state the transaction was admitted with, if it was journaled.
+    pub fn account_state(&self, tx_hash: TransactionHash) -> Option<AccountState> {
+        self.account_states.get(&tx_hash).copied()
+    }
+
+    /// Returns whether the journal should be compacted, given the number of transactions held in
+    /// the mempool. Compacting only once the journal doubles in size makes its cost amortized
+    /// constant per entry.
+    pub fn should_compact(&self, n_txs: usize) -> bool {
+        self.n_entries >= MIN_ENTRIES_TO_COMPACT.max(2 * n_txs)
+    }
+
+    /// Atomically replaces the content of the journal with the given committed nonces and
+    /// transactions, along with their submission times.
+    pub fn compact(
+        &mut self,
+        committed_nonces: HashMap<ContractAddress, Nonce>,
+        txs: Vec<(AddTransactionArgs, SystemTime)>,
+    ) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        let mut tmp_file = File::create(&tmp_path)?;
+        write_entry(&mut tmp_file, &JournalEntry::CommittedNonces(committed_nonces))?;
+        let n_entries = txs.len() + 1;
+        let mut account_states = HashMap::with_capacity(txs.len());
+        for (args, submission_time) in txs {
+            account_states.insert(args.tx.tx_hash(), args.account_state);
+            write_entry(&mut tmp_file, &JournalEntry::AddTransaction { args, submission_time })?;
+        }
+        tmp_file.sync_all()?;
+
+        fs::rename(&tmp_path, &self.path)?;
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+        self.n_entries = n_entries;
+        self.account_states = account_states;
+
+        Ok(())
+    }
+}
+
+fn write_entry(file: &mut File, entry: &JournalEntry) -> io::Result<()> {
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    file.write_all(&line)
+}
+
+fn read_entries(file: File) -> io::Result<Vec<JournalEntry>> {
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(error) => {
+                // A partially written entry is expected if the node crashed mid-write.
+                tracing::warn!(
+                    "Stopped reading the mempool journal at a malformed entry: {error}."
+                );
+                break;
+            }
+        }
+    }
+
+    Ok(entries)
+}
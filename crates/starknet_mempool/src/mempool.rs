@@ -1,8 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
 use starknet_api::block::GasPrice;
-use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::state::SierraContractClass;
 use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
 use starknet_mempool_types::errors::MempoolError;
@@ -10,28 +19,154 @@ use starknet_mempool_types::mempool_types::{
     AccountState,
     AddTransactionArgs,
     CommitBlockArgs,
+    GetQueueSnapshotArgs,
     MempoolResult,
+    RevertBlockArgs,
+    TransactionStatus,
+    TransactionWithStatus,
 };
+use starknet_types_core::felt::Felt;
+use validator::{Validate, ValidationError};
 
+use crate::journal::{JournalEntry, MempoolJournal};
+use crate::metrics::{MEMPOOL_EXPIRED_TXS, MEMPOOL_SUSPENSION_TIMEOUTS};
+use crate::suspended_transaction_pool::SuspendedTransactionPool;
 use crate::transaction_pool::TransactionPool;
 use crate::transaction_queue::TransactionQueue;
-use crate::utils::try_increment_nonce;
+use crate::utils::{
+    sierra_class_size_in_bytes,
+    try_increment_nonce,
+    tx_size_in_bytes,
+    Clock,
+    InstantClock,
+};
 
 #[cfg(test)]
 #[path = "mempool_test.rs"]
 pub mod mempool_test;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Validate)]
+#[validate(schema(function = "validate_mempool_config"))]
 pub struct MempoolConfig {
-    enable_fee_escalation: bool,
+    pub enable_fee_escalation: bool,
     // TODO: consider adding validations; should be bounded?
     // Percentage increase for tip and max gas price to enable transaction replacement.
-    fee_escalation_percentage: u8, // E.g., 10 for a 10% increase.
+    pub fee_escalation_percentage: u8, // E.g., 10 for a 10% increase.
+    // Maximal number of transactions held in the pool.
+    pub capacity_in_txs: usize,
+    // Maximal total serialized size of the transactions held in the pool.
+    pub capacity_in_bytes: usize,
+    // Time after which a transaction that was not proposed for sequencing is removed.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub transaction_ttl: Duration,
+    // Interval between sweeps removing expired transactions, which run even if the mempool is
+    // idle.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub expiry_sweep_interval: Duration,
+    // Time after which a transaction awaiting the deployment of its account is dropped.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub suspended_tx_timeout: Duration,
+    // Maximal number of transactions awaiting the deployment of their account. These are held in
+    // the pool, thus count toward its capacity as well.
+    pub max_suspended_txs: usize,
+    // Whether to journal accepted transactions to disk, and restore them upon startup.
+    pub enable_journal: bool,
+    pub journal_path: PathBuf,
 }
 
 impl Default for MempoolConfig {
     fn default() -> Self {
-        MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage: 10 }
+        MempoolConfig {
+            enable_fee_escalation: true,
+            fee_escalation_percentage: 10,
+            capacity_in_txs: 100_000,
+            capacity_in_bytes: 1 << 30, // 1 GiB.
+            transaction_ttl: Duration::from_secs(60 * 60),
+            expiry_sweep_interval: Duration::from_secs(60),
+            suspended_tx_timeout: Duration::from_secs(60),
+            max_suspended_txs: 10_000,
+            enable_journal: false,
+            journal_path: PathBuf::from("./data/mempool/journal"),
+        }
+    }
+}
+
+fn validate_mempool_config(config: &MempoolConfig) -> Result<(), ValidationError> {
+    if config.transaction_ttl.is_zero() {
+        return Err(ValidationError::new("transaction_ttl should be positive"));
+    }
+    if config.expiry_sweep_interval.is_zero() {
+        return Err(ValidationError::new("expiry_sweep_interval should be positive"));
+    }
+    Ok(())
+}
+
+impl SerializeConfig for MempoolConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enable_fee_escalation",
+                &self.enable_fee_escalation,
+                "If true, transactions can be replaced by ones with sufficiently higher fees.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "fee_escalation_percentage",
+                &self.fee_escalation_percentage,
+                "Percentage increase of the tip and max L2 gas price required to replace a \
+                 transaction.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "capacity_in_txs",
+                &self.capacity_in_txs,
+                "Maximal number of transactions held in the mempool.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "capacity_in_bytes",
+                &self.capacity_in_bytes,
+                "Maximal total serialized size in bytes of the transactions held in the mempool.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "transaction_ttl",
+                &self.transaction_ttl.as_secs(),
+                "Time in seconds after which a transaction not proposed for sequencing is removed.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "expiry_sweep_interval",
+                &self.expiry_sweep_interval.as_secs(),
+                "Interval in seconds between sweeps removing expired transactions.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "suspended_tx_timeout",
+                &self.suspended_tx_timeout.as_secs(),
+                "Time in seconds after which a transaction awaiting the deployment of its account \
+                 is dropped.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_suspended_txs",
+                &self.max_suspended_txs,
+                "Maximal number of transactions awaiting the deployment of their account.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "enable_journal",
+                &self.enable_journal,
+                "If true, accepted transactions are journaled to disk, and restored on startup.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "journal_path",
+                &self.journal_path,
+                "The path of the mempool journal.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
     }
 }
 
@@ -107,6 +242,18 @@ impl MempoolState {
         addresses_to_rewind
     }
 
+    /// Returns whether the transaction was already proposed for sequencing, in the block
+    /// in-progress.
+    fn is_staged(&self, tx_reference: &TransactionReference) -> bool {
+        self.staged
+            .get(&tx_reference.address)
+            .is_some_and(|&next_nonce| tx_reference.nonce < next_nonce)
+    }
+
+    fn remove_tentative(&mut self, address: ContractAddress) {
+        self.tentative.remove(&address);
+    }
+
     fn validate_incoming_tx(&self, tx_reference: TransactionReference) -> MempoolResult<()> {
         let TransactionReference { address, nonce: tx_nonce, .. } = tx_reference;
         if self.get(address).is_some_and(|existing_nonce| tx_nonce < existing_nonce) {
@@ -116,23 +263,50 @@ impl MempoolState {
         Ok(())
     }
 
-    fn validate_commitment(&self, address: ContractAddress, next_nonce: Nonce) {
-        // FIXME: Remove after first POC.
-        // If commit_block wants to decrease the stored account nonce this can mean one of two
-        // things:
-        // 1. this is a reorg, which should be handled by a dedicated TBD mechanism and not inside
-        //    commit_block
-        // 2. the stored nonce originated from add_tx, so should be treated as tentative due to
-        //    possible races with the gateway; these types of nonces should be tagged somehow so
-        //    that commit_block can override them. Regardless, in the first POC this cannot happen
-        //    because the GW nonces are always 1.
-        if let Some(&committed_nonce) = self.committed.get(&address) {
-            assert!(committed_nonce <= next_nonce, "NOT SUPPORTED YET {address:?} {next_nonce:?}.")
+    /// Rewinds committed nonces to their values prior to a reverted block. Staged nonces are
+    /// discarded, as the block in-progress was built on top of the reverted one.
+    /// Returns the addresses whose nonces were rewound.
+    fn revert(&mut self, address_to_nonce: AddressToNonce) -> HashSet<ContractAddress> {
+        let mut rewound_addresses: HashSet<_> =
+            self.staged.drain().map(|(address, _)| address).collect();
+        for (address, nonce) in address_to_nonce {
+            self.tentative.remove(&address);
+            self.committed.insert(address, nonce);
+            rewound_addresses.insert(address);
         }
+
+        rewound_addresses
+    }
+
+    fn validate_commitment(
+        &self,
+        address: ContractAddress,
+        next_nonce: Nonce,
+    ) -> MempoolResult<()> {
+        // Decreasing a committed nonce means a reorg took place, which should be handled by
+        // reverting the affected blocks prior to the commitment.
+        if self.committed.get(&address).is_some_and(|&committed_nonce| next_nonce < committed_nonce)
+        {
+            return Err(MempoolError::CommitmentNonceTooOld { address, nonce: next_nonce });
+        }
+
+        Ok(())
+    }
+
+    fn validate_revert(&self, address: ContractAddress, prior_nonce: Nonce) -> MempoolResult<()> {
+        if self
+            .committed
+            .get(&address)
+            .is_some_and(|&committed_nonce| prior_nonce > committed_nonce)
+        {
+            return Err(MempoolError::RevertNonceTooLarge { address, nonce: prior_nonce });
+        }
+
+        Ok(())
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Mempool {
     config: MempoolConfig,
     // TODO: add docstring explaining visibility and coupling of the fields.
@@ -140,10 +314,55 @@ pub struct Mempool {
     tx_pool: TransactionPool,
     // Transactions eligible for sequencing.
     tx_queue: TransactionQueue,
+    // Transactions awaiting the deployment of their account.
+    suspended_tx_pool: SuspendedTransactionPool,
     state: MempoolState,
+    clock: Arc<dyn Clock>,
+    // Persists accepted transactions, if enabled.
+    journal: Option<MempoolJournal>,
+}
+
+impl Default for Mempool {
+    fn default() -> Self {
+        Mempool::new(MempoolConfig::default(), Arc::new(InstantClock))
+            .expect("A mempool without a journal should be created infallibly.")
+    }
 }
 
 impl Mempool {
+    /// Creates a mempool; if journaling is enabled, it is restored from the journal.
+    pub fn new(config: MempoolConfig, clock: Arc<dyn Clock>) -> io::Result<Self> {
+        let journal_path = config.enable_journal.then(|| config.journal_path.clone());
+        let mut mempool = Mempool {
+            config,
+            tx_pool: TransactionPool::default(),
+            tx_queue: TransactionQueue::default(),
+            suspended_tx_pool: SuspendedTransactionPool::default(),
+            state: MempoolState::default(),
+            clock,
+            journal: None,
+        };
+
+        if let Some(journal_path) = journal_path {
+            mempool.restore_from_journal(journal_path)?;
+        }
+
+        Ok(mempool)
+    }
+
+    /// Returns the number of transactions held in the mempool.
+    pub fn n_txs(&self) -> usize {
+        self.tx_pool.n_txs()
+    }
+
+    pub fn capacity_in_txs(&self) -> usize {
+        self.config.capacity_in_txs
+    }
+
+    pub fn expiry_sweep_interval(&self) -> Duration {
+        self.config.expiry_sweep_interval
+    }
+
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter(&self) -> impl Iterator<Item = &TransactionReference> {
@@ -187,6 +406,75 @@ impl Mempool {
             .collect())
     }
 
+    /// Returns the Sierra classes declared by the given transactions, keyed by their class hash.
+    /// Transactions unknown to the mempool, and those it holds no Sierra class for, are skipped.
+    pub fn get_sierra_classes(
+        &self,
+        tx_hashes: &[TransactionHash],
+    ) -> Vec<(ClassHash, SierraContractClass)> {
+        tx_hashes
+            .iter()
+            .filter_map(|&tx_hash| {
+                let AccountTransaction::Declare(declare_tx) =
+                    self.tx_pool.get_by_tx_hash(tx_hash).ok()?
+                else {
+                    return None;
+                };
+                let class = self.tx_pool.get_sierra_class(tx_hash).ok().flatten()?;
+                Some((declare_tx.class_hash(), class.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns the transaction with the given hash, along with its status in the mempool.
+    pub fn get_tx(&self, tx_hash: TransactionHash) -> MempoolResult<TransactionWithStatus> {
+        let tx = self.tx_pool.get_by_tx_hash(tx_hash)?;
+        let tx_reference = TransactionReference::new(tx);
+
+        let status = if self.suspended_tx_pool.contains(tx_hash) {
+            TransactionStatus::Suspended
+        } else if self.state.is_staged(&tx_reference) {
+            TransactionStatus::Staged
+        } else if self.tx_queue.get_nonce(tx_reference.address) == Some(tx_reference.nonce) {
+            TransactionStatus::Queued
+        } else {
+            TransactionStatus::Pending
+        };
+
+        Ok(TransactionWithStatus { tx: tx.clone(), status })
+    }
+
+    /// Returns the next nonce of the account, as viewed by the mempool: its nonce in the mempool
+    /// state, advanced past the consecutive transactions held in the pool. Returns `None` if the
+    /// account is unknown to the mempool.
+    pub fn get_account_next_nonce(&self, address: ContractAddress) -> MempoolResult<Option<Nonce>> {
+        let Some(mut next_nonce) = self.state.get(address) else {
+            return Ok(None);
+        };
+
+        let state_nonce = next_nonce;
+        let pool_nonces = self
+            .tx_pool
+            .account_txs_sorted_by_nonce(address)
+            .map(|tx_reference| tx_reference.nonce)
+            .skip_while(|&nonce| nonce < state_nonce);
+        for nonce in pool_nonces {
+            if nonce != next_nonce {
+                break;
+            }
+            next_nonce = try_increment_nonce(next_nonce)?;
+        }
+
+        Ok(Some(next_nonce))
+    }
+
+    /// Returns a page of the hashes of the transactions eligible for sequencing, ordered by their
+    /// priority.
+    pub fn get_queue_snapshot(&self, args: GetQueueSnapshotArgs) -> Vec<TransactionHash> {
+        let GetQueueSnapshotArgs { offset, limit } = args;
+        self.iter().skip(offset).take(limit).map(|tx_reference| tx_reference.tx_hash).collect()
+    }
+
     /// Adds a new transaction to the mempool.
     #[tracing::instrument(
         skip(self, args),
@@ -200,19 +488,39 @@ impl Mempool {
         err
     )]
     pub fn add_tx(&mut self, args: AddTransactionArgs) -> MempoolResult<()> {
-        let AddTransactionArgs { tx, account_state } = args;
+        let submission_time = self.clock.now();
+        self.add_tx_submitted_at(args, submission_time)
+    }
+
+    fn add_tx_submitted_at(
+        &mut self,
+        args: AddTransactionArgs,
+        submission_time: Instant,
+    ) -> MempoolResult<()> {
+        // Free up room taken by expired transactions, before considering eviction.
+        self.remove_expired_txs();
+
+        let AddTransactionArgs { tx, account_state, sierra_contract_class } = args;
         let tx_reference = TransactionReference::new(&tx);
         self.validate_incoming_tx(tx_reference)?;
 
-        self.handle_fee_escalation(&tx)?;
-        self.tx_pool.insert(tx)?;
+        let journal_entry = self.journal.is_some().then(|| JournalEntry::AddTransaction {
+            args: AddTransactionArgs {
+                tx: tx.clone(),
+                account_state,
+                sierra_contract_class: sierra_contract_class.clone(),
+            },
+            submission_time: self.to_system_time(submission_time),
+        });
 
-        // Align to account nonce, only if it is at least the one stored.
-        let AccountState { address, nonce: incoming_account_nonce } = account_state;
-        let stored_account_nonce = self.state.get_or_insert(address, incoming_account_nonce);
-        if tx_reference.nonce == stored_account_nonce {
-            self.tx_queue.remove(address);
-            self.tx_queue.insert(tx_reference);
+        let suspend = self.should_suspend(&tx, account_state);
+        self.insert_tx(tx, sierra_contract_class, account_state, submission_time, suspend)?;
+        if tx_reference.nonce == Nonce(Felt::ZERO) {
+            self.resume_suspended_tx(tx_reference.address);
+        }
+
+        if let Some(journal_entry) = journal_entry {
+            self.append_to_journal(journal_entry);
         }
 
         Ok(())
@@ -222,13 +530,16 @@ impl Mempool {
     /// updates account balances).
     #[tracing::instrument(skip(self, args), err)]
     pub fn commit_block(&mut self, args: CommitBlockArgs) -> MempoolResult<()> {
+        let journal_entry = self.journal.is_some().then(|| JournalEntry::CommitBlock(args.clone()));
         let CommitBlockArgs { address_to_nonce, tx_hashes } = args;
         tracing::debug!("Committing block with {} transactions to mempool.", tx_hashes.len());
 
-        // Align mempool data to committed nonces.
         for (&address, &next_nonce) in &address_to_nonce {
-            self.validate_commitment(address, next_nonce);
+            self.validate_commitment(address, next_nonce)?;
+        }
 
+        // Align mempool data to committed nonces.
+        for (&address, &next_nonce) in &address_to_nonce {
             // Maybe remove out-of-date transactions.
             if self
                 .tx_queue
@@ -252,6 +563,7 @@ impl Mempool {
         }
 
         // Commit block and rewind nonces of addresses that were not included in block.
+        let committed_addresses: Vec<ContractAddress> = address_to_nonce.keys().copied().collect();
         let addresses_to_rewind = self.state.commit(address_to_nonce);
         for address in addresses_to_rewind {
             // Account nonce is the minimal nonce of this address: it was proposed but not included.
@@ -264,6 +576,11 @@ impl Mempool {
             self.tx_queue.insert(*tx_reference);
         }
 
+        // Transactions awaiting a deployment that was sequenced elsewhere may now be admitted.
+        for &address in &committed_addresses {
+            self.resume_suspended_tx(address);
+        }
+
         tracing::debug!("Aligned mempool to committed nonces.");
 
         // Hard-delete: finally, remove committed transactions from the mempool.
@@ -271,21 +588,364 @@ impl Mempool {
             let Ok(_tx) = self.tx_pool.remove(tx_hash) else {
                 continue; // Transaction hash unknown to mempool, from a different node.
             };
-
-            // TODO(clean_accounts): remove address with no transactions left after a block cycle /
-            // TTL.
         }
         tracing::debug!("Removed committed transactions known to mempool.");
 
+        self.remove_expired_txs();
+        if let Some(journal_entry) = journal_entry {
+            self.append_to_journal(journal_entry);
+        }
+
+        Ok(())
+    }
+
+    /// Reverts a committed block, e.g., due to a reorg: returns its transactions to the mempool and
+    /// rewinds account nonces to their values prior to the block. Multiple blocks should be
+    /// reverted one at a time, starting from the latest.
+    #[tracing::instrument(skip(self, args), err)]
+    pub fn revert_block(&mut self, args: RevertBlockArgs) -> MempoolResult<()> {
+        let journal_entry = self.journal.is_some().then(|| JournalEntry::RevertBlock(args.clone()));
+        let RevertBlockArgs { address_to_nonce, txs } = args;
+        tracing::debug!("Reverting block with {} transactions in mempool.", txs.len());
+
+        for (&address, &prior_nonce) in &address_to_nonce {
+            self.state.validate_revert(address, prior_nonce)?;
+        }
+
+        let rewound_addresses = self.state.revert(address_to_nonce);
+
+        // Reverted transactions were already sequenced, so they are reinserted regardless of the
+        // pool capacity.
+        let submission_time = self.clock.now();
+        for tx in txs {
+            let tx_reference = TransactionReference::new(&tx);
+            let TransactionReference { address, nonce, .. } = tx_reference;
+            if self.validate_incoming_tx(tx_reference).is_err()
+                || self.tx_pool.get_by_address_and_nonce(address, nonce).is_some()
+            {
+                tracing::debug!("Skipping reinsertion of reverted {tx_reference}.");
+                continue;
+            }
+
+            // The Sierra classes of reverted declare transactions are not returned to the mempool.
+            let tx_size = tx_size_in_bytes(&tx);
+            self.tx_pool.insert(tx, None, tx_size, submission_time)?;
+        }
+
+        // Rebuild the queue according to the rewound nonces.
+        for address in rewound_addresses {
+            self.tx_queue.remove(address);
+            let Some(account_nonce) = self.state.get(address) else {
+                continue;
+            };
+            if let Some(tx_reference) =
+                self.tx_pool.get_by_address_and_nonce(address, account_nonce)
+            {
+                self.tx_queue.insert(tx_reference);
+            }
+        }
+
+        tracing::debug!("Rewound mempool to the nonces prior to the reverted block.");
+
+        if let Some(journal_entry) = journal_entry {
+            self.append_to_journal(journal_entry);
+        }
+
+        Ok(())
+    }
+
+    /// Removes transactions that were held in the mempool longer than the configured TTL, except
+    /// for those proposed in the block in-progress. The tentative nonces of accounts left with no
+    /// transactions are removed as well. Suspended transactions whose account was not deployed in
+    /// time are dropped.
+    pub fn remove_expired_txs(&mut self) {
+        self.remove_timed_out_suspended_txs();
+
+        let Some(expiry_time) = self.clock.now().checked_sub(self.config.transaction_ttl) else {
+            return;
+        };
+
+        let expired_txs: Vec<TransactionReference> = self
+            .tx_pool
+            .iter_submitted_before(expiry_time)
+            .filter(|tx_reference| !self.state.is_staged(tx_reference))
+            .copied()
+            .collect();
+        if expired_txs.is_empty() {
+            return;
+        }
+
+        for tx_reference in &expired_txs {
+            let TransactionReference { address, nonce, tx_hash, .. } = *tx_reference;
+            if self.tx_queue.get_nonce(address) == Some(nonce) {
+                self.tx_queue.remove(address);
+            }
+            self.tx_pool.remove(tx_hash).expect("Transaction hash from pool must exist.");
+            self.suspended_tx_pool.remove_by_tx_hash(tx_hash);
+            self.remove_tentative_if_no_txs_left(address);
+        }
+
+        tracing::debug!("Removed {} expired transactions from mempool.", expired_txs.len());
+        let n_expired_txs =
+            u64::try_from(expired_txs.len()).expect("Number of transactions fits in u64.");
+        metrics::counter!(MEMPOOL_EXPIRED_TXS, n_expired_txs);
+    }
+
+    fn remove_timed_out_suspended_txs(&mut self) {
+        let Some(timeout_time) = self.clock.now().checked_sub(self.config.suspended_tx_timeout)
+        else {
+            return;
+        };
+
+        let timed_out_txs = self.suspended_tx_pool.remove_suspended_before(timeout_time);
+        if timed_out_txs.is_empty() {
+            return;
+        }
+
+        for &tx_hash in &timed_out_txs {
+            let Ok(tx) = self.tx_pool.get_by_tx_hash(tx_hash) else {
+                continue;
+            };
+            let tx_reference = TransactionReference::new(tx);
+            // Suspended transactions are neither queued nor staged, unless their account was
+            // deployed meanwhile.
+            if self.tx_queue.get_nonce(tx_reference.address) == Some(tx_reference.nonce)
+                || self.state.is_staged(&tx_reference)
+            {
+                continue;
+            }
+            self.tx_pool.remove(tx_hash).expect("Transaction hash from pool must exist.");
+            self.remove_tentative_if_no_txs_left(tx_reference.address);
+        }
+
+        tracing::debug!(
+            "Dropped {} suspended transactions whose account was not deployed in time.",
+            timed_out_txs.len()
+        );
+        let n_timed_out_txs =
+            u64::try_from(timed_out_txs.len()).expect("Number of transactions fits in u64.");
+        metrics::counter!(MEMPOOL_SUSPENSION_TIMEOUTS, n_timed_out_txs);
+    }
+
+    /// Inserts a validated transaction into the pool, and enqueues it if it is eligible for
+    /// sequencing. A suspended transaction is held in the pool, but is not enqueued until its
+    /// account is deployed.
+    fn insert_tx(
+        &mut self,
+        tx: AccountTransaction,
+        sierra_contract_class: Option<SierraContractClass>,
+        account_state: AccountState,
+        submission_time: Instant,
+        suspend: bool,
+    ) -> MempoolResult<()> {
+        let tx_reference = TransactionReference::new(&tx);
+        let tx_size = tx_size_in_bytes(&tx)
+            + sierra_contract_class.as_ref().map_or(0, sierra_class_size_in_bytes);
+        // All checks precede any removal, so a rejected transaction leaves the pool untouched.
+        let replaced_tx = self.validate_fee_escalation(tx_reference)?;
+        let replaces_suspended_tx = replaced_tx
+            .is_some_and(|replaced_tx| self.suspended_tx_pool.contains(replaced_tx.tx_hash));
+        if suspend
+            && !replaces_suspended_tx
+            && self.suspended_tx_pool.len() >= self.config.max_suspended_txs
+        {
+            return Err(MempoolError::MempoolFull);
+        }
+        let txs_to_evict = self.select_txs_to_evict(tx_reference, tx_size, replaced_tx)?;
+
+        let mut suspension_time = submission_time;
+        if let Some(replaced_tx) = replaced_tx {
+            tracing::debug!("{replaced_tx} will be replaced by {tx_reference}.");
+            self.tx_queue.remove(replaced_tx.address);
+            self.tx_pool
+                .remove(replaced_tx.tx_hash)
+                .expect("Transaction hash from pool must exist.");
+            // A replacement does not extend the time the account has to be deployed.
+            if let Some(replaced_suspension_time) =
+                self.suspended_tx_pool.remove_by_tx_hash(replaced_tx.tx_hash)
+            {
+                suspension_time = replaced_suspension_time;
+            }
+        }
+        self.evict_txs(txs_to_evict);
+        self.tx_pool.insert(tx, sierra_contract_class, tx_size, submission_time)?;
+
+        if suspend {
+            tracing::debug!("Suspending {tx_reference} until its account is deployed.");
+            self.suspended_tx_pool.insert(
+                tx_reference.address,
+                tx_reference.tx_hash,
+                suspension_time,
+            );
+        }
+
+        // Align to account nonce, only if it is at least the one stored.
+        let AccountState { address, nonce: incoming_account_nonce } = account_state;
+        let stored_account_nonce = self.state.get_or_insert(address, incoming_account_nonce);
+        if tx_reference.nonce == stored_account_nonce {
+            self.tx_queue.remove(address);
+            self.tx_queue.insert(tx_reference);
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the transaction should be suspended: an invoke of nonce 1 skips stateful
+    /// validation when its account was not deployed yet, so it is only admitted following a
+    /// nonce-0 transaction of the account, typically its `DeployAccount` transaction. A nonce-0
+    /// invoke passes stateful validation only if the account is already deployed.
+    fn should_suspend(&self, tx: &AccountTransaction, account_state: AccountState) -> bool {
+        let nonce_0 = Nonce(Felt::ZERO);
+        let nonce_1 = Nonce(Felt::ONE);
+        let TransactionReference { address, nonce, .. } = TransactionReference::new(tx);
+        if !matches!(tx, AccountTransaction::Invoke(_))
+            || nonce != nonce_1
+            || account_state.nonce != nonce_0
+        {
+            return false;
+        }
+
+        // The deployment was already proposed or committed.
+        if self.state.get(address).is_some_and(|account_nonce| account_nonce > nonce_0) {
+            return false;
+        }
+
+        self.tx_pool.get_by_address_and_nonce(address, nonce_0).is_none()
+    }
+
+    /// Resumes the transaction suspended for the account, if any. It is already in the pool, and
+    /// is enqueued once the account nonce reaches it.
+    fn resume_suspended_tx(&mut self, address: ContractAddress) {
+        if let Some(tx_hash) = self.suspended_tx_pool.remove_by_address(address) {
+            tracing::debug!("Resumed suspended transaction {tx_hash}.");
+        }
+    }
+
+    /// Replays the journal: committed nonces are restored, journaled transactions are re-added
+    /// with the account state and submission time they were admitted with, and journaled blocks
+    /// are committed or reverted; stale and expired transactions are thus dropped.
+    fn restore_from_journal(&mut self, journal_path: PathBuf) -> io::Result<()> {
+        let (journal, entries) = MempoolJournal::open(journal_path)?;
+
+        for entry in entries {
+            match entry {
+                JournalEntry::CommittedNonces(address_to_nonce) => {
+                    self.state.commit(address_to_nonce);
+                }
+                JournalEntry::AddTransaction { args, submission_time } => {
+                    let tx_hash = args.tx.tx_hash();
+                    let Some(submission_time) = self.to_instant(submission_time) else {
+                        tracing::debug!("Dropped expired journaled transaction {tx_hash}.");
+                        continue;
+                    };
+                    if let Err(error) = self.add_tx_submitted_at(args, submission_time) {
+                        tracing::debug!("Dropped journaled transaction {tx_hash}: {error}.");
+                    }
+                }
+                JournalEntry::CommitBlock(args) => {
+                    if let Err(error) = self.commit_block(args) {
+                        tracing::debug!("Skipped a journaled block commitment: {error}.");
+                    }
+                }
+                JournalEntry::RevertBlock(args) => {
+                    if let Err(error) = self.revert_block(args) {
+                        tracing::debug!("Skipped a journaled block revert: {error}.");
+                    }
+                }
+            }
+        }
+        self.remove_expired_txs();
+
+        self.journal = Some(journal);
+        self.compact_journal();
+        tracing::info!("Restored {} transactions from the mempool journal.", self.tx_pool.n_txs());
+
         Ok(())
     }
 
+    /// Appends the entry to the journal, compacting the journal if it grew large enough.
+    fn append_to_journal(&mut self, entry: JournalEntry) {
+        let Some(journal) = &mut self.journal else {
+            return;
+        };
+
+        if let Err(error) = journal.append(&entry) {
+            tracing::error!("Failed to append to the mempool journal: {error}.");
+        }
+        if journal.should_compact(self.tx_pool.n_txs()) {
+            self.compact_journal();
+        }
+    }
+
+    /// Rewrites the journal to match the current content of the mempool.
+    fn compact_journal(&mut self) {
+        let Some(journal) = &self.journal else {
+            return;
+        };
+
+        let txs = self
+            .tx_pool
+            .iter()
+            .map(|tx| {
+                let address = tx.contract_address();
+                let tx_hash = tx.tx_hash();
+                let account_state = journal.account_state(tx_hash).unwrap_or_else(|| {
+                    // Transactions reinserted by a revert were not journaled; they are admitted
+                    // with the account nonce known to the mempool.
+                    let nonce = self.state.get(address).unwrap_or_else(|| tx.nonce());
+                    AccountState { address, nonce }
+                });
+                let submission_time = self
+                    .tx_pool
+                    .get_submission_time(tx_hash)
+                    .expect("Transaction hash from pool must exist.");
+                let sierra_contract_class = self
+                    .tx_pool
+                    .get_sierra_class(tx_hash)
+                    .expect("Transaction hash from pool must exist.")
+                    .cloned();
+                (
+                    AddTransactionArgs { tx: tx.clone(), account_state, sierra_contract_class },
+                    self.to_system_time(submission_time),
+                )
+            })
+            .collect();
+
+        let committed_nonces = self.state.committed.clone();
+        let journal = self.journal.as_mut().expect("The journal is enabled.");
+        if let Err(error) = journal.compact(committed_nonces, txs) {
+            tracing::error!("Failed to compact the mempool journal: {error}.");
+        }
+    }
+
+    /// Converts an instant of the mempool's clock to the wall-clock time, to be journaled.
+    fn to_system_time(&self, instant: Instant) -> SystemTime {
+        let elapsed = self.clock.now().saturating_duration_since(instant);
+        self.clock.system_time().checked_sub(elapsed).unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
+    /// Converts a journaled wall-clock time to an instant of the mempool's clock. Returns `None` if
+    /// the time is older than the transaction TTL.
+    fn to_instant(&self, system_time: SystemTime) -> Option<Instant> {
+        let elapsed = self.clock.system_time().duration_since(system_time).unwrap_or_default();
+        if elapsed >= self.config.transaction_ttl {
+            return None;
+        }
+        let now = self.clock.now();
+        // An instant cannot precede the boot of the machine; such a transaction is considered new.
+        Some(now.checked_sub(elapsed).unwrap_or(now))
+    }
+
     fn validate_incoming_tx(&self, tx_reference: TransactionReference) -> MempoolResult<()> {
         self.state.validate_incoming_tx(tx_reference)
     }
 
-    fn validate_commitment(&self, address: ContractAddress, next_nonce: Nonce) {
-        self.state.validate_commitment(address, next_nonce);
+    fn validate_commitment(
+        &self,
+        address: ContractAddress,
+        next_nonce: Nonce,
+    ) -> MempoolResult<()> {
+        self.state.validate_commitment(address, next_nonce)
     }
 
     // TODO(Mohammad): Rename this method once consensus API is added.
@@ -307,25 +967,25 @@ impl Mempool {
         Ok(())
     }
 
-    #[tracing::instrument(level = "debug", skip(self, incoming_tx), err)]
-    fn handle_fee_escalation(&mut self, incoming_tx: &AccountTransaction) -> MempoolResult<()> {
-        let incoming_tx_reference = TransactionReference::new(incoming_tx);
+    /// Returns the transaction to be replaced by the incoming one, if any. Fails if the incoming
+    /// transaction has the nonce of an existing one, and does not replace it.
+    #[tracing::instrument(level = "debug", skip(self, incoming_tx_reference), err)]
+    fn validate_fee_escalation(
+        &self,
+        incoming_tx_reference: TransactionReference,
+    ) -> MempoolResult<Option<TransactionReference>> {
         let TransactionReference { address, nonce, .. } = incoming_tx_reference;
 
-        if !self.config.enable_fee_escalation {
-            if self.tx_pool.get_by_address_and_nonce(address, nonce).is_some() {
-                return Err(MempoolError::DuplicateNonce { address, nonce });
-            };
-
-            return Ok(());
-        }
-
         let Some(existing_tx_reference) = self.tx_pool.get_by_address_and_nonce(address, nonce)
         else {
             // Replacement irrelevant: no existing transaction with the same nonce for address.
-            return Ok(());
+            return Ok(None);
         };
 
+        if !self.config.enable_fee_escalation {
+            return Err(MempoolError::DuplicateNonce { address, nonce });
+        }
+
         if !self.should_replace_tx(&existing_tx_reference, &incoming_tx_reference) {
             tracing::debug!(
                 "{existing_tx_reference} was not replaced by {incoming_tx_reference} due to
@@ -335,14 +995,95 @@ impl Mempool {
             return Err(MempoolError::DuplicateNonce { address, nonce });
         }
 
-        tracing::debug!("{existing_tx_reference} will be replaced by {incoming_tx_reference}.");
+        Ok(Some(existing_tx_reference))
+    }
 
-        self.tx_queue.remove(address);
-        self.tx_pool
-            .remove(existing_tx_reference.tx_hash)
-            .expect("Transaction hash from pool must exist.");
+    /// Returns the transactions to evict to make room for the incoming transaction, given the
+    /// transaction it replaces, if any. Only the lowest-priority transactions that are neither
+    /// queued nor staged are evicted, and only if their priority is strictly lower than the
+    /// incoming one; if these do not free enough room, the incoming transaction is rejected.
+    #[tracing::instrument(level = "debug", skip(self, incoming_tx_reference), err)]
+    fn select_txs_to_evict(
+        &self,
+        incoming_tx_reference: TransactionReference,
+        incoming_tx_size: usize,
+        replaced_tx: Option<TransactionReference>,
+    ) -> MempoolResult<HashSet<TransactionHash>> {
+        let (n_replaced_txs, n_replaced_bytes) = match replaced_tx {
+            Some(replaced_tx) => (1, self.tx_pool.get_size_in_bytes(replaced_tx.tx_hash)?),
+            None => (0, 0),
+        };
+        let mut n_txs_to_free = (self.tx_pool.n_txs() + 1)
+            .saturating_sub(n_replaced_txs)
+            .saturating_sub(self.config.capacity_in_txs);
+        let mut n_bytes_to_free = (self.tx_pool.size_in_bytes() + incoming_tx_size)
+            .saturating_sub(n_replaced_bytes)
+            .saturating_sub(self.config.capacity_in_bytes);
+        let mut txs_to_evict = HashSet::new();
+        if n_txs_to_free == 0 && n_bytes_to_free == 0 {
+            return Ok(txs_to_evict);
+        }
 
-        Ok(())
+        // The replaced transaction is removed regardless, so it is not accounted for again.
+        let replaced_tx_hash = replaced_tx.map(|replaced_tx| replaced_tx.tx_hash);
+        let incoming_priority = (incoming_tx_reference.tip, incoming_tx_reference.max_l2_gas_price);
+        for candidate in self.tx_pool.iter_by_ascending_priority() {
+            if n_txs_to_free == 0 && n_bytes_to_free == 0 {
+                break;
+            }
+            if (candidate.tip, candidate.max_l2_gas_price) >= incoming_priority {
+                break;
+            }
+            if !self.is_evictable(candidate) || txs_to_evict.contains(&candidate.tx_hash) {
+                continue;
+            }
+
+            // Evicting a transaction creates a nonce gap, so the subsequent transactions of the
+            // account are evicted along with it.
+            let account_txs_from_nonce = self
+                .tx_pool
+                .account_txs_sorted_by_nonce(candidate.address)
+                .filter(|tx_reference| tx_reference.nonce >= candidate.nonce)
+                .filter(|tx_reference| Some(tx_reference.tx_hash) != replaced_tx_hash);
+            for tx_reference in account_txs_from_nonce {
+                if txs_to_evict.insert(tx_reference.tx_hash) {
+                    let tx_size = self.tx_pool.get_size_in_bytes(tx_reference.tx_hash)?;
+                    n_txs_to_free = n_txs_to_free.saturating_sub(1);
+                    n_bytes_to_free = n_bytes_to_free.saturating_sub(tx_size);
+                }
+            }
+        }
+
+        if n_txs_to_free > 0 || n_bytes_to_free > 0 {
+            return Err(MempoolError::MempoolFull);
+        }
+
+        tracing::debug!(
+            "Evicting {} transactions to make room for {incoming_tx_reference}.",
+            txs_to_evict.len()
+        );
+        Ok(txs_to_evict)
+    }
+
+    fn evict_txs(&mut self, txs_to_evict: HashSet<TransactionHash>) {
+        for tx_hash in txs_to_evict {
+            let tx = self.tx_pool.remove(tx_hash).expect("Transaction hash from pool must exist.");
+            self.suspended_tx_pool.remove_by_tx_hash(tx_hash);
+            self.remove_tentative_if_no_txs_left(tx.contract_address());
+        }
+    }
+
+    /// Returns whether the transaction may be evicted, i.e., it is neither eligible for sequencing
+    /// nor proposed in the block in-progress.
+    fn is_evictable(&self, tx_reference: &TransactionReference) -> bool {
+        let is_queued = self.tx_queue.get_nonce(tx_reference.address) == Some(tx_reference.nonce);
+        !is_queued && !self.state.is_staged(tx_reference)
+    }
+
+    fn remove_tentative_if_no_txs_left(&mut self, address: ContractAddress) {
+        if !self.tx_pool.contains_account(address) {
+            self.state.remove_tentative(address);
+        }
     }
 
     fn should_replace_tx(
@@ -1,31 +1,63 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use assert_matches::assert_matches;
 use mockall::predicate;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_test_utils::{get_rng, GetTestInstance};
 use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
 use starknet_api::block::GasPrice;
+use starknet_api::contract_class::{ClassInfo, ContractClass, SierraVersion};
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::rpc_transaction::{
     RpcDeployAccountTransaction,
     RpcInvokeTransaction,
     RpcTransaction,
 };
-use starknet_api::{contract_address, nonce};
+use starknet_api::state::SierraContractClass;
+use starknet_api::test_utils::declare::executable_declare_tx;
+use starknet_api::{class_hash, contract_address, declare_tx_args, nonce, tx_hash};
 use starknet_mempool_p2p_types::communication::MockMempoolP2pPropagatorClient;
-use starknet_mempool_types::communication::AddTransactionArgsWrapper;
+use starknet_mempool_types::communication::{
+    AddTransactionArgsWrapper,
+    MempoolRequest,
+    MempoolResponse,
+};
 use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::AddTransactionArgs;
+use starknet_mempool_types::mempool_types::{
+    AccountState,
+    AddTransactionArgs,
+    CommitBlockArgs,
+    GetQueueSnapshotArgs,
+    RevertBlockArgs,
+    TransactionStatus,
+    TransactionWithStatus,
+};
+use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
+use starknet_sequencer_infra::component_health::{ComponentHealth, HealthStatus};
+use validator::Validate;
 
 use crate::communication::MempoolCommunicationWrapper;
 use crate::mempool::{Mempool, MempoolConfig, TransactionReference};
-use crate::test_utils::{add_tx, add_tx_expect_error, commit_block, get_txs_and_assert_expected};
+use crate::test_utils::{
+    add_tx,
+    add_tx_expect_error,
+    commit_block,
+    get_txs_and_assert_expected,
+    revert_block,
+    FakeClock,
+};
 use crate::transaction_pool::TransactionPool;
 use crate::transaction_queue::transaction_queue_test_utils::{
     TransactionQueueContent,
     TransactionQueueContentBuilder,
 };
+use crate::utils::{tx_size_in_bytes, InstantClock};
 use crate::{add_tx_input, tx};
 
 // Utils.
@@ -61,8 +93,11 @@ impl From<MempoolContent> for Mempool {
             tx_queue: tx_queue_content
                 .map(|content| content.complete_to_tx_queue())
                 .unwrap_or_default(),
+            suspended_tx_pool: Default::default(),
             // TODO: Add implementation when needed.
             state: Default::default(),
+            clock: Arc::new(InstantClock),
+            journal: None,
         }
     }
 }
@@ -114,7 +149,8 @@ impl MempoolContentBuilder {
     }
 
     fn with_fee_escalation_percentage(mut self, fee_escalation_percentage: u8) -> Self {
-        self.config = MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage };
+        self.config =
+            MempoolConfig { enable_fee_escalation: true, fee_escalation_percentage, ..self.config };
         self
     }
 
@@ -135,12 +171,17 @@ impl FromIterator<AccountTransaction> for TransactionPool {
     fn from_iter<T: IntoIterator<Item = AccountTransaction>>(txs: T) -> Self {
         let mut pool = Self::default();
         for tx in txs {
-            pool.insert(tx).unwrap();
+            let tx_size = tx_size_in_bytes(&tx);
+            pool.insert(tx, None, tx_size, Instant::now()).unwrap();
         }
         pool
     }
 }
 
+fn mempool_with_config(config: MempoolConfig) -> Mempool {
+    Mempool::new(config, Arc::new(InstantClock)).unwrap()
+}
+
 #[track_caller]
 fn add_tx_and_verify_replacement(
     mut mempool: Mempool,
@@ -502,6 +543,49 @@ fn test_add_tx_fills_nonce_gap(mut mempool: Mempool) {
     expected_mempool_content.assert_eq(&mempool);
 }
 
+#[rstest]
+fn test_add_tx_rejects_suspension_beyond_limit() {
+    // Setup.
+    let mut mempool =
+        mempool_with_config(MempoolConfig { max_suspended_txs: 1, ..Default::default() });
+    let input_suspended =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 10);
+    add_tx(&mut mempool, &input_suspended);
+
+    // Test and assert.
+    let input_over_limit =
+        add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 1, account_nonce: 0, tip: 20);
+    add_tx_expect_error(&mut mempool, &input_over_limit, MempoolError::MempoolFull);
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool([input_suspended.tx]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_suspended_txs_count_toward_capacity() {
+    // Setup.
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_txs: 1, ..Default::default() });
+    let input_suspended =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 10);
+    add_tx(&mut mempool, &input_suspended);
+
+    // Test and assert: a suspended transaction takes room like any other.
+    let input_lower_priority =
+        add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 5);
+    add_tx_expect_error(&mut mempool, &input_lower_priority, MempoolError::MempoolFull);
+
+    // Test and assert: it is evicted in favor of a higher-priority transaction.
+    let input_higher_priority =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 20);
+    add_tx(&mut mempool, &input_higher_priority);
+    let suspended_tx_hash = input_suspended.tx.tx_hash();
+    assert_eq!(
+        mempool.get_tx(suspended_tx_hash),
+        Err(MempoolError::TransactionNotFound { tx_hash: suspended_tx_hash })
+    );
+}
+
 #[rstest]
 fn test_add_tx_does_not_decrease_account_nonce(mut mempool: Mempool) {
     // Setup.
@@ -521,6 +605,309 @@ fn test_add_tx_does_not_decrease_account_nonce(mut mempool: Mempool) {
     assert_eq!(mempool.state.get(contract_address!("0x0")), Some(nonce!(2)));
 }
 
+// Capacity tests.
+
+#[rstest]
+fn test_add_tx_evicts_lowest_priority_non_queued_tx() {
+    // Setup.
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_txs: 3, ..Default::default() });
+    let input_queued_tip_10 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10);
+    let input_nonce_gap_tip_20 =
+        add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 1, account_nonce: 0, tip: 20);
+    let input_nonce_gap_tip_30 =
+        add_tx_input!(tx_hash: 3, address: "0x2", tx_nonce: 1, account_nonce: 0, tip: 30);
+    for input in [&input_queued_tip_10, &input_nonce_gap_tip_20, &input_nonce_gap_tip_30] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let input_tip_25 =
+        add_tx_input!(tx_hash: 4, address: "0x3", tx_nonce: 0, account_nonce: 0, tip: 25);
+    add_tx(&mut mempool, &input_tip_25);
+
+    // Assert: the non-queued transaction with the lowest tip was evicted, along with its account.
+    let expected_queue_txs =
+        [&input_tip_25.tx, &input_queued_tip_10.tx].map(TransactionReference::new);
+    let expected_pool_txs = [input_queued_tip_10.tx, input_nonce_gap_tip_30.tx, input_tip_25.tx];
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool(expected_pool_txs)
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+    assert_eq!(mempool.state.get(contract_address!("0x1")), None);
+}
+
+#[rstest]
+fn test_add_tx_evicts_subsequent_account_txs() {
+    // Setup.
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_txs: 3, ..Default::default() });
+    let input_nonce_1_tip_10 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 10);
+    let input_nonce_2_tip_100 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 2, account_nonce: 0, tip: 100);
+    let input_queued =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 0);
+    for input in [&input_nonce_1_tip_10, &input_nonce_2_tip_100, &input_queued] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let input_tip_50 =
+        add_tx_input!(tx_hash: 4, address: "0x2", tx_nonce: 0, account_nonce: 0, tip: 50);
+    add_tx(&mut mempool, &input_tip_50);
+
+    // Assert: the higher-nonce transaction, left unreachable by the nonce gap, was evicted as well.
+    let expected_pool_txs = [input_queued.tx, input_tip_50.tx];
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool(expected_pool_txs).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_does_not_evict_queued_or_staged_txs() {
+    // Setup.
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_txs: 2, ..Default::default() });
+    let input_staged = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_queued = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    for input in [&input_staged, &input_queued] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(&mut mempool, 1, &[input_staged.tx.clone()]);
+
+    // Test and assert.
+    let input_tip_100 =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 100);
+    add_tx_expect_error(&mut mempool, &input_tip_100, MempoolError::MempoolFull);
+
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_staged.tx, input_queued.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&input_queued.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_does_not_evict_higher_or_equal_priority_txs() {
+    // Setup.
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_txs: 1, ..Default::default() });
+    let input_nonce_gap =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 10);
+    add_tx(&mut mempool, &input_nonce_gap);
+
+    // Test and assert.
+    for (tx_hash, tip) in [(2, 5), (3, 10)] {
+        let input = add_tx_input!(tx_hash: tx_hash, address: "0x1", tip: tip);
+        add_tx_expect_error(&mut mempool, &input, MempoolError::MempoolFull);
+    }
+
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool([input_nonce_gap.tx]).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_evicts_by_size_in_bytes() {
+    // Setup.
+    let input_nonce_gap_tip_10 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 10);
+    let input_nonce_gap_tip_20 =
+        add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 1, account_nonce: 0, tip: 20);
+    let input_tip_30 =
+        add_tx_input!(tx_hash: 3, address: "0x2", tx_nonce: 0, account_nonce: 0, tip: 30);
+    // Room for all transactions but a single byte.
+    let capacity_in_bytes = [&input_nonce_gap_tip_10, &input_nonce_gap_tip_20, &input_tip_30]
+        .map(|input| tx_size_in_bytes(&input.tx))
+        .iter()
+        .sum::<usize>()
+        - 1;
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_bytes, ..Default::default() });
+    for input in [&input_nonce_gap_tip_10, &input_nonce_gap_tip_20] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    add_tx(&mut mempool, &input_tip_30);
+
+    // Assert.
+    let expected_pool_txs = [input_nonce_gap_tip_20.tx, input_tip_30.tx];
+    let expected_mempool_content =
+        MempoolContentBuilder::new().with_pool(expected_pool_txs).build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_replacement_rejected_when_full_keeps_existing_tx() {
+    // Setup.
+    let input_tip_10 = add_tx_input!(
+        tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10, max_l2_gas_price: 10
+    );
+    let input_replacement = add_tx_input!(
+        tx_hash: 2,
+        address: "0x0",
+        tx_nonce: 0,
+        account_nonce: 0,
+        tip: 1_000_000_000,
+        max_l2_gas_price: 1_000_000_000
+    );
+    let capacity_in_bytes = tx_size_in_bytes(&input_tip_10.tx);
+    assert!(tx_size_in_bytes(&input_replacement.tx) > capacity_in_bytes);
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_bytes, ..Default::default() });
+    add_tx(&mut mempool, &input_tip_10);
+
+    // Test and assert: the replacement does not fit, so the existing transaction is kept.
+    add_tx_expect_error(&mut mempool, &input_replacement, MempoolError::MempoolFull);
+
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_tip_10.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&input_tip_10.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[rstest]
+fn test_add_tx_replacement_does_not_count_replaced_tx_toward_capacity() {
+    // Setup.
+    let mut mempool =
+        mempool_with_config(MempoolConfig { capacity_in_txs: 1, ..Default::default() });
+    let input_tip_10 = add_tx_input!(
+        tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10, max_l2_gas_price: 10
+    );
+    let input_replacement = add_tx_input!(
+        tx_hash: 2, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 20, max_l2_gas_price: 20
+    );
+    add_tx(&mut mempool, &input_tip_10);
+
+    // Test.
+    add_tx(&mut mempool, &input_replacement);
+
+    // Assert.
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_replacement.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&input_replacement.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+// Expiry tests.
+
+#[rstest]
+#[case::zero_ttl(MempoolConfig { transaction_ttl: Duration::ZERO, ..Default::default() })]
+#[case::zero_sweep_interval(MempoolConfig {
+    expiry_sweep_interval: Duration::ZERO,
+    ..Default::default()
+})]
+fn test_config_rejects_zero_expiry_durations(#[case] config: MempoolConfig) {
+    assert!(MempoolConfig::default().validate().is_ok());
+    assert!(config.validate().is_err());
+}
+
+#[rstest]
+fn test_add_tx_removes_expired_txs() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig { transaction_ttl: Duration::from_secs(60), ..Default::default() };
+    let mut mempool = Mempool::new(config, clock.clone()).unwrap();
+
+    let input_queued = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_gap = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 1, account_nonce: 0);
+    for input in [&input_queued, &input_nonce_gap] {
+        add_tx(&mut mempool, input);
+    }
+    clock.advance(Duration::from_secs(30));
+    let input_fresh = add_tx_input!(tx_hash: 3, address: "0x2", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &input_fresh);
+
+    // Test.
+    clock.advance(Duration::from_secs(31));
+    let input_new = add_tx_input!(tx_hash: 4, address: "0x3", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &input_new);
+
+    // Assert: expired transactions and their accounts were removed.
+    let expected_queue_txs = [&input_new.tx, &input_fresh.tx].map(TransactionReference::new);
+    let expected_pool_txs = [input_fresh.tx, input_new.tx];
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool(expected_pool_txs)
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+    for address in [contract_address!("0x0"), contract_address!("0x1")] {
+        assert_eq!(mempool.state.get(address), None);
+    }
+}
+
+#[rstest]
+fn test_expiry_does_not_remove_staged_txs() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig { transaction_ttl: Duration::from_secs(60), ..Default::default() };
+    let mut mempool = Mempool::new(config, clock.clone()).unwrap();
+
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(&mut mempool, 1, &[input_nonce_0.tx.clone()]);
+
+    // Test.
+    clock.advance(Duration::from_secs(61));
+    let input_new = add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &input_new);
+
+    // Assert: only the queued transaction expired; the staged one awaits the block commitment.
+    let expected_queue_txs = [TransactionReference::new(&input_new.tx)];
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_nonce_0.tx, input_new.tx.clone()])
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+
+    commit_block(&mut mempool, [("0x0", 1)], [1]);
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_new.tx])
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_expired_txs_removed_while_idle() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig {
+        transaction_ttl: Duration::from_secs(60),
+        expiry_sweep_interval: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let mut mempool = Mempool::new(config, clock.clone()).unwrap();
+    let input = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &input);
+    let mut mempool_wrapper =
+        MempoolCommunicationWrapper::new(mempool, Arc::new(MockMempoolP2pPropagatorClient::new()));
+    let health_reporter = mempool_wrapper.health_reporter();
+    mempool_wrapper.start().await.unwrap();
+
+    // Test: the transaction expires while no requests reach the mempool.
+    clock.advance(Duration::from_secs(61));
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    // Assert.
+    let tx_hash = input.tx.tx_hash();
+    assert_eq!(health_reporter.health().await.details["n_txs"], 0);
+    let response = mempool_wrapper.handle_request(MempoolRequest::GetTransaction(tx_hash)).await;
+    assert_matches!(
+        response,
+        MempoolResponse::GetTransaction(Err(MempoolError::TransactionNotFound { .. }))
+    );
+}
+
 // `commit_block` tests.
 
 #[rstest]
@@ -561,6 +948,444 @@ fn test_commit_block_includes_all_proposed_txs() {
     expected_mempool_content.assert_eq(&mempool);
 }
 
+#[rstest]
+fn test_commit_block_rejects_nonce_decrease(mut mempool: Mempool) {
+    // Setup.
+    let input = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 3, account_nonce: 3);
+    add_tx(&mut mempool, &input);
+    commit_block(&mut mempool, [("0x0", 3)], []);
+
+    // Test.
+    let address_to_nonce = HashMap::from([(contract_address!("0x0"), nonce!(2))]);
+    let args = CommitBlockArgs { address_to_nonce, tx_hashes: Default::default() };
+    let result = mempool.commit_block(args);
+
+    // Assert: the commitment is rejected, and the mempool is left untouched.
+    assert_eq!(
+        result,
+        Err(MempoolError::CommitmentNonceTooOld {
+            address: contract_address!("0x0"),
+            nonce: nonce!(2)
+        })
+    );
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&input.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+}
+
+// `revert_block` tests.
+
+#[rstest]
+fn test_revert_block_returns_txs_to_mempool(mut mempool: Mempool) {
+    // Setup.
+    let input_address_0_nonce_0 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10);
+    let input_address_0_nonce_1 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 10);
+    let input_address_1_nonce_0 =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 20);
+    for input in [&input_address_0_nonce_0, &input_address_0_nonce_1, &input_address_1_nonce_0] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(
+        &mut mempool,
+        3,
+        &[
+            input_address_1_nonce_0.tx.clone(),
+            input_address_0_nonce_0.tx.clone(),
+            input_address_0_nonce_1.tx.clone(),
+        ],
+    );
+    commit_block(&mut mempool, [("0x0", 2), ("0x1", 1)], [1, 2, 3]);
+
+    // Test.
+    let reverted_txs = [
+        input_address_0_nonce_0.tx.clone(),
+        input_address_0_nonce_1.tx.clone(),
+        input_address_1_nonce_0.tx.clone(),
+    ];
+    revert_block(&mut mempool, [("0x0", 0), ("0x1", 0)], reverted_txs.clone());
+
+    // Assert: transactions are back in the mempool, and the accounts are queued from their prior
+    // nonces.
+    let expected_queue_txs =
+        [&input_address_1_nonce_0.tx, &input_address_0_nonce_0.tx].map(TransactionReference::new);
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool(reverted_txs)
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+    assert_eq!(mempool.state.get(contract_address!("0x0")), Some(nonce!(0)));
+}
+
+#[rstest]
+fn test_revert_block_replaces_queued_tx_and_discards_staged_nonces(mut mempool: Mempool) {
+    // Setup.
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let input_nonce_2 = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 2, account_nonce: 0);
+    for input in [&input_nonce_0, &input_nonce_1, &input_nonce_2] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(&mut mempool, 1, &[input_nonce_0.tx.clone()]);
+    commit_block(&mut mempool, [("0x0", 1)], [1]);
+    // Nonce 1 is proposed in the block in-progress, when the previous block is reverted.
+    get_txs_and_assert_expected(&mut mempool, 1, &[input_nonce_1.tx.clone()]);
+
+    // Test.
+    revert_block(&mut mempool, [("0x0", 0)], [input_nonce_0.tx.clone()]);
+
+    // Assert.
+    let expected_queue_txs = [TransactionReference::new(&input_nonce_0.tx)];
+    let expected_mempool_content = MempoolContentBuilder::new()
+        .with_pool([input_nonce_0.tx.clone(), input_nonce_1.tx.clone(), input_nonce_2.tx.clone()])
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool);
+    get_txs_and_assert_expected(
+        &mut mempool,
+        3,
+        &[input_nonce_0.tx, input_nonce_1.tx, input_nonce_2.tx],
+    );
+}
+
+#[rstest]
+fn test_revert_block_rejects_uncommitted_nonce(mut mempool: Mempool) {
+    // Setup.
+    commit_block(&mut mempool, [("0x0", 1)], []);
+
+    // Test and assert.
+    let address_to_nonce = HashMap::from([(contract_address!("0x0"), nonce!(2))]);
+    let args = RevertBlockArgs { address_to_nonce, txs: vec![] };
+    assert_eq!(
+        mempool.revert_block(args),
+        Err(MempoolError::RevertNonceTooLarge {
+            address: contract_address!("0x0"),
+            nonce: nonce!(2)
+        })
+    );
+}
+
+// Query tests.
+
+#[rstest]
+fn test_get_tx_returns_status(mut mempool: Mempool) {
+    // Setup.
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let input_nonce_3 = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 3, account_nonce: 0);
+    for input in [&input_nonce_0, &input_nonce_1, &input_nonce_3] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(&mut mempool, 1, &[input_nonce_0.tx.clone()]);
+
+    // Test and assert.
+    for (input, status) in [
+        (input_nonce_0, TransactionStatus::Staged),
+        (input_nonce_1, TransactionStatus::Queued),
+        (input_nonce_3, TransactionStatus::Pending),
+    ] {
+        let tx_hash = input.tx.tx_hash();
+        assert_eq!(mempool.get_tx(tx_hash), Ok(TransactionWithStatus { tx: input.tx, status }));
+    }
+    assert_eq!(
+        mempool.get_tx(tx_hash!(4)),
+        Err(MempoolError::TransactionNotFound { tx_hash: tx_hash!(4) })
+    );
+}
+
+#[rstest]
+fn test_get_account_next_nonce(mut mempool: Mempool) {
+    // Setup.
+    let input_nonce_2 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 2, account_nonce: 2);
+    let input_nonce_3 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 3, account_nonce: 2);
+    let input_nonce_5 = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 5, account_nonce: 2);
+    let input_nonce_gap = add_tx_input!(tx_hash: 4, address: "0x1", tx_nonce: 1, account_nonce: 0);
+    for input in [&input_nonce_2, &input_nonce_3, &input_nonce_5, &input_nonce_gap] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test and assert: the next nonce follows the consecutive transactions of the account.
+    assert_eq!(mempool.get_account_next_nonce(contract_address!("0x0")), Ok(Some(nonce!(4))));
+    assert_eq!(mempool.get_account_next_nonce(contract_address!("0x1")), Ok(Some(nonce!(0))));
+    assert_eq!(mempool.get_account_next_nonce(contract_address!("0x2")), Ok(None));
+
+    // Staged transactions are accounted for.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_nonce_2.tx, input_nonce_3.tx]);
+    assert_eq!(mempool.get_account_next_nonce(contract_address!("0x0")), Ok(Some(nonce!(4))));
+}
+
+#[rstest]
+#[case::first_page(0, 2, &[2, 1])]
+#[case::last_page(2, 2, &[3])]
+#[case::beyond_last_page(3, 2, &[])]
+fn test_get_queue_snapshot(
+    #[case] offset: usize,
+    #[case] limit: usize,
+    #[case] expected_tx_hashes: &[u8],
+) {
+    // Setup.
+    let tx_tip_20 = tx!(tx_hash: 1, address: "0x0", tip: 20);
+    let tx_tip_30 = tx!(tx_hash: 2, address: "0x1", tip: 30);
+    let tx_tip_10 = tx!(tx_hash: 3, address: "0x2", tip: 10);
+
+    let queue_txs = [&tx_tip_20, &tx_tip_30, &tx_tip_10].map(TransactionReference::new);
+    let mempool = MempoolContentBuilder::new()
+        .with_pool([tx_tip_20, tx_tip_30, tx_tip_10])
+        .with_priority_queue(queue_txs)
+        .build_into_mempool();
+
+    // Test and assert.
+    let snapshot = mempool.get_queue_snapshot(GetQueueSnapshotArgs { offset, limit });
+    let expected_snapshot: Vec<_> =
+        expected_tx_hashes.iter().map(|&tx_hash| tx_hash!(tx_hash)).collect();
+    assert_eq!(snapshot, expected_snapshot);
+}
+
+// Sierra class tests.
+
+/// Returns an input for `add_tx` of a declare transaction, holding the given Sierra class next to
+/// it.
+fn declare_tx_input(
+    tx_hash: u64,
+    address: &str,
+    sierra_contract_class: Option<SierraContractClass>,
+) -> AddTransactionArgs {
+    let class_info = ClassInfo {
+        contract_class: ContractClass::V0(Default::default()),
+        sierra_program_length: 0,
+        abi_length: 0,
+        sierra_version: SierraVersion::DEPRECATED,
+    };
+    let address = contract_address!(address);
+    let tx = executable_declare_tx(
+        declare_tx_args!(tx_hash: tx_hash!(tx_hash), sender_address: address, class_hash: class_hash!(tx_hash)),
+        class_info,
+    );
+    AddTransactionArgs {
+        tx,
+        account_state: AccountState { address, nonce: nonce!(0) },
+        sierra_contract_class,
+    }
+}
+
+#[rstest]
+fn test_get_sierra_classes() {
+    // Setup.
+    let sierra_contract_class = SierraContractClass::default();
+    let declare_with_class = declare_tx_input(1, "0x0", Some(sierra_contract_class.clone()));
+    let declare_without_class = declare_tx_input(2, "0x1", None);
+    let invoke = add_tx_input!(tx_hash: 3, address: "0x2", tx_nonce: 0, account_nonce: 0);
+
+    let mut mempool = Mempool::default();
+    for input in [&declare_with_class, &declare_without_class, &invoke] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Test.
+    let sierra_classes = mempool.get_sierra_classes(&[
+        tx_hash!(1),
+        tx_hash!(2),
+        tx_hash!(3),
+        tx_hash!(4), // Unknown to the mempool.
+    ]);
+
+    // Assert.
+    assert_eq!(sierra_classes, vec![(class_hash!(1_u64), sierra_contract_class)]);
+}
+
+#[rstest]
+fn test_sierra_class_counts_towards_capacity() {
+    let declare_without_class = declare_tx_input(1, "0x0", None);
+    let declare_with_class = declare_tx_input(1, "0x0", Some(SierraContractClass::default()));
+
+    let mut mempool = Mempool::default();
+    add_tx(&mut mempool, &declare_without_class);
+    let size_without_class = mempool.tx_pool.size_in_bytes();
+
+    let mut mempool = Mempool::default();
+    add_tx(&mut mempool, &declare_with_class);
+    assert!(mempool.tx_pool.size_in_bytes() > size_without_class);
+}
+
+// Journal tests.
+
+fn journaled_mempool(journal_path: &Path) -> Mempool {
+    mempool_with_config(MempoolConfig {
+        enable_journal: true,
+        journal_path: journal_path.to_path_buf(),
+        ..Default::default()
+    })
+}
+
+#[rstest]
+fn test_journal_restores_txs_after_restart() {
+    // Setup.
+    let journal_dir = tempfile::tempdir().unwrap();
+    let journal_path = journal_dir.path().join("journal");
+    let input_address_0_nonce_0 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10);
+    let input_address_0_nonce_1 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0, tip: 10);
+    let input_address_1_nonce_0 =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 20);
+
+    let mut mempool = journaled_mempool(&journal_path);
+    for input in [&input_address_0_nonce_0, &input_address_0_nonce_1, &input_address_1_nonce_0] {
+        add_tx(&mut mempool, input);
+    }
+    drop(mempool);
+
+    // Test.
+    let mut mempool = journaled_mempool(&journal_path);
+
+    // Assert.
+    get_txs_and_assert_expected(
+        &mut mempool,
+        3,
+        &[input_address_1_nonce_0.tx, input_address_0_nonce_0.tx, input_address_0_nonce_1.tx],
+    );
+}
+
+#[rstest]
+fn test_journal_restores_sierra_classes_after_restart() {
+    // Setup.
+    let journal_dir = tempfile::tempdir().unwrap();
+    let journal_path = journal_dir.path().join("journal");
+    let sierra_contract_class = SierraContractClass::default();
+    let input = declare_tx_input(1, "0x0", Some(sierra_contract_class.clone()));
+
+    let mut mempool = journaled_mempool(&journal_path);
+    add_tx(&mut mempool, &input);
+    drop(mempool);
+
+    // Test.
+    let mempool = journaled_mempool(&journal_path);
+
+    // Assert.
+    assert_eq!(
+        mempool.get_sierra_classes(&[tx_hash!(1)]),
+        vec![(class_hash!(1_u64), sierra_contract_class)]
+    );
+}
+
+#[rstest]
+fn test_journal_drops_committed_txs() {
+    // Setup.
+    let journal_dir = tempfile::tempdir().unwrap();
+    let journal_path = journal_dir.path().join("journal");
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+
+    let mut mempool = journaled_mempool(&journal_path);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+    commit_block(&mut mempool, [("0x0", 1)], [1]);
+    drop(mempool);
+
+    // Test.
+    let mut mempool = journaled_mempool(&journal_path);
+
+    // Assert: the committed transaction is gone, and its nonce cannot be reused.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_nonce_1.tx]);
+    add_tx_expect_error(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 0, account_nonce: 0),
+        MempoolError::NonceTooOld { address: contract_address!("0x0"), nonce: nonce!(0) },
+    );
+}
+
+#[rstest]
+fn test_journal_ignores_truncated_entry() {
+    // Setup.
+    let journal_dir = tempfile::tempdir().unwrap();
+    let journal_path = journal_dir.path().join("journal");
+    let input = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+
+    let mut mempool = journaled_mempool(&journal_path);
+    add_tx(&mut mempool, &input);
+    drop(mempool);
+
+    // Simulate a crash in the middle of writing an entry.
+    let mut journal_file = OpenOptions::new().append(true).open(&journal_path).unwrap();
+    journal_file.write_all(b"{\"AddTransaction\":{\"tx\":").unwrap();
+    drop(journal_file);
+
+    // Test.
+    let mut mempool = journaled_mempool(&journal_path);
+
+    // Assert.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input.tx]);
+}
+
+#[rstest]
+fn test_journal_appends_block_commitment_without_rewriting() {
+    // Setup.
+    let journal_dir = tempfile::tempdir().unwrap();
+    let journal_path = journal_dir.path().join("journal");
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+
+    let mut mempool = journaled_mempool(&journal_path);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+    let journal_before_commit = std::fs::read_to_string(&journal_path).unwrap();
+
+    // Test.
+    commit_block(&mut mempool, [("0x0", 1)], [1]);
+
+    // Assert: the block commitment was appended to the existing entries.
+    let journal_after_commit = std::fs::read_to_string(&journal_path).unwrap();
+    assert!(journal_after_commit.starts_with(&journal_before_commit));
+    assert_eq!(journal_after_commit.lines().count(), journal_before_commit.lines().count() + 1);
+}
+
+#[rstest]
+fn test_journal_keeps_submission_time_after_restart() {
+    // Setup.
+    let journal_dir = tempfile::tempdir().unwrap();
+    let clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig {
+        transaction_ttl: Duration::from_secs(60),
+        enable_journal: true,
+        journal_path: journal_dir.path().join("journal"),
+        ..Default::default()
+    };
+    let input_old = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_new = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0);
+
+    let mut mempool = Mempool::new(config.clone(), clock.clone()).unwrap();
+    add_tx(&mut mempool, &input_old);
+    clock.advance(Duration::from_secs(30));
+    add_tx(&mut mempool, &input_new);
+    drop(mempool);
+
+    // Test.
+    clock.advance(Duration::from_secs(31));
+    let mut mempool = Mempool::new(config, clock.clone()).unwrap();
+
+    // Assert: the older transaction expired despite the restart.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_new.tx]);
+}
+
+#[rstest]
+fn test_journal_open_failure_is_returned() {
+    // Setup: the journal path is taken by a directory.
+    let journal_dir = tempfile::tempdir().unwrap();
+    let config = MempoolConfig {
+        enable_journal: true,
+        journal_path: journal_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+
+    // Test and assert.
+    assert!(Mempool::new(config, Arc::new(InstantClock)).is_err());
+}
+
 // Fee escalation tests.
 
 #[rstest]
@@ -806,3 +1631,29 @@ async fn test_propagated_tx_sent_to_p2p(mempool: Mempool) {
 
     mempool_wrapper.add_tx(propagated_args).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_health_reports_mempool_size() {
+    // Setup.
+    let mempool = mempool_with_config(MempoolConfig { capacity_in_txs: 1, ..Default::default() });
+    let mut mock_mempool_p2p_propagator_client = MockMempoolP2pPropagatorClient::new();
+    mock_mempool_p2p_propagator_client.expect_add_transaction().returning(|_| Ok(()));
+    let mut mempool_wrapper =
+        MempoolCommunicationWrapper::new(mempool, Arc::new(mock_mempool_p2p_propagator_client));
+    let health_reporter = mempool_wrapper.health_reporter();
+    assert_eq!(health_reporter.health().await.status, HealthStatus::Healthy);
+
+    // Test.
+    let args = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let request = MempoolRequest::AddTransaction(AddTransactionArgsWrapper {
+        args,
+        p2p_message_metadata: None,
+    });
+    let response = mempool_wrapper.handle_request(request).await;
+    assert!(matches!(response, MempoolResponse::AddTransaction(Ok(()))));
+
+    // Assert: the mempool is full.
+    let report = health_reporter.health().await;
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.details, serde_json::json!({ "n_txs": 1, "capacity_in_txs": 1 }));
+}
@@ -0,0 +1,4 @@
+/// The number of transactions removed from the mempool after their time-to-live has passed.
+pub const MEMPOOL_EXPIRED_TXS: &str = "mempool_expired_txs";
+/// The number of suspended transactions dropped since their account was not deployed in time.
+pub const MEMPOOL_SUSPENSION_TIMEOUTS: &str = "mempool_suspension_timeouts";
@@ -1,30 +1,73 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
 
-use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::core::ContractAddress;
+use starknet_api::transaction::TransactionHash;
 
-use crate::mempool::TransactionReference;
-
-type _AddressNonceToTransaction = HashMap<(ContractAddress, Nonce), TransactionReference>;
-
-#[derive(Debug)]
-pub struct _SuspendedTransactionPool {
-    suspended_tx_pool: _AddressNonceToTransaction,
+/// Indexes the transactions in the pool that are suspended until the deployment of their account.
+/// Only nonce-1 transactions are suspended, so an account has at most one suspended transaction.
+#[derive(Debug, Default)]
+pub struct SuspendedTransactionPool {
+    address_to_tx: HashMap<ContractAddress, (TransactionHash, Instant)>,
+    hash_to_address: HashMap<TransactionHash, ContractAddress>,
+    txs_by_suspension_time: BTreeMap<(Instant, TransactionHash), ContractAddress>,
 }
 
-impl _SuspendedTransactionPool {
-    pub fn _contains(&self, address: ContractAddress, nonce: Nonce) -> bool {
-        self.suspended_tx_pool.contains_key(&(address, nonce))
+impl SuspendedTransactionPool {
+    pub fn contains(&self, tx_hash: TransactionHash) -> bool {
+        self.hash_to_address.contains_key(&tx_hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.hash_to_address.len()
     }
 
-    pub fn _insert(&mut self, tx: TransactionReference) {
+    pub fn insert(
+        &mut self,
+        address: ContractAddress,
+        tx_hash: TransactionHash,
+        suspension_time: Instant,
+    ) {
         assert_eq!(
-            self.suspended_tx_pool.insert((tx.address, tx.nonce), tx),
+            self.address_to_tx.insert(address, (tx_hash, suspension_time)),
             None,
             "Keys should be unique; duplicates are checked prior."
         );
+        self.hash_to_address.insert(tx_hash, address);
+        self.txs_by_suspension_time.insert((suspension_time, tx_hash), address);
+    }
+
+    /// Removes the transaction suspended for the given account, if any, returning its hash.
+    pub fn remove_by_address(&mut self, address: ContractAddress) -> Option<TransactionHash> {
+        let (tx_hash, suspension_time) = self.address_to_tx.remove(&address)?;
+        self.hash_to_address.remove(&tx_hash);
+        self.txs_by_suspension_time.remove(&(suspension_time, tx_hash));
+
+        Some(tx_hash)
+    }
+
+    /// Removes the transaction with the given hash, if suspended, returning its suspension time.
+    pub fn remove_by_tx_hash(&mut self, tx_hash: TransactionHash) -> Option<Instant> {
+        let address = self.hash_to_address.remove(&tx_hash)?;
+        let (_, suspension_time) =
+            self.address_to_tx.remove(&address).expect("Address of a suspended hash must exist.");
+        self.txs_by_suspension_time.remove(&(suspension_time, tx_hash));
+
+        Some(suspension_time)
     }
 
-    pub fn _remove(&mut self, tx: &TransactionReference) -> bool {
-        self.suspended_tx_pool.remove(&(tx.address, tx.nonce)).is_some()
+    /// Removes the transactions suspended before the given time, returning their hashes.
+    pub fn remove_suspended_before(&mut self, time: Instant) -> Vec<TransactionHash> {
+        let timed_out_txs: Vec<_> = self
+            .txs_by_suspension_time
+            .range(..(time, TransactionHash::default()))
+            .map(|(&(_, tx_hash), _)| tx_hash)
+            .collect();
+
+        for &tx_hash in &timed_out_txs {
+            self.remove_by_tx_hash(tx_hash);
+        }
+
+        timed_out_txs
     }
 }
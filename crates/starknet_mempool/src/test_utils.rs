@@ -1,12 +1,45 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
 
 use pretty_assertions::assert_eq;
 use starknet_api::executable_transaction::AccountTransaction;
 use starknet_api::{contract_address, nonce, tx_hash};
 use starknet_mempool_types::errors::MempoolError;
-use starknet_mempool_types::mempool_types::{AddTransactionArgs, CommitBlockArgs};
+use starknet_mempool_types::mempool_types::{AddTransactionArgs, CommitBlockArgs, RevertBlockArgs};
 
 use crate::mempool::Mempool;
+use crate::utils::Clock;
+
+/// A clock that only advances when told to.
+#[derive(Debug)]
+pub struct FakeClock {
+    now: Mutex<(Instant, SystemTime)>,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        FakeClock { now: Mutex::new((Instant::now(), SystemTime::now())) }
+    }
+}
+
+impl FakeClock {
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap();
+        now.0 += duration;
+        now.1 += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.now.lock().unwrap().0
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.now.lock().unwrap().1
+    }
+}
 
 /// Creates an executable invoke transaction with the given field subset (the rest receive default
 /// values).
@@ -112,7 +145,7 @@ macro_rules! add_tx_input {
         let account_nonce = nonce!($account_nonce);
         let account_state = AccountState { address, nonce: account_nonce };
 
-        AddTransactionArgs { tx, account_state }
+        AddTransactionArgs { tx, account_state, sierra_contract_class: None }
     }};
     (
         tx_hash: $tx_hash:expr,
@@ -215,6 +248,27 @@ macro_rules! add_tx_input {
     };
 }
 
+/// Creates an input for `add_tx` with a `DeployAccount` transaction of the given account.
+#[macro_export]
+macro_rules! deploy_account_tx_input {
+    (tx_hash: $tx_hash:expr,address: $address:expr) => {{
+        use starknet_api::executable_transaction::{AccountTransaction, DeployAccountTransaction};
+        use starknet_api::test_utils::deploy_account::deploy_account_tx;
+        use starknet_api::{contract_address, deploy_account_tx_args, nonce, tx_hash};
+        use starknet_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
+
+        let address = contract_address!($address);
+        let tx = AccountTransaction::DeployAccount(DeployAccountTransaction {
+            tx: deploy_account_tx(deploy_account_tx_args!(), nonce!(0)),
+            tx_hash: tx_hash!($tx_hash),
+            contract_address: address,
+        });
+        let account_state = AccountState { address, nonce: nonce!(0) };
+
+        AddTransactionArgs { tx, account_state, sierra_contract_class: None }
+    }};
+}
+
 #[track_caller]
 pub fn add_tx(mempool: &mut Mempool, input: &AddTransactionArgs) {
     assert_eq!(mempool.add_tx(input.clone()), Ok(()));
@@ -244,6 +298,20 @@ pub fn commit_block(
     assert_eq!(mempool.commit_block(args), Ok(()));
 }
 
+#[track_caller]
+pub fn revert_block(
+    mempool: &mut Mempool,
+    nonces: impl IntoIterator<Item = (&'static str, u8)>,
+    txs: impl IntoIterator<Item = AccountTransaction>,
+) {
+    let nonces = HashMap::from_iter(
+        nonces.into_iter().map(|(address, nonce)| (contract_address!(address), nonce!(nonce))),
+    );
+    let args = RevertBlockArgs { address_to_nonce: nonces, txs: txs.into_iter().collect() };
+
+    assert_eq!(mempool.revert_block(args), Ok(()));
+}
+
 #[track_caller]
 pub fn get_txs_and_assert_expected(
     mempool: &mut Mempool,
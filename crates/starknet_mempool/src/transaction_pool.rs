@@ -1,7 +1,10 @@
-use std::collections::{hash_map, BTreeMap, HashMap};
+use std::cmp::Ordering;
+use std::collections::{hash_map, BTreeMap, BTreeSet, HashMap};
+use std::time::Instant;
 
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::state::SierraContractClass;
 use starknet_api::transaction::TransactionHash;
 use starknet_mempool_types::errors::MempoolError;
 use starknet_mempool_types::mempool_types::{AccountState, MempoolResult};
@@ -9,30 +12,63 @@ use starknet_mempool_types::mempool_types::{AccountState, MempoolResult};
 use crate::mempool::TransactionReference;
 use crate::utils::try_increment_nonce;
 
-type HashToTransaction = HashMap<TransactionHash, AccountTransaction>;
+type HashToTransaction = HashMap<TransactionHash, PoolTransaction>;
+
+/// A transaction held in the pool, along with the Sierra class it declares, if any, and their
+/// serialized size, computed once upon insertion.
+#[derive(Debug, Eq, PartialEq)]
+struct PoolTransaction {
+    tx: AccountTransaction,
+    sierra_contract_class: Option<SierraContractClass>,
+    size_in_bytes: usize,
+}
 
 /// Contains all transactions currently held in the mempool.
 /// Invariant: both data structures are consistent regarding the existence of transactions:
 /// A transaction appears in one if and only if it appears in the other.
 /// No duplicate transactions appear in the pool.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug, Default)]
 pub struct TransactionPool {
     // Holds the complete transaction objects; it should be the sole entity that does so.
     tx_pool: HashToTransaction,
     // Transactions organized by account address, sorted by ascending nonce values.
     txs_by_account: AccountTransactionIndex,
+    // Transactions sorted by ascending priority, used for choosing eviction candidates.
+    txs_by_priority: PriorityIndex,
+    // Transactions sorted by their submission time, used for expiring old transactions.
+    txs_by_submission_time: TimedTransactionMap,
     // Tracks the capacity of the pool.
     capacity: PoolCapacity,
 }
 
+// Submission times are excluded from the comparison, as they depend on the time of insertion.
+impl PartialEq for TransactionPool {
+    fn eq(&self, other: &Self) -> bool {
+        self.tx_pool == other.tx_pool
+            && self.txs_by_account == other.txs_by_account
+            && self.txs_by_priority == other.txs_by_priority
+            && self.capacity == other.capacity
+    }
+}
+
+impl Eq for TransactionPool {}
+
 impl TransactionPool {
-    pub fn insert(&mut self, tx: AccountTransaction) -> MempoolResult<()> {
+    /// Inserts the transaction, along with the Sierra class it declares, if any; their serialized
+    /// size is given to avoid recomputing it.
+    pub fn insert(
+        &mut self,
+        tx: AccountTransaction,
+        sierra_contract_class: Option<SierraContractClass>,
+        size_in_bytes: usize,
+        submission_time: Instant,
+    ) -> MempoolResult<()> {
         let tx_reference = TransactionReference::new(&tx);
         let tx_hash = tx_reference.tx_hash;
 
         // Insert to pool.
         if let hash_map::Entry::Vacant(entry) = self.tx_pool.entry(tx_hash) {
-            entry.insert(tx);
+            entry.insert(PoolTransaction { tx, sierra_contract_class, size_in_bytes });
         } else {
             return Err(MempoolError::DuplicateTransaction { tx_hash });
         }
@@ -46,26 +82,31 @@ impl TransactionPool {
                 mapping",
             )
         };
+        self.txs_by_priority.insert(tx_reference);
+        self.txs_by_submission_time.insert(tx_reference, submission_time);
 
-        self.capacity.add();
+        self.capacity.add(size_in_bytes);
 
         Ok(())
     }
 
     pub fn remove(&mut self, tx_hash: TransactionHash) -> MempoolResult<AccountTransaction> {
         // Remove from pool.
-        let tx =
+        let PoolTransaction { tx, size_in_bytes, .. } =
             self.tx_pool.remove(&tx_hash).ok_or(MempoolError::TransactionNotFound { tx_hash })?;
 
         // Remove from account mapping.
-        self.txs_by_account.remove(TransactionReference::new(&tx)).unwrap_or_else(|| {
+        let tx_reference = TransactionReference::new(&tx);
+        self.txs_by_account.remove(tx_reference).unwrap_or_else(|| {
             panic!(
                 "Transaction pool consistency error: transaction with hash {tx_hash} appears in
                 main mapping, but does not appear in the account mapping"
             )
         });
+        self.txs_by_priority.remove(tx_reference);
+        self.txs_by_submission_time.remove(tx_hash);
 
-        self.capacity.remove();
+        self.capacity.remove(size_in_bytes);
 
         Ok(tx)
     }
@@ -73,18 +114,26 @@ impl TransactionPool {
     pub fn remove_up_to_nonce(&mut self, address: ContractAddress, nonce: Nonce) {
         let removed_txs = self.txs_by_account.remove_up_to_nonce(address, nonce);
 
-        for TransactionReference { tx_hash, .. } in removed_txs {
-            self.tx_pool.remove(&tx_hash).unwrap_or_else(|| {
-                panic!(
-                    "Transaction pool consistency error: transaction with hash {tx_hash} appears
-                    in account mapping, but does not appear in the main mapping"
-                );
-            });
-
-            self.capacity.remove();
+        for tx_reference in removed_txs {
+            let tx_hash = tx_reference.tx_hash;
+            let PoolTransaction { size_in_bytes, .. } =
+                self.tx_pool.remove(&tx_hash).unwrap_or_else(|| {
+                    panic!(
+                        "Transaction pool consistency error: transaction with hash {tx_hash} \
+                         appears in account mapping, but does not appear in the main mapping"
+                    );
+                });
+            self.txs_by_priority.remove(tx_reference);
+            self.txs_by_submission_time.remove(tx_hash);
+
+            self.capacity.remove(size_in_bytes);
         }
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &AccountTransaction> {
+        self.tx_pool.values().map(|pool_tx| &pool_tx.tx)
+    }
+
     pub fn account_txs_sorted_by_nonce(
         &self,
         address: ContractAddress,
@@ -93,7 +142,34 @@ impl TransactionPool {
     }
 
     pub fn get_by_tx_hash(&self, tx_hash: TransactionHash) -> MempoolResult<&AccountTransaction> {
-        self.tx_pool.get(&tx_hash).ok_or(MempoolError::TransactionNotFound { tx_hash })
+        self.tx_pool
+            .get(&tx_hash)
+            .map(|pool_tx| &pool_tx.tx)
+            .ok_or(MempoolError::TransactionNotFound { tx_hash })
+    }
+
+    /// Returns the Sierra class declared by the transaction, if it was given upon its insertion.
+    pub fn get_sierra_class(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolResult<Option<&SierraContractClass>> {
+        self.tx_pool
+            .get(&tx_hash)
+            .map(|pool_tx| pool_tx.sierra_contract_class.as_ref())
+            .ok_or(MempoolError::TransactionNotFound { tx_hash })
+    }
+
+    pub fn get_submission_time(&self, tx_hash: TransactionHash) -> Option<Instant> {
+        self.txs_by_submission_time.hash_to_submission_time.get(&tx_hash).copied()
+    }
+
+    /// Returns the serialized size of the transaction and its Sierra class, as computed upon its
+    /// insertion.
+    pub fn get_size_in_bytes(&self, tx_hash: TransactionHash) -> MempoolResult<usize> {
+        self.tx_pool
+            .get(&tx_hash)
+            .map(|pool_tx| pool_tx.size_in_bytes)
+            .ok_or(MempoolError::TransactionNotFound { tx_hash })
     }
 
     pub fn get_by_address_and_nonce(
@@ -113,8 +189,30 @@ impl TransactionPool {
         Ok(self.get_by_address_and_nonce(address, next_nonce))
     }
 
-    pub fn _contains_account(&self, address: ContractAddress) -> bool {
-        self.txs_by_account._contains(address)
+    pub fn contains_account(&self, address: ContractAddress) -> bool {
+        self.txs_by_account.contains(address)
+    }
+
+    /// Returns an iterator of all transactions in the pool, ordered by ascending priority.
+    pub fn iter_by_ascending_priority(&self) -> impl Iterator<Item = &TransactionReference> {
+        self.txs_by_priority.iter()
+    }
+
+    /// Returns an iterator of the transactions submitted before the given time, ordered by
+    /// submission time.
+    pub fn iter_submitted_before(
+        &self,
+        time: Instant,
+    ) -> impl Iterator<Item = &TransactionReference> {
+        self.txs_by_submission_time.iter_submitted_before(time)
+    }
+
+    pub fn n_txs(&self) -> usize {
+        self.capacity.n_txs
+    }
+
+    pub fn size_in_bytes(&self) -> usize {
+        self.capacity.n_bytes
     }
 }
 
@@ -172,24 +270,114 @@ impl AccountTransactionIndex {
         txs_with_lower_nonce.into_values().collect()
     }
 
-    fn _contains(&self, address: ContractAddress) -> bool {
+    fn contains(&self, address: ContractAddress) -> bool {
         self.0.contains_key(&address)
     }
 }
 
+#[derive(Debug, Default, Eq, PartialEq)]
+struct PriorityIndex(BTreeSet<EvictionOrderedTransaction>);
+
+impl PriorityIndex {
+    fn insert(&mut self, tx: TransactionReference) {
+        assert!(self.0.insert(tx.into()), "Keys should be unique; duplicates are checked prior.");
+    }
+
+    fn remove(&mut self, tx: TransactionReference) {
+        assert!(
+            self.0.remove(&tx.into()),
+            "Transaction pool consistency error: transaction with hash {} does not appear in the \
+             priority index.",
+            tx.tx_hash
+        );
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &TransactionReference> {
+        self.0.iter().map(|tx| &tx.0)
+    }
+}
+
+#[derive(Debug, Default)]
+struct TimedTransactionMap {
+    txs_by_submission_time: BTreeMap<(Instant, TransactionHash), TransactionReference>,
+    hash_to_submission_time: HashMap<TransactionHash, Instant>,
+}
+
+impl TimedTransactionMap {
+    fn insert(&mut self, tx: TransactionReference, submission_time: Instant) {
+        let tx_hash = tx.tx_hash;
+        assert_eq!(
+            self.hash_to_submission_time.insert(tx_hash, submission_time),
+            None,
+            "Keys should be unique; duplicates are checked prior."
+        );
+        self.txs_by_submission_time.insert((submission_time, tx_hash), tx);
+    }
+
+    fn remove(&mut self, tx_hash: TransactionHash) {
+        let submission_time = self.hash_to_submission_time.remove(&tx_hash).unwrap_or_else(|| {
+            panic!(
+                "Transaction pool consistency error: transaction with hash {tx_hash} does not \
+                 appear in the submission time mapping."
+            )
+        });
+        self.txs_by_submission_time.remove(&(submission_time, tx_hash));
+    }
+
+    fn iter_submitted_before(&self, time: Instant) -> impl Iterator<Item = &TransactionReference> {
+        self.txs_by_submission_time
+            .iter()
+            .take_while(move |((submission_time, _), _)| *submission_time < time)
+            .map(|(_, tx)| tx)
+    }
+}
+
+/// Encapsulates a transaction reference to assess its eviction order (i.e., tip, then gas price).
+#[derive(Clone, Copy, Debug, derive_more::Deref, derive_more::From)]
+struct EvictionOrderedTransaction(pub TransactionReference);
+
+impl PartialEq for EvictionOrderedTransaction {
+    fn eq(&self, other: &EvictionOrderedTransaction) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for EvictionOrderedTransaction {}
+
+impl Ord for EvictionOrderedTransaction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tip
+            .cmp(&other.tip)
+            .then_with(|| self.max_l2_gas_price.cmp(&other.max_l2_gas_price))
+            .then_with(|| self.tx_hash.cmp(&other.tx_hash))
+    }
+}
+
+impl PartialOrd for EvictionOrderedTransaction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct PoolCapacity {
     n_txs: usize,
-    // TODO(Ayelet): Add size tracking.
+    // Total serialized size of the transactions in the pool.
+    n_bytes: usize,
 }
 
 impl PoolCapacity {
-    fn add(&mut self) {
+    fn add(&mut self, tx_size: usize) {
         self.n_txs += 1;
+        self.n_bytes += tx_size;
     }
 
-    fn remove(&mut self) {
+    fn remove(&mut self, tx_size: usize) {
         self.n_txs =
             self.n_txs.checked_sub(1).expect("Underflow: Cannot subtract from an empty pool.");
+        self.n_bytes = self
+            .n_bytes
+            .checked_sub(tx_size)
+            .expect("Underflow: Cannot subtract more bytes than the pool holds.");
     }
 }
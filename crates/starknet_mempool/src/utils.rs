@@ -1,7 +1,43 @@
+use std::fmt::Debug;
+use std::time::{Instant, SystemTime};
+
 use starknet_api::core::Nonce;
+use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::state::SierraContractClass;
 use starknet_mempool_types::communication::MempoolResult;
 use starknet_mempool_types::errors::MempoolError;
 
 pub fn try_increment_nonce(nonce: Nonce) -> MempoolResult<Nonce> {
     nonce.try_increment().map_err(|_| MempoolError::NonceTooLarge(nonce))
 }
+
+/// Returns the size of the transaction, as serialized for inter-component communication.
+pub fn tx_size_in_bytes(tx: &AccountTransaction) -> usize {
+    serde_json::to_vec(tx).expect("Transaction serialization should not fail.").len()
+}
+
+/// Returns the size of the Sierra class held next to a declare transaction, as serialized for
+/// inter-component communication.
+pub fn sierra_class_size_in_bytes(class: &SierraContractClass) -> usize {
+    serde_json::to_vec(class).expect("Sierra class serialization should not fail.").len()
+}
+
+/// Provides the current time, enabling time control in tests.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> Instant;
+    /// Returns the wall-clock time, which, unlike an `Instant`, remains meaningful across restarts.
+    fn system_time(&self) -> SystemTime;
+}
+
+#[derive(Debug, Default)]
+pub struct InstantClock;
+
+impl Clock for InstantClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
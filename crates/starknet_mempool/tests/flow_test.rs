@@ -1,15 +1,21 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use rstest::{fixture, rstest};
 use starknet_api::block::GasPrice;
 use starknet_api::{contract_address, nonce};
-use starknet_mempool::add_tx_input;
-use starknet_mempool::mempool::Mempool;
+use starknet_mempool::mempool::{Mempool, MempoolConfig};
 use starknet_mempool::test_utils::{
     add_tx,
     add_tx_expect_error,
     commit_block,
     get_txs_and_assert_expected,
+    revert_block,
+    FakeClock,
 };
+use starknet_mempool::{add_tx_input, deploy_account_tx_input};
 use starknet_mempool_types::errors::MempoolError;
+use starknet_mempool_types::mempool_types::TransactionStatus;
 
 // Fixtures.
 
@@ -291,6 +297,83 @@ fn test_commit_block_from_different_leader(mut mempool: Mempool) {
     get_txs_and_assert_expected(&mut mempool, 1, &[tx_nonce_4.tx]);
 }
 
+#[rstest]
+fn test_revert_multiple_blocks(mut mempool: Mempool) {
+    // Setup.
+    let input_address_0_nonce_0 =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_address_0_nonce_1 =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let input_address_0_nonce_2 =
+        add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 2, account_nonce: 0);
+    let input_address_1_nonce_0 =
+        add_tx_input!(tx_hash: 4, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    for input in [
+        &input_address_0_nonce_0,
+        &input_address_0_nonce_1,
+        &input_address_0_nonce_2,
+        &input_address_1_nonce_0,
+    ] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Block 1: address 0 nonce 0 and address 1 nonce 0.
+    get_txs_and_assert_expected(
+        &mut mempool,
+        2,
+        &[input_address_1_nonce_0.tx.clone(), input_address_0_nonce_0.tx.clone()],
+    );
+    commit_block(&mut mempool, [("0x0", 1), ("0x1", 1)], [1, 4]);
+
+    // Block 2: address 0 nonce 1.
+    get_txs_and_assert_expected(&mut mempool, 1, &[input_address_0_nonce_1.tx.clone()]);
+    commit_block(&mut mempool, [("0x0", 2)], [2]);
+
+    // Test: revert both blocks, latest first.
+    revert_block(&mut mempool, [("0x0", 1)], [input_address_0_nonce_1.tx.clone()]);
+    get_txs_and_assert_expected(&mut mempool, 0, &[]);
+    revert_block(
+        &mut mempool,
+        [("0x0", 0), ("0x1", 0)],
+        [input_address_0_nonce_0.tx.clone(), input_address_1_nonce_0.tx.clone()],
+    );
+
+    // Assert: all transactions are sequenced again, starting from the rewound nonces.
+    get_txs_and_assert_expected(
+        &mut mempool,
+        4,
+        &[
+            input_address_1_nonce_0.tx,
+            input_address_0_nonce_0.tx,
+            input_address_0_nonce_1.tx,
+            input_address_0_nonce_2.tx,
+        ],
+    );
+}
+
+#[rstest]
+fn test_revert_block_then_commit_replacing_block(mut mempool: Mempool) {
+    // Setup.
+    let input_nonce_0 = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let input_nonce_1 = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    for input in [&input_nonce_0, &input_nonce_1] {
+        add_tx(&mut mempool, input);
+    }
+    get_txs_and_assert_expected(
+        &mut mempool,
+        2,
+        &[input_nonce_0.tx.clone(), input_nonce_1.tx.clone()],
+    );
+    commit_block(&mut mempool, [("0x0", 2)], [1, 2]);
+
+    // Test: the decided block is replaced by one that includes only the first transaction.
+    revert_block(&mut mempool, [("0x0", 0)], [input_nonce_0.tx.clone(), input_nonce_1.tx.clone()]);
+    commit_block(&mut mempool, [("0x0", 1)], [1]);
+
+    // Assert.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_nonce_1.tx]);
+}
+
 #[rstest]
 fn test_update_gas_price_threshold(mut mempool: Mempool) {
     // Setup.
@@ -313,3 +396,73 @@ fn test_update_gas_price_threshold(mut mempool: Mempool) {
     mempool.update_gas_price_threshold(GasPrice(10));
     get_txs_and_assert_expected(&mut mempool, 2, &[input_gas_price_20.tx]);
 }
+
+#[rstest]
+fn test_deploy_account_then_invoke(mut mempool: Mempool) {
+    // Setup.
+    let input_deploy_account = deploy_account_tx_input!(tx_hash: 1, address: "0x0");
+    let input_invoke = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+
+    // Test.
+    for input in [&input_deploy_account, &input_invoke] {
+        add_tx(&mut mempool, input);
+    }
+
+    // Assert: the invoke follows the deployment of its account.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_deploy_account.tx, input_invoke.tx]);
+}
+
+#[rstest]
+fn test_invoke_then_deploy_account(mut mempool: Mempool) {
+    // Setup.
+    let input_invoke = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let input_deploy_account = deploy_account_tx_input!(tx_hash: 2, address: "0x0");
+
+    // Test and assert: the invoke is suspended until its account is deployed.
+    add_tx(&mut mempool, &input_invoke);
+    assert_eq!(
+        mempool.get_tx(input_invoke.tx.tx_hash()).map(|tx_with_status| tx_with_status.status),
+        Ok(TransactionStatus::Suspended)
+    );
+    get_txs_and_assert_expected(&mut mempool, 2, &[]);
+
+    add_tx(&mut mempool, &input_deploy_account);
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_deploy_account.tx, input_invoke.tx]);
+}
+
+#[rstest]
+fn test_suspended_invoke_dropped_after_timeout() {
+    // Setup.
+    let clock = Arc::new(FakeClock::default());
+    let config =
+        MempoolConfig { suspended_tx_timeout: Duration::from_secs(60), ..Default::default() };
+    let mut mempool = Mempool::new(config, clock.clone()).unwrap();
+    let input_invoke = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let input_deploy_account = deploy_account_tx_input!(tx_hash: 2, address: "0x0");
+    add_tx(&mut mempool, &input_invoke);
+
+    // Test.
+    clock.advance(Duration::from_secs(61));
+    add_tx(&mut mempool, &input_deploy_account);
+
+    // Assert: the deployment arrived too late for the invoke.
+    get_txs_and_assert_expected(&mut mempool, 2, &[input_deploy_account.tx]);
+    let invoke_tx_hash = input_invoke.tx.tx_hash();
+    assert_eq!(
+        mempool.get_tx(invoke_tx_hash),
+        Err(MempoolError::TransactionNotFound { tx_hash: invoke_tx_hash })
+    );
+}
+
+#[rstest]
+fn test_suspended_invoke_resumed_by_committed_deployment(mut mempool: Mempool) {
+    // Setup.
+    let input_invoke = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    add_tx(&mut mempool, &input_invoke);
+
+    // Test: the account is deployed by a block of a different leader.
+    commit_block(&mut mempool, [("0x0", 1)], [2]);
+
+    // Assert.
+    get_txs_and_assert_expected(&mut mempool, 1, &[input_invoke.tx]);
+}
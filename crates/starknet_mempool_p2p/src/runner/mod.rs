@@ -12,7 +12,7 @@ use papyrus_network::network_manager::{
 };
 use papyrus_protobuf::mempool::RpcTransactionWrapper;
 use starknet_gateway_types::communication::{GatewayClientError, SharedGatewayClient};
-use starknet_gateway_types::errors::GatewayError;
+use starknet_gateway_types::errors::{GatewayError, GatewaySpecError};
 use starknet_gateway_types::gateway_types::GatewayInput;
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use starknet_sequencer_infra::component_server::WrapperServer;
@@ -59,8 +59,12 @@ impl ComponentStarter for MempoolP2pRunner {
                         Ok(_) => {}
                         Err(gateway_client_error) => {
                             if let GatewayClientError::GatewayError(
-                                GatewayError::GatewaySpecError{p2p_message_metadata: Some(p2p_message_metadata), ..}
+                                GatewayError::GatewaySpecError{source, p2p_message_metadata: Some(p2p_message_metadata)}
                             ) = gateway_client_error {
+                                // Rate limits other than the per-peer one are not the peer's fault.
+                                if matches!(source, GatewaySpecError::RateLimitExceeded { .. }) {
+                                    continue;
+                                }
                                 if let Err(e) = self.broadcast_topic_client.report_peer(p2p_message_metadata.clone()).await {
                                     warn!("Failed to report peer: {:?}", e);
                                 }
@@ -14,11 +14,12 @@ use papyrus_network::network_manager::{BroadcastTopicChannels, NetworkManager};
 use papyrus_network::NetworkConfig;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_protobuf::mempool::RpcTransactionWrapper;
+use papyrus_rpc::AddInvokeOkResultRPC0_8 as AddInvokeOkResult;
 use papyrus_test_utils::{get_rng, GetTestInstance};
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
 use starknet_gateway_types::communication::{GatewayClient, GatewayClientResult};
-use starknet_gateway_types::gateway_types::GatewayInput;
+use starknet_gateway_types::gateway_types::{GatewayInput, GatewayOutput};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
 use tokio::time::sleep;
 
@@ -32,9 +33,11 @@ struct MockGatewayClient {
 
 #[async_trait]
 impl GatewayClient for MockGatewayClient {
-    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<TransactionHash> {
+    async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<GatewayOutput> {
         let _ = self.clone().add_tx_sender.send(gateway_input.rpc_tx).await;
-        Ok(TransactionHash::default())
+        Ok(GatewayOutput::Invoke(AddInvokeOkResult {
+            transaction_hash: TransactionHash::default(),
+        }))
     }
 }
 
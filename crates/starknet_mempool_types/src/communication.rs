@@ -6,7 +6,10 @@ use mockall::automock;
 use papyrus_network_types::network_types::BroadcastedMessageMetadata;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::state::SierraContractClass;
+use starknet_api::transaction::TransactionHash;
 use starknet_sequencer_infra::component_client::{
     ClientError,
     LocalComponentClient,
@@ -19,7 +22,13 @@ use starknet_sequencer_infra::component_definitions::{
 use thiserror::Error;
 
 use crate::errors::MempoolError;
-use crate::mempool_types::{AddTransactionArgs, CommitBlockArgs};
+use crate::mempool_types::{
+    AddTransactionArgs,
+    CommitBlockArgs,
+    GetQueueSnapshotArgs,
+    RevertBlockArgs,
+    TransactionWithStatus,
+};
 
 pub type LocalMempoolClient = LocalComponentClient<MempoolRequest, MempoolResponse>;
 pub type RemoteMempoolClient = RemoteComponentClient<MempoolRequest, MempoolResponse>;
@@ -44,21 +53,45 @@ pub trait MempoolClient: Send + Sync {
     // TODO: Rename tx to transaction
     async fn add_tx(&self, args: AddTransactionArgsWrapper) -> MempoolClientResult<()>;
     async fn commit_block(&self, args: CommitBlockArgs) -> MempoolClientResult<()>;
+    async fn revert_block(&self, args: RevertBlockArgs) -> MempoolClientResult<()>;
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<AccountTransaction>>;
+    async fn get_sierra_classes(
+        &self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> MempoolClientResult<Vec<(ClassHash, SierraContractClass)>>;
+    async fn get_tx(&self, tx_hash: TransactionHash) -> MempoolClientResult<TransactionWithStatus>;
+    async fn get_account_next_nonce(
+        &self,
+        address: ContractAddress,
+    ) -> MempoolClientResult<Option<Nonce>>;
+    async fn get_queue_snapshot(
+        &self,
+        args: GetQueueSnapshotArgs,
+    ) -> MempoolClientResult<Vec<TransactionHash>>;
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MempoolRequest {
     AddTransaction(AddTransactionArgsWrapper),
     CommitBlock(CommitBlockArgs),
+    RevertBlock(RevertBlockArgs),
     GetTransactions(usize),
+    GetSierraClasses(Vec<TransactionHash>),
+    GetTransaction(TransactionHash),
+    GetAccountNextNonce(ContractAddress),
+    GetQueueSnapshot(GetQueueSnapshotArgs),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MempoolResponse {
     AddTransaction(MempoolResult<()>),
     CommitBlock(MempoolResult<()>),
+    RevertBlock(MempoolResult<()>),
     GetTransactions(MempoolResult<Vec<AccountTransaction>>),
+    GetSierraClasses(MempoolResult<Vec<(ClassHash, SierraContractClass)>>),
+    GetTransaction(MempoolResult<TransactionWithStatus>),
+    GetAccountNextNonce(MempoolResult<Option<Nonce>>),
+    GetQueueSnapshot(MempoolResult<Vec<TransactionHash>>),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -86,6 +119,12 @@ where
         handle_response_variants!(MempoolResponse, CommitBlock, MempoolClientError, MempoolError)
     }
 
+    async fn revert_block(&self, args: RevertBlockArgs) -> MempoolClientResult<()> {
+        let request = MempoolRequest::RevertBlock(args);
+        let response = self.send(request).await;
+        handle_response_variants!(MempoolResponse, RevertBlock, MempoolClientError, MempoolError)
+    }
+
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<AccountTransaction>> {
         let request = MempoolRequest::GetTransactions(n_txs);
         let response = self.send(request).await;
@@ -96,4 +135,52 @@ where
             MempoolError
         )
     }
+
+    async fn get_sierra_classes(
+        &self,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> MempoolClientResult<Vec<(ClassHash, SierraContractClass)>> {
+        let request = MempoolRequest::GetSierraClasses(tx_hashes);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetSierraClasses,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_tx(&self, tx_hash: TransactionHash) -> MempoolClientResult<TransactionWithStatus> {
+        let request = MempoolRequest::GetTransaction(tx_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(MempoolResponse, GetTransaction, MempoolClientError, MempoolError)
+    }
+
+    async fn get_account_next_nonce(
+        &self,
+        address: ContractAddress,
+    ) -> MempoolClientResult<Option<Nonce>> {
+        let request = MempoolRequest::GetAccountNextNonce(address);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetAccountNextNonce,
+            MempoolClientError,
+            MempoolError
+        )
+    }
+
+    async fn get_queue_snapshot(
+        &self,
+        args: GetQueueSnapshotArgs,
+    ) -> MempoolClientResult<Vec<TransactionHash>> {
+        let request = MempoolRequest::GetQueueSnapshot(args);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            MempoolResponse,
+            GetQueueSnapshot,
+            MempoolClientError,
+            MempoolError
+        )
+    }
 }
@@ -5,14 +5,23 @@ use thiserror::Error;
 
 #[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MempoolError {
+    #[error(
+        "Committing nonce {nonce} for account address {address} decreases its committed nonce; \
+         the block should be reverted instead."
+    )]
+    CommitmentNonceTooOld { address: ContractAddress, nonce: Nonce },
     #[error("Duplicate transaction, sender address: {address}, nonce: {:?}", nonce)]
     DuplicateNonce { address: ContractAddress, nonce: Nonce },
     #[error("Duplicate transaction, with hash: {tx_hash}")]
     DuplicateTransaction { tx_hash: TransactionHash },
+    #[error("Mempool is full, and no transaction with a lower priority can be evicted.")]
+    MempoolFull,
     #[error("{0}")]
     NonceTooLarge(Nonce),
     #[error("Nonce: {nonce} for account address {address} has already been processed.")]
     NonceTooOld { address: ContractAddress, nonce: Nonce },
+    #[error("Reverting account address {address} to nonce {nonce}, which was not committed yet.")]
+    RevertNonceTooLarge { address: ContractAddress, nonce: Nonce },
     #[error("Transaction with hash: {tx_hash} could not be sent using p2p client.")]
     P2pPropagatorClientError { tx_hash: TransactionHash },
     #[error("Transaction with hash: {tx_hash} not found")]
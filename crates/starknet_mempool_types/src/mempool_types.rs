@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::state::SierraContractClass;
 use starknet_api::transaction::TransactionHash;
 
 use crate::errors::MempoolError;
@@ -25,6 +26,9 @@ impl std::fmt::Display for AccountState {
 pub struct AddTransactionArgs {
     pub tx: AccountTransaction,
     pub account_state: AccountState,
+    // The Sierra class a declare transaction declares; it is not needed for sequencing, but is
+    // held next to the transaction for the batcher to store along with the block including it.
+    pub sierra_contract_class: Option<SierraContractClass>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -33,4 +37,39 @@ pub struct CommitBlockArgs {
     pub tx_hashes: HashSet<TransactionHash>,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RevertBlockArgs {
+    // Account nonces prior to the reverted block.
+    pub address_to_nonce: HashMap<ContractAddress, Nonce>,
+    // Transactions of the reverted block, to be returned to the mempool.
+    pub txs: Vec<AccountTransaction>,
+}
+
+/// The state of a transaction held in the mempool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    // Eligible for sequencing.
+    Queued,
+    // Waiting for preceding nonces of the same account to be sequenced (e.g., a nonce gap).
+    Pending,
+    // Proposed for sequencing in the block in-progress.
+    Staged,
+    // Awaiting the deployment of its account.
+    Suspended,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransactionWithStatus {
+    pub tx: AccountTransaction,
+    pub status: TransactionStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetQueueSnapshotArgs {
+    // Number of transactions to skip, from the highest priority one.
+    pub offset: usize,
+    // Maximal number of transactions to return.
+    pub limit: usize,
+}
+
 pub type MempoolResult<T> = Result<T, MempoolError>;
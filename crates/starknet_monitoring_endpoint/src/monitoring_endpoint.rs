@@ -1,13 +1,16 @@
 use std::any::type_name;
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::routing::get;
-use axum::{async_trait, Router, Server};
+use axum::{async_trait, Json, Router, Server};
 use hyper::Error;
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use starknet_sequencer_infra::component_definitions::ComponentStarter;
+use starknet_sequencer_infra::component_health::{NodeHealthReport, SharedComponentHealth};
 use starknet_sequencer_infra::errors::ComponentError;
 use tracing::{info, instrument};
 
@@ -22,15 +25,24 @@ pub(crate) const ALIVE: &str = "alive";
 pub(crate) const READY: &str = "ready";
 pub(crate) const VERSION: &str = "nodeVersion";
 pub(crate) const METRICS: &str = "metrics";
+pub(crate) const STATUS: &str = "status";
+
+/// The health reporters of the node components, by component name.
+pub type ComponentsHealth = BTreeMap<String, SharedComponentHealth>;
 
 pub struct MonitoringEndpoint {
     config: MonitoringEndpointConfig,
     version: &'static str,
     prometheus_handle: Option<PrometheusHandle>,
+    components_health: Arc<ComponentsHealth>,
 }
 
 impl MonitoringEndpoint {
-    pub fn new(config: MonitoringEndpointConfig, version: &'static str) -> Self {
+    pub fn new(
+        config: MonitoringEndpointConfig,
+        version: &'static str,
+        components_health: ComponentsHealth,
+    ) -> Self {
         // TODO(Tsabary): consider error handling
         let prometheus_handle = if config.collect_metrics {
             Some(
@@ -41,7 +53,12 @@ impl MonitoringEndpoint {
         } else {
             None
         };
-        MonitoringEndpoint { config, version, prometheus_handle }
+        MonitoringEndpoint {
+            config,
+            version,
+            prometheus_handle,
+            components_health: Arc::new(components_health),
+        }
     }
 
     #[instrument(
@@ -64,6 +81,8 @@ impl MonitoringEndpoint {
     fn app(&self) -> Router {
         let version = self.version.to_string();
         let prometheus_handle = self.prometheus_handle.clone();
+        let ready_components_health = self.components_health.clone();
+        let status_components_health = self.components_health.clone();
 
         Router::new()
             .route(
@@ -72,7 +91,11 @@ impl MonitoringEndpoint {
             )
             .route(
                 format!("/{MONITORING_PREFIX}/{READY}").as_str(),
-                get(move || async { StatusCode::OK.to_string() }),
+                get(move || ready(ready_components_health)),
+            )
+            .route(
+                format!("/{MONITORING_PREFIX}/{STATUS}").as_str(),
+                get(move || status(status_components_health)),
             )
             .route(
                 format!("/{MONITORING_PREFIX}/{VERSION}").as_str(),
@@ -88,8 +111,9 @@ impl MonitoringEndpoint {
 pub fn create_monitoring_endpoint(
     config: MonitoringEndpointConfig,
     version: &'static str,
+    components_health: ComponentsHealth,
 ) -> MonitoringEndpoint {
-    MonitoringEndpoint::new(config, version)
+    MonitoringEndpoint::new(config, version, components_health)
 }
 
 #[async_trait]
@@ -100,6 +124,21 @@ impl ComponentStarter for MonitoringEndpoint {
     }
 }
 
+/// Returns status code 200 if no component is unhealthy, and 503: service unavailable otherwise.
+#[instrument(level = "debug", ret, skip(components_health))]
+async fn ready(components_health: Arc<ComponentsHealth>) -> Response {
+    let report = NodeHealthReport::collect(&components_health).await;
+    let status_code =
+        if report.is_ready() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status_code, status_code.to_string()).into_response()
+}
+
+/// Returns the health of the node, broken down by component, as JSON.
+#[instrument(level = "debug", skip(components_health))]
+async fn status(components_health: Arc<ComponentsHealth>) -> Json<NodeHealthReport> {
+    Json(NodeHealthReport::collect(&components_health).await)
+}
+
 /// Returns prometheus metrics.
 /// In case the node doesn’t collect metrics returns an empty response with status code 405: method
 /// not allowed.
@@ -1,12 +1,21 @@
+use std::collections::BTreeMap;
 use std::net::IpAddr;
+use std::sync::Arc;
 
 use axum::http::StatusCode;
 use axum::response::Response;
-use axum::Router;
+use axum::{async_trait, Router};
 use hyper::body::to_bytes;
 use hyper::Client;
 use metrics::{absolute_counter, describe_counter, register_counter};
 use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use starknet_sequencer_infra::component_health::{
+    ComponentHealth,
+    HealthReport,
+    HealthStatus,
+    SharedComponentHealth,
+};
 use tokio::spawn;
 use tokio::task::yield_now;
 use tower::ServiceExt;
@@ -14,10 +23,12 @@ use tower::ServiceExt;
 use super::MonitoringEndpointConfig;
 use crate::monitoring_endpoint::{
     create_monitoring_endpoint,
+    ComponentsHealth,
     MonitoringEndpoint,
     ALIVE,
     METRICS,
     READY,
+    STATUS,
     VERSION,
 };
 use crate::test_utils::build_request;
@@ -26,7 +37,28 @@ const TEST_VERSION: &str = "1.2.3-dev";
 
 fn setup_monitoring_endpoint(config: Option<MonitoringEndpointConfig>) -> MonitoringEndpoint {
     let config = config.unwrap_or_default();
-    create_monitoring_endpoint(config, TEST_VERSION)
+    create_monitoring_endpoint(config, TEST_VERSION, ComponentsHealth::new())
+}
+
+struct StubComponent(HealthStatus);
+
+#[async_trait]
+impl ComponentHealth for StubComponent {
+    async fn health(&self) -> HealthReport {
+        HealthReport::new(self.0, json!({ "stub": true }))
+    }
+}
+
+fn setup_monitoring_endpoint_with_components(
+    statuses: [(&str, HealthStatus); 2],
+) -> MonitoringEndpoint {
+    let components_health: BTreeMap<String, SharedComponentHealth> = statuses
+        .into_iter()
+        .map(|(name, status)| -> (String, SharedComponentHealth) {
+            (name.to_string(), Arc::new(StubComponent(status)))
+        })
+        .collect();
+    create_monitoring_endpoint(MonitoringEndpointConfig::default(), TEST_VERSION, components_health)
 }
 
 async fn request_app(app: Router, method: &str) -> Response {
@@ -54,6 +86,49 @@ async fn ready_endpoint() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn ready_endpoint_with_degraded_component() {
+    let monitoring_endpoint = setup_monitoring_endpoint_with_components([
+        ("batcher", HealthStatus::Healthy),
+        ("mempool", HealthStatus::Degraded),
+    ]);
+    let response = request_app(monitoring_endpoint.app(), READY).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn ready_endpoint_with_unhealthy_component() {
+    let monitoring_endpoint = setup_monitoring_endpoint_with_components([
+        ("batcher", HealthStatus::Unhealthy),
+        ("mempool", HealthStatus::Healthy),
+    ]);
+    let response = request_app(monitoring_endpoint.app(), READY).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn status_endpoint() {
+    let monitoring_endpoint = setup_monitoring_endpoint_with_components([
+        ("batcher", HealthStatus::Healthy),
+        ("mempool", HealthStatus::Degraded),
+    ]);
+    let response = request_app(monitoring_endpoint.app(), STATUS).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "status": "Degraded",
+            "components": {
+                "batcher": { "status": "Healthy", "details": { "stub": true } },
+                "mempool": { "status": "Degraded", "details": { "stub": true } },
+            },
+        })
+    );
+}
+
 #[tokio::test]
 async fn with_metrics() {
     let config = MonitoringEndpointConfig { collect_metrics: true, ..Default::default() };
@@ -1,7 +1,9 @@
 pub mod errors;
 pub mod filled_tree;
+pub mod merkle_proof;
 pub mod node_data;
 pub mod original_skeleton_tree;
+pub mod traversal;
 pub mod types;
 pub mod updated_skeleton_tree;
 
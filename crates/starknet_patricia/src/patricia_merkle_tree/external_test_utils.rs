@@ -17,7 +17,13 @@ use crate::felt::Felt;
 use crate::hash::hash_trait::HashOutput;
 use crate::patricia_merkle_tree::errors::TypesError;
 use crate::storage::map_storage::MapStorage;
-use crate::storage::storage_trait::{create_db_key, StarknetPrefix, StorageKey, StorageValue};
+use crate::storage::storage_trait::{
+    create_db_key,
+    StarknetPrefix,
+    Storage,
+    StorageKey,
+    StorageValue,
+};
 
 impl TryFrom<&U256> for Felt {
     type Error = TypesError<U256>;
@@ -66,7 +72,7 @@ pub fn get_random_u256<R: Rng>(rng: &mut R, low: U256, high: U256) -> U256 {
 
 pub async fn tree_computation_flow<L, TH>(
     leaf_modifications: LeafModifications<L>,
-    storage: &MapStorage,
+    storage: &impl Storage,
     root_hash: HashOutput,
     config: impl OriginalSkeletonTreeConfig<L>,
 ) -> FilledTreeImpl<L>
@@ -0,0 +1,17 @@
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::types::NodeIndex;
+use crate::storage::errors::{DeserializationError, StorageError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum MerkleProofError {
+    #[error(transparent)]
+    Deserialization(#[from] DeserializationError),
+    #[error("The node keyed by the hash {0:?} has a different hash.")]
+    InvalidNodeHash(HashOutput),
+    #[error("The proof does not match the given value of the leaf at index {0:?}.")]
+    LeafMismatch(NodeIndex),
+    #[error("Missing node with hash {0:?}.")]
+    MissingNode(HashOutput),
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
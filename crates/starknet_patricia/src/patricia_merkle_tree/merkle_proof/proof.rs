@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::filled_tree::node::FilledNode;
+use crate::patricia_merkle_tree::merkle_proof::errors::MerkleProofError;
+use crate::patricia_merkle_tree::node_data::inner_node::{BinaryData, EdgeData, NodeData};
+use crate::patricia_merkle_tree::node_data::leaf::Leaf;
+use crate::patricia_merkle_tree::types::NodeIndex;
+use crate::patricia_merkle_tree::updated_skeleton_tree::hash_function::TreeHashFunction;
+use crate::storage::storage_trait::{create_db_key, ReadOnlyStorage, StarknetPrefix};
+
+#[cfg(test)]
+#[path = "proof_test.rs"]
+pub mod proof_test;
+
+pub type MerkleProofResult<T> = Result<T, MerkleProofError>;
+
+/// An inner node on the path from the root of a tree to a proven leaf.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofNode {
+    Binary(BinaryData),
+    Edge(EdgeData),
+}
+
+impl ProofNode {
+    fn to_node_data<L: Leaf>(&self) -> NodeData<L> {
+        match self {
+            Self::Binary(binary_data) => NodeData::Binary(binary_data.clone()),
+            Self::Edge(edge_data) => NodeData::Edge(*edge_data),
+        }
+    }
+
+    /// Returns the index and the hash of the next node on the path from the node (at the given
+    /// index) to the given leaf. Returns None if the node is an edge that skips the leaf, i.e., if
+    /// the leaf is empty.
+    fn next_on_path(
+        &self,
+        index: NodeIndex,
+        leaf_index: NodeIndex,
+    ) -> Option<(NodeIndex, HashOutput)> {
+        match self {
+            Self::Binary(BinaryData { left_hash, right_hash }) => {
+                let [left_index, right_index] = index.get_children_indices();
+                Some(match is_ancestor(left_index, leaf_index) {
+                    true => (left_index, *left_hash),
+                    false => (right_index, *right_hash),
+                })
+            }
+            Self::Edge(EdgeData { bottom_hash, path_to_bottom }) => {
+                let bottom_index = path_to_bottom.bottom_index(index);
+                is_ancestor(bottom_index, leaf_index).then_some((bottom_index, *bottom_hash))
+            }
+        }
+    }
+}
+
+/// A proof of the values of some leaves of a tree: the inner nodes on the paths from the root to
+/// the leaves, keyed by their hashes.
+/// The path to an empty leaf ends at the edge node that skips it (or at the root, if the tree is
+/// empty), which proves the leaf is empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerkleProof<L: Leaf> {
+    pub nodes: HashMap<HashOutput, ProofNode>,
+    /// The values of the proven leaves that are not empty.
+    pub leaves: HashMap<NodeIndex, L>,
+}
+
+impl<L: Leaf> MerkleProof<L> {
+    /// Reads the proof of the given leaves from the storage of the tree with the given root.
+    pub fn fetch(
+        storage: &impl ReadOnlyStorage,
+        root_hash: HashOutput,
+        leaf_indices: &[NodeIndex],
+    ) -> MerkleProofResult<Self> {
+        let mut proof = Self::default();
+        for leaf_index in leaf_indices {
+            proof.fetch_path(storage, root_hash, *leaf_index)?;
+        }
+        Ok(proof)
+    }
+
+    fn fetch_path(
+        &mut self,
+        storage: &impl ReadOnlyStorage,
+        root_hash: HashOutput,
+        leaf_index: NodeIndex,
+    ) -> MerkleProofResult<()> {
+        let mut index = NodeIndex::ROOT;
+        let mut hash = root_hash;
+        while hash != HashOutput::ROOT_OF_EMPTY_TREE {
+            if index.is_leaf() {
+                let key = create_db_key(L::prefix(), &hash.0.to_bytes_be());
+                let value = storage.get(&key)?.ok_or(MerkleProofError::MissingNode(hash))?;
+                self.leaves.insert(index, L::deserialize(&value)?);
+                return Ok(());
+            }
+
+            // Paths to different leaves share their top nodes.
+            let node = match self.nodes.get(&hash) {
+                Some(node) => node.clone(),
+                None => {
+                    let node = fetch_inner_node::<L>(storage, hash)?;
+                    self.nodes.insert(hash, node.clone());
+                    node
+                }
+            };
+            match node.next_on_path(index, leaf_index) {
+                Some((next_index, next_hash)) => (index, hash) = (next_index, next_hash),
+                None => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Verifies that the leaf at the given index holds the given value in the tree with the given
+/// root, using only the given proof nodes. An empty leaf is verified by proving that the leaf is
+/// not in the tree.
+pub fn verify_merkle_proof<L: Leaf, TH: TreeHashFunction<L>>(
+    root_hash: HashOutput,
+    leaf_index: NodeIndex,
+    leaf: &L,
+    nodes: &HashMap<HashOutput, ProofNode>,
+) -> MerkleProofResult<()> {
+    let mut index = NodeIndex::ROOT;
+    let mut hash = root_hash;
+    while hash != HashOutput::ROOT_OF_EMPTY_TREE {
+        if index.is_leaf() {
+            // Empty leaves are not part of the tree.
+            if leaf.is_empty() || TH::compute_leaf_hash(leaf) != hash {
+                return Err(MerkleProofError::LeafMismatch(leaf_index));
+            }
+            return Ok(());
+        }
+
+        let node = nodes.get(&hash).ok_or(MerkleProofError::MissingNode(hash))?;
+        if TH::compute_node_hash(&node.to_node_data()) != hash {
+            return Err(MerkleProofError::InvalidNodeHash(hash));
+        }
+        match node.next_on_path(index, leaf_index) {
+            Some((next_index, next_hash)) => (index, hash) = (next_index, next_hash),
+            None => break,
+        }
+    }
+
+    match leaf.is_empty() {
+        true => Ok(()),
+        false => Err(MerkleProofError::LeafMismatch(leaf_index)),
+    }
+}
+
+fn fetch_inner_node<L: Leaf>(
+    storage: &impl ReadOnlyStorage,
+    hash: HashOutput,
+) -> MerkleProofResult<ProofNode> {
+    let key = create_db_key(StarknetPrefix::InnerNode.to_storage_prefix(), &hash.0.to_bytes_be());
+    let value = storage.get(&key)?.ok_or(MerkleProofError::MissingNode(hash))?;
+    match FilledNode::<L>::deserialize(hash, &value, false)?.data {
+        NodeData::Binary(binary_data) => Ok(ProofNode::Binary(binary_data)),
+        NodeData::Edge(edge_data) => Ok(ProofNode::Edge(edge_data)),
+        NodeData::Leaf(_) => unreachable!("Inner nodes are not deserialized as leaves."),
+    }
+}
+
+fn is_ancestor(index: NodeIndex, leaf_index: NodeIndex) -> bool {
+    leaf_index >> (NodeIndex::BITS - index.bit_length()) == index
+}
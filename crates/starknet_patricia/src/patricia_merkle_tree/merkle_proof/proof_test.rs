@@ -0,0 +1,166 @@
+use rstest::rstest;
+
+use crate::felt::Felt;
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::external_test_utils::tree_computation_flow;
+use crate::patricia_merkle_tree::filled_tree::tree::FilledTree;
+use crate::patricia_merkle_tree::internal_test_utils::{
+    MockLeaf,
+    OriginalSkeletonMockTrieConfig,
+    TestTreeHashFunction,
+};
+use crate::patricia_merkle_tree::merkle_proof::errors::MerkleProofError;
+use crate::patricia_merkle_tree::merkle_proof::proof::{verify_merkle_proof, MerkleProof};
+use crate::patricia_merkle_tree::node_data::leaf::LeafModifications;
+use crate::patricia_merkle_tree::types::NodeIndex;
+use crate::storage::map_storage::MapStorage;
+
+fn leaf_index(leaf_number: u128) -> NodeIndex {
+    NodeIndex::FIRST_LEAF + leaf_number
+}
+
+/// Returns the storage and the root hash of a tree with leaves 5, 6, 100 and 2^100.
+async fn create_tree() -> (MapStorage, HashOutput) {
+    let leaf_modifications: LeafModifications<MockLeaf> = [
+        (leaf_index(5), MockLeaf(Felt::from(55_u8))),
+        (leaf_index(6), MockLeaf(Felt::from(66_u8))),
+        (leaf_index(100), MockLeaf(Felt::from(100_u8))),
+        (leaf_index(1 << 100), MockLeaf(Felt::from(7_u8))),
+    ]
+    .into_iter()
+    .collect();
+    let filled_tree = tree_computation_flow::<MockLeaf, TestTreeHashFunction>(
+        leaf_modifications,
+        &MapStorage::default(),
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        OriginalSkeletonMockTrieConfig::new(false),
+    )
+    .await;
+    (MapStorage::from(filled_tree.serialize()), filled_tree.get_root_hash())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_inclusion_proof() {
+    let (storage, root_hash) = create_tree().await;
+    let leaf_indices = [leaf_index(5), leaf_index(6), leaf_index(100), leaf_index(1 << 100)];
+    let proof = MerkleProof::<MockLeaf>::fetch(&storage, root_hash, &leaf_indices).unwrap();
+
+    assert_eq!(proof.leaves.len(), leaf_indices.len());
+    for (index, leaf) in &proof.leaves {
+        verify_merkle_proof::<MockLeaf, TestTreeHashFunction>(
+            root_hash,
+            *index,
+            leaf,
+            &proof.nodes,
+        )
+        .unwrap();
+    }
+    assert_eq!(proof.leaves[&leaf_index(100)], MockLeaf(Felt::from(100_u8)));
+
+    // A wrong value of an existing leaf is rejected.
+    assert!(matches!(
+        verify_merkle_proof::<MockLeaf, TestTreeHashFunction>(
+            root_hash,
+            leaf_index(6),
+            &MockLeaf(Felt::from(67_u8)),
+            &proof.nodes
+        ),
+        Err(MerkleProofError::LeafMismatch(index)) if index == leaf_index(6)
+    ));
+}
+
+#[rstest]
+#[case::sibling_of_a_leaf(7)]
+#[case::between_leaves(50)]
+#[case::right_of_all_leaves((1 << 100) + 1)]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_non_inclusion_proof(#[case] leaf_number: u128) {
+    let (storage, root_hash) = create_tree().await;
+    let proof =
+        MerkleProof::<MockLeaf>::fetch(&storage, root_hash, &[leaf_index(leaf_number)]).unwrap();
+
+    assert!(proof.leaves.is_empty());
+    verify_merkle_proof::<MockLeaf, TestTreeHashFunction>(
+        root_hash,
+        leaf_index(leaf_number),
+        &MockLeaf::default(),
+        &proof.nodes,
+    )
+    .unwrap();
+    assert!(matches!(
+        verify_merkle_proof::<MockLeaf, TestTreeHashFunction>(
+            root_hash,
+            leaf_index(leaf_number),
+            &MockLeaf(Felt::ONE),
+            &proof.nodes
+        ),
+        Err(MerkleProofError::LeafMismatch(_))
+    ));
+}
+
+#[test]
+fn test_empty_tree_proof() {
+    let proof = MerkleProof::<MockLeaf>::fetch(
+        &MapStorage::default(),
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        &[leaf_index(5)],
+    )
+    .unwrap();
+
+    assert_eq!(proof, MerkleProof::default());
+    verify_merkle_proof::<MockLeaf, TestTreeHashFunction>(
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        leaf_index(5),
+        &MockLeaf::default(),
+        &proof.nodes,
+    )
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tampered_proof() {
+    let (storage, root_hash) = create_tree().await;
+    let proof = MerkleProof::<MockLeaf>::fetch(&storage, root_hash, &[leaf_index(5)]).unwrap();
+    let leaf = &proof.leaves[&leaf_index(5)];
+
+    // Replace the root with a node of another hash.
+    let mut tampered_nodes = proof.nodes.clone();
+    let other_node = tampered_nodes
+        .iter()
+        .find_map(|(hash, node)| (*hash != root_hash).then_some(node.clone()))
+        .unwrap();
+    tampered_nodes.insert(root_hash, other_node);
+    assert!(matches!(
+        verify_merkle_proof::<MockLeaf, TestTreeHashFunction>(
+            root_hash,
+            leaf_index(5),
+            leaf,
+            &tampered_nodes
+        ),
+        Err(MerkleProofError::InvalidNodeHash(hash)) if hash == root_hash
+    ));
+
+    // Remove the root.
+    let mut partial_nodes = proof.nodes.clone();
+    partial_nodes.remove(&root_hash);
+    assert!(matches!(
+        verify_merkle_proof::<MockLeaf, TestTreeHashFunction>(
+            root_hash,
+            leaf_index(5),
+            leaf,
+            &partial_nodes
+        ),
+        Err(MerkleProofError::MissingNode(hash)) if hash == root_hash
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fetch_proof_with_missing_node() {
+    let (mut storage, root_hash) = create_tree().await;
+    storage.storage.retain(|key, _| !key.0.ends_with(&root_hash.0.to_bytes_be()));
+
+    assert!(matches!(
+        MerkleProof::<MockLeaf>::fetch(&storage, root_hash, &[leaf_index(5)]),
+        Err(MerkleProofError::MissingNode(hash)) if hash == root_hash
+    ));
+}
@@ -22,7 +22,7 @@ use crate::patricia_merkle_tree::original_skeleton_tree::tree::{
 use crate::patricia_merkle_tree::original_skeleton_tree::utils::split_leaves;
 use crate::patricia_merkle_tree::types::{NodeIndex, SortedLeafIndices, SubTreeHeight};
 use crate::storage::errors::StorageError;
-use crate::storage::storage_trait::{create_db_key, StarknetPrefix, Storage, StorageKey};
+use crate::storage::storage_trait::{create_db_key, ReadOnlyStorage, StarknetPrefix, StorageKey};
 
 #[cfg(test)]
 #[path = "create_tree_test.rs"]
@@ -117,7 +117,7 @@ impl<'a> OriginalSkeletonTreeImpl<'a> {
     fn fetch_nodes<L: Leaf>(
         &mut self,
         subtrees: Vec<SubTree<'a>>,
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         leaf_modifications: &LeafModifications<L>,
         config: &impl OriginalSkeletonTreeConfig<L>,
         mut previous_leaves: Option<&mut HashMap<NodeIndex, L>>,
@@ -214,7 +214,7 @@ impl<'a> OriginalSkeletonTreeImpl<'a> {
     // TODO(Aviv, 17/07/2024): Split between storage prefix implementation and function logic.
     fn calculate_subtrees_roots<L: Leaf>(
         subtrees: &[SubTree<'a>],
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
     ) -> OriginalSkeletonTreeResult<Vec<FilledNode<L>>> {
         let mut subtrees_roots = vec![];
         let db_keys: Vec<StorageKey> = subtrees
@@ -231,18 +231,22 @@ impl<'a> OriginalSkeletonTreeImpl<'a> {
             })
             .collect();
 
-        let db_vals = storage.mget(&db_keys);
+        let db_vals = storage.mget(&db_keys)?;
         for ((subtree, optional_val), db_key) in
-            subtrees.iter().zip(db_vals.iter()).zip(db_keys.into_iter())
+            subtrees.iter().zip(db_vals.into_iter()).zip(db_keys.into_iter())
         {
             let val = optional_val.ok_or(StorageError::MissingKey(db_key))?;
-            subtrees_roots.push(FilledNode::deserialize(subtree.root_hash, val, subtree.is_leaf())?)
+            subtrees_roots.push(FilledNode::deserialize(
+                subtree.root_hash,
+                &val,
+                subtree.is_leaf(),
+            )?)
         }
         Ok(subtrees_roots)
     }
 
     pub(crate) fn create_impl<L: Leaf>(
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         root_hash: HashOutput,
         sorted_leaf_indices: SortedLeafIndices<'a>,
         config: &impl OriginalSkeletonTreeConfig<L>,
@@ -272,7 +276,7 @@ impl<'a> OriginalSkeletonTreeImpl<'a> {
     }
 
     pub(crate) fn create_and_get_previous_leaves_impl<L: Leaf>(
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         root_hash: HashOutput,
         sorted_leaf_indices: SortedLeafIndices<'a>,
         leaf_modifications: &LeafModifications<L>,
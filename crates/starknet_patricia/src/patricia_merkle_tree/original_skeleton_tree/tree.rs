@@ -6,7 +6,7 @@ use crate::patricia_merkle_tree::original_skeleton_tree::config::OriginalSkeleto
 use crate::patricia_merkle_tree::original_skeleton_tree::errors::OriginalSkeletonTreeError;
 use crate::patricia_merkle_tree::original_skeleton_tree::node::OriginalSkeletonNode;
 use crate::patricia_merkle_tree::types::{NodeIndex, SortedLeafIndices};
-use crate::storage::storage_trait::Storage;
+use crate::storage::storage_trait::ReadOnlyStorage;
 
 pub type OriginalSkeletonNodeMap = HashMap<NodeIndex, OriginalSkeletonNode>;
 pub type OriginalSkeletonTreeResult<T> = Result<T, OriginalSkeletonTreeError>;
@@ -17,7 +17,7 @@ pub type OriginalSkeletonTreeResult<T> = Result<T, OriginalSkeletonTreeError>;
 /// nodes on the Merkle paths from the updated leaves to the root.
 pub trait OriginalSkeletonTree<'a>: Sized {
     fn create<L: Leaf>(
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         root_hash: HashOutput,
         sorted_leaf_indices: SortedLeafIndices<'a>,
         config: &impl OriginalSkeletonTreeConfig<L>,
@@ -29,7 +29,7 @@ pub trait OriginalSkeletonTree<'a>: Sized {
     fn get_nodes_mut(&mut self) -> &mut OriginalSkeletonNodeMap;
 
     fn create_and_get_previous_leaves<L: Leaf>(
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         root_hash: HashOutput,
         sorted_leaf_indices: SortedLeafIndices<'a>,
         config: &impl OriginalSkeletonTreeConfig<L>,
@@ -49,7 +49,7 @@ pub struct OriginalSkeletonTreeImpl<'a> {
 
 impl<'a> OriginalSkeletonTree<'a> for OriginalSkeletonTreeImpl<'a> {
     fn create<L: Leaf>(
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         root_hash: HashOutput,
         sorted_leaf_indices: SortedLeafIndices<'a>,
         config: &impl OriginalSkeletonTreeConfig<L>,
@@ -67,7 +67,7 @@ impl<'a> OriginalSkeletonTree<'a> for OriginalSkeletonTreeImpl<'a> {
     }
 
     fn create_and_get_previous_leaves<L: Leaf>(
-        storage: &impl Storage,
+        storage: &impl ReadOnlyStorage,
         root_hash: HashOutput,
         sorted_leaf_indices: SortedLeafIndices<'a>,
         config: &impl OriginalSkeletonTreeConfig<L>,
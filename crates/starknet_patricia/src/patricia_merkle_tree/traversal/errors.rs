@@ -0,0 +1,12 @@
+use crate::hash::hash_trait::HashOutput;
+use crate::storage::errors::{DeserializationError, StorageError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum TraversalError {
+    #[error(transparent)]
+    Deserialization(#[from] DeserializationError),
+    #[error("Missing node with hash {0:?}.")]
+    MissingNode(HashOutput),
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::filled_tree::node::FilledNode;
+use crate::patricia_merkle_tree::node_data::inner_node::{BinaryData, EdgeData, NodeData};
+use crate::patricia_merkle_tree::node_data::leaf::Leaf;
+use crate::patricia_merkle_tree::traversal::errors::TraversalError;
+use crate::patricia_merkle_tree::types::NodeIndex;
+use crate::storage::storage_trait::{create_db_key, ReadOnlyStorage, StarknetPrefix, StorageKey};
+
+#[cfg(test)]
+#[path = "tree_diff_test.rs"]
+pub mod tree_diff_test;
+
+pub type TraversalResult<T> = Result<T, TraversalError>;
+
+/// The nodes that differ between two versions of a tree.
+///
+/// Nodes are keyed by their hashes, so a key may occur at several indices of a tree (e.g., two
+/// leaves with the same value). The keys are listed once per index they were removed from or
+/// inserted to.
+#[derive(Debug, Default, PartialEq)]
+pub struct TreeDiff<L: Leaf> {
+    /// The keys of the nodes of the old tree that are not at the same index in the new tree.
+    pub removed_keys: Vec<StorageKey>,
+    /// The keys of the nodes of the new tree that are not at the same index in the old tree.
+    pub inserted_keys: Vec<StorageKey>,
+    /// The leaves that were modified, as (index, old leaf, new leaf). An empty leaf is the default
+    /// leaf.
+    pub modified_leaves: Vec<(NodeIndex, L, L)>,
+}
+
+/// Returns the nodes that differ between the trees with the given roots. Both trees are read from
+/// the given storage.
+///
+/// Subtrees with the same root at the same index are identical, so only the paths to the modified
+/// leaves are traversed.
+pub fn diff_trees<L: Leaf>(
+    storage: &impl ReadOnlyStorage,
+    old_root_hash: HashOutput,
+    new_root_hash: HashOutput,
+) -> TraversalResult<TreeDiff<L>> {
+    let mut diff = TreeDiff {
+        removed_keys: Vec::new(),
+        inserted_keys: Vec::new(),
+        modified_leaves: Vec::new(),
+    };
+    let mut old_nodes = BTreeMap::new();
+    let mut new_nodes = BTreeMap::new();
+    if old_root_hash != HashOutput::ROOT_OF_EMPTY_TREE {
+        old_nodes.insert(NodeIndex::ROOT, old_root_hash);
+    }
+    if new_root_hash != HashOutput::ROOT_OF_EMPTY_TREE {
+        new_nodes.insert(NodeIndex::ROOT, new_root_hash);
+    }
+
+    // The index of a node is smaller than the indices of its descendants, so visiting the smallest
+    // index first reaches every index after both trees were expanded above it.
+    while let Some(index) =
+        [old_nodes.keys().next(), new_nodes.keys().next()].into_iter().flatten().min().copied()
+    {
+        let old_hash = old_nodes.remove(&index);
+        let new_hash = new_nodes.remove(&index);
+        if old_hash == new_hash {
+            continue;
+        }
+        let old_leaf = match old_hash {
+            Some(hash) => visit_node(storage, index, hash, &mut old_nodes, &mut diff.removed_keys)?,
+            None => None,
+        };
+        let new_leaf = match new_hash {
+            Some(hash) => {
+                visit_node(storage, index, hash, &mut new_nodes, &mut diff.inserted_keys)?
+            }
+            None => None,
+        };
+        if index.is_leaf() {
+            diff.modified_leaves.push((
+                index,
+                old_leaf.unwrap_or_default(),
+                new_leaf.unwrap_or_default(),
+            ));
+        }
+    }
+    Ok(diff)
+}
+
+/// Adds the key of the node to `keys` and its children to `nodes`, and returns the node if it is a
+/// leaf.
+fn visit_node<L: Leaf>(
+    storage: &impl ReadOnlyStorage,
+    index: NodeIndex,
+    hash: HashOutput,
+    nodes: &mut BTreeMap<NodeIndex, HashOutput>,
+    keys: &mut Vec<StorageKey>,
+) -> TraversalResult<Option<L>> {
+    let key = match index.is_leaf() {
+        true => create_db_key(L::prefix(), &hash.0.to_bytes_be()),
+        false => {
+            create_db_key(StarknetPrefix::InnerNode.to_storage_prefix(), &hash.0.to_bytes_be())
+        }
+    };
+    let value = storage.get(&key)?.ok_or(TraversalError::MissingNode(hash))?;
+    keys.push(key);
+
+    if index.is_leaf() {
+        return Ok(Some(L::deserialize(&value)?));
+    }
+    match FilledNode::<L>::deserialize(hash, &value, false)?.data {
+        NodeData::Binary(BinaryData { left_hash, right_hash }) => {
+            let [left_index, right_index] = index.get_children_indices();
+            nodes.insert(left_index, left_hash);
+            nodes.insert(right_index, right_hash);
+        }
+        NodeData::Edge(EdgeData { bottom_hash, path_to_bottom }) => {
+            nodes.insert(path_to_bottom.bottom_index(index), bottom_hash);
+        }
+        NodeData::Leaf(_) => unreachable!("Inner nodes are not deserialized as leaves."),
+    }
+    Ok(None)
+}
@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use crate::felt::Felt;
+use crate::hash::hash_trait::HashOutput;
+use crate::patricia_merkle_tree::external_test_utils::tree_computation_flow;
+use crate::patricia_merkle_tree::filled_tree::tree::FilledTree;
+use crate::patricia_merkle_tree::internal_test_utils::{
+    MockLeaf,
+    OriginalSkeletonMockTrieConfig,
+    TestTreeHashFunction,
+};
+use crate::patricia_merkle_tree::node_data::leaf::LeafModifications;
+use crate::patricia_merkle_tree::traversal::errors::TraversalError;
+use crate::patricia_merkle_tree::traversal::tree_diff::{diff_trees, TreeDiff};
+use crate::patricia_merkle_tree::types::NodeIndex;
+use crate::storage::map_storage::MapStorage;
+use crate::storage::storage_trait::{Storage, StorageKey};
+
+/// Applies the modifications to the tree with the given root, writes the new nodes to the storage
+/// and returns the new root and the keys of the new nodes.
+async fn update_tree(
+    storage: &mut MapStorage,
+    root_hash: HashOutput,
+    modifications: &[(u128, u8)],
+) -> (HashOutput, HashSet<StorageKey>) {
+    let leaf_modifications: LeafModifications<MockLeaf> = modifications
+        .iter()
+        .map(|(leaf_number, value)| {
+            (NodeIndex::FIRST_LEAF + *leaf_number, MockLeaf(Felt::from(*value)))
+        })
+        .collect();
+    let filled_tree = tree_computation_flow::<MockLeaf, TestTreeHashFunction>(
+        leaf_modifications,
+        &*storage,
+        root_hash,
+        OriginalSkeletonMockTrieConfig::new(false),
+    )
+    .await;
+    let new_nodes = filled_tree.serialize();
+    let new_keys = new_nodes.keys().cloned().collect();
+    storage.mset(new_nodes).unwrap();
+    (filled_tree.get_root_hash(), new_keys)
+}
+
+fn leaf(leaf_number: u128, value: u8) -> (NodeIndex, MockLeaf) {
+    (NodeIndex::FIRST_LEAF + leaf_number, MockLeaf(Felt::from(value)))
+}
+
+fn key_set(keys: &[StorageKey]) -> HashSet<StorageKey> {
+    keys.iter().cloned().collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_diff_from_empty_tree() {
+    let mut storage = MapStorage::default();
+    let (root_hash, new_keys) = update_tree(
+        &mut storage,
+        HashOutput::ROOT_OF_EMPTY_TREE,
+        &[(5, 55), (6, 66), (100, 100), (1 << 100, 7)],
+    )
+    .await;
+
+    let mut diff =
+        diff_trees::<MockLeaf>(&storage, HashOutput::ROOT_OF_EMPTY_TREE, root_hash).unwrap();
+
+    assert!(diff.removed_keys.is_empty());
+    assert_eq!(diff.inserted_keys.len(), new_keys.len());
+    assert_eq!(key_set(&diff.inserted_keys), new_keys);
+    diff.modified_leaves.sort_by_key(|(index, _, _)| *index);
+    let expected_leaves: Vec<_> = [(5, 55), (6, 66), (100, 100), (1 << 100, 7)]
+        .into_iter()
+        .map(|(leaf_number, value)| {
+            let (index, new_leaf) = leaf(leaf_number, value);
+            (index, MockLeaf::default(), new_leaf)
+        })
+        .collect();
+    assert_eq!(diff.modified_leaves, expected_leaves);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_diff_of_updated_tree() {
+    let mut storage = MapStorage::default();
+    let (old_root_hash, _) =
+        update_tree(&mut storage, HashOutput::ROOT_OF_EMPTY_TREE, &[(5, 55), (6, 66), (100, 100)])
+            .await;
+    let (new_root_hash, new_keys) = update_tree(&mut storage, old_root_hash, &[(100, 101)]).await;
+
+    let diff = diff_trees::<MockLeaf>(&storage, old_root_hash, new_root_hash).unwrap();
+
+    // Only the path to the modified leaf is replaced.
+    assert!(key_set(&diff.inserted_keys).is_subset(&new_keys));
+    assert_eq!(diff.removed_keys.len(), diff.inserted_keys.len());
+    assert!(key_set(&diff.removed_keys).is_disjoint(&new_keys));
+    let (index, new_leaf) = leaf(100, 101);
+    assert_eq!(diff.modified_leaves, vec![(index, MockLeaf(Felt::from(100_u8)), new_leaf)]);
+
+    // The reverse diff swaps the removed and the inserted nodes.
+    let reverse_diff = diff_trees::<MockLeaf>(&storage, new_root_hash, old_root_hash).unwrap();
+    assert_eq!(key_set(&reverse_diff.removed_keys), key_set(&diff.inserted_keys));
+    assert_eq!(key_set(&reverse_diff.inserted_keys), key_set(&diff.removed_keys));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_diff_of_identical_trees() {
+    let mut storage = MapStorage::default();
+    let (root_hash, _) =
+        update_tree(&mut storage, HashOutput::ROOT_OF_EMPTY_TREE, &[(5, 55), (6, 66)]).await;
+
+    let diff = diff_trees::<MockLeaf>(&storage, root_hash, root_hash).unwrap();
+
+    assert_eq!(diff, TreeDiff::default());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_diff_with_missing_node() {
+    let mut storage = MapStorage::default();
+    let (root_hash, new_keys) =
+        update_tree(&mut storage, HashOutput::ROOT_OF_EMPTY_TREE, &[(5, 55), (6, 66)]).await;
+    let leaf_keys: Vec<_> =
+        new_keys.into_iter().filter(|key| !key.0.starts_with(b"patricia_node")).collect();
+    storage.mdelete(&leaf_keys).unwrap();
+
+    let result = diff_trees::<MockLeaf>(&storage, HashOutput::ROOT_OF_EMPTY_TREE, root_hash);
+
+    assert!(matches!(result, Err(TraversalError::MissingNode(_))));
+}
@@ -1,4 +1,6 @@
 pub mod db_object;
 pub mod errors;
 pub mod map_storage;
+#[cfg(feature = "mdbx_storage")]
+pub mod mdbx_storage;
 pub mod storage_trait;
@@ -5,10 +5,15 @@ use thiserror::Error;
 use crate::patricia_merkle_tree::node_data::errors::{EdgePathError, PathToBottomError};
 use crate::storage::storage_trait::StorageKey;
 
+pub type StorageResult<T> = Result<T, StorageError>;
+
 #[derive(Debug, Error)]
 pub enum StorageError {
     #[error("The key {0:?} does not exist in storage.")]
     MissingKey(StorageKey),
+    #[cfg(feature = "mdbx_storage")]
+    #[error(transparent)]
+    Mdbx(#[from] papyrus_storage::StorageError),
 }
 
 #[derive(thiserror::Error, Debug)]
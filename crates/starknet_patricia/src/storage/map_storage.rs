@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use serde::Serialize;
 
-use crate::storage::storage_trait::{Storage, StorageKey, StorageValue};
+use crate::storage::errors::StorageResult;
+use crate::storage::storage_trait::{ReadOnlyStorage, Storage, StorageKey, StorageValue};
 
 #[derive(Serialize, Debug, Default)]
 #[cfg_attr(any(test, feature = "testing"), derive(Clone))]
@@ -10,25 +11,35 @@ pub struct MapStorage {
     pub storage: HashMap<StorageKey, StorageValue>,
 }
 
-impl Storage for MapStorage {
-    fn get(&self, key: &StorageKey) -> Option<&StorageValue> {
-        self.storage.get(key)
+impl ReadOnlyStorage for MapStorage {
+    fn get(&self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        Ok(self.storage.get(key).cloned())
     }
 
-    fn set(&mut self, key: StorageKey, value: StorageValue) -> Option<StorageValue> {
-        self.storage.insert(key, value)
+    fn mget(&self, keys: &[StorageKey]) -> StorageResult<Vec<Option<StorageValue>>> {
+        keys.iter().map(|key| self.get(key)).collect()
     }
+}
 
-    fn mget(&self, keys: &[StorageKey]) -> Vec<Option<&StorageValue>> {
-        keys.iter().map(|key| self.get(key)).collect::<Vec<_>>()
+impl Storage for MapStorage {
+    fn set(&mut self, key: StorageKey, value: StorageValue) -> StorageResult<Option<StorageValue>> {
+        Ok(self.storage.insert(key, value))
     }
 
-    fn mset(&mut self, key_to_value: HashMap<StorageKey, StorageValue>) {
+    fn mset(&mut self, key_to_value: HashMap<StorageKey, StorageValue>) -> StorageResult<()> {
         self.storage.extend(key_to_value);
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        Ok(self.storage.remove(key))
     }
 
-    fn delete(&mut self, key: &StorageKey) -> Option<StorageValue> {
-        self.storage.remove(key)
+    fn mdelete(&mut self, keys: &[StorageKey]) -> StorageResult<()> {
+        for key in keys {
+            self.storage.remove(key);
+        }
+        Ok(())
     }
 }
 
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use papyrus_storage::db::TransactionKind;
+use papyrus_storage::patricia::{PatriciaStorageReader, PatriciaStorageWriter};
+use papyrus_storage::{open_storage, StorageConfig, StorageReader, StorageTxn, StorageWriter};
+
+use crate::storage::errors::StorageResult;
+use crate::storage::storage_trait::{ReadOnlyStorage, Storage, StorageKey, StorageValue};
+
+#[cfg(test)]
+#[path = "mdbx_storage_test.rs"]
+pub mod mdbx_storage_test;
+
+/// A persistent storage, kept in the Patricia nodes table of a papyrus storage (an MDBX
+/// environment).
+///
+/// Every write is committed in its own transaction, so the nodes written in a single `mset` (e.g.,
+/// all the new nodes of a block) are committed atomically.
+/// Note that an environment has a single writer, so the storage must not be shared with another
+/// writer of the same environment.
+pub struct MdbxStorage {
+    reader: StorageReader,
+    writer: StorageWriter,
+}
+
+impl MdbxStorage {
+    pub fn new(reader: StorageReader, writer: StorageWriter) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Opens (or creates) the papyrus storage of the given config.
+    pub fn open(config: StorageConfig) -> StorageResult<Self> {
+        let (reader, writer) = open_storage(config)?;
+        Ok(Self::new(reader, writer))
+    }
+}
+
+/// Reads the nodes from the snapshot of the transaction, e.g., to read the trees of a block
+/// together with other data of the block.
+impl<Mode: TransactionKind> ReadOnlyStorage for StorageTxn<'_, Mode> {
+    fn get(&self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        Ok(self.get_patricia_node(&key.0)?.map(StorageValue))
+    }
+
+    fn mget(&self, keys: &[StorageKey]) -> StorageResult<Vec<Option<StorageValue>>> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+}
+
+impl ReadOnlyStorage for StorageReader {
+    fn get(&self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        self.begin_ro_txn()?.get(key)
+    }
+
+    fn mget(&self, keys: &[StorageKey]) -> StorageResult<Vec<Option<StorageValue>>> {
+        // Read all the keys from the same snapshot.
+        self.begin_ro_txn()?.mget(keys)
+    }
+}
+
+impl ReadOnlyStorage for MdbxStorage {
+    fn get(&self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        self.reader.get(key)
+    }
+
+    fn mget(&self, keys: &[StorageKey]) -> StorageResult<Vec<Option<StorageValue>>> {
+        self.reader.mget(keys)
+    }
+}
+
+impl Storage for MdbxStorage {
+    fn set(&mut self, key: StorageKey, value: StorageValue) -> StorageResult<Option<StorageValue>> {
+        let txn = self.writer.begin_rw_txn()?;
+        let previous_value = txn.get_patricia_node(&key.0)?.map(StorageValue);
+        txn.set_patricia_node(&key.0, &value.0)?.commit()?;
+        Ok(previous_value)
+    }
+
+    fn mset(&mut self, key_to_value: HashMap<StorageKey, StorageValue>) -> StorageResult<()> {
+        let mut txn = self.writer.begin_rw_txn()?;
+        for (key, value) in key_to_value {
+            txn = txn.set_patricia_node(&key.0, &value.0)?;
+        }
+        Ok(txn.commit()?)
+    }
+
+    fn delete(&mut self, key: &StorageKey) -> StorageResult<Option<StorageValue>> {
+        let txn = self.writer.begin_rw_txn()?;
+        let Some(previous_value) = txn.get_patricia_node(&key.0)? else {
+            return Ok(None);
+        };
+        txn.delete_patricia_node(&key.0)?.commit()?;
+        Ok(Some(StorageValue(previous_value)))
+    }
+
+    fn mdelete(&mut self, keys: &[StorageKey]) -> StorageResult<()> {
+        let mut txn = self.writer.begin_rw_txn()?;
+        for key in keys {
+            txn = txn.delete_patricia_node(&key.0)?;
+        }
+        Ok(txn.commit()?)
+    }
+}
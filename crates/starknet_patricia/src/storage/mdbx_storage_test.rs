@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use papyrus_storage::db::DbConfig;
+use papyrus_storage::mmap_file::MmapFileConfig;
+use papyrus_storage::StorageConfig;
+use tempfile::{tempdir, TempDir};
+
+use crate::storage::mdbx_storage::MdbxStorage;
+use crate::storage::storage_trait::{ReadOnlyStorage, Storage, StorageKey, StorageValue};
+
+fn test_config(path: &Path) -> StorageConfig {
+    StorageConfig {
+        db_config: DbConfig {
+            path_prefix: path.to_path_buf(),
+            min_size: 1 << 20,    // 1MB
+            max_size: 1 << 35,    // 32GB
+            growth_step: 1 << 26, // 64MB
+            ..Default::default()
+        },
+        mmap_file_config: MmapFileConfig {
+            max_size: 1 << 24,        // 16MB
+            growth_step: 1 << 20,     // 1MB
+            max_object_size: 1 << 16, // 64KB
+        },
+        ..Default::default()
+    }
+}
+
+fn open_test_storage() -> (MdbxStorage, TempDir) {
+    let dir = tempdir().unwrap();
+    (MdbxStorage::open(test_config(dir.path())).unwrap(), dir)
+}
+
+fn key(byte: u8) -> StorageKey {
+    StorageKey(vec![byte; 3])
+}
+
+fn value(byte: u8) -> StorageValue {
+    StorageValue(vec![byte; 5])
+}
+
+#[test]
+fn get_set_and_delete() {
+    let (mut storage, _dir) = open_test_storage();
+    assert_eq!(storage.get(&key(1)).unwrap(), None);
+
+    assert_eq!(storage.set(key(1), value(1)).unwrap(), None);
+    assert_eq!(storage.get(&key(1)).unwrap(), Some(value(1)));
+
+    // Overwriting a key returns the previous value.
+    assert_eq!(storage.set(key(1), value(2)).unwrap(), Some(value(1)));
+    assert_eq!(storage.get(&key(1)).unwrap(), Some(value(2)));
+
+    assert_eq!(storage.delete(&key(1)).unwrap(), Some(value(2)));
+    assert_eq!(storage.get(&key(1)).unwrap(), None);
+    assert_eq!(storage.delete(&key(1)).unwrap(), None);
+}
+
+#[test]
+fn mget_and_mset() {
+    let (mut storage, _dir) = open_test_storage();
+    storage.mset(HashMap::from([(key(1), value(1)), (key(3), value(3))])).unwrap();
+
+    assert_eq!(
+        storage.mget(&[key(3), key(2), key(1)]).unwrap(),
+        vec![Some(value(3)), None, Some(value(1))]
+    );
+}
+
+#[test]
+fn mdelete() {
+    let (mut storage, _dir) = open_test_storage();
+    storage
+        .mset(HashMap::from([(key(1), value(1)), (key(2), value(2)), (key(3), value(3))]))
+        .unwrap();
+
+    storage.mdelete(&[key(1), key(3), key(4)]).unwrap();
+    assert_eq!(storage.mget(&[key(1), key(2), key(3)]).unwrap(), vec![None, Some(value(2)), None]);
+}
+
+#[test]
+fn nodes_persist_after_reopening_the_storage() {
+    let dir = {
+        let (mut storage, dir) = open_test_storage();
+        storage.mset(HashMap::from([(key(1), value(1))])).unwrap();
+        dir
+    };
+
+    let storage = MdbxStorage::open(test_config(dir.path())).unwrap();
+    assert_eq!(storage.get(&key(1)).unwrap(), Some(value(1)));
+}
@@ -3,32 +3,38 @@ use std::collections::HashMap;
 use serde::{Serialize, Serializer};
 
 use crate::felt::Felt;
+use crate::storage::errors::StorageResult;
 
 #[derive(Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(any(test, feature = "testing"), derive(Clone))]
 pub struct StorageKey(pub Vec<u8>);
 
-#[derive(Debug, Eq, PartialEq, Serialize)]
-#[cfg_attr(any(test, feature = "testing"), derive(Clone))]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct StorageValue(pub Vec<u8>);
 
-pub trait Storage: From<HashMap<StorageKey, StorageValue>> {
+/// Read access to a storage, e.g., for reading a snapshot of the trees that cannot be modified.
+pub trait ReadOnlyStorage {
     /// Returns value from storage, if it exists.
-    fn get(&self, key: &StorageKey) -> Option<&StorageValue>;
-
-    /// Sets value in storage. If key already exists, its value is overwritten and the old value is
-    /// returned.
-    fn set(&mut self, key: StorageKey, value: StorageValue) -> Option<StorageValue>;
+    fn get(&self, key: &StorageKey) -> StorageResult<Option<StorageValue>>;
 
     /// Returns values from storage in same order of given keys. Value is None for keys that do not
     /// exist.
-    fn mget(&self, keys: &[StorageKey]) -> Vec<Option<&StorageValue>>;
+    fn mget(&self, keys: &[StorageKey]) -> StorageResult<Vec<Option<StorageValue>>>;
+}
 
-    /// Sets values in storage.
-    fn mset(&mut self, key_to_value: HashMap<StorageKey, StorageValue>);
+pub trait Storage: ReadOnlyStorage {
+    /// Sets value in storage. If key already exists, its value is overwritten and the old value is
+    /// returned.
+    fn set(&mut self, key: StorageKey, value: StorageValue) -> StorageResult<Option<StorageValue>>;
+
+    /// Sets values in storage. Either all of the values are written, or none of them is.
+    fn mset(&mut self, key_to_value: HashMap<StorageKey, StorageValue>) -> StorageResult<()>;
 
     /// Deletes value from storage and returns its value if it exists. Returns None if not.
-    fn delete(&mut self, key: &StorageKey) -> Option<StorageValue>;
+    fn delete(&mut self, key: &StorageKey) -> StorageResult<Option<StorageValue>>;
+
+    /// Deletes values from storage. Either all of the values are deleted, or none of them is.
+    fn mdelete(&mut self, keys: &[StorageKey]) -> StorageResult<()>;
 }
 
 // TODO(Aviv, 17/07/2024); Split between Storage prefix representation (trait) and node
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use hyper::StatusCode;
 use serde::de::DeserializeOwned;
@@ -7,19 +8,24 @@ use thiserror::Error;
 
 use super::{LocalComponentClient, RemoteComponentClient};
 use crate::component_definitions::ServerError;
+use crate::serde_utils::WireFormatError;
 
 #[derive(Clone, Debug, Error)]
 pub enum ClientError {
     #[error("Communication error: {0}")]
     CommunicationFailure(Arc<hyper::Error>),
     #[error("Could not deserialize server response: {0}")]
-    ResponseDeserializationFailure(Arc<serde_json::Error>),
+    ResponseDeserializationFailure(Arc<WireFormatError>),
     #[error("Could not parse the response: {0}")]
     ResponseParsingFailure(Arc<hyper::Error>),
     #[error("Got status code: {0}, with server error: {1}")]
     ResponseError(StatusCode, ServerError),
     #[error("Got an unexpected response type: {0}")]
     UnexpectedResponse(String),
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("Circuit breaker is open after {0} consecutive failed requests, failing fast")]
+    CircuitBreakerOpen(usize),
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
@@ -1,24 +1,36 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::net::IpAddr;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use hyper::body::to_bytes;
-use hyper::header::CONTENT_TYPE;
+use hyper::client::HttpConnector;
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
+use hyper::http::uri::PathAndQuery;
 use hyper::{Body, Client, Request as HyperRequest, Response as HyperResponse, StatusCode, Uri};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use rand::Rng;
+use rustls::{ClientConfig, RootCertStore};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::json;
+use tokio::time::{sleep, timeout};
+use tracing::warn;
 
 use super::definitions::{ClientError, ClientResult};
 use crate::component_definitions::{
     ComponentClient,
     RemoteClientConfig,
     ServerError,
-    APPLICATION_OCTET_STREAM,
+    HEALTH_CHECK_PATH,
+    PROTOCOL_VERSION,
+    PROTOCOL_VERSION_HEADER,
 };
-use crate::serde_utils::SerdeWrapper;
+use crate::component_health::{ComponentHealth, HealthReport};
+use crate::serde_utils::{SerdeWrapper, WireFormat};
+use crate::tls_utils::client_tls_config;
 
 /// The `RemoteComponentClient` struct is a generic client for sending component requests and
 /// receiving responses asynchronously through HTTP connection.
@@ -32,6 +44,7 @@ use crate::serde_utils::SerdeWrapper;
 /// - `uri`: URI address of the server.
 /// - `client`: The inner HTTP client that initiates the connection to the server and manages it.
 /// - `config`: Client configuration.
+/// - `circuit_breaker`: Fails requests fast while the server keeps failing; shared by all clones.
 ///
 /// # Example
 /// ```rust
@@ -68,6 +81,14 @@ use crate::serde_utils::SerdeWrapper;
 ///         retries: 3,
 ///         idle_connections: usize::MAX,
 ///         idle_timeout: 90,
+///         wire_format: Default::default(),
+///         initial_retry_backoff: std::time::Duration::from_millis(50),
+///         max_retry_backoff: std::time::Duration::from_secs(1),
+///         request_timeout: std::time::Duration::from_secs(10),
+///         circuit_breaker_failure_threshold: 5,
+///         circuit_breaker_reset_timeout: std::time::Duration::from_secs(5),
+///         tls_config: None,
+///         auth_token: None,
 ///     };
 ///     let client = RemoteComponentClient::<MyRequest, MyResponse>::new(config);
 ///
@@ -89,8 +110,9 @@ where
     Response: DeserializeOwned,
 {
     uri: Uri,
-    client: Client<hyper::client::HttpConnector>,
+    client: Client<HttpsConnector<HttpConnector>>,
     config: RemoteClientConfig,
+    circuit_breaker: Arc<Mutex<CircuitBreaker>>,
     _req: PhantomData<Request>,
     _res: PhantomData<Response>,
 }
@@ -101,43 +123,119 @@ where
     Response: Serialize + DeserializeOwned + Debug,
 {
     pub fn new(config: RemoteClientConfig) -> Self {
+        let scheme = if config.tls_config.is_some() { "https" } else { "http" };
         let ip_address = config.socket.ip();
         let port = config.socket.port();
         let uri = match ip_address {
-            IpAddr::V4(ip_address) => format!("http://{}:{}/", ip_address, port).parse().unwrap(),
-            IpAddr::V6(ip_address) => format!("http://[{}]:{}/", ip_address, port).parse().unwrap(),
+            IpAddr::V4(ip_address) => {
+                format!("{scheme}://{}:{}/", ip_address, port).parse().unwrap()
+            }
+            IpAddr::V6(ip_address) => {
+                format!("{scheme}://[{}]:{}/", ip_address, port).parse().unwrap()
+            }
         };
+        // Without a TLS config, the connector is used for plain HTTP only.
+        let tls_config = match &config.tls_config {
+            Some(tls_config) => client_tls_config(tls_config)
+                .unwrap_or_else(|err| panic!("Failed to load the client TLS config: {err}")),
+            None => ClientConfig::builder()
+                .with_safe_defaults()
+                .with_root_certificates(RootCertStore::empty())
+                .with_no_client_auth(),
+        };
+        let connector = HttpsConnectorBuilder::new()
+            .with_tls_config(tls_config)
+            .https_or_http()
+            .enable_http2()
+            .build();
         let client = Client::builder()
             .http2_only(true)
             .pool_max_idle_per_host(config.idle_connections)
             .pool_idle_timeout(Duration::from_secs(config.idle_timeout))
-            .build_http();
-        Self { uri, client, config, _req: PhantomData, _res: PhantomData }
+            .build(connector);
+        Self {
+            uri,
+            client,
+            config,
+            circuit_breaker: Default::default(),
+            _req: PhantomData,
+            _res: PhantomData,
+        }
     }
 
     fn construct_http_request(&self, serialized_request: Vec<u8>) -> HyperRequest<Body> {
-        HyperRequest::post(self.uri.clone())
-            .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-            .body(Body::from(serialized_request))
-            .expect("Request building should succeed")
+        let mut http_request = HyperRequest::post(self.uri.clone())
+            .header(CONTENT_TYPE, self.config.wire_format.content_type())
+            .header(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION);
+        if let Some(auth_token) = &self.config.auth_token {
+            http_request = http_request.header(AUTHORIZATION, format!("Bearer {auth_token}"));
+        }
+        http_request.body(Body::from(serialized_request)).expect("Request building should succeed")
+    }
+
+    fn construct_health_check_request(&self) -> HyperRequest<Body> {
+        let mut uri_parts = self.uri.clone().into_parts();
+        uri_parts.path_and_query = Some(PathAndQuery::from_static(HEALTH_CHECK_PATH));
+        let uri = Uri::from_parts(uri_parts).expect("Health check URI should be valid");
+        let mut http_request =
+            HyperRequest::get(uri).header(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION);
+        if let Some(auth_token) = &self.config.auth_token {
+            http_request = http_request.header(AUTHORIZATION, format!("Bearer {auth_token}"));
+        }
+        http_request.body(Body::empty()).expect("Request building should succeed")
     }
 
     async fn try_send(&self, http_request: HyperRequest<Body>) -> ClientResult<Response> {
+        timeout(self.config.request_timeout, self.try_send_without_timeout(http_request))
+            .await
+            .map_err(|_| ClientError::Timeout(self.config.request_timeout))?
+    }
+
+    async fn try_send_without_timeout(
+        &self,
+        http_request: HyperRequest<Body>,
+    ) -> ClientResult<Response> {
         let http_response = self
             .client
             .request(http_request)
             .await
             .map_err(|e| ClientError::CommunicationFailure(Arc::new(e)))?;
 
-        match http_response.status() {
-            StatusCode::OK => get_response_body(http_response).await,
-            status_code => Err(ClientError::ResponseError(
-                status_code,
-                ServerError::RequestDeserializationFailure(
-                    "Could not deserialize server response".to_string(),
-                ),
-            )),
+        let status_code = http_response.status();
+        if let Some(server_error) = check_protocol_version(&http_response) {
+            return Err(ClientError::ResponseError(status_code, server_error));
         }
+        let wire_format = response_wire_format(&http_response).unwrap_or(self.config.wire_format);
+
+        match status_code {
+            StatusCode::OK => get_response_body(http_response, wire_format).await,
+            status_code => {
+                let server_error =
+                    get_response_body(http_response, wire_format).await.unwrap_or_else(|_| {
+                        ServerError::RequestDeserializationFailure(
+                            "Could not deserialize server response".to_string(),
+                        )
+                    });
+                Err(ClientError::ResponseError(status_code, server_error))
+            }
+        }
+    }
+
+    // Exponential backoff, capped by the configured maximum, with a random jitter of up to half of
+    // the delay, so that clients failing together do not retry together.
+    fn retry_backoff(&self, retry: usize) -> Duration {
+        let backoff = u32::try_from(retry)
+            .ok()
+            .and_then(|retry| 2_u32.checked_pow(retry))
+            .and_then(|factor| self.config.initial_retry_backoff.checked_mul(factor))
+            .map_or(self.config.max_retry_backoff, |backoff| {
+                backoff.min(self.config.max_retry_backoff)
+            });
+        rand::thread_rng().gen_range(backoff / 2..=backoff)
+    }
+
+    fn circuit_breaker(&self) -> MutexGuard<'_, CircuitBreaker> {
+        self.circuit_breaker.lock().expect("Circuit breaker lock should not be poisoned")
     }
 }
 
@@ -151,27 +249,125 @@ where
     async fn send(&self, component_request: Request) -> ClientResult<Response> {
         // Serialize the request.
         let serialized_request = SerdeWrapper::new(component_request)
-            .wrapper_serialize()
+            .wrapper_serialize(self.config.wire_format)
             .expect("Request serialization should succeed");
 
-        // Construct the request, and send it up to 'max_retries + 1' times. Return if received a
-        // successful response, or the last response if all attempts failed.
+        // Fail fast while the server keeps failing.
+        self.circuit_breaker().check(&self.config)?;
+
+        // Construct the request, and send it up to 'max_retries + 1' times, backing off between
+        // attempts. Return if received a successful response or a rejection of the request, or the
+        // last response if all attempts failed.
         let max_attempts = self.config.retries + 1;
         for attempt in 0..max_attempts {
+            if attempt > 0 {
+                sleep(self.retry_backoff(attempt - 1)).await;
+            }
             let http_request = self.construct_http_request(serialized_request.clone());
             let res = self.try_send(http_request).await;
-            if res.is_ok() {
-                return res;
-            }
-            if attempt == max_attempts - 1 {
-                return res;
+            match &res {
+                Ok(_) => {
+                    self.circuit_breaker().record_success();
+                    return res;
+                }
+                // The server is reachable, and would reject the request again.
+                Err(err) if is_request_rejected(err) => return res,
+                Err(_) if attempt == max_attempts - 1 => {
+                    self.circuit_breaker().record_failure(&self.config);
+                    return res;
+                }
+                Err(_) => {}
             }
         }
         unreachable!("Guaranteed to return a response before reaching this point.");
     }
 }
 
-async fn get_response_body<Response>(response: HyperResponse<Body>) -> Result<Response, ClientError>
+/// The client is healthy if its server answers a health check, sent over the configured transport
+/// and with the configured authorization. While the circuit breaker is open, the server is reported
+/// unreachable without probing it.
+#[async_trait]
+impl<Request, Response> ComponentHealth for RemoteComponentClient<Request, Response>
+where
+    Request: Send + Sync + Serialize + DeserializeOwned + Debug,
+    Response: Send + Sync + Serialize + DeserializeOwned + Debug,
+{
+    async fn health(&self) -> HealthReport {
+        let socket = self.config.socket;
+        if let Err(err) = self.circuit_breaker().check(&self.config) {
+            return HealthReport::unhealthy(err);
+        }
+        let http_request = self.construct_health_check_request();
+        let http_response =
+            match timeout(self.config.request_timeout, self.client.request(http_request)).await {
+                Ok(Ok(http_response)) => http_response,
+                Ok(Err(err)) => {
+                    return HealthReport::unhealthy(format!("Failed to reach {socket}: {err}"));
+                }
+                Err(_) => return HealthReport::unhealthy(format!("Timed out reaching {socket}")),
+            };
+
+        let status_code = http_response.status();
+        if let Some(server_error) = check_protocol_version(&http_response) {
+            return HealthReport::unhealthy(server_error);
+        }
+        if status_code != StatusCode::OK {
+            let wire_format = response_wire_format(&http_response).unwrap_or(WireFormat::Json);
+            return match get_response_body::<ServerError>(http_response, wire_format).await {
+                Ok(server_error) => HealthReport::unhealthy(format!(
+                    "Health check failed with status {status_code}: {server_error}"
+                )),
+                Err(_) => HealthReport::unhealthy(format!(
+                    "Health check failed with status {status_code}"
+                )),
+            };
+        }
+        HealthReport::healthy(json!({ "socket": socket }))
+    }
+}
+
+/// Counts consecutive failed requests. Once the configured threshold is reached, the breaker opens
+/// and requests fail without reaching the server. After the reset timeout, requests are let through
+/// again: a success closes the breaker, and a failure re-opens it.
+#[derive(Debug, Default)]
+struct CircuitBreaker {
+    consecutive_failures: usize,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn check(&self, config: &RemoteClientConfig) -> ClientResult<()> {
+        match self.opened_at {
+            Some(opened_at) if opened_at.elapsed() < config.circuit_breaker_reset_timeout => {
+                Err(ClientError::CircuitBreakerOpen(self.consecutive_failures))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    fn record_failure(&mut self, config: &RemoteClientConfig) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= config.circuit_breaker_failure_threshold {
+            if self.opened_at.is_none() {
+                warn!(
+                    "Opening the circuit breaker after {} consecutive failed requests.",
+                    self.consecutive_failures
+                );
+            }
+            self.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+async fn get_response_body<Response>(
+    response: HyperResponse<Body>,
+    wire_format: WireFormat,
+) -> Result<Response, ClientError>
 where
     Response: Serialize + DeserializeOwned + Debug,
 {
@@ -179,10 +375,39 @@ where
         .await
         .map_err(|e| ClientError::ResponseParsingFailure(Arc::new(e)))?;
 
-    SerdeWrapper::<Response>::wrapper_deserialize(&body_bytes)
+    SerdeWrapper::<Response>::wrapper_deserialize(&body_bytes, wire_format)
         .map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
 }
 
+fn response_wire_format(response: &HyperResponse<Body>) -> Option<WireFormat> {
+    let content_type = response.headers().get(CONTENT_TYPE)?.to_str().ok()?;
+    WireFormat::from_content_type(content_type)
+}
+
+// Whether the server rejected the request itself, e.g. as unauthorized or of an unsupported
+// protocol, rather than failing to handle it. Such requests fail the same way on every attempt.
+fn is_request_rejected(error: &ClientError) -> bool {
+    match error {
+        ClientError::ResponseError(_, ServerError::ProtocolVersionMismatch { .. }) => true,
+        ClientError::ResponseError(status_code, _) => status_code.is_client_error(),
+        _ => false,
+    }
+}
+
+// Returns an error if the server responded with a different protocol version.
+fn check_protocol_version(response: &HyperResponse<Body>) -> Option<ServerError> {
+    let received = response
+        .headers()
+        .get(PROTOCOL_VERSION_HEADER)
+        .map(|version| String::from_utf8_lossy(version.as_bytes()).into_owned());
+    match received {
+        Some(version) if version == PROTOCOL_VERSION.to_string() => None,
+        received => {
+            Some(ServerError::ProtocolVersionMismatch { expected: PROTOCOL_VERSION, received })
+        }
+    }
+}
+
 // Can't derive because derive forces the generics to also be `Clone`, which we prefer not to do
 // since it'll require the generic Request and Response types to be cloneable.
 impl<Request, Response> Clone for RemoteComponentClient<Request, Response>
@@ -195,6 +420,7 @@ where
             uri: self.uri.clone(),
             client: self.client.clone(),
             config: self.config.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
             _req: PhantomData,
             _res: PhantomData,
         }
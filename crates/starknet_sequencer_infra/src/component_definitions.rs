@@ -2,9 +2,18 @@ use std::any::type_name;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
 
 use async_trait::async_trait;
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::converters::deserialize_milliseconds_to_duration;
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_param,
+    ser_optional_sub_config,
+    ser_param,
+    SerializeConfig,
+};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -15,12 +24,27 @@ use validator::Validate;
 
 use crate::component_client::ClientResult;
 use crate::errors::ComponentError;
+use crate::serde_utils::WireFormat;
 
 pub const APPLICATION_OCTET_STREAM: &str = "application/octet-stream";
+pub const APPLICATION_JSON: &str = "application/json";
+/// The header carrying the protocol version of remote component communication.
+pub const PROTOCOL_VERSION_HEADER: &str = "x-component-protocol-version";
+/// The version of the remote component communication protocol. Must be bumped on any incompatible
+/// change to the wire format, so that components of mismatching versions reject each other's
+/// messages rather than mis-deserialize them.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// The path of the health checks of remote component clients, which carry no component request.
+pub const HEALTH_CHECK_PATH: &str = "/health";
 const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 32;
 const DEFAULT_RETRIES: usize = 3;
 const DEFAULT_IDLE_CONNECTIONS: usize = usize::MAX;
 const DEFAULT_IDLE_TIMEOUT: u64 = 90;
+const DEFAULT_INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+const DEFAULT_MAX_RETRY_BACKOFF: Duration = Duration::from_millis(1000);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_millis(10000);
+const DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD: usize = 5;
+const DEFAULT_CIRCUIT_BREAKER_RESET_TIMEOUT: Duration = Duration::from_millis(5000);
 
 #[async_trait]
 pub trait ComponentRequestHandler<Request, Response> {
@@ -74,8 +98,14 @@ where
 
 #[derive(Debug, Error, Deserialize, Serialize, Clone)]
 pub enum ServerError {
+    #[error("Mismatching protocol versions: expected {expected}, got {received:?}.")]
+    ProtocolVersionMismatch { expected: u32, received: Option<String> },
     #[error("Could not deserialize client request: {0}")]
     RequestDeserializationFailure(String),
+    #[error("Unsupported content type: {0:?}.")]
+    UnsupportedContentType(Option<String>),
+    #[error("Missing or invalid authentication token.")]
+    Unauthorized,
 }
 
 // The communication configuration of the local component.
@@ -108,6 +138,20 @@ pub struct RemoteClientConfig {
     pub retries: usize,
     pub idle_connections: usize,
     pub idle_timeout: u64,
+    pub wire_format: WireFormat,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub initial_retry_backoff: Duration,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub max_retry_backoff: Duration,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub request_timeout: Duration,
+    pub circuit_breaker_failure_threshold: usize,
+    #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
+    pub circuit_breaker_reset_timeout: Duration,
+    // If set, the server is reached over TLS.
+    pub tls_config: Option<ClientTlsConfig>,
+    // If set, sent as a bearer token with every request.
+    pub auth_token: Option<String>,
 }
 
 impl Default for RemoteClientConfig {
@@ -118,13 +162,21 @@ impl Default for RemoteClientConfig {
             retries: DEFAULT_RETRIES,
             idle_connections: DEFAULT_IDLE_CONNECTIONS,
             idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            wire_format: WireFormat::default(),
+            initial_retry_backoff: DEFAULT_INITIAL_RETRY_BACKOFF,
+            max_retry_backoff: DEFAULT_MAX_RETRY_BACKOFF,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            circuit_breaker_failure_threshold: DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+            circuit_breaker_reset_timeout: DEFAULT_CIRCUIT_BREAKER_RESET_TIMEOUT,
+            tls_config: None,
+            auth_token: None,
         }
     }
 }
 
 impl SerializeConfig for RemoteClientConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut config = BTreeMap::from_iter([
             ser_param(
                 "socket",
                 &self.socket.to_string(),
@@ -149,29 +201,155 @@ impl SerializeConfig for RemoteClientConfig {
                 "The duration in seconds to keep an idle connection open before closing.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+            ser_param(
+                "wire_format",
+                &self.wire_format,
+                "The encoding of the sent messages: Binary, or Json for debugging.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "initial_retry_backoff",
+                &self.initial_retry_backoff.as_millis(),
+                "The delay in milliseconds before the first retry, doubled on each further retry.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_retry_backoff",
+                &self.max_retry_backoff.as_millis(),
+                "The maximal delay in milliseconds between retries.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "request_timeout",
+                &self.request_timeout.as_millis(),
+                "The duration in milliseconds to wait for a response to a single attempt.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "circuit_breaker_failure_threshold",
+                &self.circuit_breaker_failure_threshold,
+                "The number of consecutive failed requests after which requests fail fast.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "circuit_breaker_reset_timeout",
+                &self.circuit_breaker_reset_timeout.as_millis(),
+                "The duration in milliseconds to fail fast before trying to reach the server \
+                 again.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        config.extend(ser_optional_sub_config(&self.tls_config, "tls_config"));
+        config.extend(ser_auth_token(&self.auth_token));
+        config
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
 pub struct RemoteServerConfig {
     pub socket: SocketAddr,
+    // If set, the server accepts only TLS connections.
+    pub tls_config: Option<ServerTlsConfig>,
+    // If set, requests without this bearer token are rejected.
+    pub auth_token: Option<String>,
 }
 
 impl Default for RemoteServerConfig {
     fn default() -> Self {
         let socket = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 8080);
-        Self { socket }
+        Self { socket, tls_config: None, auth_token: None }
     }
 }
 
 impl SerializeConfig for RemoteServerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([ser_param(
+        let mut config = BTreeMap::from_iter([ser_param(
             "socket",
             &self.socket.to_string(),
             "The remote component server socket.",
             ParamPrivacyInput::Public,
-        )])
+        )]);
+        config.extend(ser_optional_sub_config(&self.tls_config, "tls_config"));
+        config.extend(ser_auth_token(&self.auth_token));
+        config
+    }
+}
+
+fn ser_auth_token(auth_token: &Option<String>) -> BTreeMap<ParamPath, SerializedParam> {
+    ser_optional_param(
+        auth_token,
+        String::new(),
+        "auth_token",
+        "The bearer token shared by the remote component client and server.",
+        ParamPrivacyInput::Private,
+    )
+}
+
+/// A certificate chain and its private key, in PEM files.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TlsIdentityConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl SerializeConfig for TlsIdentityConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "cert_path",
+                &self.cert_path,
+                "The path to the PEM file of the certificate chain.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "key_path",
+                &self.key_path,
+                "The path to the PEM file of the private key.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ServerTlsConfig {
+    pub identity: TlsIdentityConfig,
+    // If set, clients must present a certificate signed by this CA (mutual TLS).
+    pub client_ca_cert_path: Option<PathBuf>,
+}
+
+impl SerializeConfig for ServerTlsConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = append_sub_config_name(self.identity.dump(), "identity");
+        config.extend(ser_optional_param(
+            &self.client_ca_cert_path,
+            PathBuf::new(),
+            "client_ca_cert_path",
+            "The path to the PEM file of the CA certificate that client certificates must be \
+             signed by. If set, clients must authenticate with a certificate (mutual TLS).",
+            ParamPrivacyInput::Public,
+        ));
+        config
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ClientTlsConfig {
+    pub ca_cert_path: PathBuf,
+    // The client certificate, for servers that require mutual TLS.
+    pub identity: Option<TlsIdentityConfig>,
+}
+
+impl SerializeConfig for ClientTlsConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut config = BTreeMap::from_iter([ser_param(
+            "ca_cert_path",
+            &self.ca_cert_path,
+            "The path to the PEM file of the CA certificate that the server certificate must be \
+             signed by.",
+            ParamPrivacyInput::Public,
+        )]);
+        config.extend(ser_optional_sub_config(&self.identity, "identity"));
+        config
     }
 }
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[cfg(test)]
+#[path = "component_health_test.rs"]
+mod component_health_test;
+
+/// The health of a component, ordered from the healthiest to the least healthy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HealthStatus {
+    Healthy,
+    // The component works, but not as expected (e.g., it lags behind); the node is still ready.
+    Degraded,
+    Unhealthy,
+}
+
+/// A health report of a single component, with component specific details (e.g., its size or lag).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub details: Value,
+}
+
+impl HealthReport {
+    pub fn new(status: HealthStatus, details: Value) -> Self {
+        Self { status, details }
+    }
+
+    pub fn healthy(details: Value) -> Self {
+        Self::new(HealthStatus::Healthy, details)
+    }
+
+    pub fn unhealthy(reason: impl ToString) -> Self {
+        Self::new(HealthStatus::Unhealthy, json!({ "error": reason.to_string() }))
+    }
+}
+
+/// Implemented by the components of the node to report their health to the monitoring endpoint.
+/// Components are moved into their servers when the node starts, so the implementors are typically
+/// handles, taken from the component beforehand, that share its state.
+#[async_trait]
+pub trait ComponentHealth: Send + Sync {
+    async fn health(&self) -> HealthReport;
+}
+
+pub type SharedComponentHealth = Arc<dyn ComponentHealth>;
+
+/// The health of the node: the reports of its components, by component name.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeHealthReport {
+    pub status: HealthStatus,
+    pub components: BTreeMap<String, HealthReport>,
+}
+
+impl NodeHealthReport {
+    /// Collects the reports of the given components. The node is as healthy as its least healthy
+    /// component.
+    pub async fn collect(components: &BTreeMap<String, SharedComponentHealth>) -> Self {
+        let reports: BTreeMap<String, HealthReport> = join_all(
+            components
+                .iter()
+                .map(|(name, component)| async move { (name.clone(), component.health().await) }),
+        )
+        .await
+        .into_iter()
+        .collect();
+        let status =
+            reports.values().map(|report| report.status).max().unwrap_or(HealthStatus::Healthy);
+        Self { status, components: reports }
+    }
+
+    /// The node is ready unless one of its components is unhealthy.
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Unhealthy
+    }
+}
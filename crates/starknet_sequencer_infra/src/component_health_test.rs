@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::component_health::{
+    ComponentHealth,
+    HealthReport,
+    HealthStatus,
+    NodeHealthReport,
+    SharedComponentHealth,
+};
+
+struct StubComponent(HealthStatus);
+
+#[async_trait]
+impl ComponentHealth for StubComponent {
+    async fn health(&self) -> HealthReport {
+        HealthReport::new(self.0, json!({}))
+    }
+}
+
+#[rstest]
+#[case::no_components(&[], HealthStatus::Healthy, true)]
+#[case::all_healthy(&[HealthStatus::Healthy, HealthStatus::Healthy], HealthStatus::Healthy, true)]
+#[case::degraded(&[HealthStatus::Healthy, HealthStatus::Degraded], HealthStatus::Degraded, true)]
+#[case::unhealthy(
+    &[HealthStatus::Unhealthy, HealthStatus::Degraded],
+    HealthStatus::Unhealthy,
+    false
+)]
+#[tokio::test]
+async fn test_node_health_report(
+    #[case] statuses: &[HealthStatus],
+    #[case] expected_status: HealthStatus,
+    #[case] expected_ready: bool,
+) {
+    let components: BTreeMap<String, SharedComponentHealth> = statuses
+        .iter()
+        .enumerate()
+        .map(|(i, status)| -> (String, SharedComponentHealth) {
+            (format!("component_{i}"), Arc::new(StubComponent(*status)))
+        })
+        .collect();
+
+    let report = NodeHealthReport::collect(&components).await;
+
+    assert_eq!(report.status, expected_status);
+    assert_eq!(report.is_ready(), expected_ready);
+    assert_eq!(report.components.len(), statuses.len());
+}
+
+#[test]
+fn test_unhealthy_report_details() {
+    let report = HealthReport::unhealthy("storage is unreachable");
+    assert_eq!(report.status, HealthStatus::Unhealthy);
+    assert_eq!(report.details, json!({ "error": "storage is unreachable" }));
+}
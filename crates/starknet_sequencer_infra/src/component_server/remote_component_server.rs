@@ -1,25 +1,30 @@
 use std::fmt::Debug;
 use std::net::SocketAddr;
-use std::sync::Arc;
 
 use async_trait::async_trait;
 use hyper::body::to_bytes;
-use hyper::header::CONTENT_TYPE;
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
+use hyper::server::conn::AddrIncoming;
 use hyper::service::{make_service_fn, service_fn};
-use hyper::{Body, Request as HyperRequest, Response as HyperResponse, Server, StatusCode};
+use hyper::{Body, Method, Request as HyperRequest, Response as HyperResponse, Server, StatusCode};
+use hyper_rustls::TlsAcceptor;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use crate::component_client::{ClientError, LocalComponentClient};
+use crate::component_client::LocalComponentClient;
 use crate::component_definitions::{
     ComponentClient,
     RemoteServerConfig,
     ServerError,
-    APPLICATION_OCTET_STREAM,
+    ServerTlsConfig,
+    HEALTH_CHECK_PATH,
+    PROTOCOL_VERSION,
+    PROTOCOL_VERSION_HEADER,
 };
 use crate::component_server::ComponentServerStarter;
 use crate::errors::ComponentServerError;
-use crate::serde_utils::SerdeWrapper;
+use crate::serde_utils::{SerdeWrapper, WireFormat};
+use crate::tls_utils::server_tls_config;
 
 /// The `RemoteComponentServer` struct is a generic server that handles requests and responses for a
 /// specified component. It receives requests, processes them using the provided component, and
@@ -40,6 +45,8 @@ use crate::serde_utils::SerdeWrapper;
 ///
 /// - `component`: The component responsible for handling the requests and generating responses.
 /// - `socket`: A socket address for the server to listen on.
+/// - `tls_config`: If set, the server accepts only TLS connections.
+/// - `auth_token`: If set, the bearer token that requests must carry.
 ///
 /// # Example
 /// ```rust
@@ -93,7 +100,10 @@ use crate::serde_utils::SerdeWrapper;
 ///     // Set the ip address and port of the server's socket.
 ///     let ip_address = std::net::IpAddr::V6(std::net::Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1));
 ///     let port: u16 = 8080;
-///     let config = RemoteServerConfig { socket: std::net::SocketAddr::new(ip_address, port) };
+///     let config = RemoteServerConfig {
+///         socket: std::net::SocketAddr::new(ip_address, port),
+///         ..Default::default()
+///     };
 ///
 ///     // Instantiate the server.
 ///     let mut server = RemoteComponentServer::<MyRequest, MyResponse>::new(local_client, config);
@@ -110,6 +120,8 @@ where
     Response: Serialize + DeserializeOwned + Send + Sync + 'static,
 {
     socket: SocketAddr,
+    tls_config: Option<ServerTlsConfig>,
+    auth_token: Option<String>,
     local_client: LocalComponentClient<Request, Response>,
 }
 
@@ -122,52 +134,139 @@ where
         local_client: LocalComponentClient<Request, Response>,
         config: RemoteServerConfig,
     ) -> Self {
-        Self { local_client, socket: config.socket }
+        Self {
+            local_client,
+            socket: config.socket,
+            tls_config: config.tls_config,
+            auth_token: config.auth_token,
+        }
     }
 
     async fn remote_component_server_handler(
         http_request: HyperRequest<Body>,
         local_client: LocalComponentClient<Request, Response>,
+        auth_token: Option<String>,
     ) -> Result<HyperResponse<Body>, hyper::Error> {
+        let content_type = header_value(&http_request, CONTENT_TYPE.as_str());
+        let wire_format = content_type.as_deref().and_then(WireFormat::from_content_type);
+
+        if let Some(auth_token) = auth_token {
+            if !is_authorized(&http_request, &auth_token) {
+                return Ok(build_response(
+                    StatusCode::UNAUTHORIZED,
+                    ServerError::Unauthorized,
+                    wire_format.unwrap_or(WireFormat::Json),
+                ));
+            }
+        }
+
+        if http_request.method() == Method::GET && http_request.uri().path() == HEALTH_CHECK_PATH {
+            return Ok(health_check_response(&http_request));
+        }
+
+        let Some(wire_format) = wire_format else {
+            // The client's format is unknown, respond in the human readable one.
+            return Ok(build_response(
+                StatusCode::BAD_REQUEST,
+                ServerError::UnsupportedContentType(content_type),
+                WireFormat::Json,
+            ));
+        };
+
+        let protocol_version = header_value(&http_request, PROTOCOL_VERSION_HEADER);
+        if protocol_version != Some(PROTOCOL_VERSION.to_string()) {
+            return Ok(build_response(
+                StatusCode::BAD_REQUEST,
+                ServerError::ProtocolVersionMismatch {
+                    expected: PROTOCOL_VERSION,
+                    received: protocol_version,
+                },
+                wire_format,
+            ));
+        }
+
         let body_bytes = to_bytes(http_request.into_body()).await?;
 
-        let http_response = match SerdeWrapper::<Request>::wrapper_deserialize(&body_bytes)
-            .map_err(|e| ClientError::ResponseDeserializationFailure(Arc::new(e)))
-        {
-            Ok(request) => {
-                let response = local_client.send(request).await;
-                match response {
-                    Ok(response) => HyperResponse::builder()
-                        .status(StatusCode::OK)
-                        .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-                        .body(Body::from(
-                            SerdeWrapper::new(response)
-                                .wrapper_serialize()
-                                .expect("Response serialization should succeed"),
-                        )),
-                    Err(error) => {
-                        panic!(
-                            "Remote server failed sending with its local client. Error: {:?}",
-                            error
-                        );
+        let http_response =
+            match SerdeWrapper::<Request>::wrapper_deserialize(&body_bytes, wire_format) {
+                Ok(request) => {
+                    let response = local_client.send(request).await;
+                    match response {
+                        Ok(response) => build_response(StatusCode::OK, response, wire_format),
+                        Err(error) => {
+                            panic!(
+                                "Remote server failed sending with its local client. Error: {:?}",
+                                error
+                            );
+                        }
                     }
                 }
-            }
-            Err(error) => {
-                let server_error = ServerError::RequestDeserializationFailure(error.to_string());
-                HyperResponse::builder().status(StatusCode::BAD_REQUEST).body(Body::from(
-                    SerdeWrapper::new(server_error)
-                        .wrapper_serialize()
-                        .expect("Server error serialization should succeed"),
-                ))
-            }
-        }
-        .expect("Response building should succeed");
+                Err(error) => build_response(
+                    StatusCode::BAD_REQUEST,
+                    ServerError::RequestDeserializationFailure(error.to_string()),
+                    wire_format,
+                ),
+            };
 
         Ok(http_response)
     }
 }
 
+// A health check reaches the server through the same transport and authorization as component
+// requests, and is answered without involving the component.
+fn health_check_response(http_request: &HyperRequest<Body>) -> HyperResponse<Body> {
+    let protocol_version = header_value(http_request, PROTOCOL_VERSION_HEADER);
+    if protocol_version != Some(PROTOCOL_VERSION.to_string()) {
+        return build_response(
+            StatusCode::BAD_REQUEST,
+            ServerError::ProtocolVersionMismatch {
+                expected: PROTOCOL_VERSION,
+                received: protocol_version,
+            },
+            WireFormat::Json,
+        );
+    }
+    HyperResponse::builder()
+        .status(StatusCode::OK)
+        .header(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION)
+        .body(Body::empty())
+        .expect("Response building should succeed")
+}
+
+fn header_value(http_request: &HyperRequest<Body>, header_name: &str) -> Option<String> {
+    http_request
+        .headers()
+        .get(header_name)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+}
+
+fn is_authorized(http_request: &HyperRequest<Body>, auth_token: &str) -> bool {
+    let expected = format!("Bearer {auth_token}");
+    let Some(received) = http_request.headers().get(AUTHORIZATION) else {
+        return false;
+    };
+    // Compare in constant time, to not leak the token through the response time.
+    received.len() == expected.len()
+        && received.as_bytes().iter().zip(expected.as_bytes()).fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn build_response<T>(status: StatusCode, body: T, wire_format: WireFormat) -> HyperResponse<Body>
+where
+    T: Serialize + DeserializeOwned + Debug,
+{
+    HyperResponse::builder()
+        .status(status)
+        .header(CONTENT_TYPE, wire_format.content_type())
+        .header(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION)
+        .body(Body::from(
+            SerdeWrapper::new(body)
+                .wrapper_serialize(wire_format)
+                .expect("Response serialization should succeed"),
+        ))
+        .expect("Response building should succeed")
+}
+
 #[async_trait]
 impl<Request, Response> ComponentServerStarter for RemoteComponentServer<Request, Response>
 where
@@ -175,19 +274,44 @@ where
     Response: Serialize + DeserializeOwned + Send + Sync + Debug + 'static,
 {
     async fn start(&mut self) -> Result<(), ComponentServerError> {
-        let make_svc = make_service_fn(|_conn| {
-            let local_client = self.local_client.clone();
-            async {
-                Ok::<_, hyper::Error>(service_fn(move |req| {
-                    Self::remote_component_server_handler(req, local_client.clone())
-                }))
-            }
-        });
+        let local_client = self.local_client.clone();
+        let auth_token = self.auth_token.clone();
+        let handle_request = move |req| {
+            Self::remote_component_server_handler(req, local_client.clone(), auth_token.clone())
+        };
+        let to_start_error =
+            |err: &dyn ToString| ComponentServerError::HttpServerStartError(err.to_string());
 
-        Server::bind(&self.socket.clone())
-            .serve(make_svc)
-            .await
-            .map_err(|err| ComponentServerError::HttpServerStartError(err.to_string()))?;
+        match &self.tls_config {
+            Some(tls_config) => {
+                let server_config =
+                    server_tls_config(tls_config).map_err(|err| to_start_error(&err))?;
+                let incoming =
+                    AddrIncoming::bind(&self.socket).map_err(|err| to_start_error(&err))?;
+                let acceptor = TlsAcceptor::builder()
+                    .with_tls_config(server_config)
+                    .with_http2_alpn()
+                    .with_incoming(incoming);
+                let make_svc = make_service_fn(|_conn| {
+                    let handle_request = handle_request.clone();
+                    async { Ok::<_, hyper::Error>(service_fn(handle_request)) }
+                });
+                Server::builder(acceptor)
+                    .serve(make_svc)
+                    .await
+                    .map_err(|err| to_start_error(&err))?;
+            }
+            None => {
+                let make_svc = make_service_fn(|_conn| {
+                    let handle_request = handle_request.clone();
+                    async { Ok::<_, hyper::Error>(service_fn(handle_request)) }
+                });
+                Server::bind(&self.socket)
+                    .serve(make_svc)
+                    .await
+                    .map_err(|err| to_start_error(&err))?;
+            }
+        }
         Ok(())
     }
 }
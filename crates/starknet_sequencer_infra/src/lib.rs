@@ -1,5 +1,6 @@
 pub mod component_client;
 pub mod component_definitions;
+pub mod component_health;
 pub mod component_server;
 pub mod errors;
 pub mod serde_utils;
@@ -7,4 +8,5 @@ pub mod serde_utils;
 pub mod test_utils;
 #[cfg(test)]
 pub mod tests;
+pub mod tls_utils;
 pub mod trace_util;
@@ -1,12 +1,52 @@
 use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
-use serde_json::{from_slice, to_vec};
+use thiserror::Error;
+
+use crate::component_definitions::{APPLICATION_JSON, APPLICATION_OCTET_STREAM};
 
 #[cfg(test)]
 #[path = "serde_utils_test.rs"]
 pub mod serde_utils_test;
 
+/// The encoding of the messages of remote component communication, selected by the content type of
+/// the HTTP message.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WireFormat {
+    // A compact binary encoding (CBOR).
+    #[default]
+    Binary,
+    // A human readable encoding, for debugging.
+    Json,
+}
+
+impl WireFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            WireFormat::Binary => APPLICATION_OCTET_STREAM,
+            WireFormat::Json => APPLICATION_JSON,
+        }
+    }
+
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type {
+            APPLICATION_OCTET_STREAM => Some(WireFormat::Binary),
+            APPLICATION_JSON => Some(WireFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WireFormatError {
+    #[error(transparent)]
+    BinarySerialization(#[from] ciborium::ser::Error<std::io::Error>),
+    #[error(transparent)]
+    BinaryDeserialization(#[from] ciborium::de::Error<std::io::Error>),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
 // A generic wrapper struct for binary serialization and deserialization, used for remote component
 // communication.
 #[derive(Serialize, Deserialize, Debug)]
@@ -22,11 +62,25 @@ where
         Self { data }
     }
 
-    pub fn wrapper_serialize(&self) -> Result<Vec<u8>, serde_json::Error> {
-        to_vec(self)
+    pub fn wrapper_serialize(&self, wire_format: WireFormat) -> Result<Vec<u8>, WireFormatError> {
+        match wire_format {
+            WireFormat::Binary => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(self, &mut bytes)?;
+                Ok(bytes)
+            }
+            WireFormat::Json => Ok(serde_json::to_vec(self)?),
+        }
     }
 
-    pub fn wrapper_deserialize(bytes: &[u8]) -> Result<T, serde_json::Error> {
-        from_slice(bytes).map(|serde_wrapper: Self| serde_wrapper.data)
+    pub fn wrapper_deserialize(
+        bytes: &[u8],
+        wire_format: WireFormat,
+    ) -> Result<T, WireFormatError> {
+        let serde_wrapper: Self = match wire_format {
+            WireFormat::Binary => ciborium::from_reader(bytes)?,
+            WireFormat::Json => serde_json::from_slice(bytes)?,
+        };
+        Ok(serde_wrapper.data)
     }
 }
@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::data_availability::DataAvailabilityMode;
 use starknet_api::rpc_transaction::{
     RpcDeclareTransaction,
@@ -11,20 +12,23 @@ use starknet_api::rpc_transaction::{
     RpcInvokeTransactionV3,
     RpcTransaction,
 };
+use starknet_api::transaction::fields::{Calldata, TransactionSignature};
 use starknet_types_core::felt::Felt;
 
-use crate::serde_utils::SerdeWrapper;
+use crate::serde_utils::{SerdeWrapper, WireFormat};
 
 fn test_generic_data_serde<T>(data: T)
 where
     T: Serialize + for<'de> Deserialize<'de> + Debug + Clone + PartialEq,
 {
-    // Serialize and deserialize the data.
-    let encoded = SerdeWrapper::new(data.clone()).wrapper_serialize().unwrap();
-    let decoded = SerdeWrapper::<T>::wrapper_deserialize(&encoded).unwrap();
+    for wire_format in [WireFormat::Binary, WireFormat::Json] {
+        // Serialize and deserialize the data.
+        let encoded = SerdeWrapper::new(data.clone()).wrapper_serialize(wire_format).unwrap();
+        let decoded = SerdeWrapper::<T>::wrapper_deserialize(&encoded, wire_format).unwrap();
 
-    // Assert that the data is the same after serialization and deserialization.
-    assert_eq!(data, decoded);
+        // Assert that the data is the same after serialization and deserialization.
+        assert_eq!(data, decoded);
+    }
 }
 
 #[test]
@@ -108,3 +112,34 @@ fn test_serde_rpc_declare_tx() {
 
     test_generic_data_serde(RpcTransaction::Declare(rpc_declare_tx));
 }
+
+#[test]
+fn test_binary_format_is_more_compact() {
+    let felts = |n: u64| (0..n).map(|i| Felt::MAX - Felt::from(i)).collect::<Vec<_>>();
+    let invoke_tx = RpcInvokeTransactionV3 {
+        sender_address: ContractAddress::try_from(Felt::from_hex_unchecked("0x7e57")).unwrap(),
+        calldata: Calldata(felts(100).into()),
+        signature: TransactionSignature(felts(2)),
+        nonce: Nonce(Felt::from(17_u8)),
+        resource_bounds: Default::default(),
+        tip: Default::default(),
+        paymaster_data: Default::default(),
+        account_deployment_data: Default::default(),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+    };
+    let data = RpcTransaction::Invoke(RpcInvokeTransaction::V3(invoke_tx));
+
+    let binary = SerdeWrapper::new(data.clone()).wrapper_serialize(WireFormat::Binary).unwrap();
+    let json = SerdeWrapper::new(data).wrapper_serialize(WireFormat::Json).unwrap();
+
+    assert!(binary.len() < json.len());
+}
+
+#[test]
+fn test_wire_format_content_type() {
+    for wire_format in [WireFormat::Binary, WireFormat::Json] {
+        assert_eq!(WireFormat::from_content_type(wire_format.content_type()), Some(wire_format));
+    }
+    assert_eq!(WireFormat::from_content_type("text/plain"), None);
+}
@@ -1,19 +1,27 @@
 use std::fmt::Debug;
+use std::future::Future;
 use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use assert_matches::assert_matches;
 use async_trait::async_trait;
 use hyper::body::to_bytes;
 use hyper::header::CONTENT_TYPE;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Client, Request, Response, Server, StatusCode, Uri};
+use rcgen::{BasicConstraints, Certificate, CertificateParams, ExtendedKeyUsagePurpose, IsCa};
 use rstest::rstest;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use starknet_types_core::felt::Felt;
+use tempfile::TempDir;
 use tokio::sync::mpsc::channel;
 use tokio::sync::Mutex;
 use tokio::task;
+use tokio::time::sleep;
 
 use crate::component_client::{
     ClientError,
@@ -22,19 +30,25 @@ use crate::component_client::{
     RemoteComponentClient,
 };
 use crate::component_definitions::{
+    ClientTlsConfig,
     ComponentClient,
     ComponentRequestAndResponseSender,
     RemoteClientConfig,
     RemoteServerConfig,
     ServerError,
+    ServerTlsConfig,
+    TlsIdentityConfig,
     APPLICATION_OCTET_STREAM,
+    PROTOCOL_VERSION,
+    PROTOCOL_VERSION_HEADER,
 };
+use crate::component_health::{ComponentHealth, HealthStatus};
 use crate::component_server::{
     ComponentServerStarter,
     LocalComponentServer,
     RemoteComponentServer,
 };
-use crate::serde_utils::SerdeWrapper;
+use crate::serde_utils::{SerdeWrapper, WireFormat};
 use crate::test_utils::get_available_socket;
 use crate::tests::{
     test_a_b_functionality,
@@ -113,6 +127,19 @@ fn assert_error_contains_keywords(error: String, expected_error_contained_keywor
     }
 }
 
+// Builds a response with the headers of the current protocol, in the binary wire format.
+fn build_response<T>(status: StatusCode, body: T) -> Response<Body>
+where
+    T: Serialize + DeserializeOwned + Debug,
+{
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
+        .header(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION)
+        .body(Body::from(SerdeWrapper::new(body).wrapper_serialize(WireFormat::Binary).unwrap()))
+        .unwrap()
+}
+
 async fn create_client_and_faulty_server<T>(body: T) -> ComponentAClient
 where
     T: Serialize + DeserializeOwned + Debug + Send + Sync + 'static + Clone,
@@ -126,10 +153,7 @@ where
         where
             T: Serialize + DeserializeOwned + Debug + Send + Sync + Clone,
         {
-            Ok(Response::builder()
-                .status(StatusCode::BAD_REQUEST)
-                .body(Body::from(SerdeWrapper::new(body).wrapper_serialize().unwrap()))
-                .unwrap())
+            Ok(build_response(StatusCode::BAD_REQUEST, body))
         }
 
         let make_svc = make_service_fn(|_conn| {
@@ -147,6 +171,111 @@ where
     ComponentAClient::new(config)
 }
 
+// Spawns a stand-in server that answers the n-th request (counting from 0) with `respond(n)`.
+// Returns the number of requests the server received.
+async fn start_stand_in_server<F, Fut>(socket: SocketAddr, respond: F) -> Arc<AtomicUsize>
+where
+    F: Fn(usize) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Response<Body>> + Send + 'static,
+{
+    let request_count = Arc::new(AtomicUsize::new(0));
+    let server_request_count = request_count.clone();
+    task::spawn(async move {
+        let make_svc = make_service_fn(move |_conn| {
+            let request_count = server_request_count.clone();
+            let respond = respond.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |_http_request: Request<Body>| {
+                    let request_index = request_count.fetch_add(1, Ordering::SeqCst);
+                    let response = respond(request_index);
+                    async move { Ok::<_, hyper::Error>(response.await) }
+                }))
+            }
+        });
+        Server::bind(&socket).serve(make_svc).await.unwrap();
+    });
+    // Ensure the server starts running.
+    task::yield_now().await;
+
+    request_count
+}
+
+// Starts component B behind a remote server with the given config.
+async fn start_component_b_server(server_config: RemoteServerConfig, value: ValueB) {
+    // Component A is not used by these tests.
+    let unused_a_client = ComponentAClient::new(RemoteClientConfig::default());
+    let component_b = ComponentB::new(value, Box::new(unused_a_client));
+    let (tx_b, rx_b) =
+        channel::<ComponentRequestAndResponseSender<ComponentBRequest, ComponentBResponse>>(32);
+    let mut component_b_local_server = LocalComponentServer::new(component_b, rx_b);
+    let mut component_b_remote_server =
+        RemoteComponentServer::new(LocalComponentClient::new(tx_b), server_config);
+
+    task::spawn(async move {
+        let _ = component_b_local_server.start().await;
+    });
+    task::spawn(async move {
+        let _ = component_b_remote_server.start().await;
+    });
+    task::yield_now().await;
+}
+
+// Self-signed certificates of a CA, and of a server and a client signed by it.
+struct TestCertificates {
+    // Keeps the PEM files alive.
+    _dir: TempDir,
+    ca_cert_path: PathBuf,
+    server_identity: TlsIdentityConfig,
+    client_identity: TlsIdentityConfig,
+}
+
+impl TestCertificates {
+    fn generate(server_socket: SocketAddr) -> Self {
+        let dir = TempDir::new().unwrap();
+
+        let mut ca_params = CertificateParams::default();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = Certificate::from_params(ca_params).unwrap();
+        let ca_cert_path = dir.path().join("ca.pem");
+        std::fs::write(&ca_cert_path, ca.serialize_pem().unwrap()).unwrap();
+
+        let server_identity = Self::issue(
+            &ca,
+            dir.path(),
+            "server",
+            vec![server_socket.ip().to_string()],
+            ExtendedKeyUsagePurpose::ServerAuth,
+        );
+        let client_identity = Self::issue(
+            &ca,
+            dir.path(),
+            "client",
+            vec!["client".to_string()],
+            ExtendedKeyUsagePurpose::ClientAuth,
+        );
+
+        Self { _dir: dir, ca_cert_path, server_identity, client_identity }
+    }
+
+    fn issue(
+        ca: &Certificate,
+        dir: &Path,
+        name: &str,
+        subject_alt_names: Vec<String>,
+        usage: ExtendedKeyUsagePurpose,
+    ) -> TlsIdentityConfig {
+        let mut params = CertificateParams::new(subject_alt_names);
+        params.extended_key_usages = vec![usage];
+        let certificate = Certificate::from_params(params).unwrap();
+
+        let cert_path = dir.join(format!("{name}.pem"));
+        let key_path = dir.join(format!("{name}.key"));
+        std::fs::write(&cert_path, certificate.serialize_pem_with_signer(ca).unwrap()).unwrap();
+        std::fs::write(&key_path, certificate.serialize_private_key_pem()).unwrap();
+        TlsIdentityConfig { cert_path, key_path }
+    }
+}
+
 async fn setup_for_tests(setup_value: ValueB, a_socket: SocketAddr, b_socket: SocketAddr) {
     let a_config = RemoteClientConfig { socket: a_socket, ..Default::default() };
     let b_config = RemoteClientConfig { socket: b_socket, ..Default::default() };
@@ -168,10 +297,14 @@ async fn setup_for_tests(setup_value: ValueB, a_socket: SocketAddr, b_socket: So
     let mut component_a_local_server = LocalComponentServer::new(component_a, rx_a);
     let mut component_b_local_server = LocalComponentServer::new(component_b, rx_b);
 
-    let mut component_a_remote_server =
-        RemoteComponentServer::new(a_local_client, RemoteServerConfig { socket: a_socket });
-    let mut component_b_remote_server =
-        RemoteComponentServer::new(b_local_client, RemoteServerConfig { socket: b_socket });
+    let mut component_a_remote_server = RemoteComponentServer::new(
+        a_local_client,
+        RemoteServerConfig { socket: a_socket, ..Default::default() },
+    );
+    let mut component_b_remote_server = RemoteComponentServer::new(
+        b_local_client,
+        RemoteServerConfig { socket: b_socket, ..Default::default() },
+    );
 
     task::spawn(async move {
         let _ = component_a_local_server.start().await;
@@ -207,6 +340,93 @@ async fn test_proper_setup() {
     test_a_b_functionality(a_remote_client, b_remote_client, setup_value).await;
 }
 
+#[tokio::test]
+async fn test_json_wire_format() {
+    let setup_value: ValueB = Felt::from(90);
+    let a_socket = get_available_socket().await;
+    let b_socket = get_available_socket().await;
+
+    setup_for_tests(setup_value, a_socket, b_socket).await;
+    let a_client_config = RemoteClientConfig {
+        socket: a_socket,
+        wire_format: WireFormat::Json,
+        ..Default::default()
+    };
+    let b_client_config = RemoteClientConfig {
+        socket: b_socket,
+        wire_format: WireFormat::Json,
+        ..Default::default()
+    };
+
+    let a_remote_client = ComponentAClient::new(a_client_config);
+    let b_remote_client = ComponentBClient::new(b_client_config);
+    test_a_b_functionality(a_remote_client, b_remote_client, setup_value).await;
+}
+
+#[rstest]
+#[case::missing_version(None)]
+#[case::different_version(Some("0"))]
+#[tokio::test]
+async fn test_protocol_version_mismatch(#[case] protocol_version: Option<&'static str>) {
+    let a_socket = get_available_socket().await;
+    let b_socket = get_available_socket().await;
+    setup_for_tests(Felt::from(123), a_socket, b_socket).await;
+
+    let uri: Uri = format!("http://[{}]:{}/", a_socket.ip(), a_socket.port()).parse().unwrap();
+    let mut http_request = Request::post(uri).header(CONTENT_TYPE, APPLICATION_OCTET_STREAM);
+    if let Some(protocol_version) = protocol_version {
+        http_request = http_request.header(PROTOCOL_VERSION_HEADER, protocol_version);
+    }
+    let http_request = http_request
+        .body(Body::from(
+            SerdeWrapper::new(ComponentARequest::AGetValue)
+                .wrapper_serialize(WireFormat::Binary)
+                .unwrap(),
+        ))
+        .unwrap();
+
+    let http_response = Client::new().request(http_request).await.unwrap();
+
+    assert_eq!(http_response.status(), StatusCode::BAD_REQUEST);
+    let body_bytes = to_bytes(http_response.into_body()).await.unwrap();
+    let server_error =
+        SerdeWrapper::<ServerError>::wrapper_deserialize(&body_bytes, WireFormat::Binary).unwrap();
+    assert_matches!(
+        server_error,
+        ServerError::ProtocolVersionMismatch { expected: PROTOCOL_VERSION, received }
+            if received.as_deref() == protocol_version
+    );
+}
+
+#[tokio::test]
+async fn test_server_with_different_protocol_version() {
+    let socket = get_available_socket().await;
+    task::spawn(async move {
+        async fn handler(_http_request: Request<Body>) -> Result<Response<Body>, hyper::Error> {
+            let mut response =
+                build_response(StatusCode::OK, ComponentAResponse::AGetValue(VALID_VALUE_A));
+            response.headers_mut().insert(PROTOCOL_VERSION_HEADER, "0".parse().unwrap());
+            Ok(response)
+        }
+
+        let make_svc =
+            make_service_fn(|_conn| async { Ok::<_, hyper::Error>(service_fn(handler)) });
+        Server::bind(&socket).serve(make_svc).await.unwrap();
+    });
+    // Ensure the server starts running.
+    task::yield_now().await;
+
+    let client = ComponentAClient::new(RemoteClientConfig { socket, ..Default::default() });
+    let Err(error) = client.a_get_value().await else {
+        panic!("Expected an error.");
+    };
+    assert_matches!(
+        error,
+        ClientError::ResponseError(_, ServerError::ProtocolVersionMismatch { received, .. })
+            if received.as_deref() == Some("0")
+    );
+}
+
 #[tokio::test]
 async fn test_faulty_client_setup() {
     let a_socket = get_available_socket().await;
@@ -227,12 +447,19 @@ async fn test_faulty_client_setup() {
                 format!("http://[{}]:{}/", self.socket.ip(), self.socket.port()).parse().unwrap();
             let http_request = Request::post(uri)
                 .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-                .body(Body::from(SerdeWrapper::new(component_request).wrapper_serialize().unwrap()))
+                .header(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION)
+                .body(Body::from(
+                    SerdeWrapper::new(component_request)
+                        .wrapper_serialize(WireFormat::Binary)
+                        .unwrap(),
+                ))
                 .unwrap();
             let http_response = Client::new().request(http_request).await.unwrap();
             let status_code = http_response.status();
             let body_bytes = to_bytes(http_response.into_body()).await.unwrap();
-            let response = SerdeWrapper::<ServerError>::wrapper_deserialize(&body_bytes).unwrap();
+            let response =
+                SerdeWrapper::<ServerError>::wrapper_deserialize(&body_bytes, WireFormat::Binary)
+                    .unwrap();
             Err(ClientError::ResponseError(status_code, response))
         }
     }
@@ -284,15 +511,9 @@ async fn test_retry_request() {
             let mut should_send_ok = should_send_ok.lock().await;
             let body = ComponentAResponse::AGetValue(VALID_VALUE_A);
             let ret = if *should_send_ok {
-                Response::builder()
-                    .status(StatusCode::OK)
-                    .body(Body::from(SerdeWrapper::new(body).wrapper_serialize().unwrap()))
-                    .unwrap()
+                build_response(StatusCode::OK, body)
             } else {
-                Response::builder()
-                    .status(StatusCode::IM_A_TEAPOT)
-                    .body(Body::from(SerdeWrapper::new(body).wrapper_serialize().unwrap()))
-                    .unwrap()
+                build_response(StatusCode::SERVICE_UNAVAILABLE, body)
             };
             *should_send_ok = !*should_send_ok;
 
@@ -320,6 +541,7 @@ async fn test_retry_request() {
         retries: 1,
         idle_connections: MAX_IDLE_CONNECTION,
         idle_timeout: IDLE_TIMEOUT,
+        ..Default::default()
     };
     let a_client_retry = ComponentAClient::new(retry_config);
     assert_eq!(a_client_retry.a_get_value().await.unwrap(), VALID_VALUE_A);
@@ -330,8 +552,288 @@ async fn test_retry_request() {
         retries: 0,
         idle_connections: MAX_IDLE_CONNECTION,
         idle_timeout: IDLE_TIMEOUT,
+        ..Default::default()
     };
     let a_client_no_retry = ComponentAClient::new(no_retry_config);
-    let expected_error_contained_keywords = [StatusCode::IM_A_TEAPOT.as_str()];
+    let expected_error_contained_keywords = [StatusCode::SERVICE_UNAVAILABLE.as_str()];
     verify_error(a_client_no_retry.clone(), &expected_error_contained_keywords).await;
 }
+
+#[tokio::test]
+async fn test_retry_backoff() {
+    let socket = get_available_socket().await;
+    // Fail the first two requests.
+    let request_count = start_stand_in_server(socket, |request_index| async move {
+        match request_index {
+            0 | 1 => build_response(StatusCode::SERVICE_UNAVAILABLE, ()),
+            _ => build_response(StatusCode::OK, ComponentAResponse::AGetValue(VALID_VALUE_A)),
+        }
+    })
+    .await;
+    let initial_retry_backoff = Duration::from_millis(100);
+    let client = ComponentAClient::new(RemoteClientConfig {
+        socket,
+        retries: 2,
+        initial_retry_backoff,
+        ..Default::default()
+    });
+
+    let start = Instant::now();
+    assert_eq!(client.a_get_value().await.unwrap(), VALID_VALUE_A);
+
+    // The backoffs are of 100ms and 200ms, each at least halved by the jitter.
+    assert!(start.elapsed() >= initial_retry_backoff / 2 + initial_retry_backoff);
+    assert_eq!(request_count.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_rejected_request_is_not_retried() {
+    let socket = get_available_socket().await;
+    let request_count = start_stand_in_server(socket, |_| async {
+        build_response(StatusCode::UNAUTHORIZED, ServerError::Unauthorized)
+    })
+    .await;
+    let client = ComponentAClient::new(RemoteClientConfig {
+        socket,
+        retries: 2,
+        circuit_breaker_failure_threshold: 1,
+        ..Default::default()
+    });
+
+    // Each request is sent once, and its rejection doesn't open the breaker.
+    for expected_request_count in 1..=2 {
+        assert_matches!(
+            client.a_get_value().await,
+            Err(ClientError::ResponseError(StatusCode::UNAUTHORIZED, ServerError::Unauthorized))
+        );
+        assert_eq!(request_count.load(Ordering::SeqCst), expected_request_count);
+    }
+}
+
+#[tokio::test]
+async fn test_request_timeout() {
+    let socket = get_available_socket().await;
+    let server_delay = Duration::from_secs(2);
+    start_stand_in_server(socket, move |_| async move {
+        sleep(server_delay).await;
+        build_response(StatusCode::OK, ComponentAResponse::AGetValue(VALID_VALUE_A))
+    })
+    .await;
+    let request_timeout = Duration::from_millis(100);
+    let client =
+        ComponentAClient::new(RemoteClientConfig { socket, request_timeout, ..Default::default() });
+
+    let start = Instant::now();
+    let result = client.a_get_value().await;
+
+    assert_matches!(result, Err(ClientError::Timeout(timeout)) if timeout == request_timeout);
+    assert!(start.elapsed() < server_delay);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker() {
+    let socket = get_available_socket().await;
+    let request_count = start_stand_in_server(socket, |_| async {
+        build_response(StatusCode::SERVICE_UNAVAILABLE, ())
+    })
+    .await;
+    let circuit_breaker_reset_timeout = Duration::from_millis(200);
+    let client = ComponentAClient::new(RemoteClientConfig {
+        socket,
+        retries: 0,
+        circuit_breaker_failure_threshold: 2,
+        circuit_breaker_reset_timeout,
+        ..Default::default()
+    });
+
+    // The breaker opens after two failed requests.
+    for _ in 0..2 {
+        assert_matches!(client.a_get_value().await, Err(ClientError::ResponseError(..)));
+    }
+    assert_matches!(client.a_get_value().await, Err(ClientError::CircuitBreakerOpen(2)));
+    // The breaker is shared between clones.
+    assert_matches!(client.clone().a_get_value().await, Err(ClientError::CircuitBreakerOpen(2)));
+    assert_eq!(request_count.load(Ordering::SeqCst), 2);
+
+    // After the reset timeout a request reaches the server, and its failure re-opens the breaker.
+    sleep(circuit_breaker_reset_timeout).await;
+    assert_matches!(client.a_get_value().await, Err(ClientError::ResponseError(..)));
+    assert_matches!(client.a_get_value().await, Err(ClientError::CircuitBreakerOpen(3)));
+    assert_eq!(request_count.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_remote_client_health() {
+    let socket = get_available_socket().await;
+    let client = ComponentBClient::new(RemoteClientConfig { socket, ..Default::default() });
+
+    // No server is listening on the socket.
+    assert_eq!(client.health().await.status, HealthStatus::Unhealthy);
+
+    start_component_b_server(RemoteServerConfig { socket, ..Default::default() }, Felt::from(90))
+        .await;
+    assert_eq!(client.health().await.status, HealthStatus::Healthy);
+}
+
+#[tokio::test]
+async fn test_remote_client_health_with_open_circuit_breaker() {
+    let socket = get_available_socket().await;
+    // Answers health checks, but not with valid component responses.
+    start_stand_in_server(socket, |_| async { build_response(StatusCode::OK, ()) }).await;
+    let client = ComponentAClient::new(RemoteClientConfig {
+        socket,
+        retries: 0,
+        circuit_breaker_failure_threshold: 1,
+        ..Default::default()
+    });
+    assert_eq!(client.health().await.status, HealthStatus::Healthy);
+
+    // A failed request opens the breaker, which marks the server unreachable.
+    assert_matches!(
+        client.a_get_value().await,
+        Err(ClientError::ResponseDeserializationFailure(_))
+    );
+    assert_eq!(client.health().await.status, HealthStatus::Unhealthy);
+}
+
+#[tokio::test]
+async fn test_remote_client_health_over_tls_with_auth() {
+    let socket = get_available_socket().await;
+    let certificates = TestCertificates::generate(socket);
+    let server_tls_config = ServerTlsConfig {
+        identity: certificates.server_identity.clone(),
+        client_ca_cert_path: None,
+    };
+    start_component_b_server(
+        RemoteServerConfig {
+            socket,
+            tls_config: Some(server_tls_config),
+            auth_token: Some("secret".to_string()),
+        },
+        Felt::from(90),
+    )
+    .await;
+    let client_config = |tls: bool, auth_token: &str| RemoteClientConfig {
+        socket,
+        tls_config: tls.then(|| ClientTlsConfig {
+            ca_cert_path: certificates.ca_cert_path.clone(),
+            identity: None,
+        }),
+        auth_token: Some(auth_token.to_string()),
+        ..Default::default()
+    };
+
+    let client = ComponentBClient::new(client_config(true, "secret"));
+    assert_eq!(client.health().await.status, HealthStatus::Healthy);
+
+    // The health check is sent like any other request, so it fails without TLS or authorization.
+    let plain_client = ComponentBClient::new(client_config(false, "secret"));
+    assert_eq!(plain_client.health().await.status, HealthStatus::Unhealthy);
+    let unauthorized_client = ComponentBClient::new(client_config(true, "guess"));
+    let report = unauthorized_client.health().await;
+    assert_eq!(report.status, HealthStatus::Unhealthy);
+    assert!(report.details["error"].as_str().unwrap().contains("401"));
+}
+
+#[tokio::test]
+async fn test_tls() {
+    let socket = get_available_socket().await;
+    let certificates = TestCertificates::generate(socket);
+    let server_tls_config = ServerTlsConfig {
+        identity: certificates.server_identity.clone(),
+        client_ca_cert_path: None,
+    };
+    let value = Felt::from(90);
+    start_component_b_server(
+        RemoteServerConfig { socket, tls_config: Some(server_tls_config), ..Default::default() },
+        value,
+    )
+    .await;
+
+    let client_tls_config =
+        ClientTlsConfig { ca_cert_path: certificates.ca_cert_path.clone(), identity: None };
+    let client = ComponentBClient::new(RemoteClientConfig {
+        socket,
+        tls_config: Some(client_tls_config),
+        ..Default::default()
+    });
+    assert_eq!(client.b_get_value().await.unwrap(), value);
+
+    // A plain HTTP client cannot reach the server.
+    let plain_client =
+        ComponentBClient::new(RemoteClientConfig { socket, retries: 0, ..Default::default() });
+    assert_matches!(plain_client.b_get_value().await, Err(ClientError::CommunicationFailure(_)));
+}
+
+#[tokio::test]
+async fn test_mutual_tls() {
+    let socket = get_available_socket().await;
+    let certificates = TestCertificates::generate(socket);
+    let server_tls_config = ServerTlsConfig {
+        identity: certificates.server_identity.clone(),
+        client_ca_cert_path: Some(certificates.ca_cert_path.clone()),
+    };
+    let value = Felt::from(90);
+    start_component_b_server(
+        RemoteServerConfig { socket, tls_config: Some(server_tls_config), ..Default::default() },
+        value,
+    )
+    .await;
+
+    let client_tls_config = ClientTlsConfig {
+        ca_cert_path: certificates.ca_cert_path.clone(),
+        identity: Some(certificates.client_identity.clone()),
+    };
+    let client = ComponentBClient::new(RemoteClientConfig {
+        socket,
+        tls_config: Some(client_tls_config),
+        ..Default::default()
+    });
+    assert_eq!(client.b_get_value().await.unwrap(), value);
+
+    // A client without a certificate is rejected.
+    let anonymous_tls_config =
+        ClientTlsConfig { ca_cert_path: certificates.ca_cert_path.clone(), identity: None };
+    let anonymous_client = ComponentBClient::new(RemoteClientConfig {
+        socket,
+        retries: 0,
+        tls_config: Some(anonymous_tls_config),
+        ..Default::default()
+    });
+    assert_matches!(
+        anonymous_client.b_get_value().await,
+        Err(ClientError::CommunicationFailure(_))
+    );
+}
+
+#[rstest]
+#[case::valid_token(Some("secret"), true)]
+#[case::invalid_token(Some("guess"), false)]
+#[case::missing_token(None, false)]
+#[tokio::test]
+async fn test_auth_token(#[case] client_auth_token: Option<&str>, #[case] is_authorized: bool) {
+    let socket = get_available_socket().await;
+    let value = Felt::from(90);
+    start_component_b_server(
+        RemoteServerConfig { socket, auth_token: Some("secret".to_string()), ..Default::default() },
+        value,
+    )
+    .await;
+
+    let client = ComponentBClient::new(RemoteClientConfig {
+        socket,
+        retries: 0,
+        auth_token: client_auth_token.map(String::from),
+        ..Default::default()
+    });
+
+    let result = client.b_get_value().await;
+    if is_authorized {
+        assert_eq!(result.unwrap(), value);
+    } else {
+        assert_matches!(
+            result,
+            Err(ClientError::ResponseError(StatusCode::UNAUTHORIZED, ServerError::Unauthorized))
+        );
+    }
+}
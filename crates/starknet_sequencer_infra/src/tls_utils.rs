@@ -0,0 +1,89 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore, ServerConfig};
+use rustls_pemfile::Item;
+use thiserror::Error;
+
+use crate::component_definitions::{ClientTlsConfig, ServerTlsConfig, TlsIdentityConfig};
+
+#[derive(Debug, Error)]
+pub enum TlsConfigError {
+    #[error("Failed to read {path:?}: {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("No certificates found in {0:?}.")]
+    MissingCertificates(PathBuf),
+    #[error("No private key found in {0:?}.")]
+    MissingPrivateKey(PathBuf),
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+}
+
+pub(crate) fn server_tls_config(config: &ServerTlsConfig) -> Result<ServerConfig, TlsConfigError> {
+    let (cert_chain, private_key) = load_identity(&config.identity)?;
+    let builder = ServerConfig::builder().with_safe_defaults();
+    let builder = match &config.client_ca_cert_path {
+        Some(client_ca_cert_path) => builder.with_client_cert_verifier(
+            AllowAnyAuthenticatedClient::new(load_root_store(client_ca_cert_path)?).boxed(),
+        ),
+        None => builder.with_no_client_auth(),
+    };
+    Ok(builder.with_single_cert(cert_chain, private_key)?)
+}
+
+pub(crate) fn client_tls_config(config: &ClientTlsConfig) -> Result<ClientConfig, TlsConfigError> {
+    let builder = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(load_root_store(&config.ca_cert_path)?);
+    match &config.identity {
+        Some(identity) => {
+            let (cert_chain, private_key) = load_identity(identity)?;
+            Ok(builder.with_client_auth_cert(cert_chain, private_key)?)
+        }
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+fn load_identity(
+    config: &TlsIdentityConfig,
+) -> Result<(Vec<Certificate>, PrivateKey), TlsConfigError> {
+    let cert_chain = load_certificates(&config.cert_path)?;
+    let private_key = read_pem_items(&config.key_path)?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| TlsConfigError::MissingPrivateKey(config.key_path.clone()))?;
+    Ok((cert_chain, private_key))
+}
+
+fn load_root_store(ca_cert_path: &Path) -> Result<RootCertStore, TlsConfigError> {
+    let mut root_store = RootCertStore::empty();
+    for certificate in load_certificates(ca_cert_path)? {
+        root_store.add(&certificate)?;
+    }
+    Ok(root_store)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<Certificate>, TlsConfigError> {
+    let certificates: Vec<Certificate> = read_pem_items(path)?
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::X509Certificate(certificate) => Some(Certificate(certificate)),
+            _ => None,
+        })
+        .collect();
+    if certificates.is_empty() {
+        return Err(TlsConfigError::MissingCertificates(path.to_path_buf()));
+    }
+    Ok(certificates)
+}
+
+fn read_pem_items(path: &Path) -> Result<Vec<Item>, TlsConfigError> {
+    let to_io_error = |source| TlsConfigError::Io { path: path.to_path_buf(), source };
+    let file = File::open(path).map_err(to_io_error)?;
+    rustls_pemfile::read_all(&mut BufReader::new(file)).map_err(to_io_error)
+}
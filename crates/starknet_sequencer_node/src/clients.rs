@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
 use std::sync::Arc;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use starknet_batcher_types::communication::{
     BatcherRequest,
     BatcherResponse,
@@ -31,6 +35,7 @@ use starknet_mempool_types::communication::{
     SharedMempoolClient,
 };
 use starknet_sequencer_infra::component_client::{Client, LocalComponentClient};
+use starknet_sequencer_infra::component_health::SharedComponentHealth;
 use starknet_state_sync_types::communication::{
     LocalStateSyncClient,
     RemoteStateSyncClient,
@@ -158,6 +163,44 @@ impl SequencerNodeClients {
     ) -> Option<LocalComponentClient<StateSyncRequest, StateSyncResponse>> {
         self.state_sync_client.get_local_client()
     }
+
+    /// Returns the health reporters of the remote clients, reporting whether their servers are
+    /// reachable, by client name.
+    pub fn get_remote_clients_health(&self) -> BTreeMap<String, SharedComponentHealth> {
+        let mut remote_clients_health = BTreeMap::new();
+        insert_remote_client_health(&mut remote_clients_health, "batcher", &self.batcher_client);
+        insert_remote_client_health(&mut remote_clients_health, "mempool", &self.mempool_client);
+        insert_remote_client_health(&mut remote_clients_health, "gateway", &self.gateway_client);
+        insert_remote_client_health(
+            &mut remote_clients_health,
+            "mempool_p2p_propagator",
+            &self.mempool_p2p_propagator_client,
+        );
+        insert_remote_client_health(
+            &mut remote_clients_health,
+            "state_sync",
+            &self.state_sync_client,
+        );
+        insert_remote_client_health(
+            &mut remote_clients_health,
+            "l1_provider",
+            &self.l1_provider_client,
+        );
+        remote_clients_health
+    }
+}
+
+fn insert_remote_client_health<Request, Response>(
+    remote_clients_health: &mut BTreeMap<String, SharedComponentHealth>,
+    component_name: &str,
+    client: &Client<Request, Response>,
+) where
+    Request: Send + Sync + Serialize + DeserializeOwned + Debug + 'static,
+    Response: Send + Sync + Serialize + DeserializeOwned + Debug + 'static,
+{
+    if let Some(remote_client) = client.get_remote_client() {
+        remote_clients_health.insert(format!("{component_name}_client"), Arc::new(remote_client));
+    }
 }
 
 /// A macro for creating a component client fitting the component's execution mode. Returns a
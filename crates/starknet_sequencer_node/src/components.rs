@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use papyrus_consensus::decision_certificate::DecisionCertificateVerifier;
+use starknet_api::block::DecidedHeaderField;
 use starknet_batcher::batcher::{create_batcher, Batcher};
 use starknet_consensus_manager::consensus_manager::ConsensusManager;
 use starknet_gateway::gateway::{create_gateway, Gateway};
@@ -69,17 +71,47 @@ pub fn create_node_components(
         }
         ActiveComponentExecutionMode::Disabled => None,
     };
+    let (state_sync, state_sync_runner) = match config.components.state_sync.execution_mode {
+        ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
+        | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
+            // The validators are known only if the node runs consensus.
+            let decision_certificate_verifier =
+                match config.components.consensus_manager.execution_mode {
+                    ActiveComponentExecutionMode::Enabled => {
+                        // The sequencer decides on the state diff commitments of the blocks.
+                        Some(DecisionCertificateVerifier::from_config(
+                            &config.consensus_manager_config.consensus_config,
+                            DecidedHeaderField::StateDiffCommitment,
+                        ))
+                    }
+                    ActiveComponentExecutionMode::Disabled => None,
+                };
+            let (state_sync, state_sync_runner) = create_state_sync_and_runner(
+                config.state_sync_config.clone(),
+                decision_certificate_verifier,
+            );
+            (Some(state_sync), Some(state_sync_runner))
+        }
+        ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
+            (None, None)
+        }
+    };
+
     let gateway = match config.components.gateway.execution_mode {
         ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
         | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
             let mempool_client =
                 clients.get_mempool_shared_client().expect("Mempool Client should be available");
+            let state_sync_client = clients.get_state_sync_shared_client();
+            let storage_reader = state_sync.as_ref().map(StateSync::storage_reader);
 
             Some(create_gateway(
                 config.gateway_config.clone(),
                 config.rpc_state_reader_config.clone(),
                 config.compiler_config.clone(),
                 mempool_client,
+                state_sync_client,
+                storage_reader,
             ))
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => None,
@@ -118,30 +150,38 @@ pub fn create_node_components(
             let mempool_p2p_propagator_client = clients
                 .get_mempool_p2p_propagator_shared_client()
                 .expect("Propagator Client should be available");
-            let mempool = create_mempool(mempool_p2p_propagator_client);
+            let mempool =
+                create_mempool(config.mempool_config.clone(), mempool_p2p_propagator_client)
+                    .expect("Failed to open the mempool journal.");
             Some(mempool)
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => None,
     };
 
     let monitoring_endpoint = match config.components.monitoring_endpoint.execution_mode {
-        ActiveComponentExecutionMode::Enabled => Some(create_monitoring_endpoint(
-            config.monitoring_endpoint_config.clone(),
-            VERSION_FULL,
-        )),
-        ActiveComponentExecutionMode::Disabled => None,
-    };
-
-    let (state_sync, state_sync_runner) = match config.components.state_sync.execution_mode {
-        ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
-        | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
-            let (state_sync, state_sync_runner) =
-                create_state_sync_and_runner(config.state_sync_config.clone());
-            (Some(state_sync), Some(state_sync_runner))
-        }
-        ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
-            (None, None)
+        ActiveComponentExecutionMode::Enabled => {
+            // Components are moved into their servers, hence their health is reported by handles
+            // taken beforehand.
+            let mut components_health = clients.get_remote_clients_health();
+            if let Some(batcher) = &batcher {
+                components_health
+                    .insert("batcher".to_string(), Arc::new(batcher.health_reporter()));
+            }
+            if let Some(mempool) = &mempool {
+                components_health
+                    .insert("mempool".to_string(), Arc::new(mempool.health_reporter()));
+            }
+            if let Some(state_sync) = &state_sync {
+                components_health
+                    .insert("state_sync".to_string(), Arc::new(state_sync.health_reporter()));
+            }
+            Some(create_monitoring_endpoint(
+                config.monitoring_endpoint_config.clone(),
+                VERSION_FULL,
+                components_health,
+            ))
         }
+        ActiveComponentExecutionMode::Disabled => None,
     };
 
     let l1_provider = match config.components.l1_provider.execution_mode {
@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use papyrus_config::dumping::{append_sub_config_name, SerializeConfig};
-use papyrus_config::{ParamPath, SerializedParam};
+use papyrus_config::converters::deserialize_seconds_to_duration;
+use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
+use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_network::NetworkConfig;
 use papyrus_p2p_sync::client::P2PSyncClientConfig;
 use papyrus_storage::db::DbConfig;
@@ -18,6 +20,9 @@ pub struct StateSyncConfig {
     pub p2p_sync_client_config: P2PSyncClientConfig,
     #[validate]
     pub network_config: NetworkConfig,
+    // The age of the latest synced block beyond which state sync is reported degraded.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub max_healthy_sync_lag: Duration,
 }
 
 impl SerializeConfig for StateSyncConfig {
@@ -26,6 +31,13 @@ impl SerializeConfig for StateSyncConfig {
             append_sub_config_name(self.storage_config.dump(), "storage_config"),
             append_sub_config_name(self.p2p_sync_client_config.dump(), "p2p_sync_client_config"),
             append_sub_config_name(self.network_config.dump(), "network_config"),
+            BTreeMap::from_iter([ser_param(
+                "max_healthy_sync_lag",
+                &self.max_healthy_sync_lag.as_secs(),
+                "The time in seconds since the latest synced block was created, beyond which \
+                 state sync is reported as lagging behind the network.",
+                ParamPrivacyInput::Public,
+            )]),
         ]
         .into_iter()
         .flatten()
@@ -45,6 +57,7 @@ impl Default for StateSyncConfig {
             },
             p2p_sync_client_config: Default::default(),
             network_config: Default::default(),
+            max_healthy_sync_lag: Duration::from_secs(300),
         }
     }
 }
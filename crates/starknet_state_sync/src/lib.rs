@@ -1,30 +1,50 @@
 pub mod config;
 pub mod runner;
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use async_trait::async_trait;
+use papyrus_consensus::decision_certificate::DecisionCertificateVerifier;
 use papyrus_storage::body::BodyStorageReader;
+use papyrus_storage::class::ClassStorageReader;
+use papyrus_storage::compiled_class::CasmStorageReader;
+use papyrus_storage::db::RO;
+use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::state::StateStorageReader;
-use papyrus_storage::StorageReader;
-use starknet_api::block::BlockNumber;
+use papyrus_storage::{StorageReader, StorageTxn};
+use serde_json::json;
+use starknet_api::block::{BlockHeaderWithoutHash, BlockNumber};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::{StateNumber, StorageKey};
 use starknet_sequencer_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
+use starknet_sequencer_infra::component_health::{ComponentHealth, HealthReport, HealthStatus};
 use starknet_sequencer_infra::component_server::{LocalComponentServer, RemoteComponentServer};
 use starknet_state_sync_types::communication::{
     StateSyncRequest,
     StateSyncResponse,
     StateSyncResult,
 };
+use starknet_state_sync_types::errors::StateSyncError;
 use starknet_state_sync_types::state_sync_types::SyncBlock;
+use starknet_types_core::felt::Felt;
 
 use crate::config::StateSyncConfig;
 use crate::runner::StateSyncRunner;
 
-pub fn create_state_sync_and_runner(config: StateSyncConfig) -> (StateSync, StateSyncRunner) {
-    let (state_sync_runner, storage_reader) = StateSyncRunner::new(config);
-    (StateSync { storage_reader }, state_sync_runner)
+pub fn create_state_sync_and_runner(
+    config: StateSyncConfig,
+    decision_certificate_verifier: Option<DecisionCertificateVerifier>,
+) -> (StateSync, StateSyncRunner) {
+    let max_healthy_sync_lag = config.max_healthy_sync_lag;
+    let (state_sync_runner, storage_reader) =
+        StateSyncRunner::new(config, decision_certificate_verifier);
+    (StateSync { storage_reader, max_healthy_sync_lag }, state_sync_runner)
 }
 
 pub struct StateSync {
     storage_reader: StorageReader,
+    max_healthy_sync_lag: Duration,
 }
 
 // TODO(shahak): Have StateSyncRunner call StateSync instead of the opposite once we stop supporting
@@ -39,29 +59,270 @@ impl ComponentRequestHandler<StateSyncRequest, StateSyncResponse> for StateSync
             StateSyncRequest::AddNewBlock(_block_number, _sync_block) => {
                 todo!()
             }
+            StateSyncRequest::GetLatestBlockNumber => {
+                StateSyncResponse::GetLatestBlockNumber(self.get_latest_block_number())
+            }
+            StateSyncRequest::GetBlockHeader(block_number) => {
+                StateSyncResponse::GetBlockHeader(self.get_block_header(block_number))
+            }
+            StateSyncRequest::GetStorageAt(block_number, contract_address, storage_key) => {
+                StateSyncResponse::GetStorageAt(self.get_storage_at(
+                    block_number,
+                    contract_address,
+                    storage_key,
+                ))
+            }
+            StateSyncRequest::GetNonceAt(block_number, contract_address) => {
+                StateSyncResponse::GetNonceAt(self.get_nonce_at(block_number, contract_address))
+            }
+            StateSyncRequest::GetClassHashAt(block_number, contract_address) => {
+                StateSyncResponse::GetClassHashAt(
+                    self.get_class_hash_at(block_number, contract_address),
+                )
+            }
+            StateSyncRequest::GetCompiledClass(block_number, class_hash) => {
+                StateSyncResponse::GetCompiledClass(
+                    self.get_compiled_class(block_number, class_hash),
+                )
+            }
+            StateSyncRequest::GetCompiledClassHash(block_number, class_hash) => {
+                StateSyncResponse::GetCompiledClassHash(
+                    self.get_compiled_class_hash(block_number, class_hash),
+                )
+            }
         }
     }
 }
 
 impl StateSync {
+    /// Returns a reader of the synced storage, for components running in the same process.
+    pub fn storage_reader(&self) -> StorageReader {
+        self.storage_reader.clone()
+    }
+
+    /// Returns a handle reporting the sync progress, which outlives moving state sync into its
+    /// server.
+    pub fn health_reporter(&self) -> StateSyncHealth {
+        StateSyncHealth {
+            storage_reader: self.storage_reader.clone(),
+            max_healthy_sync_lag: self.max_healthy_sync_lag,
+        }
+    }
+
+    // Returns the block once all of its parts are synced.
     fn get_block(&self, block_number: BlockNumber) -> StateSyncResult<Option<SyncBlock>> {
         let txn = self.storage_reader.begin_ro_txn()?;
-        if let Some(block_transaction_hashes) = txn.get_block_transaction_hashes(block_number)? {
-            if let Some(thin_state_diff) = txn.get_state_diff(block_number)? {
-                return Ok(Some(SyncBlock {
-                    block_number,
-                    state_diff: thin_state_diff,
-                    transaction_hashes: block_transaction_hashes,
-                }));
-            }
+        if txn.get_class_marker()? <= block_number {
+            return Ok(None);
+        }
+        let (
+            Some(block_header),
+            Some(transactions),
+            Some(transaction_outputs),
+            Some(transaction_hashes),
+            Some(state_diff),
+        ) = (
+            txn.get_block_header(block_number)?,
+            txn.get_block_transactions(block_number)?,
+            txn.get_block_transaction_outputs(block_number)?,
+            txn.get_block_transaction_hashes(block_number)?,
+            txn.get_state_diff(block_number)?,
+        )
+        else {
+            return Ok(None);
+        };
+
+        let classes = state_diff
+            .declared_classes
+            .keys()
+            .map(|&class_hash| -> StateSyncResult<_> {
+                let class =
+                    txn.get_class(&class_hash)?.ok_or(StateSyncError::ClassNotFound(class_hash))?;
+                Ok((class_hash, class))
+            })
+            .collect::<StateSyncResult<_>>()?;
+        let deprecated_classes = state_diff
+            .deprecated_declared_classes
+            .iter()
+            .map(|&class_hash| -> StateSyncResult<_> {
+                let class = txn
+                    .get_deprecated_class(&class_hash)?
+                    .ok_or(StateSyncError::ClassNotFound(class_hash))?;
+                Ok((class_hash, class))
+            })
+            .collect::<StateSyncResult<_>>()?;
+
+        Ok(Some(SyncBlock {
+            block_number,
+            state_diff,
+            transaction_hashes,
+            block_header,
+            transactions,
+            transaction_outputs,
+            classes,
+            deprecated_classes,
+        }))
+    }
+
+    fn get_latest_block_number(&self) -> StateSyncResult<Option<BlockNumber>> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        Ok(txn.get_state_marker()?.prev())
+    }
+
+    fn get_block_header(
+        &self,
+        block_number: BlockNumber,
+    ) -> StateSyncResult<Option<BlockHeaderWithoutHash>> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        Ok(txn.get_block_header(block_number)?.map(|header| header.block_header_without_hash))
+    }
+
+    fn get_storage_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+        storage_key: StorageKey,
+    ) -> StateSyncResult<Felt> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        let state_number = synced_state_number(&txn, block_number)?;
+        Ok(txn.get_state_reader()?.get_storage_at(state_number, &contract_address, &storage_key)?)
+    }
+
+    fn get_nonce_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncResult<Nonce> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        let state_number = synced_state_number(&txn, block_number)?;
+        let nonce = txn.get_state_reader()?.get_nonce_at(state_number, &contract_address)?;
+        // Undeployed contracts have the default nonce.
+        Ok(nonce.unwrap_or_default())
+    }
+
+    fn get_class_hash_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncResult<ClassHash> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        let state_number = synced_state_number(&txn, block_number)?;
+        let class_hash =
+            txn.get_state_reader()?.get_class_hash_at(state_number, &contract_address)?;
+        // Undeployed contracts have the default class hash.
+        Ok(class_hash.unwrap_or_default())
+    }
+
+    fn get_compiled_class(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncResult<ContractClass> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        let state_number = synced_state_number(&txn, block_number)?;
+        let state_reader = txn.get_state_reader()?;
+
+        let is_cairo_1_class_declared = state_reader
+            .get_class_definition_block_number(&class_hash)?
+            .is_some_and(|declaration_block_number| declaration_block_number <= block_number);
+        if is_cairo_1_class_declared {
+            return txn
+                .get_casm(&class_hash)?
+                .map(ContractClass::V1)
+                .ok_or(StateSyncError::ClassNotFound(class_hash));
         }
 
-        Ok(None)
+        state_reader
+            .get_deprecated_class_definition_at(state_number, &class_hash)?
+            .map(ContractClass::V0)
+            .ok_or(StateSyncError::ClassNotFound(class_hash))
+    }
+
+    fn get_compiled_class_hash(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncResult<CompiledClassHash> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        synced_state_number(&txn, block_number)?;
+
+        let Some(declaration_block_number) = txn
+            .get_state_reader()?
+            .get_class_definition_block_number(&class_hash)?
+            .filter(|&declaration_block_number| declaration_block_number <= block_number)
+        else {
+            return Ok(CompiledClassHash::default());
+        };
+        let state_diff = txn
+            .get_state_diff(declaration_block_number)?
+            .ok_or(StateSyncError::BlockNotFound(declaration_block_number))?;
+        Ok(state_diff.declared_classes.get(&class_hash).copied().unwrap_or_default())
     }
 }
 
+/// Returns the state number right after the given block, if the block's state was synced.
+fn synced_state_number(
+    txn: &StorageTxn<'_, RO>,
+    block_number: BlockNumber,
+) -> StateSyncResult<StateNumber> {
+    if block_number >= txn.get_state_marker()? {
+        return Err(StateSyncError::BlockNotFound(block_number));
+    }
+    Ok(StateNumber::unchecked_right_after_block(block_number))
+}
+
 pub type LocalStateSyncServer =
     LocalComponentServer<StateSync, StateSyncRequest, StateSyncResponse>;
 pub type RemoteStateSyncServer = RemoteComponentServer<StateSyncRequest, StateSyncResponse>;
 
 impl ComponentStarter for StateSync {}
+
+/// Reports how far the synced state lags behind the network, measured by the time passed since the
+/// latest synced block was created. Blocks are created continuously, so a synced node is at most a
+/// few block times behind.
+#[derive(Clone)]
+pub struct StateSyncHealth {
+    storage_reader: StorageReader,
+    max_healthy_sync_lag: Duration,
+}
+
+impl StateSyncHealth {
+    // Returns the markers and the timestamp of the latest block whose state is synced, if any.
+    fn sync_progress(&self) -> StateSyncResult<(BlockNumber, BlockNumber, Option<u64>)> {
+        let txn = self.storage_reader.begin_ro_txn()?;
+        let header_marker = txn.get_header_marker()?;
+        let state_marker = txn.get_state_marker()?;
+        let latest_timestamp = match state_marker.prev() {
+            Some(latest_block_number) => txn
+                .get_block_header(latest_block_number)?
+                .map(|header| header.block_header_without_hash.timestamp.0),
+            None => None,
+        };
+        Ok((header_marker, state_marker, latest_timestamp))
+    }
+}
+
+#[async_trait]
+impl ComponentHealth for StateSyncHealth {
+    async fn health(&self) -> HealthReport {
+        let (header_marker, state_marker, latest_timestamp) = match self.sync_progress() {
+            Ok(progress) => progress,
+            Err(err) => return HealthReport::unhealthy(err),
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        // Until the state of a block is synced, the node is as far behind as it can be.
+        let lag = latest_timestamp.map(|timestamp| now.saturating_sub(timestamp));
+        let status = match lag {
+            Some(lag) if lag <= self.max_healthy_sync_lag.as_secs() => HealthStatus::Healthy,
+            _ => HealthStatus::Degraded,
+        };
+        HealthReport::new(
+            status,
+            json!({
+                "header_marker": header_marker,
+                "state_marker": state_marker,
+                "lag_seconds": lag,
+            }),
+        )
+    }
+}
@@ -4,6 +4,7 @@ mod test;
 use async_trait::async_trait;
 use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt};
+use papyrus_consensus::decision_certificate::DecisionCertificateVerifier;
 use papyrus_network::network_manager::{self, NetworkError};
 use papyrus_p2p_sync::client::{P2PSyncClient, P2PSyncClientChannels, P2PSyncClientError};
 use papyrus_p2p_sync::server::{P2PSyncServer, P2PSyncServerChannels};
@@ -38,7 +39,10 @@ impl ComponentStarter for StateSyncRunner {
 }
 
 impl StateSyncRunner {
-    pub fn new(config: StateSyncConfig) -> (Self, StorageReader) {
+    pub fn new(
+        config: StateSyncConfig,
+        decision_certificate_verifier: Option<DecisionCertificateVerifier>,
+    ) -> (Self, StorageReader) {
         let (storage_reader, storage_writer) =
             open_storage(config.storage_config).expect("StateSyncRunner failed opening storage");
 
@@ -55,11 +59,14 @@ impl StateSyncRunner {
             .register_sqmr_protocol_client(Protocol::Transaction.into(), BUFFER_SIZE);
         let class_client_sender =
             network_manager.register_sqmr_protocol_client(Protocol::Class.into(), BUFFER_SIZE);
+        let decision_certificate_client_sender = network_manager
+            .register_sqmr_protocol_client(Protocol::DecisionCertificate.into(), BUFFER_SIZE);
         let p2p_sync_client_channels = P2PSyncClientChannels::new(
             header_client_sender,
             state_diff_client_sender,
             transaction_client_sender,
             class_client_sender,
+            decision_certificate_client_sender,
         );
         let p2p_sync_client = P2PSyncClient::new(
             config.p2p_sync_client_config,
@@ -67,6 +74,7 @@ impl StateSyncRunner {
             storage_writer,
             p2p_sync_client_channels,
             futures::stream::pending().boxed(),
+            decision_certificate_verifier,
         );
 
         let header_server_receiver = network_manager
@@ -79,12 +87,15 @@ impl StateSyncRunner {
             network_manager.register_sqmr_protocol_server(Protocol::Class.into(), BUFFER_SIZE);
         let event_server_receiver =
             network_manager.register_sqmr_protocol_server(Protocol::Event.into(), BUFFER_SIZE);
+        let decision_certificate_server_receiver = network_manager
+            .register_sqmr_protocol_server(Protocol::DecisionCertificate.into(), BUFFER_SIZE);
         let p2p_sync_server_channels = P2PSyncServerChannels::new(
             header_server_receiver,
             state_diff_server_receiver,
             transaction_server_receiver,
             class_server_receiver,
             event_server_receiver,
+            decision_certificate_server_receiver,
         );
         let p2p_sync_server = P2PSyncServer::new(storage_reader.clone(), p2p_sync_server_channels);
 
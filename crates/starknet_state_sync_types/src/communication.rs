@@ -3,7 +3,10 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use papyrus_proc_macros::handle_response_variants;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::BlockNumber;
+use starknet_api::block::{BlockHeaderWithoutHash, BlockNumber};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
 use starknet_sequencer_infra::component_client::{
     ClientError,
     LocalComponentClient,
@@ -13,6 +16,7 @@ use starknet_sequencer_infra::component_definitions::{
     ComponentClient,
     ComponentRequestAndResponseSender,
 };
+use starknet_types_core::felt::Felt;
 use thiserror::Error;
 
 use crate::errors::StateSyncError;
@@ -34,7 +38,54 @@ pub trait StateSyncClient: Send + Sync {
         sync_block: SyncBlock,
     ) -> StateSyncClientResult<()>;
 
-    // TODO: Add state reader methods for gateway.
+    /// Returns the number of the latest block whose state was synced, or None if no block was
+    /// synced yet.
+    async fn get_latest_block_number(&self) -> StateSyncClientResult<Option<BlockNumber>>;
+
+    /// Request for the header of a block at a specific height.
+    /// If the block doesn't exist, or if the sync didn't download it yet, returns None.
+    async fn get_block_header(
+        &self,
+        block_number: BlockNumber,
+    ) -> StateSyncClientResult<Option<BlockHeaderWithoutHash>>;
+
+    // The state reader methods below read the state right after the given block; they fail with
+    // `BlockNotFound` if the sync didn't download the block yet.
+
+    async fn get_storage_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+        storage_key: StorageKey,
+    ) -> StateSyncClientResult<Felt>;
+
+    async fn get_nonce_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncClientResult<Nonce>;
+
+    async fn get_class_hash_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncClientResult<ClassHash>;
+
+    /// Returns the compiled class of the given class hash: a Casm class if it is a Cairo 1 class,
+    /// or a deprecated class otherwise.
+    async fn get_compiled_class(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncClientResult<ContractClass>;
+
+    /// Returns the compiled class hash of the given class hash, or the default one if the class
+    /// was not declared as a Cairo 1 class by the given block.
+    async fn get_compiled_class_hash(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncClientResult<CompiledClassHash>;
 }
 
 pub type StateSyncResult<T> = Result<T, StateSyncError>;
@@ -58,12 +109,26 @@ pub type StateSyncRequestAndResponseSender =
 pub enum StateSyncRequest {
     GetBlock(BlockNumber),
     AddNewBlock(BlockNumber, SyncBlock),
+    GetLatestBlockNumber,
+    GetBlockHeader(BlockNumber),
+    GetStorageAt(BlockNumber, ContractAddress, StorageKey),
+    GetNonceAt(BlockNumber, ContractAddress),
+    GetClassHashAt(BlockNumber, ContractAddress),
+    GetCompiledClass(BlockNumber, ClassHash),
+    GetCompiledClassHash(BlockNumber, ClassHash),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum StateSyncResponse {
     GetBlock(StateSyncResult<Option<SyncBlock>>),
     AddNewBlock(StateSyncResult<()>),
+    GetLatestBlockNumber(StateSyncResult<Option<BlockNumber>>),
+    GetBlockHeader(StateSyncResult<Option<BlockHeaderWithoutHash>>),
+    GetStorageAt(StateSyncResult<Felt>),
+    GetNonceAt(StateSyncResult<Nonce>),
+    GetClassHashAt(StateSyncResult<ClassHash>),
+    GetCompiledClass(StateSyncResult<ContractClass>),
+    GetCompiledClassHash(StateSyncResult<CompiledClassHash>),
 }
 
 #[async_trait]
@@ -91,6 +156,106 @@ impl StateSyncClient for LocalStateSyncClient {
             StateSyncError
         )
     }
+
+    async fn get_latest_block_number(&self) -> StateSyncClientResult<Option<BlockNumber>> {
+        let request = StateSyncRequest::GetLatestBlockNumber;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetLatestBlockNumber,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_block_header(
+        &self,
+        block_number: BlockNumber,
+    ) -> StateSyncClientResult<Option<BlockHeaderWithoutHash>> {
+        let request = StateSyncRequest::GetBlockHeader(block_number);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetBlockHeader,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_storage_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+        storage_key: StorageKey,
+    ) -> StateSyncClientResult<Felt> {
+        let request = StateSyncRequest::GetStorageAt(block_number, contract_address, storage_key);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetStorageAt,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_nonce_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncClientResult<Nonce> {
+        let request = StateSyncRequest::GetNonceAt(block_number, contract_address);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetNonceAt,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_class_hash_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncClientResult<ClassHash> {
+        let request = StateSyncRequest::GetClassHashAt(block_number, contract_address);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetClassHashAt,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_compiled_class(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncClientResult<ContractClass> {
+        let request = StateSyncRequest::GetCompiledClass(block_number, class_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetCompiledClass,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+    async fn get_compiled_class_hash(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncClientResult<CompiledClassHash> {
+        let request = StateSyncRequest::GetCompiledClassHash(block_number, class_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetCompiledClassHash,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
 }
 
 #[async_trait]
@@ -118,6 +283,106 @@ impl StateSyncClient for RemoteStateSyncClient {
             StateSyncError
         )
     }
+
+    async fn get_latest_block_number(&self) -> StateSyncClientResult<Option<BlockNumber>> {
+        let request = StateSyncRequest::GetLatestBlockNumber;
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetLatestBlockNumber,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_block_header(
+        &self,
+        block_number: BlockNumber,
+    ) -> StateSyncClientResult<Option<BlockHeaderWithoutHash>> {
+        let request = StateSyncRequest::GetBlockHeader(block_number);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetBlockHeader,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_storage_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+        storage_key: StorageKey,
+    ) -> StateSyncClientResult<Felt> {
+        let request = StateSyncRequest::GetStorageAt(block_number, contract_address, storage_key);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetStorageAt,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_nonce_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncClientResult<Nonce> {
+        let request = StateSyncRequest::GetNonceAt(block_number, contract_address);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetNonceAt,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_class_hash_at(
+        &self,
+        block_number: BlockNumber,
+        contract_address: ContractAddress,
+    ) -> StateSyncClientResult<ClassHash> {
+        let request = StateSyncRequest::GetClassHashAt(block_number, contract_address);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetClassHashAt,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+
+    async fn get_compiled_class(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncClientResult<ContractClass> {
+        let request = StateSyncRequest::GetCompiledClass(block_number, class_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetCompiledClass,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
+    async fn get_compiled_class_hash(
+        &self,
+        block_number: BlockNumber,
+        class_hash: ClassHash,
+    ) -> StateSyncClientResult<CompiledClassHash> {
+        let request = StateSyncRequest::GetCompiledClassHash(block_number, class_hash);
+        let response = self.send(request).await;
+        handle_response_variants!(
+            StateSyncResponse,
+            GetCompiledClassHash,
+            StateSyncClientError,
+            StateSyncError
+        )
+    }
 }
 
 // TODO(shahak): Remove this once we connect state sync to the node.
@@ -139,4 +404,56 @@ impl StateSyncClient for EmptyStateSyncClient {
     ) -> StateSyncClientResult<()> {
         Ok(())
     }
+
+    async fn get_latest_block_number(&self) -> StateSyncClientResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    async fn get_block_header(
+        &self,
+        _block_number: BlockNumber,
+    ) -> StateSyncClientResult<Option<BlockHeaderWithoutHash>> {
+        Ok(None)
+    }
+
+    async fn get_storage_at(
+        &self,
+        block_number: BlockNumber,
+        _contract_address: ContractAddress,
+        _storage_key: StorageKey,
+    ) -> StateSyncClientResult<Felt> {
+        Err(StateSyncError::BlockNotFound(block_number).into())
+    }
+
+    async fn get_nonce_at(
+        &self,
+        block_number: BlockNumber,
+        _contract_address: ContractAddress,
+    ) -> StateSyncClientResult<Nonce> {
+        Err(StateSyncError::BlockNotFound(block_number).into())
+    }
+
+    async fn get_class_hash_at(
+        &self,
+        block_number: BlockNumber,
+        _contract_address: ContractAddress,
+    ) -> StateSyncClientResult<ClassHash> {
+        Err(StateSyncError::BlockNotFound(block_number).into())
+    }
+
+    async fn get_compiled_class(
+        &self,
+        block_number: BlockNumber,
+        _class_hash: ClassHash,
+    ) -> StateSyncClientResult<ContractClass> {
+        Err(StateSyncError::BlockNotFound(block_number).into())
+    }
+
+    async fn get_compiled_class_hash(
+        &self,
+        block_number: BlockNumber,
+        _class_hash: ClassHash,
+    ) -> StateSyncClientResult<CompiledClassHash> {
+        Err(StateSyncError::BlockNotFound(block_number).into())
+    }
 }
@@ -1,9 +1,15 @@
 use papyrus_storage::StorageError;
 use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::ClassHash;
 use thiserror::Error;
 
 #[derive(Debug, Error, Serialize, Deserialize, Clone)]
 pub enum StateSyncError {
+    #[error("Block number {0} was not found")]
+    BlockNotFound(BlockNumber),
+    #[error("Class hash {0} was not found")]
+    ClassNotFound(ClassHash),
     #[error("Communication error between StateSync and StateSyncRunner")]
     RunnerCommunicationError,
     // StorageError does not derive Serialize, Deserialize and Clone Traits.
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
-use starknet_api::block::BlockNumber;
-use starknet_api::state::ThinStateDiff;
-use starknet_api::transaction::TransactionHash;
+use starknet_api::block::{BlockHeader, BlockNumber};
+use starknet_api::core::ClassHash;
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use starknet_api::state::{SierraContractClass, ThinStateDiff};
+use starknet_api::transaction::{Transaction, TransactionHash, TransactionOutput};
 
 use crate::errors::StateSyncError;
 
@@ -16,6 +18,12 @@ pub type StateSyncResult<T> = Result<T, StateSyncError>;
 pub struct SyncBlock {
     pub block_number: BlockNumber,
     pub state_diff: ThinStateDiff,
-    // TODO: decide if we want block hash, parent block hash and full classes here.
     pub transaction_hashes: Vec<TransactionHash>,
+    // The rest of the block, so that it is stored in full, like the blocks built on top of it.
+    pub block_header: BlockHeader,
+    pub transactions: Vec<Transaction>,
+    pub transaction_outputs: Vec<TransactionOutput>,
+    // The classes declared in the block.
+    pub classes: Vec<(ClassHash, SierraContractClass)>,
+    pub deprecated_classes: Vec<(ClassHash, DeprecatedContractClass)>,
 }